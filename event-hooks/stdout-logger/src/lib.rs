@@ -15,8 +15,9 @@ impl EventHandler for StdoutLogger {
     }
 }
 
-#[no_mangle]
-pub fn register_handler(registrar: &mut dyn HandlerRegistrar) -> Result<()> {
+fn register_handler(registrar: &mut dyn HandlerRegistrar) -> Result<()> {
     registrar.register(Box::new(StdoutLogger {}));
     Ok(())
 }
+
+solo_machine_plugin::export_handler!(register_handler);
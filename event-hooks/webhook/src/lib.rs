@@ -0,0 +1,300 @@
+//! # Webhook
+//!
+//! Sends events emitted by solo machine to configured URLs as JSON (using HTTP `POST` requests).
+//!
+//! ## Arguments
+//!
+//! Webhook event hook can take following arguments via environment variables:
+//!
+//! - `SOLO_WEBHOOK_URL`: URL to which events are sent (required if `SOLO_WEBHOOK_EVENT_URLS` is not provided)
+//! - `SOLO_WEBHOOK_EVENT_URLS`: Comma separated mapping of event types to URLs (e.g.
+//!   "TokensMinted=https://example.com/minted,TokensBurnt=https://example.com/burnt"). Events of mapped types are sent
+//!   to their mapped URL instead of `SOLO_WEBHOOK_URL`. Events which are neither mapped nor have a default URL are
+//!   not sent.
+//! - `SOLO_WEBHOOK_SECRET`: Secret used for signing payloads using HMAC-SHA256 (required)
+//! - `SOLO_WEBHOOK_TIMEOUT`: Timeout of each HTTP request (default: "10s", should be less than `--handler-timeout`)
+//!
+//! ## Signature
+//!
+//! Each request contains following headers:
+//!
+//! - `X-Solo-Event`: Type of event (e.g. "TokensMinted")
+//! - `X-Solo-Timestamp`: Unix timestamp (in seconds) at which the request was signed
+//! - `X-Solo-Signature`: `sha256=` followed by hex encoded HMAC-SHA256 of `<timestamp>.<body>` using
+//!   `SOLO_WEBHOOK_SECRET` as key
+//!
+//! Receivers should recompute the signature and reject requests with an old timestamp to prevent replays.
+//!
+//! ## Delivery
+//!
+//! Events are delivered one at a time (from event outbox of solo machine) in the order they were emitted, and an event
+//! is only acknowledged once it is delivered. Failed deliveries (connection errors, timeouts, `429` and `5xx`
+//! responses) are returned as errors to solo machine, which retries them according to `--handler-error-policy` (with
+//! `retry` policy, events which still fail are delivered again later). Events rejected with any other status code are
+//! logged and skipped. Logging is configured using `RUST_LOG` environment variable.
+use std::{
+    collections::HashMap,
+    env,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode, Url};
+use sha2::Sha256;
+use solo_machine_core::{
+    event::{EventHandler, HandlerRegistrar},
+    Event,
+};
+
+const DEFAULT_TIMEOUT: &str = "10s";
+
+/// Configuration of webhook
+struct WebhookConfig {
+    /// URL to which events without a mapping are sent
+    default_url: Option<Url>,
+    /// Mapping of event types to URLs
+    event_urls: HashMap<String, Url>,
+    /// Secret used for signing payloads
+    secret: Vec<u8>,
+    /// Timeout of each HTTP request
+    timeout: Duration,
+}
+
+impl WebhookConfig {
+    fn from_env() -> Result<Self> {
+        let default_url = env::var("SOLO_WEBHOOK_URL")
+            .ok()
+            .map(|url| url.parse().context("invalid `SOLO_WEBHOOK_URL`"))
+            .transpose()?;
+
+        let event_urls = match env::var("SOLO_WEBHOOK_EVENT_URLS") {
+            Ok(event_urls) => parse_event_urls(&event_urls)?,
+            Err(_) => HashMap::new(),
+        };
+
+        ensure!(
+            default_url.is_some() || !event_urls.is_empty(),
+            "either `SOLO_WEBHOOK_URL` or `SOLO_WEBHOOK_EVENT_URLS` environment variable is required for webhook"
+        );
+
+        let secret = get_env("SOLO_WEBHOOK_SECRET")?.into_bytes();
+
+        let timeout = parse_duration("SOLO_WEBHOOK_TIMEOUT", DEFAULT_TIMEOUT)?;
+
+        Ok(Self {
+            default_url,
+            event_urls,
+            secret,
+            timeout,
+        })
+    }
+
+    /// Returns the URL to which events of given type are sent
+    fn get_url(&self, event_type: &str) -> Option<&Url> {
+        self.event_urls
+            .get(event_type)
+            .or(self.default_url.as_ref())
+    }
+}
+
+/// Event handler which sends events to configured URLs. Events are delivered before returning from `handle`, so
+/// that failed deliveries are retried from event outbox.
+struct Webhook {
+    client: Client,
+    config: WebhookConfig,
+}
+
+#[async_trait]
+impl EventHandler for Webhook {
+    async fn handle(&self, event: Event) -> Result<()> {
+        let payload = serde_json::to_value(&event).context("unable to serialize event")?;
+        let event_type = payload["type"].as_str().unwrap_or_default();
+
+        let url = match self.config.get_url(event_type) {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+
+        let body = serde_json::to_vec(&payload).context("unable to serialize event")?;
+
+        match self.send(url, event_type, &body).await {
+            Ok(()) => Ok(()),
+            Err(DeliveryError::Retryable(err)) => {
+                Err(err.context(format!("unable to deliver {} event to {}", event_type, url)))
+            }
+            Err(DeliveryError::Permanent(err)) => {
+                log::error!(
+                    "{} event is rejected by {} (skipping event): {:#}",
+                    event_type,
+                    url,
+                    err
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Error returned when an attempt to deliver an event fails
+enum DeliveryError {
+    /// Delivery can be retried (e.g. connection errors, timeouts, etc.)
+    Retryable(Error),
+    /// Delivery should not be retried (e.g. event is rejected by receiver)
+    Permanent(Error),
+}
+
+impl Webhook {
+    fn new(config: WebhookConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .context("unable to build http client for webhook")?;
+
+        Ok(Self { client, config })
+    }
+
+    async fn send(&self, url: &Url, event_type: &str, body: &[u8]) -> Result<(), DeliveryError> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| DeliveryError::Permanent(err.into()))?
+            .as_secs();
+
+        let signature = sign(&self.config.secret, timestamp, body);
+
+        let response = self
+            .client
+            .post(url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Solo-Event", event_type)
+            .header("X-Solo-Timestamp", timestamp.to_string())
+            .header("X-Solo-Signature", format!("sha256={}", signature))
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|err| DeliveryError::Retryable(err.into()))?;
+
+        let status = response.status();
+
+        if status.is_success() {
+            Ok(())
+        } else if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Err(DeliveryError::Retryable(anyhow!(
+                "webhook responded with status: {}",
+                status
+            )))
+        } else {
+            Err(DeliveryError::Permanent(anyhow!(
+                "webhook responded with status: {}",
+                status
+            )))
+        }
+    }
+}
+
+/// Returns hex encoded HMAC-SHA256 of `<timestamp>.<body>`
+fn sign(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take key of any size");
+
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn parse_event_urls(event_urls: &str) -> Result<HashMap<String, Url>> {
+    event_urls
+        .split(',')
+        .map(str::trim)
+        .filter(|mapping| !mapping.is_empty())
+        .map(|mapping| {
+            let (event_type, url) = mapping.split_once('=').ok_or_else(|| {
+                anyhow!(
+                    "invalid mapping in `SOLO_WEBHOOK_EVENT_URLS` (expected `EventType=url`): {}",
+                    mapping
+                )
+            })?;
+
+            let url = url
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid url for {} event", event_type))?;
+
+            Ok((event_type.trim().to_string(), url))
+        })
+        .collect()
+}
+
+fn parse_duration(key: &str, default: &str) -> Result<Duration> {
+    let duration = env::var(key).unwrap_or_else(|_| default.to_string());
+
+    humantime::parse_duration(&duration).with_context(|| format!("invalid `{}`", key))
+}
+
+fn get_env(key: &str) -> Result<String> {
+    env::var(key).context(format!(
+        "`{}` environment variable is required for webhook",
+        key
+    ))
+}
+
+fn register_handler(registrar: &mut dyn HandlerRegistrar) -> Result<()> {
+    // Logger of solo machine is not accessible from a dynamically loaded library
+    let _ = env_logger::try_init();
+
+    let config = WebhookConfig::from_env()?;
+    registrar.register(Box::new(Webhook::new(config)?));
+    Ok(())
+}
+
+solo_machine_plugin::export_handler!(register_handler);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_uses_timestamp_and_body() {
+        let signature = sign(b"secret", 1700000000, br#"{"type":"TokensMinted"}"#);
+
+        assert_eq!(
+            signature,
+            "5a8ec61f7568bcd14a3741a24024b6248108453c0ceac839ce74238ffa3f658a"
+        );
+        assert_ne!(
+            signature,
+            sign(b"secret", 1700000001, br#"{"type":"TokensMinted"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign(b"other", 1700000000, br#"{"type":"TokensMinted"}"#)
+        );
+    }
+
+    #[test]
+    fn parse_event_urls_trims_mappings() {
+        let event_urls = parse_event_urls(
+            " TokensMinted = https://example.com/minted ,,TokensBurnt=https://example.com/burnt",
+        )
+        .unwrap();
+
+        assert_eq!(event_urls.len(), 2);
+        assert_eq!(
+            event_urls["TokensMinted"].as_str(),
+            "https://example.com/minted"
+        );
+        assert_eq!(
+            event_urls["TokensBurnt"].as_str(),
+            "https://example.com/burnt"
+        );
+    }
+
+    #[test]
+    fn parse_event_urls_rejects_invalid_mappings() {
+        assert!(parse_event_urls("TokensMinted").is_err());
+        assert!(parse_event_urls("TokensMinted=not a url").is_err());
+        assert!(parse_event_urls("").unwrap().is_empty());
+    }
+}
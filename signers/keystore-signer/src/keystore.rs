@@ -0,0 +1,436 @@
+//! Encrypted keystore file format (similar to Web3 secret storage)
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use argon2::{Algorithm, Argon2, Params as Argon2Params, Version};
+use rand::RngCore;
+use scrypt::Params as ScryptParams;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Version of keystore format
+pub const KEYSTORE_VERSION: u32 = 1;
+/// Cipher used for encrypting private keys
+pub const CIPHER: &str = "aes-256-gcm";
+
+/// Length of derived key (AES-256)
+const DERIVED_KEY_LEN: usize = 32;
+/// Length of salt for key derivation
+const SALT_LEN: usize = 32;
+/// Length of nonce for AES-GCM
+const NONCE_LEN: usize = 12;
+
+/// Maximum memory which key derivation may use (keystore files are untrusted input, so their parameters should not
+/// be able to exhaust memory or CPU of signer)
+const MAX_KDF_MEMORY: u64 = 1 << 30;
+/// Maximum `log2(N)` of scrypt
+const MAX_SCRYPT_LOG_N: u32 = 20;
+/// Maximum block size of scrypt
+const MAX_SCRYPT_R: u32 = 32;
+/// Maximum number of iterations of argon2id
+const MAX_ARGON2_T: u32 = 64;
+/// Maximum parallelization of scrypt and argon2id
+const MAX_KDF_P: u32 = 16;
+
+/// Encrypted keystore containing a secp256k1 private key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Version of keystore format
+    pub version: u32,
+    /// Random identifier of keystore
+    pub id: String,
+    /// Account address of key (for identifying the key without decrypting it)
+    pub address: String,
+    /// Algorithm used for address generation (`secp256k1` or `eth-secp256k1`)
+    pub algo: String,
+    /// Account prefix for generating addresses
+    pub account_prefix: String,
+    /// Encrypted private key and parameters needed to decrypt it
+    pub crypto: Crypto,
+}
+
+/// Encrypted private key along with cipher and key derivation parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crypto {
+    /// Cipher used for encryption (only `aes-256-gcm` is supported)
+    pub cipher: String,
+    /// Parameters of cipher
+    pub cipherparams: CipherParams,
+    /// Hex encoded encrypted private key (including authentication tag)
+    pub ciphertext: String,
+    /// Key derivation function and its parameters
+    #[serde(flatten)]
+    pub kdf: KdfParams,
+}
+
+/// Parameters of cipher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+    /// Hex encoded nonce
+    pub nonce: String,
+}
+
+/// Key derivation function used for deriving encryption key from password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KdfParams {
+    /// Scrypt
+    Scrypt {
+        /// Length of derived key
+        dklen: usize,
+        /// CPU/memory cost (power of two)
+        n: u64,
+        /// Block size
+        r: u32,
+        /// Parallelization
+        p: u32,
+        /// Hex encoded salt
+        salt: String,
+    },
+    /// Argon2id
+    Argon2id {
+        /// Length of derived key
+        dklen: usize,
+        /// Memory cost (in KiB)
+        m: u32,
+        /// Number of iterations
+        t: u32,
+        /// Parallelization
+        p: u32,
+        /// Hex encoded salt
+        salt: String,
+    },
+}
+
+impl KdfParams {
+    /// Creates scrypt parameters with given `log2(N)` and a random salt
+    pub fn scrypt(log_n: u8) -> Self {
+        Self::Scrypt {
+            dklen: DERIVED_KEY_LEN,
+            n: 1u64.checked_shl(log_n.into()).unwrap_or_default(),
+            r: 8,
+            p: 1,
+            salt: hex::encode(random_bytes::<SALT_LEN>()),
+        }
+    }
+
+    /// Creates argon2id parameters with given memory cost (in KiB), number of iterations and a random salt
+    pub fn argon2id(memory: u32, iterations: u32) -> Self {
+        Self::Argon2id {
+            dklen: DERIVED_KEY_LEN,
+            m: memory,
+            t: iterations,
+            p: 1,
+            salt: hex::encode(random_bytes::<SALT_LEN>()),
+        }
+    }
+
+    /// Returns name of key derivation function
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Scrypt { .. } => "scrypt",
+            Self::Argon2id { .. } => "argon2id",
+        }
+    }
+
+    fn derive_key(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        match self {
+            Self::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                ensure!(
+                    *dklen == DERIVED_KEY_LEN,
+                    "scrypt `dklen` should be equal to {}",
+                    DERIVED_KEY_LEN
+                );
+                ensure!(n.is_power_of_two(), "scrypt `n` should be a power of two");
+                ensure!(
+                    n.trailing_zeros() <= MAX_SCRYPT_LOG_N,
+                    "scrypt `n` should be at most 2^{}",
+                    MAX_SCRYPT_LOG_N
+                );
+                ensure!(
+                    *r <= MAX_SCRYPT_R,
+                    "scrypt `r` should be at most {}",
+                    MAX_SCRYPT_R
+                );
+                ensure!(
+                    *p <= MAX_KDF_P,
+                    "scrypt `p` should be at most {}",
+                    MAX_KDF_P
+                );
+                ensure!(
+                    128 * u64::from(*r) * n <= MAX_KDF_MEMORY,
+                    "scrypt parameters require more than {} bytes of memory",
+                    MAX_KDF_MEMORY
+                );
+
+                let params = ScryptParams::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+                    .map_err(|err| anyhow!("invalid scrypt parameters: {}", err))?;
+                let salt = hex::decode(salt).context("invalid scrypt salt")?;
+
+                let mut key = Zeroizing::new(vec![0; *dklen]);
+                scrypt::scrypt(password, &salt, &params, &mut key)
+                    .map_err(|err| anyhow!("unable to derive key using scrypt: {}", err))?;
+
+                Ok(key)
+            }
+            Self::Argon2id {
+                dklen,
+                m,
+                t,
+                p,
+                salt,
+            } => {
+                ensure!(
+                    *dklen == DERIVED_KEY_LEN,
+                    "argon2id `dklen` should be equal to {}",
+                    DERIVED_KEY_LEN
+                );
+                ensure!(
+                    u64::from(*m) * 1024 <= MAX_KDF_MEMORY,
+                    "argon2id `m` should be at most {} KiB",
+                    MAX_KDF_MEMORY / 1024
+                );
+                ensure!(
+                    *t <= MAX_ARGON2_T,
+                    "argon2id `t` should be at most {}",
+                    MAX_ARGON2_T
+                );
+                ensure!(
+                    *p <= MAX_KDF_P,
+                    "argon2id `p` should be at most {}",
+                    MAX_KDF_P
+                );
+
+                let params = Argon2Params::new(*m, *t, *p, Some(*dklen))
+                    .map_err(|err| anyhow!("invalid argon2id parameters: {}", err))?;
+                let salt = hex::decode(salt).context("invalid argon2id salt")?;
+
+                let mut key = Zeroizing::new(vec![0; *dklen]);
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password, &salt, &mut key)
+                    .map_err(|err| anyhow!("unable to derive key using argon2id: {}", err))?;
+
+                Ok(key)
+            }
+        }
+    }
+}
+
+impl Keystore {
+    /// Encrypts given private key using a key derived from password
+    pub fn encrypt(
+        private_key: &[u8],
+        password: &[u8],
+        kdf: KdfParams,
+        algo: String,
+        account_prefix: String,
+        address: String,
+    ) -> Result<Self> {
+        let id = new_id();
+        let nonce = random_bytes::<NONCE_LEN>();
+
+        let key = kdf.derive_key(password)?;
+        let aad = associated_data(&id, &algo, &account_prefix, &address);
+
+        let ciphertext = Aes256Gcm::new_from_slice(&key)
+            .map_err(|_| anyhow!("invalid encryption key length"))?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: private_key,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("unable to encrypt private key"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            id,
+            address,
+            algo,
+            account_prefix,
+            crypto: Crypto {
+                cipher: CIPHER.to_owned(),
+                cipherparams: CipherParams {
+                    nonce: hex::encode(nonce),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+            },
+        })
+    }
+
+    /// Decrypts private key using a key derived from password
+    pub fn decrypt(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        ensure!(
+            self.version == KEYSTORE_VERSION,
+            "unsupported keystore version: {}",
+            self.version
+        );
+        ensure!(
+            self.crypto.cipher == CIPHER,
+            "unsupported keystore cipher: {}",
+            self.crypto.cipher
+        );
+
+        let nonce =
+            hex::decode(&self.crypto.cipherparams.nonce).context("invalid keystore nonce")?;
+        ensure!(nonce.len() == NONCE_LEN, "invalid keystore nonce length");
+        let ciphertext =
+            hex::decode(&self.crypto.ciphertext).context("invalid keystore ciphertext")?;
+
+        let key = self.crypto.kdf.derive_key(password)?;
+        let aad = associated_data(&self.id, &self.algo, &self.account_prefix, &self.address);
+
+        let private_key = Aes256Gcm::new_from_slice(&key)
+            .map_err(|_| anyhow!("invalid encryption key length"))?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            // Authentication fails both for wrong passwords and tampered keystores
+            .map_err(|_| {
+                anyhow!("unable to decrypt keystore (wrong password or corrupted keystore)")
+            })?;
+
+        Ok(Zeroizing::new(private_key))
+    }
+
+    /// Reads keystore from given file
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("unable to read keystore from {}", path.display()))?;
+
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("invalid keystore file: {}", path.display()))
+    }
+
+    /// Writes keystore to given file (fails if the file already exists)
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("unable to create keystore file {}", path.display()))?;
+
+        let json = serde_json::to_vec_pretty(self).context("unable to serialize keystore")?;
+
+        file.write_all(&json)
+            .with_context(|| format!("unable to write keystore to {}", path.display()))
+    }
+}
+
+/// Metadata of keystore authenticated along with private key (so that it cannot be changed without the password)
+fn associated_data(id: &str, algo: &str, account_prefix: &str, address: &str) -> String {
+    format!("{}:{}:{}:{}", id, algo, account_prefix, address)
+}
+
+/// Generates a random (version 4) UUID
+fn new_id() -> String {
+    let mut bytes = random_bytes::<16>();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: [u8; 32] = [7; 32];
+
+    fn encrypt(kdf: KdfParams) -> Keystore {
+        Keystore::encrypt(
+            &PRIVATE_KEY,
+            b"password",
+            kdf,
+            "secp256k1".to_owned(),
+            "cosmos".to_owned(),
+            "cosmos1address".to_owned(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for kdf in [KdfParams::scrypt(10), KdfParams::argon2id(1024, 1)] {
+            let keystore = encrypt(kdf);
+            let json = serde_json::to_string(&keystore).unwrap();
+            let keystore: Keystore = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(
+                PRIVATE_KEY.as_slice(),
+                keystore.decrypt(b"password").unwrap().as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_password() {
+        let keystore = encrypt(KdfParams::scrypt(10));
+
+        let err = keystore.decrypt(b"wrong password").unwrap_err();
+        assert!(err.to_string().contains("wrong password"));
+    }
+
+    #[test]
+    fn tampered_metadata() {
+        let mut keystore = encrypt(KdfParams::scrypt(10));
+        keystore.address = "cosmos1other".to_owned();
+
+        assert!(keystore.decrypt(b"password").is_err());
+    }
+
+    #[test]
+    fn kdf_limits() {
+        let mut keystore = encrypt(KdfParams::scrypt(10));
+
+        keystore.crypto.kdf = KdfParams::scrypt(30);
+        assert!(keystore.decrypt(b"password").is_err());
+
+        keystore.crypto.kdf = KdfParams::scrypt(64);
+        assert!(keystore.decrypt(b"password").is_err());
+
+        keystore.crypto.kdf = KdfParams::argon2id(u32::MAX, 1);
+        assert!(keystore.decrypt(b"password").is_err());
+
+        keystore.crypto.kdf = KdfParams::argon2id(1024, u32::MAX);
+        assert!(keystore.decrypt(b"password").is_err());
+    }
+}
@@ -0,0 +1,268 @@
+//! # Keystore Signer
+//!
+//! Signs transaction using a private key stored in an encrypted keystore file (created using `solo-keystore` CLI).
+//!
+//! ## Arguments
+//!
+//! Keystore signer takes the following arguments via environment variables:
+//!
+//! - `SOLO_KEYSTORE`: Path to keystore file (required)
+//! - `SOLO_KEYSTORE_PASSWORD`: Source of keystore password (default: "prompt") (possible values: ["prompt", "stdin",
+//!   "file:<path>"])
+//!
+//! Account prefix and address algorithm are stored in keystore file. When password source is "prompt", the password
+//! is read from the controlling terminal (TTY) of solo machine process.
+pub mod keystore;
+
+use std::{
+    env, fmt,
+    io::{self, BufRead},
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
+use async_trait::async_trait;
+use bip32::{DerivationPath, ExtendedPrivateKey, Mnemonic};
+use k256::ecdsa::SigningKey;
+use solo_machine_core::{
+    cosmos::crypto::PublicKey,
+    signer::{AddressAlgo, Message, MessageDescription, SignerRegistrar},
+    Signer, ToPublicKey,
+};
+use zeroize::Zeroizing;
+
+use self::keystore::Keystore;
+
+/// Source of keystore password
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordSource {
+    /// Prompt for password on controlling terminal (TTY)
+    Prompt,
+    /// Read first line of standard input
+    Stdin,
+    /// Read first line of given file
+    File(PathBuf),
+}
+
+impl PasswordSource {
+    /// Reads password from current source (`prompt` is shown only when reading from TTY)
+    pub fn read_password(&self, prompt: &str) -> Result<Zeroizing<String>> {
+        let password = match self {
+            Self::Prompt => Zeroizing::new(
+                rpassword::prompt_password(prompt).context("unable to read password from tty")?,
+            ),
+            Self::Stdin => {
+                let mut line = Zeroizing::new(String::new());
+                io::stdin()
+                    .lock()
+                    .read_line(&mut line)
+                    .context("unable to read password from stdin")?;
+                trim_newline(line)
+            }
+            Self::File(path) => {
+                let contents =
+                    Zeroizing::new(std::fs::read_to_string(path).with_context(|| {
+                        format!("unable to read password from {}", path.display())
+                    })?);
+                trim_newline(Zeroizing::new(
+                    contents.lines().next().unwrap_or_default().to_owned(),
+                ))
+            }
+        };
+
+        ensure!(!password.is_empty(), "empty input read from {}", self);
+
+        Ok(password)
+    }
+}
+
+fn trim_newline(mut line: Zeroizing<String>) -> Zeroizing<String> {
+    while line.ends_with('\n') || line.ends_with('\r') {
+        line.pop();
+    }
+
+    line
+}
+
+impl FromStr for PasswordSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prompt" => Ok(Self::Prompt),
+            "stdin" => Ok(Self::Stdin),
+            _ => match s.strip_prefix("file:") {
+                Some(path) if !path.is_empty() => Ok(Self::File(PathBuf::from(path))),
+                _ => Err(anyhow!(
+                    "invalid password source: {} (possible values: [\"prompt\", \"stdin\", \"file:<path>\"])",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for PasswordSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prompt => write!(f, "prompt"),
+            Self::Stdin => write!(f, "stdin"),
+            Self::File(path) => write!(f, "file:{}", path.display()),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Signer implementation using encrypted keystore
+pub struct KeystoreSigner {
+    /// Decrypted private key of signer
+    signing_key: SigningKey,
+    /// Bech32 prefix
+    pub account_prefix: String,
+    /// Algorithm used for address generation
+    pub algo: AddressAlgo,
+}
+
+impl KeystoreSigner {
+    /// Creates signer by decrypting keystore given in environment variables
+    pub fn from_env() -> Result<Self> {
+        let path = PathBuf::from(get_env("SOLO_KEYSTORE")?);
+        let password_source = match env::var("SOLO_KEYSTORE_PASSWORD") {
+            Ok(source) => source.parse()?,
+            Err(_) => PasswordSource::Prompt,
+        };
+
+        let keystore = Keystore::read(&path)?;
+        let password = password_source.read_password(&format!(
+            "Enter password of keystore {}: ",
+            keystore.address
+        ))?;
+
+        Self::from_keystore(&keystore, password.as_bytes())
+    }
+
+    /// Decrypts given keystore and checks that the decrypted key matches address of keystore
+    pub fn from_keystore(keystore: &Keystore, password: &[u8]) -> Result<Self> {
+        let private_key = keystore.decrypt(password)?;
+
+        let signer = Self::new(
+            SigningKey::from_slice(&private_key).context("invalid private key in keystore")?,
+            keystore.account_prefix.clone(),
+            keystore.algo.parse()?,
+        );
+
+        ensure!(
+            signer.to_account_address()? == keystore.address,
+            "address of decrypted key does not match keystore address: {}",
+            keystore.address
+        );
+
+        Ok(signer)
+    }
+
+    /// Creates a new signer from given private key
+    pub fn new(signing_key: SigningKey, account_prefix: String, algo: AddressAlgo) -> Self {
+        Self {
+            signing_key,
+            account_prefix,
+            algo,
+        }
+    }
+
+    /// Creates a new signer from given mnemonic and HD path
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        hd_path: &str,
+        account_prefix: String,
+        algo: AddressAlgo,
+    ) -> Result<Self> {
+        let seed = mnemonic.to_seed("");
+        let hd_path = DerivationPath::from_str(hd_path).context("invalid HD path")?;
+        let private_key =
+            ExtendedPrivateKey::<SigningKey>::derive_from_path(seed.as_bytes(), &hd_path)
+                .map_err(|err| anyhow!("unable to derive private key: {}", err))?;
+
+        Ok(Self::new(private_key.into(), account_prefix, algo))
+    }
+
+    /// Encrypts private key of signer into a keystore
+    pub fn to_keystore(&self, password: &[u8], kdf: keystore::KdfParams) -> Result<Keystore> {
+        let private_key = Zeroizing::new(self.signing_key.to_bytes());
+
+        Keystore::encrypt(
+            &private_key,
+            password,
+            kdf,
+            self.algo.to_string(),
+            self.account_prefix.clone(),
+            self.to_account_address()?,
+        )
+    }
+
+    /// Signs given message bytes (used for both online and offline signing)
+    pub fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.algo.sign(&self.signing_key, message)
+    }
+}
+
+fn get_env(key: &str) -> Result<String> {
+    env::var(key).context(format!(
+        "`{}` environment variable is required for keystore signer",
+        key
+    ))
+}
+
+impl ToPublicKey for KeystoreSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        let verifying_key = self.signing_key.verifying_key();
+
+        match self.algo {
+            AddressAlgo::Secp256k1 => Ok(PublicKey::Secp256k1(*verifying_key)),
+            #[cfg(feature = "ethermint")]
+            AddressAlgo::EthSecp256k1 => Ok(PublicKey::EthSecp256k1(*verifying_key)),
+        }
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.to_public_key()?
+            .account_address(self.get_account_prefix())
+    }
+}
+
+#[async_trait]
+impl Signer for KeystoreSigner {
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        message: Message<'_>,
+        _description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        self.sign_message(message.as_ref())
+    }
+}
+
+fn register_signer(registrar: &mut dyn SignerRegistrar) -> Result<()> {
+    registrar.register(Arc::new(KeystoreSigner::from_env()?));
+    Ok(())
+}
+
+solo_machine_plugin::export_signer!(register_signer);
+
+/// Parses a hex encoded secp256k1 private key
+pub fn parse_private_key(hex_key: &str) -> Result<SigningKey> {
+    let bytes = Zeroizing::new(
+        hex::decode(hex_key.trim().trim_start_matches("0x")).context("invalid hex private key")?,
+    );
+
+    if bytes.len() != 32 {
+        bail!("private key should be 32 bytes long");
+    }
+
+    SigningKey::from_slice(&bytes).context("invalid secp256k1 private key")
+}
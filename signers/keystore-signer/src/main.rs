@@ -0,0 +1,392 @@
+//! # Solo Keystore
+//!
+//! CLI for creating, importing and inspecting encrypted keystore files used by keystore signer. It can also sign
+//! messages of offline signing sessions of solo machine on an air-gapped machine.
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
+use bip32::{Language, Mnemonic};
+use keystore_signer::{
+    keystore::{KdfParams, Keystore},
+    parse_private_key, KeystoreSigner, PasswordSource,
+};
+use rand::RngCore;
+use solo_machine_core::{
+    signer::{AddressAlgo, MessageSignature, SigningRequests, SigningResponse},
+    ToPublicKey,
+};
+use structopt::StructOpt;
+use zeroize::Zeroizing;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "solo-keystore",
+    about = "Create and import encrypted keystores for keystore signer of IBC solo machine"
+)]
+enum Command {
+    /// Creates a new keystore with a randomly generated mnemonic
+    Create {
+        #[structopt(flatten)]
+        key: KeyOptions,
+        #[structopt(flatten)]
+        keystore: KeystoreOptions,
+    },
+    /// Imports an existing mnemonic (or private key) into a new keystore
+    Import {
+        /// Imports a hex encoded private key instead of a mnemonic
+        #[structopt(long)]
+        private_key: bool,
+        /// Source of mnemonic (or private key) to import
+        #[structopt(long, default_value = "prompt")]
+        secret: PasswordSource,
+        #[structopt(flatten)]
+        key: KeyOptions,
+        #[structopt(flatten)]
+        keystore: KeystoreOptions,
+    },
+    /// Shows metadata of a keystore (without decrypting it)
+    Show {
+        /// Path to keystore file
+        #[structopt(env = "SOLO_KEYSTORE")]
+        path: PathBuf,
+        /// Decrypts keystore to verify the password
+        #[structopt(long)]
+        verify: bool,
+        /// Source of keystore password (used with `--verify`)
+        #[structopt(
+            long,
+            env = "SOLO_KEYSTORE_PASSWORD",
+            default_value = "prompt",
+            hide_env_values = true
+        )]
+        password: PasswordSource,
+    },
+    /// Signs messages of an offline signing session (written by `solo-machine ibc prepare` or `ibc finalize`)
+    Sign {
+        /// Path to file containing messages to sign
+        requests: PathBuf,
+        /// Path of file to write signatures (passed to `solo-machine ibc finalize`)
+        #[structopt(short, long)]
+        output: PathBuf,
+        /// Path to keystore file
+        #[structopt(long, env = "SOLO_KEYSTORE")]
+        keystore: PathBuf,
+        /// Source of keystore password
+        #[structopt(
+            long,
+            env = "SOLO_KEYSTORE_PASSWORD",
+            default_value = "prompt",
+            hide_env_values = true
+        )]
+        password: PasswordSource,
+        /// Signs messages without asking for confirmation
+        #[structopt(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+struct KeyOptions {
+    /// HD wallet path used for deriving private key from mnemonic
+    #[structopt(long, env = "SOLO_HD_PATH", default_value = "m/44'/118'/0'/0/0")]
+    hd_path: String,
+    /// Account prefix for generating addresses
+    #[structopt(long, env = "SOLO_ACCOUNT_PREFIX", default_value = "cosmos")]
+    account_prefix: String,
+    /// Algorithm used for address generation (possible values: ["secp256k1", "eth-secp256k1"])
+    #[structopt(long, env = "SOLO_ADDRESS_ALGO", default_value = "secp256k1")]
+    algo: AddressAlgo,
+}
+
+#[derive(Debug, StructOpt)]
+struct KeystoreOptions {
+    /// Path of keystore file to create
+    #[structopt(short, long)]
+    output: PathBuf,
+    /// Source of keystore password
+    #[structopt(
+        long,
+        env = "SOLO_KEYSTORE_PASSWORD",
+        default_value = "prompt",
+        hide_env_values = true
+    )]
+    password: PasswordSource,
+    /// Key derivation function used for deriving encryption key from password
+    #[structopt(long, default_value = "scrypt", possible_values = &["scrypt", "argon2id"])]
+    kdf: Kdf,
+    /// `log2(N)` cost parameter of scrypt
+    #[structopt(long, default_value = "18")]
+    scrypt_log_n: u8,
+    /// Memory cost (in KiB) of argon2id
+    #[structopt(long, default_value = "65536")]
+    argon2_memory: u32,
+    /// Number of iterations of argon2id
+    #[structopt(long, default_value = "3")]
+    argon2_iterations: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kdf {
+    Scrypt,
+    Argon2id,
+}
+
+impl FromStr for Kdf {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scrypt" => Ok(Self::Scrypt),
+            "argon2id" => Ok(Self::Argon2id),
+            _ => Err(anyhow!("invalid key derivation function: {}", s)),
+        }
+    }
+}
+
+impl KeystoreOptions {
+    fn kdf_params(&self) -> KdfParams {
+        match self.kdf {
+            Kdf::Scrypt => KdfParams::scrypt(self.scrypt_log_n),
+            Kdf::Argon2id => KdfParams::argon2id(self.argon2_memory, self.argon2_iterations),
+        }
+    }
+
+    /// Reads new password of keystore (asks for confirmation when prompting on TTY)
+    fn read_new_password(&self) -> Result<Zeroizing<String>> {
+        let password = self
+            .password
+            .read_password("Enter new keystore password: ")?;
+
+        if self.password == PasswordSource::Prompt {
+            let confirmation = self.password.read_password("Confirm keystore password: ")?;
+            ensure!(password == confirmation, "passwords do not match");
+        }
+
+        Ok(password)
+    }
+
+    /// Encrypts private key of signer and writes keystore to output file
+    fn write(&self, signer: &KeystoreSigner) -> Result<()> {
+        ensure!(
+            !self.output.exists(),
+            "keystore file already exists: {}",
+            self.output.display()
+        );
+
+        let password = self.read_new_password()?;
+        let keystore = signer.to_keystore(password.as_bytes(), self.kdf_params())?;
+        keystore.write(&self.output)?;
+
+        println!(
+            "Created keystore {} for {}",
+            self.output.display(),
+            keystore.address
+        );
+
+        Ok(())
+    }
+}
+
+impl Command {
+    fn execute(self) -> Result<()> {
+        match self {
+            Self::Create { key, keystore } => {
+                let mut entropy = Zeroizing::new([0; 32]);
+                rand::rng().fill_bytes(entropy.as_mut());
+
+                let mnemonic = Mnemonic::from_entropy(*entropy, Language::English);
+                let signer = KeystoreSigner::from_mnemonic(
+                    &mnemonic,
+                    &key.hd_path,
+                    key.account_prefix,
+                    key.algo,
+                )?;
+
+                keystore.write(&signer)?;
+
+                println!();
+                println!("Write down the following mnemonic and keep it in a safe place (it is not shown again):");
+                println!();
+                println!("{}", mnemonic.phrase());
+
+                Ok(())
+            }
+            Self::Import {
+                private_key,
+                secret,
+                key,
+                keystore,
+            } => {
+                let signer = if private_key {
+                    let hex_key = secret.read_password("Enter hex encoded private key: ")?;
+                    KeystoreSigner::new(parse_private_key(&hex_key)?, key.account_prefix, key.algo)
+                } else {
+                    let phrase = secret.read_password("Enter mnemonic: ")?;
+                    let mnemonic = Mnemonic::new(phrase.trim(), Language::English)
+                        .map_err(|_| anyhow!("invalid mnemonic"))?;
+
+                    KeystoreSigner::from_mnemonic(
+                        &mnemonic,
+                        &key.hd_path,
+                        key.account_prefix,
+                        key.algo,
+                    )?
+                };
+
+                keystore.write(&signer)
+            }
+            Self::Show {
+                path,
+                verify,
+                password,
+            } => {
+                let keystore = Keystore::read(&path)?;
+
+                println!("ID: {}", keystore.id);
+                println!("Address: {}", keystore.address);
+                println!("Algorithm: {}", keystore.algo);
+                println!("Account prefix: {}", keystore.account_prefix);
+                println!("Cipher: {}", keystore.crypto.cipher);
+                println!("KDF: {}", keystore.crypto.kdf.name());
+
+                if verify {
+                    let password = password.read_password("Enter keystore password: ")?;
+                    let signer = KeystoreSigner::from_keystore(&keystore, password.as_bytes())
+                        .context("unable to verify keystore")?;
+
+                    println!("Verified: {}", signer.to_account_address()?);
+                    println!("Public key: {}", signer.to_public_key()?.encode());
+                }
+
+                Ok(())
+            }
+            Self::Sign {
+                requests,
+                output,
+                keystore,
+                password,
+                yes,
+            } => {
+                ensure!(
+                    !output.exists(),
+                    "signatures file already exists: {}",
+                    output.display()
+                );
+
+                let bytes = std::fs::read(&requests).with_context(|| {
+                    format!(
+                        "unable to read messages to sign from {}",
+                        requests.display()
+                    )
+                })?;
+                let requests: SigningRequests = serde_json::from_slice(&bytes)
+                    .with_context(|| format!("invalid messages file: {}", requests.display()))?;
+
+                let keystore = Keystore::read(&keystore)?;
+                ensure!(
+                    keystore.address == requests.address,
+                    "messages should be signed by {} but keystore belongs to {}",
+                    requests.address,
+                    keystore.address
+                );
+
+                // Messages file is carried from a networked machine, so, the descriptions shown for approval are checked
+                // against the messages which are actually signed
+                for request in requests.requests.iter() {
+                    ensure!(
+                        request.description.chain_id == requests.chain_id.to_string(),
+                        "description of message is for chain {} but messages are for chain {}",
+                        request.description.chain_id,
+                        requests.chain_id
+                    );
+                    request
+                        .description
+                        .verify(&request.message())
+                        .context("refusing to sign messages")?;
+                }
+
+                println!(
+                    "Round {} of offline signing session {} on chain {}:",
+                    requests.round, requests.session_id, requests.chain_id
+                );
+                println!();
+
+                for request in requests.requests.iter() {
+                    println!("- {}", request.description);
+                }
+
+                if requests
+                    .requests
+                    .iter()
+                    .any(|request| !request.description.is_chain_bound())
+                {
+                    println!();
+                    println!(
+                        "Note: sign-bytes do not contain chain ID, so, chain of sign-bytes messages above can't be \
+                         verified (check their sequences and paths instead)."
+                    );
+                }
+
+                println!();
+
+                if !yes && !confirm(&format!("Sign {} message(s)?", requests.requests.len()))? {
+                    bail!("signing cancelled");
+                }
+
+                let password = password.read_password(&format!(
+                    "Enter password of keystore {}: ",
+                    keystore.address
+                ))?;
+                let signer = KeystoreSigner::from_keystore(&keystore, password.as_bytes())?;
+
+                let signatures = requests
+                    .requests
+                    .iter()
+                    .map(|request| {
+                        Ok(MessageSignature {
+                            message: request.message.clone(),
+                            signature: signer.sign_message(request.message().as_ref())?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let response = SigningResponse {
+                    session_id: requests.session_id,
+                    round: requests.round,
+                    signatures,
+                };
+
+                let json = serde_json::to_vec_pretty(&response)
+                    .context("unable to serialize signatures")?;
+                std::fs::write(&output, json).with_context(|| {
+                    format!("unable to write signatures to {}", output.display())
+                })?;
+
+                println!("Signatures written to {}", output.display());
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Asks for confirmation on standard input
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush().context("unable to flush stdout")?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("unable to read confirmation from stdin")?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn main() -> Result<()> {
+    Command::from_args().execute()
+}
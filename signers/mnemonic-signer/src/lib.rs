@@ -18,7 +18,7 @@ use bip32::{DerivationPath, ExtendedPrivateKey, Language, Mnemonic};
 use k256::ecdsa::SigningKey;
 use solo_machine_core::{
     cosmos::crypto::PublicKey,
-    signer::{AddressAlgo, Message, SignerRegistrar},
+    signer::{AddressAlgo, Message, MessageDescription, SignerRegistrar},
     Signer, ToPublicKey,
 };
 
@@ -102,32 +102,20 @@ impl ToPublicKey for MnemonicSigner {
 
 #[async_trait]
 impl Signer for MnemonicSigner {
-    async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        message: Message<'_>,
+        _description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
         let signing_key = self.get_signing_key()?;
-
-        match self.algo {
-            AddressAlgo::Secp256k1 => Ok(<SigningKey as k256::ecdsa::signature::Signer<
-                k256::ecdsa::Signature,
-            >>::sign(&signing_key, message.as_ref())
-            .to_bytes()
-            .to_vec()),
-            #[cfg(feature = "ethermint")]
-            AddressAlgo::EthSecp256k1 => {
-                let (signature, recovery_id) = signing_key.sign_recoverable(message.as_ref())?;
-
-                let mut buf = signature.to_bytes().to_vec();
-                buf.push(recovery_id.to_byte());
-
-                buf.shrink_to_fit();
-
-                Ok(buf)
-            }
-        }
+        self.algo.sign(&signing_key, message.as_ref())
     }
 }
 
-#[no_mangle]
-pub fn register_signer(registrar: &mut dyn SignerRegistrar) -> Result<()> {
+fn register_signer(registrar: &mut dyn SignerRegistrar) -> Result<()> {
     registrar.register(Arc::new(MnemonicSigner::from_env()?));
     Ok(())
 }
+
+solo_machine_plugin::export_signer!(register_signer);
@@ -0,0 +1,195 @@
+//! # Signing Server
+//!
+//! Reference implementation of remote signing service (`solo_machine.signer.v1.Signer` gRPC protocol) backed by
+//! mnemonic signer. Solo machine can use it as a signer using `--remote-signer` CLI option.
+//!
+//! ## Arguments
+//!
+//! Signing server takes mnemonic signer's arguments via environment variables (`SOLO_MNEMONIC`, `SOLO_HD_PATH`,
+//! `SOLO_ACCOUNT_PREFIX` and `SOLO_ADDRESS_ALGO`) and following CLI options:
+//!
+//! - `--addr`: Address to serve signing service on (default: "127.0.0.1:9100") [env: SOLO_SIGNING_SERVER_ADDR]
+//! - `--tls-cert`: Path to PEM encoded TLS certificate of server (enables TLS) [env: SOLO_SIGNING_SERVER_TLS_CERT]
+//! - `--tls-key`: Path to PEM encoded TLS private key of server [env: SOLO_SIGNING_SERVER_TLS_KEY]
+//! - `--tls-client-ca`: Path to PEM encoded CA certificate used to verify client certificates (enables mutual TLS)
+//!   [env: SOLO_SIGNING_SERVER_TLS_CLIENT_CA]
+use std::{
+    convert::TryFrom,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use mnemonic_signer::MnemonicSigner;
+use solo_machine_core::{
+    cosmos::crypto::PublicKey,
+    proto::solo_machine::signer::v1::{
+        signer_server::{Signer as SignerService, SignerServer},
+        GetPublicKeyRequest, GetPublicKeyResponse, MessageType, SignRequest, SignResponse,
+    },
+    signer::{Message, MessageDescription},
+    Signer, ToPublicKey,
+};
+use structopt::StructOpt;
+use tonic::{
+    transport::{Certificate, Identity, Server, ServerTlsConfig},
+    Request, Response, Status,
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "signing-server",
+    about = "Reference remote signing service for IBC solo machine (backed by mnemonic signer)"
+)]
+struct Command {
+    /// Address to serve signing service on
+    #[structopt(
+        long,
+        env = "SOLO_SIGNING_SERVER_ADDR",
+        default_value = "127.0.0.1:9100"
+    )]
+    addr: SocketAddr,
+    /// Path to PEM encoded TLS certificate of server (enables TLS)
+    #[structopt(long, env = "SOLO_SIGNING_SERVER_TLS_CERT", requires = "tls-key")]
+    tls_cert: Option<PathBuf>,
+    /// Path to PEM encoded TLS private key of server
+    #[structopt(
+        long,
+        env = "SOLO_SIGNING_SERVER_TLS_KEY",
+        hide_env_values = true,
+        requires = "tls-cert"
+    )]
+    tls_key: Option<PathBuf>,
+    /// Path to PEM encoded CA certificate used to verify client certificates (enables mutual TLS)
+    #[structopt(long, env = "SOLO_SIGNING_SERVER_TLS_CLIENT_CA", requires = "tls-cert")]
+    tls_client_ca: Option<PathBuf>,
+}
+
+impl Command {
+    async fn execute(self) -> Result<()> {
+        let signer = MnemonicSigner::from_env()?;
+        log::info!(
+            "serving signer {} on {}",
+            signer.to_account_address()?,
+            self.addr
+        );
+
+        let mut server = Server::builder();
+
+        match (self.tls_cert, self.tls_key) {
+            (Some(cert), Some(key)) => {
+                let cert = read_pem(&cert, "server certificate").await?;
+                let key = read_pem(&key, "server private key").await?;
+
+                let mut tls_config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+
+                match self.tls_client_ca {
+                    Some(ref client_ca) => {
+                        let client_ca = read_pem(client_ca, "client ca certificate").await?;
+                        tls_config = tls_config.client_ca_root(Certificate::from_pem(client_ca));
+                    }
+                    None => log::warn!(
+                        "`tls-client-ca` is not provided (any client can request signatures)"
+                    ),
+                }
+
+                server = server
+                    .tls_config(tls_config)
+                    .context("invalid tls configuration")?;
+            }
+            _ => log::warn!("`tls-cert` is not provided (signing service is served without TLS)"),
+        }
+
+        server
+            .add_service(SignerServer::new(SigningService { signer }))
+            .serve(self.addr)
+            .await
+            .context("unable to serve signing service")
+    }
+}
+
+/// Implementation of remote signing service using mnemonic signer
+struct SigningService {
+    signer: MnemonicSigner,
+}
+
+#[tonic::async_trait]
+impl SignerService for SigningService {
+    async fn get_public_key(
+        &self,
+        _request: Request<GetPublicKeyRequest>,
+    ) -> Result<Response<GetPublicKeyResponse>, Status> {
+        let public_key = self
+            .signer
+            .to_public_key()
+            .map_err(|err| Status::internal(format!("{:#}", err)))?;
+
+        let (algo, verifying_key) = match public_key {
+            PublicKey::Secp256k1(verifying_key) => ("secp256k1", verifying_key),
+            #[cfg(feature = "ethermint")]
+            PublicKey::EthSecp256k1(verifying_key) => ("eth-secp256k1", verifying_key),
+            _ => return Err(Status::internal("unsupported public key algorithm")),
+        };
+
+        Ok(Response::new(GetPublicKeyResponse {
+            algo: algo.to_owned(),
+            public_key: verifying_key.to_sec1_bytes().to_vec(),
+            account_prefix: self.signer.get_account_prefix().to_owned(),
+        }))
+    }
+
+    async fn sign(&self, request: Request<SignRequest>) -> Result<Response<SignResponse>, Status> {
+        let request = request.into_inner();
+
+        let message = match request.message_type() {
+            MessageType::SignBytes => Message::SignBytes(&request.message),
+            MessageType::SignDoc => Message::SignDoc(&request.message),
+            MessageType::Unspecified => {
+                return Err(Status::invalid_argument("message type is not specified"))
+            }
+        };
+
+        let description = match request.description.clone() {
+            Some(description) => MessageDescription::try_from(description)
+                .map_err(|err| Status::invalid_argument(format!("{:#}", err)))?,
+            None => {
+                return Err(Status::invalid_argument(
+                    "message description is not specified",
+                ))
+            }
+        };
+
+        // Description is provided by the client, so, it is checked against message before logging (or signing) it
+        description
+            .verify(&message)
+            .map_err(|err| Status::invalid_argument(format!("{:#}", err)))?;
+
+        log::info!(
+            "signing {} [request ID: {}]",
+            description,
+            request.request_id.as_deref().unwrap_or("-")
+        );
+
+        let signature = self
+            .signer
+            .sign(request.request_id.as_deref(), message, &description)
+            .await
+            .map_err(|err| Status::internal(format!("{:#}", err)))?;
+
+        Ok(Response::new(SignResponse { signature }))
+    }
+}
+
+async fn read_pem(path: &Path, name: &str) -> Result<Vec<u8>> {
+    tokio::fs::read(path)
+        .await
+        .with_context(|| format!("unable to read {} from {}", name, path.display()))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _ = dotenv::dotenv();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    Command::from_args().execute().await
+}
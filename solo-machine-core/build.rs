@@ -1,16 +1,23 @@
 use std::{
     error::Error,
     fs::{read_dir, DirEntry},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
+/// Protobuf files of solo machine's own services (compiled with both client and server)
+const SERVICE_PROTO_DIR: &str = "./proto/solo_machine";
+
 fn main() -> Result<(), Box<dyn Error>> {
     let mut files = Vec::new();
 
     let paths = read_dir("./proto")?;
 
     for path in paths {
-        files.extend(get_files(path?)?);
+        let path = path?;
+
+        if path.path() != Path::new(SERVICE_PROTO_DIR) {
+            files.extend(get_files(path)?);
+        }
     }
 
     tonic_build::configure()
@@ -26,6 +33,16 @@ fn main() -> Result<(), Box<dyn Error>> {
         .build_server(false)
         .compile_protos(&files, &["proto"])?;
 
+    let mut service_files = Vec::new();
+
+    for path in read_dir(SERVICE_PROTO_DIR)? {
+        service_files.extend(get_files(path?)?);
+    }
+
+    tonic_build::configure()
+        .protoc_arg("--experimental_allow_proto3_optional")
+        .compile_protos(&service_files, &["proto"])?;
+
     Ok(())
 }
 
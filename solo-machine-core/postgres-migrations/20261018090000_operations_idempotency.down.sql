@@ -0,0 +1,10 @@
+DELETE FROM operations
+WHERE status <> 'completed' OR denom IS NULL OR amount IS NULL OR transaction_hash IS NULL;
+
+DROP INDEX IF EXISTS operations_request_id_key;
+
+ALTER TABLE operations
+    DROP COLUMN status,
+    ALTER COLUMN denom SET NOT NULL,
+    ALTER COLUMN amount SET NOT NULL,
+    ALTER COLUMN transaction_hash SET NOT NULL;
@@ -0,0 +1,15 @@
+-- Duplicate request IDs recorded before this migration are suffixed with the operation ID so that the new uniqueness
+-- index can be applied. Request IDs are only required to be unique among operations which have not failed (so that
+-- failed operations can be retried with the same request ID).
+UPDATE operations
+SET request_id = request_id || '#' || id
+WHERE request_id IS NOT NULL
+    AND id <> (SELECT MIN(o.id) FROM operations o WHERE o.request_id = operations.request_id);
+
+ALTER TABLE operations
+    ALTER COLUMN denom DROP NOT NULL,
+    ALTER COLUMN amount DROP NOT NULL,
+    ALTER COLUMN transaction_hash DROP NOT NULL,
+    ADD COLUMN status TEXT NOT NULL DEFAULT 'completed';
+
+CREATE UNIQUE INDEX IF NOT EXISTS operations_request_id_key ON operations (request_id) WHERE status <> 'failed';
@@ -0,0 +1,14 @@
+DELETE FROM operations WHERE status = 'failed';
+
+UPDATE operations
+SET status = CASE WHEN status IN ('committed', 'acknowledged') THEN 'completed' ELSE 'pending' END;
+
+ALTER TABLE operations
+    DROP COLUMN error,
+    DROP COLUMN block_height,
+    DROP COLUMN gas_used,
+    DROP COLUMN fee_paid,
+    DROP COLUMN sequence,
+    DROP COLUMN packet_sequence,
+    DROP COLUMN updated_at,
+    ALTER COLUMN status SET DEFAULT 'completed';
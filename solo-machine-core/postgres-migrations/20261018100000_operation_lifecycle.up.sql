@@ -0,0 +1,25 @@
+-- Operations are now tracked through every stage of their lifecycle. Failed attempts are kept for tracing purposes.
+-- Sequences of solo machine before signing the transaction of an operation are recorded along with its broadcast so
+-- that they can be reverted if the transaction turns out to be rejected.
+ALTER TABLE operations
+    ALTER COLUMN status DROP DEFAULT,
+    ADD COLUMN error TEXT,
+    ADD COLUMN block_height BIGINT,
+    ADD COLUMN gas_used BIGINT,
+    ADD COLUMN fee_paid TEXT,
+    ADD COLUMN sequence BIGINT,
+    ADD COLUMN packet_sequence BIGINT,
+    ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+UPDATE operations
+SET
+    status = CASE
+        WHEN status = 'pending' THEN 'failed'
+        WHEN operation_type ? 'Mint' THEN 'acknowledged'
+        ELSE 'committed'
+    END,
+    error = CASE
+        WHEN status = 'pending' THEN 'operation was interrupted before its transaction was committed'
+        ELSE NULL
+    END,
+    updated_at = created_at;
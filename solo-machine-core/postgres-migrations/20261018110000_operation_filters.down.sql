@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS operations_chain_id;
+DROP INDEX IF EXISTS operations_address_id;
+
+ALTER TABLE operations
+    DROP COLUMN chain_id,
+    DROP COLUMN kind;
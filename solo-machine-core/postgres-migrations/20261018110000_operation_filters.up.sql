@@ -0,0 +1,21 @@
+-- Kind and chain ID of operations are stored in separate columns (in addition to `operation_type`) so that history
+-- can be filtered by them.
+ALTER TABLE operations
+    ADD COLUMN kind TEXT,
+    ADD COLUMN chain_id TEXT;
+
+UPDATE operations
+SET
+    kind = CASE
+        WHEN operation_type ? 'Mint' THEN 'mint'
+        WHEN operation_type ? 'Burn' THEN 'burn'
+        WHEN operation_type ? 'UpdateSigner' THEN 'update-signer'
+    END,
+    chain_id = COALESCE(
+        operation_type #>> '{Mint,chain_id,id}',
+        operation_type #>> '{Burn,chain_id,id}',
+        operation_type #>> '{UpdateSigner,chain_id,id}'
+    );
+
+CREATE INDEX IF NOT EXISTS operations_address_id ON operations (address, id);
+CREATE INDEX IF NOT EXISTS operations_chain_id ON operations (chain_id);
@@ -0,0 +1,14 @@
+-- Limits are stored as 32 bytes big endian u256 values (same as `operations.amount`). `NULL` means no limit.
+CREATE TABLE IF NOT EXISTS mint_policies (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id TEXT NOT NULL,
+    denom TEXT NOT NULL,
+    max_amount BYTEA,
+    daily_limit BYTEA,
+    weekly_limit BYTEA,
+    receiver_daily_limit BYTEA,
+    receiver_weekly_limit BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(chain_id, denom)
+);
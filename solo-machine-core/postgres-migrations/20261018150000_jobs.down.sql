@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS job_progress_job_id;
+
+DROP TABLE IF EXISTS job_progress;
+
+DROP INDEX IF EXISTS jobs_running_chain_id_key;
+
+DROP INDEX IF EXISTS jobs_chain_id;
+
+DROP TABLE IF EXISTS jobs;
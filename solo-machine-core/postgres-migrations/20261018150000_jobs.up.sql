@@ -0,0 +1,26 @@
+-- Long-running operations (e.g. connecting to an IBC enabled chain) which are executed in background by gRPC server.
+-- Only one job can be running for a chain at a time.
+CREATE TABLE IF NOT EXISTS jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    chain_id TEXT NOT NULL,
+    request_id TEXT,
+    status TEXT NOT NULL,
+    result JSONB,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS jobs_chain_id ON jobs(chain_id);
+CREATE UNIQUE INDEX IF NOT EXISTS jobs_running_chain_id_key ON jobs(chain_id) WHERE status = 'running';
+
+-- Events emitted while executing a job
+CREATE TABLE IF NOT EXISTS job_progress (
+    id BIGSERIAL PRIMARY KEY,
+    job_id BIGINT NOT NULL,
+    event JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS job_progress_job_id ON job_progress(job_id);
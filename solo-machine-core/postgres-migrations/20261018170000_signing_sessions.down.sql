@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS signing_sessions_open_chain_id_key;
+
+DROP INDEX IF EXISTS signing_sessions_chain_id;
+
+DROP TABLE IF EXISTS signing_sessions;
@@ -0,0 +1,27 @@
+-- Offline signing sessions of operations whose messages are signed on an air-gapped machine. Sequence and timestamp
+-- of solo machine are reserved for the session while it awaits signatures, so, only one session can be open for a
+-- chain at a time.
+CREATE TABLE IF NOT EXISTS signing_sessions (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id TEXT NOT NULL,
+    operation_id BIGINT NOT NULL,
+    request_id TEXT,
+    operation JSONB NOT NULL,
+    public_key JSONB NOT NULL,
+    account_prefix TEXT NOT NULL,
+    sequence BIGINT NOT NULL,
+    consensus_timestamp TIMESTAMPTZ NOT NULL,
+    timeout_height BIGINT,
+    packets JSONB NOT NULL,
+    status TEXT NOT NULL,
+    round BIGINT NOT NULL,
+    requests JSONB NOT NULL,
+    signatures JSONB NOT NULL,
+    transaction_hash TEXT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS signing_sessions_chain_id ON signing_sessions(chain_id);
+CREATE UNIQUE INDEX IF NOT EXISTS signing_sessions_open_chain_id_key ON signing_sessions(chain_id) WHERE status = 'awaiting-signatures';
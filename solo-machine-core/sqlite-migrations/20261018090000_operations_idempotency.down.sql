@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS operations_old (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    request_id TEXT,
+    address TEXT NOT NULL,
+    denom TEXT NOT NULL,
+    amount BLOB NOT NULL,
+    operation_type TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO operations_old (id, request_id, address, denom, amount, operation_type, transaction_hash, created_at)
+SELECT id, request_id, address, denom, amount, operation_type, transaction_hash, created_at
+FROM operations
+WHERE status = 'completed' AND denom IS NOT NULL AND amount IS NOT NULL AND transaction_hash IS NOT NULL;
+
+DROP TABLE operations;
+
+ALTER TABLE operations_old RENAME TO operations;
@@ -0,0 +1,37 @@
+-- SQLite cannot drop `NOT NULL` constraints in place, so the table is rebuilt. Duplicate request IDs recorded before
+-- this migration are suffixed with the operation ID so that the new uniqueness index can be applied. Request IDs are
+-- only required to be unique among operations which have not failed (so that failed operations can be retried with
+-- the same request ID).
+CREATE TABLE IF NOT EXISTS operations_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    request_id TEXT,
+    address TEXT NOT NULL,
+    denom TEXT,
+    amount BLOB,
+    operation_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'completed',
+    transaction_hash TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO operations_new (id, request_id, address, denom, amount, operation_type, transaction_hash, created_at)
+SELECT
+    id,
+    CASE
+        WHEN request_id IS NULL THEN NULL
+        WHEN id = (SELECT MIN(o.id) FROM operations o WHERE o.request_id = operations.request_id) THEN request_id
+        ELSE request_id || '#' || id
+    END,
+    address,
+    denom,
+    amount,
+    operation_type,
+    transaction_hash,
+    created_at
+FROM operations;
+
+DROP TABLE operations;
+
+ALTER TABLE operations_new RENAME TO operations;
+
+CREATE UNIQUE INDEX IF NOT EXISTS operations_request_id_key ON operations (request_id) WHERE status <> 'failed';
@@ -0,0 +1,32 @@
+DELETE FROM operations WHERE status = 'failed';
+
+CREATE TABLE IF NOT EXISTS operations_old (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    request_id TEXT,
+    address TEXT NOT NULL,
+    denom TEXT,
+    amount BLOB,
+    operation_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'completed',
+    transaction_hash TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO operations_old (id, request_id, address, denom, amount, operation_type, status, transaction_hash, created_at)
+SELECT
+    id,
+    request_id,
+    address,
+    denom,
+    amount,
+    operation_type,
+    CASE WHEN status IN ('committed', 'acknowledged') THEN 'completed' ELSE 'pending' END,
+    transaction_hash,
+    created_at
+FROM operations;
+
+DROP TABLE operations;
+
+ALTER TABLE operations_old RENAME TO operations;
+
+CREATE UNIQUE INDEX IF NOT EXISTS operations_request_id_key ON operations (request_id) WHERE status <> 'failed';
@@ -0,0 +1,50 @@
+-- Operations are now tracked through every stage of their lifecycle. Failed attempts are kept for tracing purposes.
+-- Sequences of solo machine before signing the transaction of an operation are recorded along with its broadcast so
+-- that they can be reverted if the transaction turns out to be rejected. SQLite cannot add columns with non-constant
+-- defaults, so the table is rebuilt (and its request ID index recreated).
+CREATE TABLE IF NOT EXISTS operations_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    request_id TEXT,
+    address TEXT NOT NULL,
+    denom TEXT,
+    amount BLOB,
+    operation_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    transaction_hash TEXT,
+    block_height BIGINT,
+    gas_used BIGINT,
+    fee_paid TEXT,
+    sequence BIGINT,
+    packet_sequence BIGINT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO operations_new (id, request_id, address, denom, amount, operation_type, status, transaction_hash, created_at, updated_at)
+SELECT
+    id,
+    request_id,
+    address,
+    denom,
+    amount,
+    operation_type,
+    CASE
+        WHEN status = 'pending' THEN 'failed'
+        WHEN operation_type LIKE '{"Mint"%' THEN 'acknowledged'
+        ELSE 'committed'
+    END,
+    transaction_hash,
+    created_at,
+    created_at
+FROM operations;
+
+UPDATE operations_new
+SET error = 'operation was interrupted before its transaction was committed'
+WHERE status = 'failed';
+
+DROP TABLE operations;
+
+ALTER TABLE operations_new RENAME TO operations;
+
+CREATE UNIQUE INDEX IF NOT EXISTS operations_request_id_key ON operations (request_id) WHERE status <> 'failed';
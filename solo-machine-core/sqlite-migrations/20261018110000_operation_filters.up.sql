@@ -0,0 +1,20 @@
+-- Kind and chain ID of operations are stored in separate columns (in addition to `operation_type`) so that history
+-- can be filtered by them.
+ALTER TABLE operations ADD COLUMN kind TEXT;
+ALTER TABLE operations ADD COLUMN chain_id TEXT;
+
+UPDATE operations
+SET
+    kind = CASE
+        WHEN json_extract(operation_type, '$.Mint') IS NOT NULL THEN 'mint'
+        WHEN json_extract(operation_type, '$.Burn') IS NOT NULL THEN 'burn'
+        WHEN json_extract(operation_type, '$.UpdateSigner') IS NOT NULL THEN 'update-signer'
+    END,
+    chain_id = COALESCE(
+        json_extract(operation_type, '$.Mint.chain_id.id'),
+        json_extract(operation_type, '$.Burn.chain_id.id'),
+        json_extract(operation_type, '$.UpdateSigner.chain_id.id')
+    );
+
+CREATE INDEX IF NOT EXISTS operations_address_id ON operations (address, id);
+CREATE INDEX IF NOT EXISTS operations_chain_id ON operations (chain_id);
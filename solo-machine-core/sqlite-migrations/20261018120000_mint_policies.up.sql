@@ -0,0 +1,14 @@
+-- Limits are stored as 32 bytes big endian u256 values (same as `operations.amount`). `NULL` means no limit.
+CREATE TABLE IF NOT EXISTS mint_policies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    chain_id TEXT NOT NULL,
+    denom TEXT NOT NULL,
+    max_amount BLOB,
+    daily_limit BLOB,
+    weekly_limit BLOB,
+    receiver_daily_limit BLOB,
+    receiver_weekly_limit BLOB,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(chain_id, denom)
+);
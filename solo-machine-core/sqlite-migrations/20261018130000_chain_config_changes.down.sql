@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS chain_config_changes_chain_id;
+
+DROP TABLE IF EXISTS chain_config_changes;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS chain_config_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    chain_id TEXT NOT NULL,
+    old_node_id TEXT NOT NULL,
+    new_node_id TEXT NOT NULL,
+    old_config TEXT NOT NULL,
+    new_config TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS chain_config_changes_chain_id ON chain_config_changes(chain_id);
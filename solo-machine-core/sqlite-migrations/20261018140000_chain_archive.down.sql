@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS archived_operations_chain_id;
+DROP INDEX IF EXISTS archived_ibc_data_chain_id;
+DROP INDEX IF EXISTS archived_chain_keys_chain_id;
+DROP INDEX IF EXISTS archived_chains_id;
+
+DROP TABLE IF EXISTS archived_operations;
+DROP TABLE IF EXISTS archived_ibc_data;
+DROP TABLE IF EXISTS archived_chain_keys;
+DROP TABLE IF EXISTS archived_chains;
@@ -0,0 +1,54 @@
+-- Archived chains (and their keys, IBC data and operations) are moved to these tables so that they are no longer part
+-- of the active set but are still available for auditing.
+CREATE TABLE IF NOT EXISTS archived_chains (
+    id TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    config TEXT NOT NULL,
+    consensus_timestamp DATETIME NOT NULL,
+    sequence BIGINT NOT NULL,
+    packet_sequence BIGINT NOT NULL,
+    connection_details TEXT,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    archived_at DATETIME NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS archived_chain_keys (
+    id BIGINT NOT NULL,
+    chain_id TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+    archived_at DATETIME NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS archived_ibc_data (
+    path TEXT NOT NULL,
+    data BLOB NOT NULL,
+    chain_id TEXT NOT NULL,
+    archived_at DATETIME NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS archived_operations (
+    id BIGINT NOT NULL,
+    request_id TEXT,
+    address TEXT NOT NULL,
+    denom TEXT,
+    amount BLOB,
+    operation_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    transaction_hash TEXT,
+    block_height BIGINT,
+    gas_used BIGINT,
+    fee_paid TEXT,
+    kind TEXT,
+    chain_id TEXT,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    archived_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS archived_chains_id ON archived_chains (id);
+CREATE INDEX IF NOT EXISTS archived_chain_keys_chain_id ON archived_chain_keys (chain_id);
+CREATE INDEX IF NOT EXISTS archived_ibc_data_chain_id ON archived_ibc_data (chain_id);
+CREATE INDEX IF NOT EXISTS archived_operations_chain_id ON archived_operations (chain_id);
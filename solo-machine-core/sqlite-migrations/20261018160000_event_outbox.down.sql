@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS event_handler_cursors;
+
+DROP TABLE IF EXISTS event_outbox;
@@ -0,0 +1,19 @@
+-- Events recorded in the same database transaction as the state change they describe (delivered to event handlers
+-- after they're committed). Position of an event is assigned once it is committed (so that events are delivered in the
+-- order they're committed; IDs are allocated at insert time, so a lower ID may be committed later).
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type TEXT NOT NULL,
+    event TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    position BIGINT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS event_outbox_position_key ON event_outbox(position);
+
+-- Position of the last event in outbox delivered to each event handler
+CREATE TABLE IF NOT EXISTS event_handler_cursors (
+    handler TEXT PRIMARY KEY,
+    position BIGINT NOT NULL,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
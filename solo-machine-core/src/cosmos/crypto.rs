@@ -68,23 +68,19 @@ impl FromStr for PublicKeyAlgo {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", content = "value")]
 pub enum PublicKey {
     #[cfg(feature = "ethermint")]
-    EthSecp256k1(
-        #[serde(
-            serialize_with = "serialize_verifying_key",
-            deserialize_with = "deserialize_verifying_key"
-        )]
-        k256::ecdsa::VerifyingKey,
-    ),
-    Secp256k1(
-        #[serde(
-            serialize_with = "serialize_verifying_key",
-            deserialize_with = "deserialize_verifying_key"
-        )]
-        k256::ecdsa::VerifyingKey,
-    ),
+    #[serde(
+        serialize_with = "serialize_verifying_key",
+        deserialize_with = "deserialize_verifying_key"
+    )]
+    EthSecp256k1(k256::ecdsa::VerifyingKey),
+    #[serde(
+        serialize_with = "serialize_verifying_key",
+        deserialize_with = "deserialize_verifying_key"
+    )]
+    Secp256k1(k256::ecdsa::VerifyingKey),
     Ed25519(ed25519_dalek::VerifyingKey),
     Multisig(MultisigPublicKey),
 }
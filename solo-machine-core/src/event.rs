@@ -4,12 +4,14 @@ mod event_handler;
 use anyhow::{anyhow, Result};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
-use tokio::sync::mpsc::UnboundedSender;
+use sqlx::Executor;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 use crate::{
     cosmos::crypto::PublicKey,
     ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier},
-    model::ConnectionDetails,
+    model::{outbox, ConnectionDetails},
+    Db,
 };
 
 pub use event_handler::*;
@@ -50,78 +52,238 @@ pub enum Event {
         /// Hash of transaction on IBC enabled chain (in hex)
         transaction_hash: String,
     },
+    /// Minting of tokens on IBC enabled chain was rejected by mint policy
+    MintRejected {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Address of account on IBC enabled chain
+        to_address: String,
+        /// Amount of tokens requested to be minted
+        amount: U256,
+        /// Denom of tokens requested to be minted
+        denom: Identifier,
+        /// Reason of rejection
+        reason: String,
+    },
+    /// Failed to mint tokens on IBC enabled chain
+    MintFailed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Address of account on IBC enabled chain
+        to_address: String,
+        /// Amount of tokens requested to be minted
+        amount: U256,
+        /// Denom of tokens requested to be minted
+        denom: Identifier,
+        /// Hash of transaction on IBC enabled chain (in hex, absent if no transaction was broadcasted)
+        transaction_hash: Option<String>,
+        /// `true` if outcome of the transaction is unknown, e.g., timed out (it may still get committed later)
+        timed_out: bool,
+        /// Error message (along with its causes)
+        error: String,
+    },
+    /// Failed to burn tokens on IBC enabled chain
+    BurnFailed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Address of account on IBC enabled chain
+        from_address: String,
+        /// Amount of tokens requested to be burnt
+        amount: U256,
+        /// Denom of tokens requested to be burnt
+        denom: Identifier,
+        /// Hash of transaction on IBC enabled chain (in hex, absent if no transaction was broadcasted)
+        transaction_hash: Option<String>,
+        /// `true` if outcome of the transaction is unknown, e.g., timed out (it may still get committed later)
+        timed_out: bool,
+        /// Error message (along with its causes)
+        error: String,
+    },
+    /// Sent acknowledgement of a packet (received from IBC enabled chain) to IBC enabled chain
+    AcknowledgementSent {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Sequence of acknowledged packet
+        packet_sequence: u64,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
     /// Updated signer's public key on IBC enabled change for future messages from solo machine
     SignerUpdated {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Old signer's public key
         old_public_key: PublicKey,
         /// New signer's public key
         new_public_key: PublicKey,
     },
+    /// Updated solo machine client on IBC enabled chain
+    SoloMachineClientUpdated {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Client ID of solo machine client on IBC enabled chain
+        client_id: ClientId,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Incremented sequence of solo machine for IBC enabled chain (i.e., solo machine signed a new message)
+    SequenceIncremented {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// New sequence of solo machine
+        sequence: u32,
+        /// Sequence of next packet sent by solo machine
+        packet_sequence: u32,
+    },
+    /// Broadcasted a transaction on IBC enabled chain
+    TransactionBroadcast {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Type of transaction (`mint`, `burn`, `update-signer` or `acknowledgement`)
+        transaction_type: String,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+        /// Time taken to broadcast the transaction and wait for it to be committed (in milliseconds)
+        duration_ms: u64,
+        /// Gas used by transaction (absent if the transaction was not executed)
+        gas_used: Option<i64>,
+        /// Class of error (`rejected`, `timeout` or `rpc`) if the transaction was not committed successfully
+        error_class: Option<String>,
+    },
 
     // ----- IBC connection handshake events ----- //
     /// Created solo machine client on IBC enabled chain
     CreatedSoloMachineClient {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Client ID of solo machine client on IBC enabled chain
         client_id: ClientId,
     },
     /// Created tendermint client on solo machine
     CreatedTendermintClient {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Client ID of IBC enabled chain on solo machine
         client_id: ClientId,
     },
     /// Initialized connection on IBC enabled chain
     InitializedConnectionOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of solo machine client on IBC enabled chain
         connection_id: ConnectionId,
     },
     /// Initialized connection on solo machine
     InitializedConnectionOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of IBC enabled chain on solo machine
         connection_id: ConnectionId,
     },
     /// Confirmed connection on IBC enabled chain
     ConfirmedConnectionOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of solo machine client on IBC enabled chain
         connection_id: ConnectionId,
     },
     /// Confirmed connection on solo machine
     ConfirmedConnectionOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection ID of IBC enabled chain on solo machine
         connection_id: ConnectionId,
     },
     /// Initialized channel on IBC enabled chain
     InitializedChannelOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of solo machine client on IBC enabled chain
         channel_id: ChannelId,
     },
     /// Close channel on IBC enabled chain
     CloseChannelInitOnSoloMachine {
         /// Chain ID of IBC enabled chain
-        chain_id: String,
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of IBC enabled chain on solo machine
         channel_id: ChannelId,
     },
     /// Initialized channel on solo machine
     InitializedChannelOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of IBC enabled chain on solo machine
         channel_id: ChannelId,
     },
     /// Confirmed channel on IBC enabled chain
     ConfirmedChannelOnTendermint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of solo machine client on IBC enabled chain
         channel_id: ChannelId,
     },
     /// Confirmed channel on solo machine
     ConfirmedChannelOnSoloMachine {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Channel ID of IBC enabled chain on solo machine
         channel_id: ChannelId,
     },
+    /// Closed channel on both solo machine and IBC enabled chain
+    ChannelClosed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
+        /// Channel ID of solo machine client on IBC enabled chain
+        solo_machine_channel_id: ChannelId,
+        /// Channel ID of IBC enabled chain on solo machine
+        tendermint_channel_id: ChannelId,
+    },
     /// Connection successfully established
     ConnectionEstablished {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
+        /// Optional request ID (for tracking purposes)
+        request_id: Option<String>,
         /// Connection details
         connection_details: ConnectionDetails,
     },
@@ -132,6 +294,38 @@ pub enum Event {
         /// Chain ID
         chain_id: ChainId,
     },
+    /// Updated configuration of chain
+    ChainConfigUpdated {
+        /// Chain ID
+        chain_id: ChainId,
+    },
+    /// Moved chain (along with its IBC data, public keys and operations) to archive
+    ChainArchived {
+        /// Chain ID
+        chain_id: ChainId,
+    },
+    /// Permanently removed chain (along with its IBC data, public keys and operations)
+    ChainRemoved {
+        /// Chain ID
+        chain_id: ChainId,
+    },
+
+    // ----- Reconciliation events ----- //
+    /// Total supply of IBC denom on IBC enabled chain does not match the supply recorded in solo machine's ledger
+    SupplyDiscrepancy {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Denom of tokens on solo machine
+        denom: Identifier,
+        /// IBC denom of tokens on IBC enabled chain
+        ibc_denom: String,
+        /// Supply recorded in solo machine's ledger (total minted minus total burnt)
+        ledger_supply: U256,
+        /// Total supply of IBC denom on IBC enabled chain
+        on_chain_supply: U256,
+        /// Balance of IBC denom held by escrow account of transfer channel on IBC enabled chain
+        escrow_balance: U256,
+    },
 
     // ----- Other events ----- //
     /// Warning
@@ -149,3 +343,55 @@ pub(crate) fn notify_event(notifier: &Option<UnboundedSender<Event>>, event: Eve
             .map_err(|err| anyhow!("unable to send event to notifier: {}", err)),
     }
 }
+
+/// Records event in event outbox and sends it to notifier. Event is recorded using given executor so that it is
+/// committed (or rolled back) along with the state change it describes. Events are recorded even when no notifier is
+/// configured (so that durable event handlers receive them later).
+///
+/// When executor is a database transaction, notifier should be the one returned by `PendingEvents::new()` so that the
+/// event is only sent to notifier after the transaction is committed.
+pub(crate) async fn record_event<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    notifier: &Option<UnboundedSender<Event>>,
+    event: Event,
+) -> Result<()> {
+    outbox::add_event(executor, &event).await?;
+    notify_event(notifier, event)
+}
+
+/// Events recorded in a database transaction which are sent to notifier only after the transaction is committed (so
+/// that event handlers never receive events of state changes which are rolled back)
+pub(crate) struct PendingEvents {
+    receiver: Option<UnboundedReceiver<Event>>,
+}
+
+impl PendingEvents {
+    /// Returns a notifier which collects events (only if `notifier` is configured) along with the collected events
+    pub fn new(
+        notifier: &Option<UnboundedSender<Event>>,
+    ) -> (Option<UnboundedSender<Event>>, Self) {
+        match notifier {
+            None => (None, Self { receiver: None }),
+            Some(_) => {
+                let (sender, receiver) = unbounded_channel();
+                (
+                    Some(sender),
+                    Self {
+                        receiver: Some(receiver),
+                    },
+                )
+            }
+        }
+    }
+
+    /// Sends collected events to notifier (should be called after the transaction is committed)
+    pub fn notify(self, notifier: &Option<UnboundedSender<Event>>) -> Result<()> {
+        if let Some(mut receiver) = self.receiver {
+            while let Ok(event) = receiver.try_recv() {
+                notify_event(notifier, event)?;
+            }
+        }
+
+        Ok(())
+    }
+}
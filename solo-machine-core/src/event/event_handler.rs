@@ -1,8 +1,17 @@
-use anyhow::Result;
+use std::{fmt, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Error, Result};
 use async_trait::async_trait;
 
 use super::Event;
 
+/// Default size of queue of an event handler
+pub const DEFAULT_QUEUE_SIZE: usize = 1024;
+/// Default timeout for handling a single event
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default number of retries (when error policy is `retry`)
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+
 /// Trait to handle events generated by solo machine
 #[async_trait]
 pub trait EventHandler: Sync + Send {
@@ -11,7 +20,70 @@ pub trait EventHandler: Sync + Send {
 }
 
 /// Trait to register different event handlers
-pub trait HandlerRegistrar: EventHandler {
-    /// Registers a new event handler
+pub trait HandlerRegistrar {
+    /// Registers a new event handler (with default options)
     fn register(&mut self, handler: Box<dyn EventHandler>);
+
+    /// Registers a new event handler with given options
+    fn register_with_options(&mut self, handler: Box<dyn EventHandler>, options: HandlerOptions);
+}
+
+/// Action taken when an event handler fails to handle an event (i.e., returns an error or times out)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Retries the event with exponential backoff. Events of durable event handlers which still fail are delivered
+    /// again later, whereas other event handlers drop the event.
+    Retry,
+    /// Logs the error and moves on to next event
+    Skip,
+    /// Logs the error and stops delivering events to the event handler (other event handlers are not affected)
+    Halt,
+}
+
+impl fmt::Display for ErrorPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Retry => write!(f, "retry"),
+            Self::Skip => write!(f, "skip"),
+            Self::Halt => write!(f, "halt"),
+        }
+    }
+}
+
+impl FromStr for ErrorPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "retry" => Ok(Self::Retry),
+            "skip" => Ok(Self::Skip),
+            "halt" => Ok(Self::Halt),
+            _ => Err(anyhow!("invalid error policy: {}", s)),
+        }
+    }
+}
+
+/// Options used for running an event handler (each event handler runs in its own task)
+#[derive(Debug, Clone, Copy)]
+pub struct HandlerOptions {
+    /// Maximum number of events queued for event handler (events are dropped when the queue of a non-durable event
+    /// handler is full)
+    pub queue_size: usize,
+    /// Action taken when event handler fails to handle an event
+    pub error_policy: ErrorPolicy,
+    /// Maximum time event handler can take to handle a single event
+    pub timeout: Duration,
+    /// Maximum number of retries for an event (when error policy is `retry`)
+    pub max_retries: u32,
+}
+
+impl Default for HandlerOptions {
+    fn default() -> Self {
+        Self {
+            queue_size: DEFAULT_QUEUE_SIZE,
+            error_policy: ErrorPolicy::Retry,
+            timeout: DEFAULT_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
 }
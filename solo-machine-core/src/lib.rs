@@ -48,6 +48,16 @@ pub async fn connect_db(connection_str: &str) -> Result<DbPool> {
         .context("unable to connect to database")
 }
 
+/// Checks if database is reachable
+pub async fn ping_db(db_pool: &DbPool) -> Result<()> {
+    sqlx::query("SELECT 1")
+        .execute(db_pool)
+        .await
+        .context("unable to reach database")?;
+
+    Ok(())
+}
+
 /// Runs all the migrations on database
 pub async fn run_migrations(db_pool: &DbPool) -> Result<()> {
     MIGRATOR
@@ -55,3 +65,60 @@ pub async fn run_migrations(db_pool: &DbPool) -> Result<()> {
         .await
         .context("unable to run migrations")
 }
+
+/// Connects to a new in-memory database with all the migrations applied (used in tests)
+#[cfg(all(test, not(feature = "postgres")))]
+pub(crate) async fn test_db_pool() -> DbPool {
+    let db_pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("unable to connect to in-memory database");
+
+    run_migrations(&db_pool)
+        .await
+        .expect("unable to run migrations");
+
+    db_pool
+}
+
+/// Adds an IBC enabled chain with given chain ID (and unreachable RPC addresses) to database (used in tests)
+#[cfg(all(test, not(feature = "postgres")))]
+pub(crate) async fn add_test_chain(db_pool: &DbPool, chain_id: &str) -> model::Chain {
+    use std::time::Duration;
+
+    let chain_id = chain_id.parse().unwrap();
+    let config = model::ChainConfig {
+        grpc_addr: "http://127.0.0.1:1".to_owned(),
+        rpc_addr: "http://127.0.0.1:1".to_owned(),
+        fallback_grpc_addrs: Vec::new(),
+        fallback_rpc_addrs: Vec::new(),
+        fee: model::Fee {
+            amount: "1000".parse().unwrap(),
+            denom: "stake".parse().unwrap(),
+            gas_limit: 300000,
+        },
+        trust_level: num_rational::Ratio::new(1, 3),
+        trusting_period: Duration::from_secs(14 * 24 * 60 * 60),
+        max_clock_drift: Duration::from_secs(3),
+        rpc_timeout: Duration::from_secs(60),
+        diversifier: "solo-machine-diversifier".to_owned(),
+        port_id: "transfer".parse().unwrap(),
+        trusted_height: 1u32.into(),
+        trusted_hash: [0; 32],
+    };
+
+    model::chain::add_chain(
+        db_pool,
+        &chain_id,
+        &"0000000000000000000000000000000000000000".parse().unwrap(),
+        &config,
+    )
+    .await
+    .unwrap();
+
+    model::chain::get_chain(db_pool, &chain_id)
+        .await
+        .unwrap()
+        .unwrap()
+}
@@ -1,12 +1,24 @@
 //! Data types used by solo machine
 pub(crate) mod chain;
 pub(crate) mod ibc;
+pub(crate) mod job;
 pub(crate) mod operation;
+pub(crate) mod outbox;
+pub(crate) mod policy;
+pub(crate) mod signing_session;
 
 pub use self::{
     chain::{
         chain_keys::ChainKey,
-        {Chain, ChainConfig, ConnectionDetails, Fee},
+        config_changes::ChainConfigChange,
+        EndpointHealth, EndpointKind,
+        {Chain, ChainConfig, ChainConfigUpdate, ConnectionDetails, ConnectionStatus, Fee},
     },
-    operation::{Operation, OperationType},
+    job::{Job, JobFilter, JobKind, JobProgress, JobStatus},
+    operation::{
+        Operation, OperationFilter, OperationKind, OperationPage, OperationStatus, OperationType,
+    },
+    outbox::{HandlerCursor, OutboxEvent},
+    policy::{MintLimits, MintPolicy},
+    signing_session::{OfflineOperation, SigningSession, SigningSessionStatus},
 };
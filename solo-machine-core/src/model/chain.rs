@@ -1,5 +1,11 @@
 #[allow(clippy::module_inception)]
 mod chain;
 pub mod chain_keys;
+pub mod config_changes;
+mod endpoint;
 
-pub use self::chain::*;
+pub(crate) use self::endpoint::RpcEndpoint;
+pub use self::{
+    chain::*,
+    endpoint::{EndpointHealth, EndpointKind},
+};
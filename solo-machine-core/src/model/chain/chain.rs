@@ -1,14 +1,21 @@
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
     time::Duration,
 };
 
 use anyhow::{anyhow, ensure, Context, Error, Result};
 use chrono::{DateTime, Utc};
-use ibc_proto::cosmos::bank::v1beta1::{
-    query_client::QueryClient as BankQueryClient, QueryBalanceRequest,
+use ibc_proto::{
+    cosmos::bank::v1beta1::{
+        query_client::QueryClient as BankQueryClient, QueryBalanceRequest, QuerySupplyOfRequest,
+    },
+    ibc::applications::transfer::v1::{
+        query_client::QueryClient as TransferQueryClient, QueryEscrowAddressRequest,
+    },
 };
 use num_rational::Ratio;
+use primitive_types::U256;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -72,20 +79,37 @@ impl Chain {
         Ok(format!("ibc/{}", hex::encode_upper(hash)))
     }
 
+    /// Returns the status of IBC connection with chain
+    pub fn connection_status(&self) -> ConnectionStatus {
+        match self.connection_details {
+            None => ConnectionStatus::NotConnected,
+            Some(ref connection_details)
+                if connection_details.solo_machine_channel_id.is_some() =>
+            {
+                ConnectionStatus::Open
+            }
+            Some(_) => ConnectionStatus::ChannelClosed,
+        }
+    }
+
     /// Fetches on-chain balance of given denom
     pub async fn get_balance(
         &self,
         signer: impl ToPublicKey,
         denom: &Identifier,
     ) -> Result<Decimal> {
-        let mut query_client = BankQueryClient::connect(self.config.grpc_addr.clone())
+        let denom = self.get_ibc_denom(denom)?;
+        self.query_balance(signer, denom).await
+    }
+
+    /// Fetches on-chain balance of fee denom (used for paying transaction fees on IBC enabled chain)
+    pub async fn get_fee_balance(&self, signer: impl ToPublicKey) -> Result<Decimal> {
+        self.query_balance(signer, self.config.fee.denom.to_string())
             .await
-            .context(format!(
-                "unable to connect to grpc query client at {}",
-                self.config.grpc_addr
-            ))?;
+    }
 
-        let denom = self.get_ibc_denom(denom)?;
+    async fn query_balance(&self, signer: impl ToPublicKey, denom: String) -> Result<Decimal> {
+        let mut query_client = BankQueryClient::new(self.grpc_channel().await?);
 
         let request = QueryBalanceRequest {
             address: signer.to_account_address()?,
@@ -101,6 +125,85 @@ impl Chain {
             .transpose()?
             .unwrap_or_default())
     }
+
+    /// Fetches on-chain total supply of IBC denom of given denom
+    pub async fn get_total_supply(&self, denom: &Identifier) -> Result<U256> {
+        let mut query_client = BankQueryClient::new(self.grpc_channel().await?);
+
+        let request = QuerySupplyOfRequest {
+            denom: self.get_ibc_denom(denom)?,
+        };
+
+        query_client
+            .supply_of(request)
+            .await?
+            .into_inner()
+            .amount
+            .map(|coin| U256::from_dec_str(&coin.amount))
+            .transpose()
+            .context("invalid total supply returned by IBC enabled chain")
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Fetches on-chain balance of IBC denom of given denom held by the escrow account of IBC enabled chain's transfer
+    /// channel
+    pub async fn get_escrow_balance(&self, denom: &Identifier) -> Result<U256> {
+        let tendermint_channel_id = self
+            .connection_details
+            .as_ref()
+            .and_then(|connection_details| connection_details.tendermint_channel_id.as_ref())
+            .ok_or_else(|| anyhow!("can't find tendermint channel, channel is already closed"))?;
+
+        let mut transfer_query_client = TransferQueryClient::new(self.grpc_channel().await?);
+
+        let escrow_address = transfer_query_client
+            .escrow_address(QueryEscrowAddressRequest {
+                port_id: self.config.port_id.to_string(),
+                channel_id: tendermint_channel_id.to_string(),
+            })
+            .await?
+            .into_inner()
+            .escrow_address;
+
+        let mut bank_query_client = BankQueryClient::new(self.grpc_channel().await?);
+
+        let request = QueryBalanceRequest {
+            address: escrow_address,
+            denom: self.get_ibc_denom(denom)?,
+        };
+
+        bank_query_client
+            .balance(request)
+            .await?
+            .into_inner()
+            .balance
+            .map(|coin| U256::from_dec_str(&coin.amount))
+            .transpose()
+            .context("invalid escrow balance returned by IBC enabled chain")
+            .map(Option::unwrap_or_default)
+    }
+}
+
+/// Status of IBC connection with an IBC enabled chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectionStatus {
+    /// Connection is not yet established
+    NotConnected,
+    /// Connection is established and channel is open
+    Open,
+    /// Connection is established but channel is closed
+    ChannelClosed,
+}
+
+impl fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotConnected => write!(f, "not-connected"),
+            Self::Open => write!(f, "open"),
+            Self::ChannelClosed => write!(f, "channel-closed"),
+        }
+    }
 }
 
 #[derive(Debug, FromRow)]
@@ -127,12 +230,18 @@ struct RawChain {
 }
 
 /// Configuration related to an IBC enabled chain
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainConfig {
     /// gRPC address
     pub grpc_addr: String,
     /// RPC address
     pub rpc_addr: String,
+    /// Fallback gRPC addresses (used when primary gRPC address is not reachable)
+    #[serde(default)]
+    pub fallback_grpc_addrs: Vec<String>,
+    /// Fallback RPC addresses (used when primary RPC address is not healthy and as light client witnesses)
+    #[serde(default)]
+    pub fallback_rpc_addrs: Vec<String>,
     /// Fee and gas limits
     pub fee: Fee,
     /// Trust level (e.g. 1/3)
@@ -154,8 +263,22 @@ pub struct ChainConfig {
     pub trusted_hash: [u8; 32],
 }
 
+impl ChainConfig {
+    /// Returns all the RPC addresses (primary address first, followed by fallback addresses)
+    pub fn rpc_addrs(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.rpc_addr.as_str())
+            .chain(self.fallback_rpc_addrs.iter().map(AsRef::as_ref))
+    }
+
+    /// Returns all the gRPC addresses (primary address first, followed by fallback addresses)
+    pub fn grpc_addrs(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.grpc_addr.as_str())
+            .chain(self.fallback_grpc_addrs.iter().map(AsRef::as_ref))
+    }
+}
+
 /// Fee and gas configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fee {
     /// Fee amount
     pub amount: Decimal,
@@ -165,6 +288,104 @@ pub struct Fee {
     pub gas_limit: u64,
 }
 
+/// Changes to configuration of an IBC enabled chain (`None` means unchanged)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ChainConfigUpdate {
+    /// gRPC address
+    pub grpc_addr: Option<String>,
+    /// RPC address
+    pub rpc_addr: Option<String>,
+    /// Fallback gRPC addresses (replaces all the existing fallback gRPC addresses)
+    pub fallback_grpc_addrs: Option<Vec<String>>,
+    /// Fallback RPC addresses (replaces all the existing fallback RPC addresses)
+    pub fallback_rpc_addrs: Option<Vec<String>>,
+    /// Fee amount
+    pub fee_amount: Option<Decimal>,
+    /// Denom of fee
+    pub fee_denom: Option<Identifier>,
+    /// Gas limit
+    pub gas_limit: Option<u64>,
+    /// Trust level (e.g. 1/3)
+    pub trust_level: Option<Ratio<u64>>,
+    /// Trusting period
+    pub trusting_period: Option<Duration>,
+    /// Maximum clock drift
+    pub max_clock_drift: Option<Duration>,
+    /// RPC timeout duration
+    pub rpc_timeout: Option<Duration>,
+    /// Diversifier used in transactions for chain (can only be changed before establishing connection)
+    pub diversifier: Option<String>,
+    /// Port ID used to create connection with chain (can only be changed before establishing connection)
+    pub port_id: Option<PortId>,
+    /// Trusted height of the chain (can only be changed before establishing connection)
+    pub trusted_height: Option<BlockHeight>,
+    /// Block hash at trusted height of the chain (can only be changed before establishing connection)
+    pub trusted_hash: Option<[u8; 32]>,
+}
+
+impl ChainConfigUpdate {
+    /// Returns `true` if this update changes a field which is fixed once connection is established
+    pub fn changes_connection_params(&self) -> bool {
+        self.diversifier.is_some()
+            || self.port_id.is_some()
+            || self.trusted_height.is_some()
+            || self.trusted_hash.is_some()
+    }
+
+    /// Applies changes on given configuration and returns the updated configuration
+    pub fn apply(self, config: &ChainConfig) -> ChainConfig {
+        let mut config = config.clone();
+
+        if let Some(grpc_addr) = self.grpc_addr {
+            config.grpc_addr = grpc_addr;
+        }
+        if let Some(rpc_addr) = self.rpc_addr {
+            config.rpc_addr = rpc_addr;
+        }
+        if let Some(fallback_grpc_addrs) = self.fallback_grpc_addrs {
+            config.fallback_grpc_addrs = fallback_grpc_addrs;
+        }
+        if let Some(fallback_rpc_addrs) = self.fallback_rpc_addrs {
+            config.fallback_rpc_addrs = fallback_rpc_addrs;
+        }
+        if let Some(fee_amount) = self.fee_amount {
+            config.fee.amount = fee_amount;
+        }
+        if let Some(fee_denom) = self.fee_denom {
+            config.fee.denom = fee_denom;
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            config.fee.gas_limit = gas_limit;
+        }
+        if let Some(trust_level) = self.trust_level {
+            config.trust_level = trust_level;
+        }
+        if let Some(trusting_period) = self.trusting_period {
+            config.trusting_period = trusting_period;
+        }
+        if let Some(max_clock_drift) = self.max_clock_drift {
+            config.max_clock_drift = max_clock_drift;
+        }
+        if let Some(rpc_timeout) = self.rpc_timeout {
+            config.rpc_timeout = rpc_timeout;
+        }
+        if let Some(diversifier) = self.diversifier {
+            config.diversifier = diversifier;
+        }
+        if let Some(port_id) = self.port_id {
+            config.port_id = port_id;
+        }
+        if let Some(trusted_height) = self.trusted_height {
+            config.trusted_height = trusted_height;
+        }
+        if let Some(trusted_hash) = self.trusted_hash {
+            config.trusted_hash = trusted_hash;
+        }
+
+        config
+    }
+}
+
 /// IBC connection details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionDetails {
@@ -261,6 +482,87 @@ pub async fn get_chain<'e>(
         .transpose()
 }
 
+/// Fetches chain with given chain ID from database and locks it until the end of current database transaction. SQLite
+/// does not support row locks, but a transaction which writes after this read fails if some other transaction has
+/// written to database in the meantime.
+pub async fn get_chain_for_update<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Option<Chain>> {
+    #[cfg(not(feature = "postgres"))]
+    const QUERY: &str = "SELECT * FROM chains WHERE id = $1";
+    #[cfg(feature = "postgres")]
+    const QUERY: &str = "SELECT * FROM chains WHERE id = $1 FOR UPDATE";
+
+    sqlx::query_as(QUERY)
+        .bind(chain_id.to_string())
+        .fetch_optional(executor)
+        .await
+        .context("unable to query chain from database")?
+        .map(|raw: RawChain| raw.try_into())
+        .transpose()
+}
+
+/// Fetches all the chains from database
+pub async fn get_chains<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    limit: i32,
+    offset: i32,
+) -> Result<Vec<Chain>> {
+    let raw: Vec<RawChain> = sqlx::query_as("SELECT * FROM chains ORDER BY id LIMIT $1 OFFSET $2")
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(executor)
+        .await
+        .context("unable to query chains from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Copies chain to archive
+pub async fn archive_chain<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    archived_at: DateTime<Utc>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO archived_chains (id, node_id, config, consensus_timestamp, sequence, packet_sequence, connection_details, created_at, updated_at, archived_at) SELECT id, node_id, config, consensus_timestamp, sequence, packet_sequence, connection_details, created_at, updated_at, $1 FROM chains WHERE id = $2",
+    )
+    .bind(archived_at)
+    .bind(chain_id.to_string())
+    .execute(executor)
+    .await
+    .context("unable to archive chain")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when archiving chain"
+    );
+
+    Ok(())
+}
+
+/// Deletes chain from database
+pub async fn delete_chain<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<()> {
+    let rows_affected = sqlx::query("DELETE FROM chains WHERE id = $1")
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to delete chain")?
+        .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when deleting chain"
+    );
+
+    Ok(())
+}
+
 /// Adds connection details for given chain id
 pub async fn add_connection_details<'e>(
     executor: impl Executor<'e, Database = Db>,
@@ -287,6 +589,27 @@ pub async fn add_connection_details<'e>(
     Ok(())
 }
 
+/// Updates configuration (and node ID) of given chain
+pub async fn update_chain_config<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    node_id: &NodeId,
+    config: &ChainConfig,
+) -> Result<Chain> {
+    let raw: RawChain = sqlx::query_as(
+        "UPDATE chains SET node_id = $1, config = $2, updated_at = $3 WHERE id = $4 RETURNING *",
+    )
+    .bind(node_id.to_string())
+    .bind(Json(config))
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .fetch_one(executor)
+    .await
+    .context("unable to update config of a chain")?;
+
+    raw.try_into()
+}
+
 pub async fn increment_sequence<'e>(
     executor: impl Executor<'e, Database = Db>,
     chain_id: &ChainId,
@@ -318,3 +641,27 @@ pub async fn increment_packet_sequence<'e>(
 
     raw.try_into()
 }
+
+/// Rewinds sequences of a chain to `previous` (i.e., `(sequence, packet_sequence)` before signing a transaction, with a
+/// single solo machine signature, which was rejected by IBC enabled chain) only if no other transaction was signed after
+/// it. Returns `false` if the sequences were changed by some other operation in the meantime.
+pub async fn revert_sequences<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    previous: (u32, u32),
+) -> Result<bool> {
+    let rows_affected = sqlx::query(
+        "UPDATE chains SET sequence = $1, packet_sequence = $2, updated_at = $3 WHERE id = $4 AND sequence = $5",
+    )
+    .bind(i64::from(previous.0))
+    .bind(i64::from(previous.1))
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(i64::from(previous.0) + 1)
+    .execute(executor)
+    .await
+    .context("unable to revert sequences of a chain")?
+    .rows_affected();
+
+    Ok(rows_affected == 1)
+}
@@ -79,6 +79,23 @@ pub async fn add_chain_key<'e>(
     Ok(())
 }
 
+/// Removes given public key from the chain keys of given chain (e.g. when the signer update which added it was rejected
+/// by IBC enabled chain)
+pub async fn remove_chain_key<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    public_key: &str,
+) -> Result<()> {
+    sqlx::query("DELETE FROM chain_keys WHERE chain_id = $1 AND public_key = $2")
+        .bind(chain_id.to_string())
+        .bind(public_key)
+        .execute(executor)
+        .await
+        .context("unable to remove chain key")?;
+
+    Ok(())
+}
+
 pub async fn get_chain_keys<'e>(
     executor: impl Executor<'e, Database = Db>,
     chain_id: &ChainId,
@@ -97,3 +114,35 @@ pub async fn get_chain_keys<'e>(
 
     chain_keys.into_iter().map(TryFrom::try_from).collect()
 }
+
+/// Copies all the chain keys of given chain to archive
+pub async fn archive_chain_keys<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    archived_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO archived_chain_keys (id, chain_id, public_key, created_at, archived_at) SELECT id, chain_id, public_key, created_at, $1 FROM chain_keys WHERE chain_id = $2",
+    )
+    .bind(archived_at)
+    .bind(chain_id.to_string())
+    .execute(executor)
+    .await
+    .context("unable to archive chain keys")?;
+
+    Ok(())
+}
+
+/// Deletes all the chain keys of given chain
+pub async fn delete_chain_keys<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<()> {
+    sqlx::query("DELETE FROM chain_keys WHERE chain_id = $1")
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to delete chain keys")?;
+
+    Ok(())
+}
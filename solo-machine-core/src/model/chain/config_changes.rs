@@ -0,0 +1,121 @@
+use std::convert::TryFrom;
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, Executor, FromRow};
+use tendermint::node::Id as NodeId;
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db};
+
+use super::ChainConfig;
+
+/// Audit trail entry of a change in configuration of an IBC enabled chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChainConfigChange {
+    /// ID of config change
+    pub id: i64,
+    /// Chain ID
+    pub chain_id: ChainId,
+    /// Node ID of chain before the change
+    pub old_node_id: NodeId,
+    /// Node ID of chain after the change
+    pub new_node_id: NodeId,
+    /// Configuration of chain before the change
+    pub old_config: ChainConfig,
+    /// Configuration of chain after the change
+    pub new_config: ChainConfig,
+    /// Time at which the change was made
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw audit trail entry of a change in configuration of an IBC enabled chain
+struct RawChainConfigChange {
+    /// ID of config change
+    pub id: i64,
+    /// Chain ID
+    pub chain_id: String,
+    /// Node ID of chain before the change
+    pub old_node_id: String,
+    /// Node ID of chain after the change
+    pub new_node_id: String,
+    /// Configuration of chain before the change
+    pub old_config: Json<ChainConfig>,
+    /// Configuration of chain after the change
+    pub new_config: Json<ChainConfig>,
+    /// Time at which the change was made
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawChainConfigChange> for ChainConfigChange {
+    type Error = Error;
+
+    fn try_from(raw: RawChainConfigChange) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            old_node_id: raw
+                .old_node_id
+                .parse()
+                .map_err(|err| anyhow!("unable to parse node id: {}", err))?,
+            new_node_id: raw
+                .new_node_id
+                .parse()
+                .map_err(|err| anyhow!("unable to parse node id: {}", err))?,
+            old_config: raw.old_config.0,
+            new_config: raw.new_config.0,
+            created_at: raw.created_at,
+        })
+    }
+}
+
+/// Records a change in configuration of an IBC enabled chain
+pub async fn add_chain_config_change<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    old_node_id: &NodeId,
+    new_node_id: &NodeId,
+    old_config: &ChainConfig,
+    new_config: &ChainConfig,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO chain_config_changes (chain_id, old_node_id, new_node_id, old_config, new_config) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(chain_id.to_string())
+    .bind(old_node_id.to_string())
+    .bind(new_node_id.to_string())
+    .bind(Json(old_config))
+    .bind(Json(new_config))
+    .execute(executor)
+    .await
+    .context("unable to add chain config change")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding chain config change"
+    );
+
+    Ok(())
+}
+
+/// Fetches changes in configuration of an IBC enabled chain (latest first)
+pub async fn get_chain_config_changes<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    limit: i32,
+    offset: i32,
+) -> Result<Vec<ChainConfigChange>> {
+    let changes: Vec<RawChainConfigChange> = sqlx::query_as(
+        "SELECT * FROM chain_config_changes WHERE chain_id = $1 ORDER BY id DESC LIMIT $2 OFFSET $3",
+    )
+    .bind(chain_id.to_string())
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(executor)
+    .await
+    .context("unable to query chain config changes from database")?;
+
+    changes.into_iter().map(TryFrom::try_from).collect()
+}
@@ -0,0 +1,474 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use serde::{Deserialize, Serialize};
+use tendermint::node::Id as NodeId;
+use tendermint_rpc::{endpoint::status::Response as StatusResponse, Client, HttpClient};
+use tonic::transport::{Channel, Endpoint};
+
+use crate::ibc::core::ics24_host::identifier::ChainId;
+
+use super::Chain;
+
+/// Duration for which the result of health check of an RPC endpoint is reused
+const RPC_HEALTH_TTL: Duration = Duration::from_secs(10);
+
+/// Time and result of health check of an RPC endpoint
+type RpcHealth = (Instant, Result<StatusResponse, String>);
+
+/// Chain ID, address and expected node ID (only for primary endpoint) of an RPC endpoint
+type RpcHealthKey = (String, String, Option<String>);
+
+/// Results of recent health checks of RPC endpoints (keyed by chain ID, address of endpoint and node ID expected from
+/// endpoint, so that a cached result is not reused after node ID of chain is changed)
+static RPC_HEALTH: Mutex<BTreeMap<RpcHealthKey, RpcHealth>> = Mutex::new(BTreeMap::new());
+
+/// Type of endpoint of an IBC enabled chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EndpointKind {
+    /// Tendermint RPC endpoint
+    Rpc,
+    /// Cosmos SDK gRPC endpoint
+    Grpc,
+}
+
+impl fmt::Display for EndpointKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc => write!(f, "rpc"),
+            Self::Grpc => write!(f, "grpc"),
+        }
+    }
+}
+
+/// Result of health check of an RPC or gRPC endpoint of an IBC enabled chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    /// Type of endpoint
+    pub kind: EndpointKind,
+    /// Address of endpoint
+    pub addr: String,
+    /// `true` if this is the primary endpoint (i.e., `rpc_addr` or `grpc_addr`), `false` for fallback endpoints
+    pub is_primary: bool,
+    /// `true` if endpoint is reachable, reports the same chain ID and (for RPC endpoints) is not catching up
+    pub is_healthy: bool,
+    /// Node ID reported by RPC endpoint
+    pub node_id: Option<NodeId>,
+    /// Latest block height reported by RPC endpoint
+    pub latest_height: Option<u64>,
+    /// Reason of endpoint being unhealthy
+    pub error: Option<String>,
+}
+
+/// A healthy RPC endpoint of an IBC enabled chain
+pub(crate) struct RpcEndpoint {
+    /// Address of endpoint
+    pub addr: String,
+    /// RPC client connected to endpoint
+    pub client: HttpClient,
+    /// Node ID of endpoint (stored node ID of chain for primary endpoint)
+    pub node_id: NodeId,
+    /// `true` if this is the primary endpoint
+    pub is_primary: bool,
+}
+
+impl Chain {
+    /// Checks health of all the RPC and gRPC endpoints (primary and fallback) of chain
+    pub async fn check_endpoints(&self) -> Vec<EndpointHealth> {
+        let mut health = Vec::new();
+
+        for (index, addr) in self.config.rpc_addrs().enumerate() {
+            let (is_healthy, node_id, latest_height, error) =
+                match self.check_rpc(addr, index == 0, false).await {
+                    Ok((_, status)) => (
+                        true,
+                        Some(status.node_info.id),
+                        Some(status.sync_info.latest_block_height.value()),
+                        None,
+                    ),
+                    Err(err) => (false, None, None, Some(format!("{:#}", err))),
+                };
+
+            health.push(EndpointHealth {
+                kind: EndpointKind::Rpc,
+                addr: addr.to_string(),
+                is_primary: index == 0,
+                is_healthy,
+                node_id,
+                latest_height,
+                error,
+            });
+        }
+
+        for (index, addr) in self.config.grpc_addrs().enumerate() {
+            let error = self
+                .connect_grpc(addr)
+                .await
+                .err()
+                .map(|err| format!("{:#}", err));
+
+            health.push(EndpointHealth {
+                kind: EndpointKind::Grpc,
+                addr: addr.to_string(),
+                is_primary: index == 0,
+                is_healthy: error.is_none(),
+                node_id: None,
+                latest_height: None,
+                error,
+            });
+        }
+
+        health
+    }
+
+    /// Returns the first healthy RPC endpoint of chain (primary endpoint is preferred over fallback endpoints)
+    pub(crate) async fn rpc_endpoint(&self) -> Result<RpcEndpoint> {
+        let mut errors = Vec::new();
+
+        for (index, addr) in self.config.rpc_addrs().enumerate() {
+            match self.check_rpc(addr, index == 0, true).await {
+                Ok((client, status)) => {
+                    return Ok(RpcEndpoint {
+                        addr: addr.to_string(),
+                        client,
+                        node_id: self.endpoint_node_id(index == 0, &status),
+                        is_primary: index == 0,
+                    })
+                }
+                Err(err) => errors.push(format!("{}: {:#}", addr, err)),
+            }
+        }
+
+        Err(anyhow!(
+            "no healthy rpc endpoint found for chain {}: [{}]",
+            self.id,
+            errors.join("; ")
+        ))
+    }
+
+    /// Returns all the healthy RPC endpoints of chain (primary endpoint first)
+    pub(crate) async fn rpc_endpoints(&self) -> Result<Vec<RpcEndpoint>> {
+        let mut endpoints = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, addr) in self.config.rpc_addrs().enumerate() {
+            match self.check_rpc(addr, index == 0, true).await {
+                Ok((client, status)) => endpoints.push(RpcEndpoint {
+                    addr: addr.to_string(),
+                    client,
+                    node_id: self.endpoint_node_id(index == 0, &status),
+                    is_primary: index == 0,
+                }),
+                Err(err) => errors.push(format!("{}: {:#}", addr, err)),
+            }
+        }
+
+        ensure!(
+            !endpoints.is_empty(),
+            "no healthy rpc endpoint found for chain {}: [{}]",
+            self.id,
+            errors.join("; ")
+        );
+
+        Ok(endpoints)
+    }
+
+    /// Returns a gRPC channel connected to the first reachable gRPC endpoint of chain (primary endpoint is preferred
+    /// over fallback endpoints)
+    pub(crate) async fn grpc_channel(&self) -> Result<Channel> {
+        let mut errors = Vec::new();
+
+        for addr in self.config.grpc_addrs() {
+            match self.connect_grpc(addr).await {
+                Ok(channel) => return Ok(channel),
+                Err(err) => errors.push(format!("{}: {:#}", addr, err)),
+            }
+        }
+
+        Err(anyhow!(
+            "unable to connect to any grpc endpoint of chain {}: [{}]",
+            self.id,
+            errors.join("; ")
+        ))
+    }
+
+    /// Returns node ID of an RPC endpoint. Node ID of primary endpoint is the one stored along with chain (which is
+    /// verified while checking its health), fallback endpoints use the node ID reported by them.
+    fn endpoint_node_id(&self, is_primary: bool, status: &StatusResponse) -> NodeId {
+        if is_primary {
+            self.node_id
+        } else {
+            status.node_info.id
+        }
+    }
+
+    /// Returns an RPC client (with configured timeout) along with status of node if the RPC endpoint is healthy.
+    /// Result of health check is reused for a short duration when `use_cache` is `true`.
+    async fn check_rpc(
+        &self,
+        addr: &str,
+        is_primary: bool,
+        use_cache: bool,
+    ) -> Result<(HttpClient, StatusResponse)> {
+        let client = HttpClient::builder(
+            addr.parse()
+                .map_err(|err| anyhow!("invalid rpc address: {}", err))?,
+        )
+        .timeout(self.config.rpc_timeout)
+        .build()
+        .context("unable to connect to rpc client")?;
+
+        // Node ID is only verified for primary endpoint
+        let key = (
+            self.id.to_string(),
+            addr.to_owned(),
+            is_primary.then(|| self.node_id.to_string()),
+        );
+
+        if use_cache {
+            let cached = RPC_HEALTH
+                .lock()
+                .map_err(|_| anyhow!("rpc health cache poisoned"))?
+                .get(&key)
+                .filter(|(checked_at, _)| checked_at.elapsed() < RPC_HEALTH_TTL)
+                .map(|(_, result)| result.clone());
+
+            if let Some(result) = cached {
+                return result.map(|status| (client, status)).map_err(Error::msg);
+            }
+        }
+
+        let result = self.fetch_status(&client, is_primary).await;
+
+        RPC_HEALTH
+            .lock()
+            .map_err(|_| anyhow!("rpc health cache poisoned"))?
+            .insert(
+                key,
+                (
+                    Instant::now(),
+                    result
+                        .as_ref()
+                        .map(Clone::clone)
+                        .map_err(|err| format!("{:#}", err)),
+                ),
+            );
+
+        result.map(|status| (client, status))
+    }
+
+    /// Fetches status of node and ensures that it is healthy (node ID of primary endpoint should match the one stored
+    /// along with chain)
+    async fn fetch_status(&self, client: &HttpClient, is_primary: bool) -> Result<StatusResponse> {
+        let status = client
+            .status()
+            .await
+            .context("unable to fetch node status")?;
+
+        let chain_id: ChainId = status.node_info.network.to_string().parse()?;
+        ensure!(
+            chain_id == self.id,
+            "node reports chain ID {} instead of {}",
+            chain_id,
+            self.id
+        );
+        ensure!(
+            !is_primary || status.node_info.id == self.node_id,
+            "node reports node ID {} instead of {} (update chain config to change node)",
+            status.node_info.id,
+            self.node_id
+        );
+        ensure!(!status.sync_info.catching_up, "node is catching up");
+
+        Ok(status)
+    }
+
+    async fn connect_grpc(&self, addr: &str) -> Result<Channel> {
+        Endpoint::from_shared(addr.to_string())
+            .map_err(Error::from)
+            .context("invalid grpc address")?
+            .connect_timeout(self.config.rpc_timeout)
+            .connect()
+            .await
+            .context("unable to connect to grpc endpoint")
+    }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const NODE_ID: &str = "1111111111111111111111111111111111111111";
+
+    /// Serves tendermint RPC `status` of a node with given chain ID and node ID, and returns its address
+    fn serve_status(chain_id: &str, node_id: &str, catching_up: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+
+        let body = serde_json::json!({
+            "id": "1",
+            "jsonrpc": "2.0",
+            "result": {
+                "node_info": {
+                    "channels": "40202122233038606100",
+                    "id": node_id,
+                    "listen_addr": "tcp://0.0.0.0:26656",
+                    "moniker": "test",
+                    "network": chain_id,
+                    "other": { "rpc_address": "tcp://0.0.0.0:26657", "tx_index": "on" },
+                    "protocol_version": { "app": "1", "block": "11", "p2p": "8" },
+                    "version": "0.34.21"
+                },
+                "sync_info": {
+                    "catching_up": catching_up,
+                    "earliest_app_hash": "",
+                    "earliest_block_hash": "56527562E5142C279254641CE18DB0D845767F2933AAFB784D752905ABF410E8",
+                    "earliest_block_height": "1",
+                    "earliest_block_time": "2022-09-22T18:57:22.193215438Z",
+                    "latest_app_hash": "0600000000000000",
+                    "latest_block_hash": "030CFF86E5DC2007E3B88C675984F2E66E0E0E9DE5835116DBB65BA58C3C47F9",
+                    "latest_block_height": "67",
+                    "latest_block_time": "2022-09-22T18:57:57.686014484Z"
+                },
+                "validator_info": {
+                    "address": "675F52E8FDA5F4047B8EAF498F946F551ED53DC2",
+                    "pub_key": {
+                        "type": "tendermint/PubKeyEd25519",
+                        "value": "OKAnhjqSneoGRrC37lMmw13vpj3pge3Au8/5Q1YfGq0="
+                    },
+                    "voting_power": "10"
+                }
+            }
+        })
+        .to_string();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+
+                let mut request = vec![0; content_length];
+                reader.read_exact(&mut request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        addr
+    }
+
+    async fn test_chain(rpc_addr: String, fallback_rpc_addrs: Vec<String>) -> Chain {
+        let db_pool = crate::test_db_pool().await;
+        let mut chain = crate::add_test_chain(&db_pool, "test-1").await;
+        chain.node_id = NODE_ID.parse().unwrap();
+        chain.config.rpc_addr = rpc_addr;
+        chain.config.fallback_rpc_addrs = fallback_rpc_addrs;
+        chain.config.rpc_timeout = Duration::from_secs(5);
+        chain
+    }
+
+    #[tokio::test]
+    async fn rpc_endpoint_prefers_healthy_primary() {
+        let primary = serve_status("test-1", NODE_ID, false);
+        let fallback = serve_status("test-1", "2222222222222222222222222222222222222222", false);
+        let chain = test_chain(primary.clone(), vec![fallback]).await;
+
+        let endpoint = chain.rpc_endpoint().await.unwrap();
+        assert_eq!(endpoint.addr, primary);
+        assert!(endpoint.is_primary);
+        assert_eq!(endpoint.node_id, chain.node_id);
+
+        assert_eq!(chain.rpc_endpoints().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rpc_endpoint_fails_over_to_healthy_fallback() {
+        let other_chain = serve_status("test-2", NODE_ID, false);
+        let catching_up = serve_status("test-1", NODE_ID, true);
+        let fallback_node_id = "2222222222222222222222222222222222222222";
+        let fallback = serve_status("test-1", fallback_node_id, false);
+        let chain = test_chain(
+            "http://127.0.0.1:1".to_owned(),
+            vec![other_chain, catching_up, fallback.clone()],
+        )
+        .await;
+
+        let endpoint = chain.rpc_endpoint().await.unwrap();
+        assert_eq!(endpoint.addr, fallback);
+        assert!(!endpoint.is_primary);
+        assert_eq!(endpoint.node_id, fallback_node_id.parse().unwrap());
+
+        let endpoints = chain.rpc_endpoints().await.unwrap();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].addr, fallback);
+    }
+
+    #[tokio::test]
+    async fn primary_with_different_node_id_is_unhealthy() {
+        // Node ID is only verified for primary endpoint
+        let primary = serve_status("test-1", "3333333333333333333333333333333333333333", false);
+        let chain = test_chain(primary, Vec::new()).await;
+
+        let err = chain.rpc_endpoint().await.err().unwrap();
+        assert!(err.to_string().contains("no healthy rpc endpoint"));
+        assert!(err.to_string().contains("node ID"));
+        assert!(chain.rpc_endpoints().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn check_endpoints_reports_health_of_all_endpoints() {
+        let fallback = serve_status("test-1", NODE_ID, false);
+        let chain = test_chain("http://127.0.0.1:1".to_owned(), vec![fallback.clone()]).await;
+
+        let health = chain.check_endpoints().await;
+        assert_eq!(health.len(), 3);
+
+        assert_eq!(health[0].kind, EndpointKind::Rpc);
+        assert!(health[0].is_primary);
+        assert!(!health[0].is_healthy);
+        assert!(health[0].error.is_some());
+
+        assert_eq!(health[1].kind, EndpointKind::Rpc);
+        assert_eq!(health[1].addr, fallback);
+        assert!(!health[1].is_primary);
+        assert!(health[1].is_healthy);
+        assert_eq!(health[1].latest_height, Some(67));
+
+        assert_eq!(health[2].kind, EndpointKind::Grpc);
+        assert!(!health[2].is_healthy);
+    }
+}
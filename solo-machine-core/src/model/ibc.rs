@@ -1,4 +1,5 @@
 use anyhow::{ensure, Context, Result};
+use chrono::{DateTime, Utc};
 use ibc_proto::ibc::{
     core::{channel::v1::Channel, client::v1::Height, connection::v1::ConnectionEnd},
     lightclients::tendermint::v1::{
@@ -6,11 +7,11 @@ use ibc_proto::ibc::{
     },
 };
 use prost::Message;
-use sqlx::{Executor, FromRow};
+use sqlx::{Executor, FromRow, QueryBuilder};
 
 use crate::{
     ibc::core::ics24_host::{
-        identifier::{ChannelId, ClientId, ConnectionId, PortId},
+        identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
         path::{ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath},
     },
     proto::proto_encode,
@@ -19,7 +20,6 @@ use crate::{
 
 #[derive(Debug, FromRow)]
 struct IbcData {
-    #[allow(dead_code)]
     path: String,
     data: Vec<u8>,
 }
@@ -58,6 +58,16 @@ pub async fn add_tendermint_consensus_state<'e>(
     add(executor, path.get_key(0).unwrap(), &data).await
 }
 
+/// Fetches tendermint consensus state at given height from database
+pub async fn get_tendermint_consensus_state<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    client_id: &ClientId,
+    height: &Height,
+) -> Result<Option<TendermintConsensusState>> {
+    let path = ConsensusStatePath::new(client_id, height);
+    get(executor, path.get_key(0).unwrap()).await
+}
+
 /// Adds connection to database
 pub async fn add_connection<'e>(
     executor: impl Executor<'e, Database = Db>,
@@ -127,6 +137,119 @@ pub async fn update_channel<'e>(
     update(executor, path.get_key(0).unwrap(), &data).await
 }
 
+/// Fetches paths of all the channels on given port which use given connection (including closed channels)
+pub async fn get_channel_paths<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Result<Vec<String>> {
+    let channels = get_channels(executor, port_id, connection_id).await?;
+
+    Ok(channels
+        .iter()
+        .map(|(channel_id, _)| {
+            ChannelPath::new(port_id, channel_id)
+                .get_key(0)
+                .unwrap()
+                .to_owned()
+        })
+        .collect())
+}
+
+/// Fetches all the channels on given port which use given connection (including closed channels)
+pub async fn get_channels<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    port_id: &PortId,
+    connection_id: &ConnectionId,
+) -> Result<Vec<(ChannelId, Channel)>> {
+    let prefix = format!("channelEnds/ports/{}/channels/", port_id);
+
+    let channels: Vec<IbcData> = sqlx::query_as(&format!(
+        "SELECT * FROM ibc_data WHERE substr(path, 1, {}) = $1 ORDER BY path",
+        prefix.len()
+    ))
+    .bind(&prefix)
+    .fetch_all(executor)
+    .await
+    .context("unable to query channels from database")?;
+
+    let connection_id = connection_id.to_string();
+    let mut result = Vec::new();
+
+    for ibc_data in channels {
+        let channel = Channel::decode(ibc_data.data.as_ref())
+            .context("unable to decode protobuf bytes for ibc data")?;
+
+        if channel.connection_hops.contains(&connection_id) {
+            let channel_id = ibc_data.path[prefix.len()..].parse()?;
+            result.push((channel_id, channel));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Copies all the IBC data of given client (client and consensus states) and the given paths to archive
+pub async fn archive_ibc_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+    paths: &[String],
+    archived_at: DateTime<Utc>,
+) -> Result<()> {
+    let mut query_builder = QueryBuilder::new(
+        "INSERT INTO archived_ibc_data (path, data, chain_id, archived_at) SELECT path, data, ",
+    );
+    query_builder
+        .push_bind(chain_id.to_string())
+        .push(", ")
+        .push_bind(archived_at)
+        .push(" FROM ibc_data WHERE ");
+    push_ibc_data_filter(&mut query_builder, client_id, paths);
+
+    query_builder
+        .build()
+        .execute(executor)
+        .await
+        .context("unable to archive ibc data")?;
+
+    Ok(())
+}
+
+/// Deletes all the IBC data of given client (client and consensus states) and the given paths
+pub async fn delete_ibc_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    client_id: &ClientId,
+    paths: &[String],
+) -> Result<()> {
+    let mut query_builder = QueryBuilder::new("DELETE FROM ibc_data WHERE ");
+    push_ibc_data_filter(&mut query_builder, client_id, paths);
+
+    query_builder
+        .build()
+        .execute(executor)
+        .await
+        .context("unable to delete ibc data")?;
+
+    Ok(())
+}
+
+fn push_ibc_data_filter(
+    query_builder: &mut QueryBuilder<'_, Db>,
+    client_id: &ClientId,
+    paths: &[String],
+) {
+    let client_prefix = format!("clients/{}/", client_id);
+
+    query_builder
+        .push(format!("substr(path, 1, {}) = ", client_prefix.len()))
+        .push_bind(client_prefix);
+
+    for path in paths {
+        query_builder.push(" OR path = ").push_bind(path.clone());
+    }
+}
+
 async fn add<'e>(
     executor: impl Executor<'e, Database = Db>,
     path: &str,
@@ -0,0 +1,367 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, Executor, FromRow, QueryBuilder};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db, Event};
+
+/// A long-running operation (e.g. connecting to an IBC enabled chain) executed in background
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Job {
+    /// ID of job
+    pub id: i64,
+    /// Kind of job
+    pub kind: JobKind,
+    /// Chain ID of IBC enabled chain on which the job is executed
+    pub chain_id: ChainId,
+    /// Optional request ID (for tracking purposes)
+    pub request_id: Option<String>,
+    /// Status of job
+    pub status: JobStatus,
+    /// Result of job (only present if the job succeeded)
+    pub result: Option<Value>,
+    /// Error detail (only present if the job failed or was cancelled)
+    pub error: Option<String>,
+    /// Time at which this job was created
+    pub created_at: DateTime<Utc>,
+    /// Time at which status of this job was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw long-running operation executed in background
+struct RawJob {
+    /// ID of job
+    pub id: i64,
+    /// Kind of job
+    pub kind: String,
+    /// Chain ID of IBC enabled chain on which the job is executed
+    pub chain_id: String,
+    /// Optional request ID (for tracking purposes)
+    pub request_id: Option<String>,
+    /// Status of job
+    pub status: String,
+    /// Result of job
+    pub result: Option<Json<Value>>,
+    /// Error detail
+    pub error: Option<String>,
+    /// Time at which this job was created
+    pub created_at: DateTime<Utc>,
+    /// Time at which status of this job was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawJob> for Job {
+    type Error = Error;
+
+    fn try_from(raw: RawJob) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            kind: raw.kind.parse()?,
+            chain_id: raw.chain_id.parse()?,
+            request_id: raw.request_id,
+            status: raw.status.parse()?,
+            result: raw.result.map(|result| result.0),
+            error: raw.error,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Kind of a long-running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobKind {
+    /// Establish connection with IBC enabled chain
+    Connect,
+    /// Close channel with IBC enabled chain
+    CloseChannel,
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect => write!(f, "connect"),
+            Self::CloseChannel => write!(f, "close-channel"),
+        }
+    }
+}
+
+impl FromStr for JobKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "connect" => Ok(Self::Connect),
+            "close-channel" => Ok(Self::CloseChannel),
+            _ => Err(anyhow!("invalid job kind: {}", s)),
+        }
+    }
+}
+
+/// Status of a long-running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobStatus {
+    /// Job is being executed
+    Running,
+    /// Job finished successfully
+    Succeeded,
+    /// Job failed (see error detail of job)
+    Failed,
+    /// Job was cancelled before it finished
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns `true` if the job is finished (i.e., it succeeded, failed or was cancelled)
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::Running)
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "running"),
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(anyhow!("invalid job status: {}", s)),
+        }
+    }
+}
+
+/// Filters for querying long-running operations (all the filters are optional)
+#[derive(Debug, Default, Clone)]
+pub struct JobFilter {
+    /// Only include jobs on IBC enabled chain with given chain ID
+    pub chain_id: Option<ChainId>,
+    /// Only include jobs of given kind
+    pub kind: Option<JobKind>,
+    /// Only include jobs with given status
+    pub status: Option<JobStatus>,
+}
+
+/// An event emitted while executing a long-running operation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobProgress {
+    /// ID of job
+    pub job_id: i64,
+    /// Event emitted by job
+    pub event: Event,
+    /// Time at which the event was emitted
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw event emitted while executing a long-running operation
+struct RawJobProgress {
+    /// ID of job
+    pub job_id: i64,
+    /// Event emitted by job
+    pub event: Json<Event>,
+    /// Time at which the event was emitted
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RawJobProgress> for JobProgress {
+    fn from(raw: RawJobProgress) -> Self {
+        Self {
+            job_id: raw.job_id,
+            event: raw.event.0,
+            created_at: raw.created_at,
+        }
+    }
+}
+
+/// Adds a running job to database and returns it. Fails if another job is already running on the same chain.
+pub async fn add_job<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    kind: JobKind,
+    chain_id: &ChainId,
+    request_id: Option<&str>,
+) -> Result<Job> {
+    let raw: RawJob = sqlx::query_as(
+        "INSERT INTO jobs (kind, chain_id, request_id, status) VALUES ($1, $2, $3, $4) RETURNING *",
+    )
+    .bind(kind.to_string())
+    .bind(chain_id.to_string())
+    .bind(request_id)
+    .bind(JobStatus::Running.to_string())
+    .fetch_one(executor)
+    .await
+    .with_context(|| {
+        format!(
+            "unable to add job (only one job can run on chain {} at a time)",
+            chain_id
+        )
+    })?;
+
+    raw.try_into()
+}
+
+/// Marks a running job as finished. Returns `false` if the job is not running anymore (e.g. it was cancelled).
+pub async fn finish_job<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    status: JobStatus,
+    result: Option<&Value>,
+    error: Option<&str>,
+) -> Result<bool> {
+    ensure!(
+        status.is_finished(),
+        "job can only be finished with succeeded, failed or cancelled status"
+    );
+
+    let rows_affected = sqlx::query(
+        "UPDATE jobs SET status = $1, result = $2, error = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $4 AND status = $5",
+    )
+    .bind(status.to_string())
+    .bind(result.map(Json))
+    .bind(error)
+    .bind(id)
+    .bind(JobStatus::Running.to_string())
+    .execute(executor)
+    .await
+    .context("unable to update status of job")?
+    .rows_affected();
+
+    Ok(rows_affected == 1)
+}
+
+/// Marks all the running jobs as failed (used when jobs were interrupted, e.g. by restart of gRPC server) and returns
+/// the number of affected jobs
+pub async fn fail_running_jobs<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    error: &str,
+) -> Result<u64> {
+    let rows_affected = sqlx::query(
+        "UPDATE jobs SET status = $1, error = $2, updated_at = CURRENT_TIMESTAMP WHERE status = $3",
+    )
+    .bind(JobStatus::Failed.to_string())
+    .bind(error)
+    .bind(JobStatus::Running.to_string())
+    .execute(executor)
+    .await
+    .context("unable to update status of running jobs")?
+    .rows_affected();
+
+    Ok(rows_affected)
+}
+
+/// Fetches a job from database
+pub async fn get_job<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<Option<Job>> {
+    let raw: Option<RawJob> = sqlx::query_as("SELECT * FROM jobs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query job from database")?;
+
+    raw.map(TryInto::try_into).transpose()
+}
+
+/// Fetches jobs matching given filter from database (latest first)
+pub async fn get_jobs<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    filter: &JobFilter,
+    limit: i32,
+    offset: i32,
+) -> Result<Vec<Job>> {
+    let mut query_builder: QueryBuilder<'_, Db> =
+        QueryBuilder::new("SELECT * FROM jobs WHERE 1 = 1");
+
+    if let Some(ref chain_id) = filter.chain_id {
+        query_builder
+            .push(" AND chain_id = ")
+            .push_bind(chain_id.to_string());
+    }
+
+    if let Some(kind) = filter.kind {
+        query_builder
+            .push(" AND kind = ")
+            .push_bind(kind.to_string());
+    }
+
+    if let Some(status) = filter.status {
+        query_builder
+            .push(" AND status = ")
+            .push_bind(status.to_string());
+    }
+
+    query_builder
+        .push(" ORDER BY id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let raw: Vec<RawJob> = query_builder
+        .build_query_as()
+        .fetch_all(executor)
+        .await
+        .context("unable to query jobs from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Records an event emitted while executing a job
+pub async fn add_job_progress<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    job_id: i64,
+    event: &Event,
+) -> Result<()> {
+    let rows_affected = sqlx::query("INSERT INTO job_progress (job_id, event) VALUES ($1, $2)")
+        .bind(job_id)
+        .bind(Json(event))
+        .execute(executor)
+        .await
+        .context("unable to add job progress")?
+        .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding job progress"
+    );
+
+    Ok(())
+}
+
+/// Fetches all the events emitted while executing a job (in the order they were emitted)
+pub async fn get_job_progress<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    job_id: i64,
+) -> Result<Vec<JobProgress>> {
+    let raw: Vec<RawJobProgress> = sqlx::query_as(
+        "SELECT job_id, event, created_at FROM job_progress WHERE job_id = $1 ORDER BY id",
+    )
+    .bind(job_id)
+    .fetch_all(executor)
+    .await
+    .context("unable to query job progress from database")?;
+
+    Ok(raw.into_iter().map(Into::into).collect())
+}
@@ -1,16 +1,18 @@
 use std::{
     convert::{TryFrom, TryInto},
     fmt,
+    str::FromStr,
 };
 
-use anyhow::{ensure, Context, Error, Result};
+use anyhow::{anyhow, ensure, Context, Error, Result};
 use chrono::{DateTime, Utc};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
-use sqlx::{types::Json, Executor, FromRow};
+use sqlx::{types::Json, Executor, FromRow, QueryBuilder};
 
 use crate::{
     ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::signing_session::SigningSessionStatus,
     Db,
 };
 
@@ -23,16 +25,28 @@ pub struct Operation {
     pub request_id: Option<String>,
     /// Address of the account
     pub address: String,
-    /// Denom of tokens
-    pub denom: Identifier,
-    /// Amount of tokens
-    pub amount: U256,
+    /// Denom of tokens (`None` for operations which do not transfer tokens)
+    pub denom: Option<Identifier>,
+    /// Amount of tokens (`None` for operations which do not transfer tokens)
+    pub amount: Option<U256>,
     /// Type of operation
     pub operation_type: OperationType,
-    /// On-chain transaction hash (in hex)
-    pub transaction_hash: String,
+    /// Status of operation
+    pub status: OperationStatus,
+    /// Error detail (only present if the operation failed or timed out)
+    pub error: Option<String>,
+    /// On-chain transaction hash (in hex, `None` if the transaction is not yet broadcasted)
+    pub transaction_hash: Option<String>,
+    /// Height of block in which the transaction was committed
+    pub block_height: Option<i64>,
+    /// Gas used by the transaction
+    pub gas_used: Option<i64>,
+    /// Fee paid for the transaction (e.g. `1000stake`)
+    pub fee_paid: Option<String>,
     /// Time at which this operation was created
     pub created_at: DateTime<Utc>,
+    /// Time at which status of this operation was last updated
+    pub updated_at: DateTime<Utc>,
 }
 
 /// Denotes an operation on an account
@@ -45,30 +59,46 @@ pub struct RawOperation {
     /// Address of the account
     pub address: String,
     /// Denom of tokens
-    pub denom: String,
+    pub denom: Option<String>,
     /// Amount of tokens
-    pub amount: Vec<u8>,
+    pub amount: Option<Vec<u8>>,
     /// Type of operation
     pub operation_type: Json<OperationType>,
+    /// Status of operation
+    pub status: String,
+    /// Error detail
+    pub error: Option<String>,
     /// On-chain transaction hash (in hex)
-    pub transaction_hash: String,
+    pub transaction_hash: Option<String>,
+    /// Height of block in which the transaction was committed
+    pub block_height: Option<i64>,
+    /// Gas used by the transaction
+    pub gas_used: Option<i64>,
+    /// Fee paid for the transaction
+    pub fee_paid: Option<String>,
     /// Time at which this operation was created
     pub created_at: DateTime<Utc>,
+    /// Time at which status of this operation was last updated
+    pub updated_at: DateTime<Utc>,
 }
 
 impl From<Operation> for RawOperation {
     fn from(op: Operation) -> Self {
-        let amount_bytes: [u8; 32] = op.amount.to_big_endian();
-
         Self {
             id: op.id,
             request_id: op.request_id,
             address: op.address,
-            denom: op.denom.to_string(),
-            amount: amount_bytes.to_vec(),
+            denom: op.denom.map(|denom| denom.to_string()),
+            amount: op.amount.map(|amount| amount.to_big_endian().to_vec()),
             operation_type: Json(op.operation_type),
+            status: op.status.to_string(),
+            error: op.error,
             transaction_hash: op.transaction_hash,
+            block_height: op.block_height,
+            gas_used: op.gas_used,
+            fee_paid: op.fee_paid,
             created_at: op.created_at,
+            updated_at: op.updated_at,
         }
     }
 }
@@ -77,31 +107,40 @@ impl TryFrom<RawOperation> for Operation {
     type Error = Error;
 
     fn try_from(op: RawOperation) -> Result<Self, Self::Error> {
-        let mut amount_bytes = [0; 32];
-
-        ensure!(
-            op.amount.len() == 32,
-            "expected amount in u256 little endian bytes {}",
-            op.amount.len()
-        );
+        let amount = op
+            .amount
+            .map(|amount| {
+                ensure!(
+                    amount.len() == 32,
+                    "expected amount in u256 little endian bytes {}",
+                    amount.len()
+                );
 
-        amount_bytes.copy_from_slice(&op.amount);
+                Ok(U256::from_big_endian(&amount))
+            })
+            .transpose()?;
 
         Ok(Self {
             id: op.id,
             request_id: op.request_id,
             address: op.address,
-            denom: op.denom.parse()?,
-            amount: U256::from_big_endian(&amount_bytes),
+            denom: op.denom.map(|denom| denom.parse()).transpose()?,
+            amount,
             operation_type: op.operation_type.0,
+            status: op.status.parse()?,
+            error: op.error,
             transaction_hash: op.transaction_hash,
+            block_height: op.block_height,
+            gas_used: op.gas_used,
+            fee_paid: op.fee_paid,
             created_at: op.created_at,
+            updated_at: op.updated_at,
         })
     }
 }
 
 /// Different types of possible operations on an account
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OperationType {
     /// Mint some tokens on IBC enabled chain
     Mint {
@@ -113,6 +152,33 @@ pub enum OperationType {
         /// Chain ID of IBC enabled chain
         chain_id: ChainId,
     },
+    /// Update signer's public key on IBC enabled chain
+    UpdateSigner {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// New signer's public key (hex encoded)
+        new_public_key: String,
+    },
+}
+
+impl OperationType {
+    /// Returns kind of operation
+    pub fn kind(&self) -> OperationKind {
+        match self {
+            Self::Mint { .. } => OperationKind::Mint,
+            Self::Burn { .. } => OperationKind::Burn,
+            Self::UpdateSigner { .. } => OperationKind::UpdateSigner,
+        }
+    }
+
+    /// Returns chain ID of IBC enabled chain on which operation is performed
+    pub fn chain_id(&self) -> &ChainId {
+        match self {
+            Self::Mint { chain_id } => chain_id,
+            Self::Burn { chain_id } => chain_id,
+            Self::UpdateSigner { chain_id, .. } => chain_id,
+        }
+    }
 }
 
 impl fmt::Display for OperationType {
@@ -120,62 +186,524 @@ impl fmt::Display for OperationType {
         match self {
             Self::Mint { chain_id } => write!(f, "mint [{}]", chain_id),
             Self::Burn { chain_id } => write!(f, "burn [{}]", chain_id),
+            Self::UpdateSigner { chain_id, .. } => write!(f, "update-signer [{}]", chain_id),
+        }
+    }
+}
+
+/// Kind of an operation (used for filtering operations)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OperationKind {
+    /// Mint some tokens on IBC enabled chain
+    Mint,
+    /// Burn some tokens on IBC enabled chain
+    Burn,
+    /// Update signer's public key on IBC enabled chain
+    UpdateSigner,
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint => write!(f, "mint"),
+            Self::Burn => write!(f, "burn"),
+            Self::UpdateSigner => write!(f, "update-signer"),
+        }
+    }
+}
+
+impl FromStr for OperationKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mint" => Ok(Self::Mint),
+            "burn" => Ok(Self::Burn),
+            "update-signer" => Ok(Self::UpdateSigner),
+            _ => Err(anyhow!("invalid operation type: {}", s)),
+        }
+    }
+}
+
+/// Status of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OperationStatus {
+    /// Operation is recorded but its transaction is not yet broadcasted to IBC enabled chain
+    Pending,
+    /// Transaction of operation is broadcasted to IBC enabled chain but not yet committed
+    Broadcast,
+    /// Transaction of operation is committed on IBC enabled chain
+    Committed,
+    /// Packets sent by the transaction of operation are successfully acknowledged
+    Acknowledged,
+    /// Operation failed (see error detail of operation)
+    Failed,
+    /// Outcome of transaction of operation is unknown, e.g., timed out while waiting for it to be committed (the
+    /// transaction may or may not be committed on IBC enabled chain)
+    TimedOut,
+}
+
+impl OperationStatus {
+    /// Returns `true` if the transaction of operation is committed on IBC enabled chain
+    pub fn is_committed(&self) -> bool {
+        matches!(self, Self::Committed | Self::Acknowledged)
+    }
+}
+
+impl fmt::Display for OperationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Broadcast => write!(f, "broadcast"),
+            Self::Committed => write!(f, "committed"),
+            Self::Acknowledged => write!(f, "acknowledged"),
+            Self::Failed => write!(f, "failed"),
+            Self::TimedOut => write!(f, "timed-out"),
+        }
+    }
+}
+
+impl FromStr for OperationStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "broadcast" => Ok(Self::Broadcast),
+            "committed" => Ok(Self::Committed),
+            "acknowledged" => Ok(Self::Acknowledged),
+            "failed" => Ok(Self::Failed),
+            "timed-out" => Ok(Self::TimedOut),
+            _ => Err(anyhow!("invalid operation status: {}", s)),
         }
     }
 }
 
-/// Adds an account operation to database
+/// Filters for querying account operations (all the filters are optional)
+#[derive(Debug, Default, Clone)]
+pub struct OperationFilter {
+    /// Only include operations on IBC enabled chain with given chain ID
+    pub chain_id: Option<ChainId>,
+    /// Only include operations with given denom
+    pub denom: Option<Identifier>,
+    /// Only include operations of given kind
+    pub kind: Option<OperationKind>,
+    /// Only include operation with given request ID
+    pub request_id: Option<String>,
+    /// Only include operations with given status
+    pub status: Option<OperationStatus>,
+    /// Only include operations created at or after given time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only include operations created before given time
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// A page of account operations (sorted by ID in descending order)
+#[derive(Debug, Serialize)]
+pub struct OperationPage {
+    /// Operations in this page
+    pub operations: Vec<Operation>,
+    /// Cursor for fetching next page (`None` if there are no more operations)
+    pub next_cursor: Option<i64>,
+}
+
+/// Adds an account operation to database and returns its ID
+#[allow(clippy::too_many_arguments)]
 pub async fn add_operation<'e>(
     executor: impl Executor<'e, Database = Db>,
     request_id: Option<&str>,
     address: &str,
-    denom: &Identifier,
-    amount: U256,
+    denom: Option<&Identifier>,
+    amount: Option<U256>,
     operation_type: &OperationType,
-    transaction_hash: &str,
-) -> Result<()> {
+    status: OperationStatus,
+    transaction_hash: Option<&str>,
+) -> Result<i64> {
+    let kind = operation_type.kind().to_string();
+    let chain_id = operation_type.chain_id().to_string();
     let operation_type = Json(operation_type);
 
-    let amount_bytes: [u8; 32] = amount.to_big_endian();
-
-    let rows_affected = sqlx::query(
-        "INSERT INTO operations (request_id, address, denom, amount, operation_type, transaction_hash) VALUES ($1, $2, $3, $4, $5, $6)",
+    let (id,): (i64,) = sqlx::query_as(
+        "INSERT INTO operations (request_id, address, denom, amount, operation_type, kind, chain_id, status, transaction_hash) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id",
     )
     .bind(request_id)
     .bind(address)
-    .bind(denom.to_string())
-    .bind(amount_bytes.to_vec())
+    .bind(denom.map(ToString::to_string))
+    .bind(amount.map(|amount| amount.to_big_endian().to_vec()))
     .bind(operation_type)
+    .bind(kind)
+    .bind(chain_id)
+    .bind(status.to_string())
+    .bind(transaction_hash)
+    .fetch_one(executor)
+    .await
+    .context("unable to add new account operation to database")?;
+
+    Ok(id)
+}
+
+/// Marks an account operation as broadcasted with given transaction hash. `sequences` are the `(sequence,
+/// packet_sequence)` of solo machine before signing the transaction (`None` if the transaction is not signed by solo
+/// machine).
+pub async fn set_operation_broadcast<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    transaction_hash: &str,
+    sequences: Option<(u32, u32)>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE operations SET status = $1, transaction_hash = $2, sequence = $3, packet_sequence = $4, updated_at = CURRENT_TIMESTAMP WHERE id = $5",
+    )
+    .bind(OperationStatus::Broadcast.to_string())
     .bind(transaction_hash)
+    .bind(sequences.map(|(sequence, _)| i64::from(sequence)))
+    .bind(sequences.map(|(_, packet_sequence)| i64::from(packet_sequence)))
+    .bind(id)
     .execute(executor)
     .await
-    .context("unable to add new account operation to database")?
+    .context("unable to mark account operation as broadcast")?
     .rows_affected();
 
-    ensure!(
-        rows_affected == 1,
-        "rows_affected should be equal to 1 when adding a new account operation"
-    );
+    ensure_single_row(rows_affected)
+}
 
-    Ok(())
+/// Marks an account operation as committed in given block height
+pub async fn set_operation_committed<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    block_height: i64,
+    gas_used: i64,
+    fee_paid: &str,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE operations SET status = $1, block_height = $2, gas_used = $3, fee_paid = $4, updated_at = CURRENT_TIMESTAMP WHERE id = $5",
+    )
+    .bind(OperationStatus::Committed.to_string())
+    .bind(block_height)
+    .bind(gas_used)
+    .bind(fee_paid)
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to mark account operation as committed")?
+    .rows_affected();
+
+    ensure_single_row(rows_affected)
 }
 
-/// Fetches account operations from database
+/// Updates status of an account operation. `error` and `transaction_hash` are only updated when they are not `None`.
+pub async fn set_operation_status<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    status: OperationStatus,
+    error: Option<&str>,
+    transaction_hash: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE operations SET status = $1, error = COALESCE($2, error), transaction_hash = COALESCE($3, transaction_hash), updated_at = CURRENT_TIMESTAMP WHERE id = $4",
+    )
+    .bind(status.to_string())
+    .bind(error)
+    .bind(transaction_hash)
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to update status of account operation")?
+    .rows_affected();
+
+    ensure_single_row(rows_affected)
+}
+
+/// Fetches `(sequence, packet_sequence)` of solo machine before signing the transaction of an account operation (`None`
+/// if they were not recorded while broadcasting)
+pub async fn get_operation_sequences<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<Option<(u32, u32)>> {
+    let sequences: Option<(Option<i64>, Option<i64>)> =
+        sqlx::query_as("SELECT sequence, packet_sequence FROM operations WHERE id = $1")
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .context("unable to query sequences of account operation from database")?;
+
+    match sequences {
+        Some((Some(sequence), Some(packet_sequence))) => {
+            Ok(Some((sequence.try_into()?, packet_sequence.try_into()?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Fetches latest account operation with given request ID from database
+pub async fn get_operation_by_request_id<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    request_id: &str,
+) -> Result<Option<Operation>> {
+    sqlx::query_as("SELECT * FROM operations WHERE request_id = $1 ORDER BY id DESC LIMIT 1")
+        .bind(request_id)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query account operation from database")?
+        .map(|raw: RawOperation| raw.try_into())
+        .transpose()
+}
+
+/// Fetches account operations from database matching given filter. When `cursor` is provided, only operations with ID
+/// less than `cursor` are returned.
 pub async fn get_operations<'e>(
     executor: impl Executor<'e, Database = Db>,
     address: &str,
+    filter: &OperationFilter,
+    cursor: Option<i64>,
     limit: i32,
     offset: i32,
+) -> Result<Vec<Operation>> {
+    let mut query_builder = QueryBuilder::<Db>::new("SELECT * FROM operations WHERE address = ");
+    query_builder.push_bind(address);
+
+    if let Some(ref chain_id) = filter.chain_id {
+        query_builder
+            .push(" AND chain_id = ")
+            .push_bind(chain_id.to_string());
+    }
+
+    if let Some(ref denom) = filter.denom {
+        query_builder
+            .push(" AND denom = ")
+            .push_bind(denom.to_string());
+    }
+
+    if let Some(kind) = filter.kind {
+        query_builder
+            .push(" AND kind = ")
+            .push_bind(kind.to_string());
+    }
+
+    if let Some(ref request_id) = filter.request_id {
+        query_builder
+            .push(" AND request_id = ")
+            .push_bind(request_id.as_str());
+    }
+
+    if let Some(status) = filter.status {
+        query_builder
+            .push(" AND status = ")
+            .push_bind(status.to_string());
+    }
+
+    if let Some(created_after) = filter.created_after {
+        push_created_at_bound(&mut query_builder, ">=", created_after);
+    }
+
+    if let Some(created_before) = filter.created_before {
+        push_created_at_bound(&mut query_builder, "<", created_before);
+    }
+
+    if let Some(cursor) = cursor {
+        query_builder.push(" AND id < ").push_bind(cursor);
+    }
+
+    query_builder
+        .push(" ORDER BY id DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let raw: Vec<RawOperation> = query_builder
+        .build_query_as()
+        .fetch_all(executor)
+        .await
+        .context("unable to query account operations from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Returns total amount of tokens minted (including operations which are still in progress or timed out) with given
+/// denom on IBC enabled chain since given time (optionally only for given receiver)
+pub async fn get_minted_amount<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    denom: &Identifier,
+    receiver: Option<&str>,
+    since: DateTime<Utc>,
+) -> Result<U256> {
+    let mut query_builder = QueryBuilder::<Db>::new("SELECT amount FROM operations WHERE kind = ");
+    query_builder
+        .push_bind(OperationKind::Mint.to_string())
+        .push(" AND chain_id = ")
+        .push_bind(chain_id.to_string())
+        .push(" AND denom = ")
+        .push_bind(denom.to_string())
+        .push(" AND status <> ")
+        .push_bind(OperationStatus::Failed.to_string());
+
+    if let Some(receiver) = receiver {
+        query_builder.push(" AND address = ").push_bind(receiver);
+    }
+
+    push_created_at_bound(&mut query_builder, ">=", since);
+
+    let amounts: Vec<(Option<Vec<u8>>,)> = query_builder
+        .build_query_as()
+        .fetch_all(executor)
+        .await
+        .context("unable to query minted amount from database")?;
+
+    amounts
+        .into_iter()
+        .filter_map(|(amount,)| amount)
+        .try_fold(U256::zero(), |total, amount| {
+            ensure!(
+                amount.len() == 32,
+                "expected amount in u256 big endian bytes {}",
+                amount.len()
+            );
+
+            total
+                .checked_add(U256::from_big_endian(&amount))
+                .ok_or_else(|| anyhow!("overflow when computing minted amount"))
+        })
+}
+
+/// Marks the operations on IBC enabled chain which are still pending (i.e., no transaction was broadcasted for them)
+/// since before `before` as failed with given error, except the ones awaiting signatures of an offline signing
+/// session. These are left behind by processes which crashed before broadcasting. Returns the number of operations
+/// marked as failed.
+pub async fn fail_stale_pending_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    before: DateTime<Utc>,
+    error: &str,
+) -> Result<u64> {
+    let mut query_builder = QueryBuilder::<Db>::new("UPDATE operations SET status = ");
+    query_builder
+        .push_bind(OperationStatus::Failed.to_string())
+        .push(", error = ")
+        .push_bind(error)
+        .push(", updated_at = CURRENT_TIMESTAMP WHERE chain_id = ")
+        .push_bind(chain_id.to_string())
+        .push(" AND status = ")
+        .push_bind(OperationStatus::Pending.to_string())
+        .push(" AND transaction_hash IS NULL")
+        .push(" AND id NOT IN (SELECT operation_id FROM signing_sessions WHERE status = ")
+        .push_bind(SigningSessionStatus::AwaitingSignatures.to_string())
+        .push(")");
+
+    push_created_at_bound(&mut query_builder, "<", before);
+
+    let rows_affected = query_builder
+        .build()
+        .execute(executor)
+        .await
+        .context("unable to fail stale pending operations")?
+        .rows_affected();
+
+    Ok(rows_affected)
+}
+
+/// SQLite stores `CURRENT_TIMESTAMP` as text in a different format than bound timestamps, so both sides are normalized
+/// before comparison
+#[cfg(not(feature = "postgres"))]
+fn push_created_at_bound(
+    query_builder: &mut QueryBuilder<'_, Db>,
+    operator: &str,
+    time: DateTime<Utc>,
+) {
+    query_builder
+        .push(format!(" AND datetime(created_at) {} datetime(", operator))
+        .push_bind(time)
+        .push(")");
+}
+
+#[cfg(feature = "postgres")]
+fn push_created_at_bound(
+    query_builder: &mut QueryBuilder<'_, Db>,
+    operator: &str,
+    time: DateTime<Utc>,
+) {
+    query_builder
+        .push(format!(" AND created_at {} ", operator))
+        .push_bind(time);
+}
+
+/// Fetches all the token (mint and burn) operations on IBC enabled chain with given chain ID from database
+pub async fn get_token_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
 ) -> Result<Vec<Operation>> {
     let raw: Vec<RawOperation> = sqlx::query_as(
-        "SELECT * FROM operations WHERE address = $1 ORDER BY id DESC LIMIT $2 OFFSET $3",
+        "SELECT * FROM operations WHERE chain_id = $1 AND kind IN ($2, $3) ORDER BY id",
     )
-    .bind(address)
-    .bind(limit)
-    .bind(offset)
+    .bind(chain_id.to_string())
+    .bind(OperationKind::Mint.to_string())
+    .bind(OperationKind::Burn.to_string())
     .fetch_all(executor)
     .await
-    .context("unable to query account operations from database")?;
+    .context("unable to query token operations from database")?;
 
     raw.into_iter().map(TryInto::try_into).collect()
 }
+
+/// Returns `true` if there is an operation on IBC enabled chain with given chain ID whose outcome is not yet known
+/// (pending, broadcast or timed out)
+pub async fn has_in_flight_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<bool> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM operations WHERE chain_id = $1 AND status IN ($2, $3, $4)",
+    )
+    .bind(chain_id.to_string())
+    .bind(OperationStatus::Pending.to_string())
+    .bind(OperationStatus::Broadcast.to_string())
+    .bind(OperationStatus::TimedOut.to_string())
+    .fetch_one(executor)
+    .await
+    .context("unable to query operations from database")?;
+
+    Ok(count > 0)
+}
+
+/// Copies all the operations on IBC enabled chain with given chain ID to archive
+pub async fn archive_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    archived_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO archived_operations (id, request_id, address, denom, amount, operation_type, status, error, transaction_hash, block_height, gas_used, fee_paid, kind, chain_id, created_at, updated_at, archived_at) SELECT id, request_id, address, denom, amount, operation_type, status, error, transaction_hash, block_height, gas_used, fee_paid, kind, chain_id, created_at, updated_at, $1 FROM operations WHERE chain_id = $2",
+    )
+    .bind(archived_at)
+    .bind(chain_id.to_string())
+    .execute(executor)
+    .await
+    .context("unable to archive operations")?;
+
+    Ok(())
+}
+
+/// Deletes all the operations on IBC enabled chain with given chain ID
+pub async fn delete_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<()> {
+    sqlx::query("DELETE FROM operations WHERE chain_id = $1")
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to delete operations")?;
+
+    Ok(())
+}
+
+fn ensure_single_row(rows_affected: u64) -> Result<()> {
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating an account operation"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use anyhow::{ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, Executor, FromRow, Transaction};
+
+use crate::{Db, Event};
+
+/// An event recorded in event outbox
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutboxEvent {
+    /// ID of event
+    pub id: i64,
+    /// Event emitted by solo machine
+    pub event: Event,
+    /// Time at which the event was recorded
+    pub created_at: DateTime<Utc>,
+    /// Position of event in outbox (events are delivered to event handlers in increasing order of their positions).
+    /// `None` if no position is assigned to the event yet.
+    pub position: Option<i64>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw event recorded in event outbox
+struct RawOutboxEvent {
+    /// ID of event
+    pub id: i64,
+    /// Event emitted by solo machine
+    pub event: Json<Event>,
+    /// Time at which the event was recorded
+    pub created_at: DateTime<Utc>,
+    /// Position of event in outbox
+    pub position: Option<i64>,
+}
+
+impl From<RawOutboxEvent> for OutboxEvent {
+    fn from(raw: RawOutboxEvent) -> Self {
+        Self {
+            id: raw.id,
+            event: raw.event.0,
+            created_at: raw.created_at,
+            position: raw.position,
+        }
+    }
+}
+
+/// Position of an event handler in event outbox
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct HandlerCursor {
+    /// Name of event handler
+    pub handler: String,
+    /// Position of the last event delivered to event handler
+    pub position: i64,
+    /// Time at which the cursor was last updated
+    pub updated_at: DateTime<Utc>,
+    /// Number of events which are not delivered to event handler yet
+    pub pending_events: i64,
+}
+
+/// Query for fetching cursors of event handlers (along with the number of events pending for each of them)
+const HANDLER_CURSORS_QUERY: &str = "SELECT handler, position, updated_at, (SELECT COUNT(*) FROM event_outbox WHERE event_outbox.position IS NULL OR event_outbox.position > event_handler_cursors.position) AS pending_events FROM event_handler_cursors";
+
+/// Key of the advisory lock held while assigning positions to events (`events` in ASCII)
+#[cfg(feature = "postgres")]
+const EVENT_POSITIONS_LOCK_KEY: i64 = 0x6576_656e_7473;
+
+/// Records an event in event outbox and returns its ID
+pub async fn add_event<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    event: &Event,
+) -> Result<i64> {
+    let event = serde_json::to_value(event).context("unable to serialize event")?;
+    let event_type = event["type"].as_str().unwrap_or_default().to_owned();
+
+    let (id,): (i64,) =
+        sqlx::query_as("INSERT INTO event_outbox (event_type, event) VALUES ($1, $2) RETURNING id")
+            .bind(event_type)
+            .bind(Json(event))
+            .fetch_one(executor)
+            .await
+            .context("unable to add event to outbox")?;
+
+    Ok(id)
+}
+
+/// Assigns positions to committed events in event outbox which don't have one yet (in the order of their IDs) and
+/// returns the number of events positioned. Positions are assigned after the end of all the existing positions, so, an
+/// event committed after some events were delivered is never positioned before them (which is not guaranteed by event
+/// IDs because they're allocated at insert time).
+pub async fn assign_event_positions(transaction: &mut Transaction<'_, Db>) -> Result<u64> {
+    // Concurrent transactions would assign the same positions otherwise (SQLite already allows only one writer)
+    #[cfg(feature = "postgres")]
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(EVENT_POSITIONS_LOCK_KEY)
+        .execute(&mut **transaction)
+        .await
+        .context("unable to lock event outbox for assigning positions")?;
+
+    let rows_affected = sqlx::query(
+        "UPDATE event_outbox SET position = (SELECT COALESCE(MAX(position), 0) FROM event_outbox) + id - (SELECT MIN(id) FROM event_outbox WHERE position IS NULL) + 1 WHERE position IS NULL",
+    )
+    .execute(&mut **transaction)
+    .await
+    .context("unable to assign positions to events in outbox")?
+    .rows_affected();
+
+    Ok(rows_affected)
+}
+
+/// Fetches events recorded in event outbox after event with given ID (in the order of their IDs)
+pub async fn get_events<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    after_id: i64,
+    limit: i32,
+) -> Result<Vec<OutboxEvent>> {
+    let raw: Vec<RawOutboxEvent> = sqlx::query_as(
+        "SELECT id, event, created_at, position FROM event_outbox WHERE id > $1 ORDER BY id LIMIT $2",
+    )
+    .bind(after_id)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("unable to query events from outbox")?;
+
+    Ok(raw.into_iter().map(Into::into).collect())
+}
+
+/// Fetches events in event outbox after given position (in the order of their positions)
+pub async fn get_events_after_position<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    after_position: i64,
+    limit: i32,
+) -> Result<Vec<OutboxEvent>> {
+    let raw: Vec<RawOutboxEvent> = sqlx::query_as(
+        "SELECT id, event, created_at, position FROM event_outbox WHERE position > $1 ORDER BY position LIMIT $2",
+    )
+    .bind(after_position)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+    .context("unable to query events from outbox")?;
+
+    Ok(raw.into_iter().map(Into::into).collect())
+}
+
+/// Returns the latest position assigned to an event in event outbox (`0` if no position is assigned yet)
+pub async fn get_latest_position<'e>(executor: impl Executor<'e, Database = Db>) -> Result<i64> {
+    let (position,): (Option<i64>,) = sqlx::query_as("SELECT MAX(position) FROM event_outbox")
+        .fetch_one(executor)
+        .await
+        .context("unable to query latest position from outbox")?;
+
+    Ok(position.unwrap_or_default())
+}
+
+/// Returns the position from which events with ID greater than or equal to given event ID are delivered, i.e., the
+/// lowest position among those events (`None` if no position is assigned to any of them yet)
+pub async fn get_first_position_from<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    event_id: i64,
+) -> Result<Option<i64>> {
+    let (position,): (Option<i64>,) =
+        sqlx::query_as("SELECT MIN(position) FROM event_outbox WHERE id >= $1")
+            .bind(event_id)
+            .fetch_one(executor)
+            .await
+            .context("unable to query position of event from outbox")?;
+
+    Ok(position)
+}
+
+/// Fetches cursor of given event handler from database
+pub async fn get_handler_cursor<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    handler: &str,
+) -> Result<Option<HandlerCursor>> {
+    sqlx::query_as(&format!("{} WHERE handler = $1", HANDLER_CURSORS_QUERY))
+        .bind(handler)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query event handler cursor from database")
+}
+
+/// Fetches cursors of all the event handlers from database
+pub async fn get_handler_cursors<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Vec<HandlerCursor>> {
+    sqlx::query_as(&format!("{} ORDER BY handler", HANDLER_CURSORS_QUERY))
+        .fetch_all(executor)
+        .await
+        .context("unable to query event handler cursors from database")
+}
+
+/// Sets cursor of given event handler to given position (creates the cursor if it does not exist)
+pub async fn set_handler_cursor<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    handler: &str,
+    position: i64,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO event_handler_cursors (handler, position) VALUES ($1, $2) ON CONFLICT (handler) DO UPDATE SET position = excluded.position, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(handler)
+    .bind(position)
+    .execute(executor)
+    .await
+    .context("unable to update event handler cursor")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating event handler cursor"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow};
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    Db,
+};
+
+/// Limits applied when minting a denom on an IBC enabled chain (`None` means no limit)
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintLimits {
+    /// Maximum amount of tokens that can be minted in a single operation
+    pub max_amount: Option<U256>,
+    /// Maximum amount of tokens that can be minted in last 24 hours
+    pub daily_limit: Option<U256>,
+    /// Maximum amount of tokens that can be minted in last 7 days
+    pub weekly_limit: Option<U256>,
+    /// Maximum amount of tokens that can be minted to a single receiver in last 24 hours
+    pub receiver_daily_limit: Option<U256>,
+    /// Maximum amount of tokens that can be minted to a single receiver in last 7 days
+    pub receiver_weekly_limit: Option<U256>,
+}
+
+/// Mint policy for a denom on an IBC enabled chain. Once a chain has at least one mint policy, only the denoms with a
+/// mint policy can be minted on that chain (i.e., mint policies also act as a denom allowlist).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MintPolicy {
+    /// ID of policy
+    pub id: i64,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Denom of tokens
+    pub denom: Identifier,
+    /// Limits applied when minting tokens
+    pub limits: MintLimits,
+    /// Time at which this policy was created
+    pub created_at: DateTime<Utc>,
+    /// Time at which this policy was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw mint policy for a denom on an IBC enabled chain
+struct RawMintPolicy {
+    /// ID of policy
+    pub id: i64,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Denom of tokens
+    pub denom: String,
+    /// Maximum amount of tokens that can be minted in a single operation
+    pub max_amount: Option<Vec<u8>>,
+    /// Maximum amount of tokens that can be minted in last 24 hours
+    pub daily_limit: Option<Vec<u8>>,
+    /// Maximum amount of tokens that can be minted in last 7 days
+    pub weekly_limit: Option<Vec<u8>>,
+    /// Maximum amount of tokens that can be minted to a single receiver in last 24 hours
+    pub receiver_daily_limit: Option<Vec<u8>>,
+    /// Maximum amount of tokens that can be minted to a single receiver in last 7 days
+    pub receiver_weekly_limit: Option<Vec<u8>>,
+    /// Time at which this policy was created
+    pub created_at: DateTime<Utc>,
+    /// Time at which this policy was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawMintPolicy> for MintPolicy {
+    type Error = Error;
+
+    fn try_from(raw: RawMintPolicy) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            denom: raw.denom.parse()?,
+            limits: MintLimits {
+                max_amount: decode_limit(raw.max_amount)?,
+                daily_limit: decode_limit(raw.daily_limit)?,
+                weekly_limit: decode_limit(raw.weekly_limit)?,
+                receiver_daily_limit: decode_limit(raw.receiver_daily_limit)?,
+                receiver_weekly_limit: decode_limit(raw.receiver_weekly_limit)?,
+            },
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Adds or replaces mint policy for given denom on IBC enabled chain
+pub async fn set_mint_policy<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    denom: &Identifier,
+    limits: &MintLimits,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO mint_policies (chain_id, denom, max_amount, daily_limit, weekly_limit, receiver_daily_limit, receiver_weekly_limit) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (chain_id, denom) DO UPDATE SET max_amount = excluded.max_amount, daily_limit = excluded.daily_limit, weekly_limit = excluded.weekly_limit, receiver_daily_limit = excluded.receiver_daily_limit, receiver_weekly_limit = excluded.receiver_weekly_limit, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(chain_id.to_string())
+    .bind(denom.to_string())
+    .bind(encode_limit(limits.max_amount))
+    .bind(encode_limit(limits.daily_limit))
+    .bind(encode_limit(limits.weekly_limit))
+    .bind(encode_limit(limits.receiver_daily_limit))
+    .bind(encode_limit(limits.receiver_weekly_limit))
+    .execute(executor)
+    .await
+    .context("unable to set mint policy")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when setting mint policy"
+    );
+
+    Ok(())
+}
+
+/// Removes mint policy for given denom on IBC enabled chain. Returns `false` if there was no such policy.
+pub async fn remove_mint_policy<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    denom: &Identifier,
+) -> Result<bool> {
+    let rows_affected = sqlx::query("DELETE FROM mint_policies WHERE chain_id = $1 AND denom = $2")
+        .bind(chain_id.to_string())
+        .bind(denom.to_string())
+        .execute(executor)
+        .await
+        .context("unable to remove mint policy")?
+        .rows_affected();
+
+    Ok(rows_affected == 1)
+}
+
+/// Removes all the mint policies of given IBC enabled chain
+pub async fn remove_mint_policies<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<()> {
+    sqlx::query("DELETE FROM mint_policies WHERE chain_id = $1")
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to remove mint policies")?;
+
+    Ok(())
+}
+
+/// Fetches mint policy for given denom on IBC enabled chain
+pub async fn get_mint_policy<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    denom: &Identifier,
+) -> Result<Option<MintPolicy>> {
+    sqlx::query_as("SELECT * FROM mint_policies WHERE chain_id = $1 AND denom = $2")
+        .bind(chain_id.to_string())
+        .bind(denom.to_string())
+        .fetch_optional(executor)
+        .await
+        .context("unable to query mint policy from database")?
+        .map(|raw: RawMintPolicy| raw.try_into())
+        .transpose()
+}
+
+/// Fetches all the mint policies (optionally only for given chain)
+pub async fn get_mint_policies<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: Option<&ChainId>,
+) -> Result<Vec<MintPolicy>> {
+    let raw: Vec<RawMintPolicy> = sqlx::query_as(
+        "SELECT * FROM mint_policies WHERE ($1 IS NULL OR chain_id = $1) ORDER BY chain_id, denom",
+    )
+    .bind(chain_id.map(ToString::to_string))
+    .fetch_all(executor)
+    .await
+    .context("unable to query mint policies from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Returns `true` if there is at least one mint policy for given chain
+pub async fn has_mint_policies<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<bool> {
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mint_policies WHERE chain_id = $1")
+        .bind(chain_id.to_string())
+        .fetch_one(executor)
+        .await
+        .context("unable to query mint policies from database")?;
+
+    Ok(count > 0)
+}
+
+fn encode_limit(limit: Option<U256>) -> Option<Vec<u8>> {
+    limit.map(|limit| limit.to_big_endian().to_vec())
+}
+
+fn decode_limit(limit: Option<Vec<u8>>) -> Result<Option<U256>> {
+    limit
+        .map(|limit| {
+            ensure!(
+                limit.len() == 32,
+                "expected limit in u256 big endian bytes {}",
+                limit.len()
+            );
+
+            Ok(U256::from_big_endian(&limit))
+        })
+        .transpose()
+}
@@ -0,0 +1,367 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, Executor, FromRow};
+
+use crate::{
+    cosmos::crypto::PublicKey,
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    signer::{MessageSignature, SignatureRequest},
+    Db,
+};
+
+/// An operation whose messages are signed offline (e.g. on an air-gapped machine)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum OfflineOperation {
+    /// Mint some tokens on IBC enabled chain
+    Mint {
+        /// Amount to send to IBC enabled chain
+        amount: U256,
+        /// Denom of tokens to send to IBC enabled chain
+        denom: Identifier,
+        /// Receiver address on IBC enabled chain (signer's address if not provided)
+        receiver: Option<String>,
+        /// Memo to include in transactions
+        memo: String,
+    },
+    /// Burn some tokens on IBC enabled chain
+    Burn {
+        /// Amount to receive from IBC enabled chain
+        amount: U256,
+        /// Denom of tokens to receive from IBC enabled chain
+        denom: Identifier,
+        /// Memo to include in transactions
+        memo: String,
+    },
+    /// Update signer's public key on IBC enabled chain
+    UpdateSigner {
+        /// New public key of signer
+        new_public_key: PublicKey,
+        /// Memo to include in transactions
+        memo: String,
+    },
+}
+
+impl fmt::Display for OfflineOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint { .. } => write!(f, "mint"),
+            Self::Burn { .. } => write!(f, "burn"),
+            Self::UpdateSigner { .. } => write!(f, "update-signer"),
+        }
+    }
+}
+
+/// An operation whose messages are signed offline in one or more rounds. Sequence and timestamp of solo machine are
+/// reserved for the session while it awaits signatures.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SigningSession {
+    /// ID of signing session
+    pub id: i64,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// ID of operation executed by this session
+    pub operation_id: i64,
+    /// Optional request ID (for tracking purposes)
+    pub request_id: Option<String>,
+    /// Operation executed by this session
+    pub operation: OfflineOperation,
+    /// Public key of offline signer
+    pub public_key: PublicKey,
+    /// Account prefix of offline signer
+    pub account_prefix: String,
+    /// Sequence of solo machine reserved for this session
+    pub sequence: u32,
+    /// Consensus timestamp of solo machine reserved for this session
+    pub consensus_timestamp: DateTime<Utc>,
+    /// Timeout height of packet sent by mint operation (fixed when the session is prepared so that the signed messages
+    /// do not change between rounds)
+    pub timeout_height: Option<u64>,
+    /// Hex encoded packets which are yet to be acknowledged (for burn operation)
+    pub packets: Vec<String>,
+    /// Status of signing session
+    pub status: SigningSessionStatus,
+    /// Current round of signing session
+    pub round: u32,
+    /// Messages which need to be signed in current round
+    pub requests: Vec<SignatureRequest>,
+    /// Signatures collected in all the rounds
+    pub signatures: Vec<MessageSignature>,
+    /// Hash of transaction of operation (present once the transaction is committed)
+    pub transaction_hash: Option<String>,
+    /// Error detail (only present if the session failed)
+    pub error: Option<String>,
+    /// Time at which this session was created
+    pub created_at: DateTime<Utc>,
+    /// Time at which this session was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw signing session
+struct RawSigningSession {
+    pub id: i64,
+    pub chain_id: String,
+    pub operation_id: i64,
+    pub request_id: Option<String>,
+    pub operation: Json<OfflineOperation>,
+    pub public_key: Json<PublicKey>,
+    pub account_prefix: String,
+    pub sequence: i64,
+    pub consensus_timestamp: DateTime<Utc>,
+    pub timeout_height: Option<i64>,
+    pub packets: Json<Vec<String>>,
+    pub status: String,
+    pub round: i64,
+    pub requests: Json<Vec<SignatureRequest>>,
+    pub signatures: Json<Vec<MessageSignature>>,
+    pub transaction_hash: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawSigningSession> for SigningSession {
+    type Error = Error;
+
+    fn try_from(raw: RawSigningSession) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            operation_id: raw.operation_id,
+            request_id: raw.request_id,
+            operation: raw.operation.0,
+            public_key: raw.public_key.0,
+            account_prefix: raw.account_prefix,
+            sequence: raw.sequence.try_into()?,
+            consensus_timestamp: raw.consensus_timestamp,
+            timeout_height: raw.timeout_height.map(TryInto::try_into).transpose()?,
+            packets: raw.packets.0,
+            status: raw.status.parse()?,
+            round: raw.round.try_into()?,
+            requests: raw.requests.0,
+            signatures: raw.signatures.0,
+            transaction_hash: raw.transaction_hash,
+            error: raw.error,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Status of an offline signing session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningSessionStatus {
+    /// Messages of current round are exported and session is waiting for their signatures
+    AwaitingSignatures,
+    /// All the messages are signed and the transactions of operation are committed
+    Completed,
+    /// Session failed (see error detail of session)
+    Failed,
+    /// Session was aborted before it completed
+    Aborted,
+}
+
+impl SigningSessionStatus {
+    /// Returns `true` if the session is finished (i.e., it completed, failed or was aborted)
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::AwaitingSignatures)
+    }
+}
+
+impl fmt::Display for SigningSessionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AwaitingSignatures => write!(f, "awaiting-signatures"),
+            Self::Completed => write!(f, "completed"),
+            Self::Failed => write!(f, "failed"),
+            Self::Aborted => write!(f, "aborted"),
+        }
+    }
+}
+
+impl FromStr for SigningSessionStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "awaiting-signatures" => Ok(Self::AwaitingSignatures),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            "aborted" => Ok(Self::Aborted),
+            _ => Err(anyhow!("invalid signing session status: {}", s)),
+        }
+    }
+}
+
+/// Adds a new signing session (awaiting signatures) to database and returns it. Fails if another session is already
+/// open on the same chain.
+#[allow(clippy::too_many_arguments)]
+pub async fn add_signing_session<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    operation_id: i64,
+    request_id: Option<&str>,
+    operation: &OfflineOperation,
+    public_key: &PublicKey,
+    account_prefix: &str,
+    sequence: u32,
+    consensus_timestamp: DateTime<Utc>,
+    timeout_height: Option<u64>,
+) -> Result<SigningSession> {
+    let timeout_height: Option<i64> = timeout_height.map(TryInto::try_into).transpose()?;
+
+    let raw: RawSigningSession = sqlx::query_as(
+        "INSERT INTO signing_sessions (chain_id, operation_id, request_id, operation, public_key, account_prefix, sequence, consensus_timestamp, timeout_height, packets, status, round, requests, signatures) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) RETURNING *",
+    )
+    .bind(chain_id.to_string())
+    .bind(operation_id)
+    .bind(request_id)
+    .bind(Json(operation))
+    .bind(Json(public_key))
+    .bind(account_prefix)
+    .bind(i64::from(sequence))
+    .bind(consensus_timestamp)
+    .bind(timeout_height)
+    .bind(Json(Vec::<String>::new()))
+    .bind(SigningSessionStatus::AwaitingSignatures.to_string())
+    .bind(0_i64)
+    .bind(Json(Vec::<SignatureRequest>::new()))
+    .bind(Json(Vec::<MessageSignature>::new()))
+    .fetch_one(executor)
+    .await
+    .context("unable to add signing session to database")?;
+
+    raw.try_into()
+}
+
+/// Updates an open signing session with the messages of its next round and the signatures collected so far
+pub async fn set_signing_session_round<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    round: u32,
+    requests: &[SignatureRequest],
+    signatures: &[MessageSignature],
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE signing_sessions SET round = $1, requests = $2, signatures = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $4 AND status = $5",
+    )
+    .bind(i64::from(round))
+    .bind(Json(requests))
+    .bind(Json(signatures))
+    .bind(id)
+    .bind(SigningSessionStatus::AwaitingSignatures.to_string())
+    .execute(executor)
+    .await
+    .context("unable to update round of signing session")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "signing session with id {} is not awaiting signatures",
+        id
+    );
+
+    Ok(())
+}
+
+/// Records progress of an open signing session after one of its transactions is broadcasted (i.e., the sequence
+/// reserved for the session, packets which are yet to be acknowledged and hash of the transaction of operation)
+pub async fn set_signing_session_progress<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    sequence: u32,
+    packets: &[String],
+    transaction_hash: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE signing_sessions SET sequence = $1, packets = $2, transaction_hash = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $4 AND status = $5",
+    )
+    .bind(i64::from(sequence))
+    .bind(Json(packets))
+    .bind(transaction_hash)
+    .bind(id)
+    .bind(SigningSessionStatus::AwaitingSignatures.to_string())
+    .execute(executor)
+    .await
+    .context("unable to update progress of signing session")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "signing session with id {} is not awaiting signatures",
+        id
+    );
+
+    Ok(())
+}
+
+/// Marks an open signing session as finished. Returns `false` if the session is not open anymore.
+pub async fn finish_signing_session<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    status: SigningSessionStatus,
+    transaction_hash: Option<&str>,
+    error: Option<&str>,
+) -> Result<bool> {
+    ensure!(
+        status.is_finished(),
+        "signing session can only be finished with completed, failed or aborted status"
+    );
+
+    let rows_affected = sqlx::query(
+        "UPDATE signing_sessions SET status = $1, transaction_hash = COALESCE($2, transaction_hash), error = $3, requests = $4, updated_at = CURRENT_TIMESTAMP WHERE id = $5 AND status = $6",
+    )
+    .bind(status.to_string())
+    .bind(transaction_hash)
+    .bind(error)
+    .bind(Json(Vec::<SignatureRequest>::new()))
+    .bind(id)
+    .bind(SigningSessionStatus::AwaitingSignatures.to_string())
+    .execute(executor)
+    .await
+    .context("unable to update status of signing session")?
+    .rows_affected();
+
+    Ok(rows_affected == 1)
+}
+
+/// Fetches a signing session from database
+pub async fn get_signing_session<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<Option<SigningSession>> {
+    let raw: Option<RawSigningSession> =
+        sqlx::query_as("SELECT * FROM signing_sessions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .context("unable to query signing session from database")?;
+
+    raw.map(TryInto::try_into).transpose()
+}
+
+/// Fetches the signing session which is awaiting signatures on given chain (if any)
+pub async fn get_open_signing_session<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Option<SigningSession>> {
+    let raw: Option<RawSigningSession> =
+        sqlx::query_as("SELECT * FROM signing_sessions WHERE chain_id = $1 AND status = $2")
+            .bind(chain_id.to_string())
+            .bind(SigningSessionStatus::AwaitingSignatures.to_string())
+            .fetch_optional(executor)
+            .await
+            .context("unable to query signing session from database")?;
+
+    raw.map(TryInto::try_into).transpose()
+}
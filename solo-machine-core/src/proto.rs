@@ -40,6 +40,14 @@ pub mod ethermint {
     }
 }
 
+pub mod solo_machine {
+    pub mod signer {
+        pub mod v1 {
+            tonic::include_proto!("solo_machine.signer.v1");
+        }
+    }
+}
+
 // #[cfg(feature = "solomachine-v2")]
 // pub mod ibc {
 //     pub mod lightclients {
@@ -1,5 +1,19 @@
 //! Services exposed by solo machine
 pub(crate) mod chain_service;
 pub(crate) mod ibc_service;
+pub(crate) mod job_service;
+pub(crate) mod outbox_service;
+pub(crate) mod policy_service;
+pub(crate) mod reconciliation_service;
 
-pub use self::{chain_service::ChainService, ibc_service::IbcService};
+pub use self::{
+    chain_service::{ChainNotFound, ChainService},
+    ibc_service::{
+        DryRunMessage, DryRunOperation, DryRunReport, DryRunTransaction, IbcChannel, IbcConnection,
+        IbcService, SigningSessionOutcome, TendermintClient,
+    },
+    job_service::{CancellationToken, JobService, OperationCancelled},
+    outbox_service::OutboxService,
+    policy_service::{MintRejected, PolicyService},
+    reconciliation_service::{ReconciliationService, SupplyReport},
+};
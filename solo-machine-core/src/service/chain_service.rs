@@ -1,20 +1,54 @@
-use anyhow::{anyhow, Context, Result};
+use std::fmt;
+
+use anyhow::{anyhow, ensure, Context, Result};
 use chain_keys::ChainKey;
+use chrono::Utc;
+use config_changes::ChainConfigChange;
+use ibc_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient as TendermintServiceClient, GetNodeInfoRequest,
+};
+use num_rational::Ratio;
 use rust_decimal::Decimal;
 use tendermint::node::Id as NodeId;
 use tendermint_rpc::{Client, HttpClient};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
-    event::notify_event,
+    event::{record_event, PendingEvents},
     ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    ibc::core::ics24_host::path::ConnectionPath,
     model::{
-        chain::{self, chain_keys},
-        Chain, ChainConfig,
+        chain::{self, chain_keys, config_changes, ConnectionStatus},
+        ibc as ibc_handler, operation, policy, Chain, ChainConfig, ChainConfigUpdate,
+        EndpointHealth,
     },
     DbPool, Event, ToPublicKey,
 };
 
+/// Error returned when details of an IBC enabled chain are not found
+#[derive(Debug)]
+pub struct ChainNotFound {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+}
+
+impl ChainNotFound {
+    /// Creates a new instance of chain not found error
+    pub fn new(chain_id: &ChainId) -> Self {
+        Self {
+            chain_id: chain_id.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ChainNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chain details for {} not found", self.chain_id)
+    }
+}
+
+impl std::error::Error for ChainNotFound {}
+
 /// Used to manage IBC enabled chain's state and metadata
 pub struct ChainService {
     db_pool: DbPool,
@@ -46,6 +80,8 @@ impl ChainService {
         let chain_id: ChainId = status.node_info.network.to_string().parse()?;
         let node_id: NodeId = status.node_info.id;
 
+        validate_fallback_endpoints(&chain_id, config).await?;
+
         let mut transaction = self
             .db_pool
             .begin()
@@ -55,19 +91,161 @@ impl ChainService {
         chain::add_chain(&mut *transaction, &chain_id, &node_id, config).await?;
         chain_keys::add_chain_key(&mut *transaction, &chain_id, public_key).await?;
 
+        let (notifier, pending_events) = PendingEvents::new(&self.notifier);
+        record_event(
+            &mut *transaction,
+            &notifier,
+            Event::ChainAdded {
+                chain_id: chain_id.clone(),
+            },
+        )
+        .await?;
+
         transaction
             .commit()
             .await
             .context("unable to commit transaction for adding IBC chain")?;
 
-        notify_event(
-            &self.notifier,
-            Event::ChainAdded {
+        pending_events.notify(&self.notifier)?;
+
+        Ok(chain_id)
+    }
+
+    /// Updates configuration of an IBC enabled chain and records the change in its audit trail. All the (new) RPC and
+    /// gRPC endpoints must report the same chain ID. Unless `allow_node_change` is `true`, the RPC endpoint must also
+    /// report the same node ID as before.
+    pub async fn update_config(
+        &self,
+        chain_id: &ChainId,
+        update: ChainConfigUpdate,
+        allow_node_change: bool,
+    ) -> Result<Chain> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        ensure!(
+            chain.connection_details.is_none() || !update.changes_connection_params(),
+            "diversifier, port ID, trusted height and trusted hash cannot be changed after connection is established"
+        );
+
+        let config = update.apply(&chain.config);
+
+        ensure!(
+            serde_json::to_value(&config)? != serde_json::to_value(&chain.config)?,
+            "no changes in chain config"
+        );
+
+        validate_config(&config)?;
+
+        let tendermint_client = HttpClient::new(config.rpc_addr.as_str())?;
+        let status = tendermint_client.status().await.context(format!(
+            "unable to fetch node status from rpc endpoint at {}",
+            config.rpc_addr
+        ))?;
+
+        let rpc_chain_id: ChainId = status.node_info.network.to_string().parse()?;
+        ensure!(
+            &rpc_chain_id == chain_id,
+            "rpc endpoint at {} reports chain ID {} instead of {}",
+            config.rpc_addr,
+            rpc_chain_id,
+            chain_id
+        );
+
+        let node_id = status.node_info.id;
+        ensure!(
+            allow_node_change || node_id == chain.node_id,
+            "rpc endpoint at {} reports node ID {} instead of {} (allow node change to update it)",
+            config.rpc_addr,
+            node_id,
+            chain.node_id
+        );
+
+        let grpc_chain_id = get_grpc_chain_id(&config.grpc_addr).await?;
+        ensure!(
+            &grpc_chain_id == chain_id,
+            "grpc endpoint at {} reports chain ID {} instead of {}",
+            config.grpc_addr,
+            grpc_chain_id,
+            chain_id
+        );
+
+        validate_fallback_endpoints(chain_id, &config).await?;
+
+        self.save_config(&chain, &node_id, &config).await
+    }
+
+    /// Replaces configuration (and node ID) of given chain (as fetched before validating the new configuration) and
+    /// records the change in its audit trail
+    async fn save_config(
+        &self,
+        chain: &Chain,
+        node_id: &NodeId,
+        config: &ChainConfig,
+    ) -> Result<Chain> {
+        let chain_id = &chain.id;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        // Old config is read again (and locked) in the transaction so that the audit trail records the config which is
+        // actually replaced
+        let old_chain = chain::get_chain_for_update(&mut *transaction, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        ensure!(
+            old_chain.node_id == chain.node_id
+                && serde_json::to_value(&old_chain.config)? == serde_json::to_value(&chain.config)?,
+            "config of chain {} was updated concurrently, please retry",
+            chain_id
+        );
+
+        let updated_chain =
+            chain::update_chain_config(&mut *transaction, chain_id, node_id, config).await?;
+        config_changes::add_chain_config_change(
+            &mut *transaction,
+            chain_id,
+            &old_chain.node_id,
+            node_id,
+            &old_chain.config,
+            config,
+        )
+        .await?;
+
+        let (notifier, pending_events) = PendingEvents::new(&self.notifier);
+        record_event(
+            &mut *transaction,
+            &notifier,
+            Event::ChainConfigUpdated {
                 chain_id: chain_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
-        Ok(chain_id)
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for updating chain config")?;
+
+        pending_events.notify(&self.notifier)?;
+
+        Ok(updated_chain)
+    }
+
+    /// Fetches audit trail of changes in configuration of an IBC enabled chain (latest first)
+    pub async fn get_config_changes(
+        &self,
+        chain_id: &ChainId,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<ChainConfigChange>> {
+        config_changes::get_chain_config_changes(&self.db_pool, chain_id, limit, offset).await
     }
 
     /// Returns the final denom of a token on solo machine after sending it on given chain
@@ -75,7 +253,7 @@ impl ChainService {
         let chain = self
             .get(chain_id)
             .await?
-            .ok_or_else(|| anyhow!("chain details not found when computing ibc denom"))?;
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
         chain.get_ibc_denom(denom)
     }
 
@@ -84,6 +262,126 @@ impl ChainService {
         chain::get_chain(&self.db_pool, chain_id).await
     }
 
+    /// Fetches all the IBC enabled chains (use `Chain::connection_status` to get status of IBC connection)
+    pub async fn list(&self, limit: i32, offset: i32) -> Result<Vec<Chain>> {
+        chain::get_chains(&self.db_pool, limit, offset).await
+    }
+
+    /// Moves an IBC enabled chain (along with its IBC data, public keys and operations) out of the active set into
+    /// archive. Fails if the channel with chain is still open or if the outcome of any of its operations is not yet
+    /// known.
+    pub async fn archive(&self, chain_id: &ChainId) -> Result<()> {
+        self.retire(chain_id, true).await
+    }
+
+    /// Permanently removes an IBC enabled chain (along with its IBC data, public keys and operations). Fails if the
+    /// channel with chain is still open or if the outcome of any of its operations is not yet known.
+    pub async fn remove(&self, chain_id: &ChainId) -> Result<()> {
+        self.retire(chain_id, false).await
+    }
+
+    async fn retire(&self, chain_id: &ChainId, archive: bool) -> Result<()> {
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let chain = chain::get_chain(&mut *transaction, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        ensure!(
+            chain.connection_status() != ConnectionStatus::Open,
+            "channel with {} is still open, close the channel first",
+            chain_id
+        );
+        ensure!(
+            !operation::has_in_flight_operations(&mut *transaction, chain_id).await?,
+            "there are operations in progress (or with unknown outcome) on {}, retry them to resolve their outcome first",
+            chain_id
+        );
+
+        let ibc_data = match chain.connection_details {
+            None => None,
+            Some(ref connection_details) => Some((
+                &connection_details.tendermint_client_id,
+                ibc_handler::get_channel_paths(
+                    &mut *transaction,
+                    &chain.config.port_id,
+                    &connection_details.tendermint_connection_id,
+                )
+                .await?
+                .into_iter()
+                .chain(std::iter::once(
+                    ConnectionPath::new(&connection_details.tendermint_connection_id)
+                        .get_key(0)
+                        .unwrap()
+                        .to_string(),
+                ))
+                .collect::<Vec<_>>(),
+            )),
+        };
+
+        if archive {
+            let archived_at = Utc::now();
+
+            chain::archive_chain(&mut *transaction, chain_id, archived_at).await?;
+            chain_keys::archive_chain_keys(&mut *transaction, chain_id, archived_at).await?;
+            operation::archive_operations(&mut *transaction, chain_id, archived_at).await?;
+
+            if let Some((client_id, ref paths)) = ibc_data {
+                ibc_handler::archive_ibc_data(
+                    &mut *transaction,
+                    chain_id,
+                    client_id,
+                    paths,
+                    archived_at,
+                )
+                .await?;
+            }
+        }
+
+        if let Some((client_id, ref paths)) = ibc_data {
+            ibc_handler::delete_ibc_data(&mut *transaction, client_id, paths).await?;
+        }
+
+        operation::delete_operations(&mut *transaction, chain_id).await?;
+        chain_keys::delete_chain_keys(&mut *transaction, chain_id).await?;
+        policy::remove_mint_policies(&mut *transaction, chain_id).await?;
+        chain::delete_chain(&mut *transaction, chain_id).await?;
+
+        let event = if archive {
+            Event::ChainArchived {
+                chain_id: chain_id.clone(),
+            }
+        } else {
+            Event::ChainRemoved {
+                chain_id: chain_id.clone(),
+            }
+        };
+
+        let (notifier, pending_events) = PendingEvents::new(&self.notifier);
+        record_event(&mut *transaction, &notifier, event).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for retiring IBC chain")?;
+
+        pending_events.notify(&self.notifier)
+    }
+
+    /// Checks health of all the RPC and gRPC endpoints (primary and fallback) of an IBC enabled chain
+    pub async fn check_endpoints(&self, chain_id: &ChainId) -> Result<Vec<EndpointHealth>> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        Ok(chain.check_endpoints().await)
+    }
+
     /// Fetches all the public keys associated with solo machine client on given chain
     pub async fn get_public_keys(
         &self,
@@ -104,8 +402,408 @@ impl ChainService {
         let chain = self
             .get(chain_id)
             .await?
-            .ok_or_else(|| anyhow!("chain details not found when fetching balance"))?;
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
 
         chain.get_balance(signer, denom).await
     }
 }
+
+fn validate_config(config: &ChainConfig) -> Result<()> {
+    ensure!(
+        config.trust_level >= Ratio::new(1, 3) && config.trust_level <= Ratio::new(1, 1),
+        "trust level should be between 1/3 and 1"
+    );
+    ensure!(
+        !config.trusting_period.is_zero(),
+        "trusting period should be greater than zero"
+    );
+    ensure!(
+        !config.rpc_timeout.is_zero(),
+        "rpc timeout should be greater than zero"
+    );
+    ensure!(
+        config.fee.gas_limit > 0,
+        "gas limit should be greater than zero"
+    );
+    ensure!(
+        !config.fee.amount.is_sign_negative(),
+        "fee amount should not be negative"
+    );
+
+    Ok(())
+}
+
+/// Ensures that all the fallback RPC and gRPC endpoints report the same chain ID
+async fn validate_fallback_endpoints(chain_id: &ChainId, config: &ChainConfig) -> Result<()> {
+    for rpc_addr in config.fallback_rpc_addrs.iter() {
+        let status = HttpClient::new(rpc_addr.as_str())?
+            .status()
+            .await
+            .context(format!(
+                "unable to fetch node status from rpc endpoint at {}",
+                rpc_addr
+            ))?;
+
+        let rpc_chain_id: ChainId = status.node_info.network.to_string().parse()?;
+        ensure!(
+            &rpc_chain_id == chain_id,
+            "rpc endpoint at {} reports chain ID {} instead of {}",
+            rpc_addr,
+            rpc_chain_id,
+            chain_id
+        );
+    }
+
+    for grpc_addr in config.fallback_grpc_addrs.iter() {
+        let grpc_chain_id = get_grpc_chain_id(grpc_addr).await?;
+        ensure!(
+            &grpc_chain_id == chain_id,
+            "grpc endpoint at {} reports chain ID {} instead of {}",
+            grpc_addr,
+            grpc_chain_id,
+            chain_id
+        );
+    }
+
+    Ok(())
+}
+
+async fn get_grpc_chain_id(grpc_addr: &str) -> Result<ChainId> {
+    let mut service_client = TendermintServiceClient::connect(grpc_addr.to_string())
+        .await
+        .context(format!(
+            "unable to connect to grpc endpoint at {}",
+            grpc_addr
+        ))?;
+
+    let node_info = service_client
+        .get_node_info(GetNodeInfoRequest {})
+        .await
+        .context(format!(
+            "unable to fetch node info from grpc endpoint at {}",
+            grpc_addr
+        ))?
+        .into_inner()
+        .default_node_info
+        .ok_or_else(|| anyhow!("node info not returned by grpc endpoint at {}", grpc_addr))?;
+
+    node_info.network.parse()
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use primitive_types::U256;
+
+    use super::*;
+    use crate::model::{operation::OperationType, ConnectionDetails, OperationStatus};
+
+    async fn add_operation(service: &ChainService, request_id: &str, status: OperationStatus) {
+        operation::add_operation(
+            &service.db_pool,
+            Some(request_id),
+            "cosmos1address",
+            Some(&"gld".parse().unwrap()),
+            Some(U256::from(100)),
+            &OperationType::Mint {
+                chain_id: "test-1".parse().unwrap(),
+            },
+            status,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn count(service: &ChainService, table: &str) -> i64 {
+        let (count,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(&service.db_pool)
+            .await
+            .unwrap();
+        count
+    }
+
+    #[tokio::test]
+    async fn archive_moves_chain_and_operations_to_archive() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        add_operation(&service, "request-1", OperationStatus::Committed).await;
+
+        assert_eq!(service.list(10, 0).await.unwrap().len(), 1);
+
+        service.archive(&chain.id).await.unwrap();
+
+        assert!(service.list(10, 0).await.unwrap().is_empty());
+        assert!(service.get(&chain.id).await.unwrap().is_none());
+        assert_eq!(count(&service, "operations").await, 0);
+        assert_eq!(count(&service, "archived_chains").await, 1);
+        assert_eq!(count(&service, "archived_operations").await, 1);
+    }
+
+    #[tokio::test]
+    async fn remove_does_not_archive_chain() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        add_operation(&service, "request-1", OperationStatus::Failed).await;
+
+        service.remove(&chain.id).await.unwrap();
+
+        assert!(service.list(10, 0).await.unwrap().is_empty());
+        assert_eq!(count(&service, "operations").await, 0);
+        assert_eq!(count(&service, "archived_chains").await, 0);
+        assert_eq!(count(&service, "archived_operations").await, 0);
+    }
+
+    #[tokio::test]
+    async fn retire_rejects_chain_with_open_channel() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        chain::add_connection_details(
+            &service.db_pool,
+            &chain.id,
+            &ConnectionDetails {
+                solo_machine_client_id: "06-solomachine-0".parse().unwrap(),
+                tendermint_client_id: "07-tendermint-0".parse().unwrap(),
+                solo_machine_connection_id: "connection-0".parse().unwrap(),
+                tendermint_connection_id: "connection-0".parse().unwrap(),
+                solo_machine_channel_id: Some("channel-0".parse().unwrap()),
+                tendermint_channel_id: Some("channel-0".parse().unwrap()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = service.archive(&chain.id).await.unwrap_err();
+        assert!(err.to_string().contains("still open"));
+        let err = service.remove(&chain.id).await.unwrap_err();
+        assert!(err.to_string().contains("still open"));
+
+        assert!(service.get(&chain.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn retire_rejects_chain_with_in_flight_operations() {
+        for status in [
+            OperationStatus::Pending,
+            OperationStatus::Broadcast,
+            OperationStatus::TimedOut,
+        ] {
+            let service = ChainService::new(crate::test_db_pool().await);
+            let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+            add_operation(&service, "request-1", status).await;
+
+            let err = service.archive(&chain.id).await.unwrap_err();
+            assert!(err.to_string().contains("operations in progress"));
+            let err = service.remove(&chain.id).await.unwrap_err();
+            assert!(err.to_string().contains("operations in progress"));
+
+            assert!(service.get(&chain.id).await.unwrap().is_some());
+            assert_eq!(count(&service, "operations").await, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn update_config_rejects_unknown_chain() {
+        let service = ChainService::new(crate::test_db_pool().await);
+
+        let err = service
+            .update_config(&"test-1".parse().unwrap(), Default::default(), false)
+            .await
+            .unwrap_err();
+        assert!(err.is::<ChainNotFound>());
+    }
+
+    #[tokio::test]
+    async fn update_config_rejects_invalid_updates() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        let err = service
+            .update_config(&chain.id, Default::default(), false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no changes"));
+
+        let update = ChainConfigUpdate {
+            trust_level: Some(Ratio::new(1, 4)),
+            ..Default::default()
+        };
+        let err = service
+            .update_config(&chain.id, update, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("trust level"));
+
+        // New RPC endpoint is unreachable, so, it can't be verified to belong to the same chain
+        let update = ChainConfigUpdate {
+            rpc_addr: Some("http://127.0.0.1:2".to_owned()),
+            ..Default::default()
+        };
+        assert!(service
+            .update_config(&chain.id, update, false)
+            .await
+            .is_err());
+
+        let unchanged = service.get(&chain.id).await.unwrap().unwrap();
+        assert_eq!(unchanged.config.rpc_addr, chain.config.rpc_addr);
+        assert!(service
+            .get_config_changes(&chain.id, 10, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn update_config_rejects_connection_params_after_connection() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        chain::add_connection_details(
+            &service.db_pool,
+            &chain.id,
+            &ConnectionDetails {
+                solo_machine_client_id: "06-solomachine-0".parse().unwrap(),
+                tendermint_client_id: "07-tendermint-0".parse().unwrap(),
+                solo_machine_connection_id: "connection-0".parse().unwrap(),
+                tendermint_connection_id: "connection-0".parse().unwrap(),
+                solo_machine_channel_id: None,
+                tendermint_channel_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let update = ChainConfigUpdate {
+            diversifier: Some("new-diversifier".to_owned()),
+            ..Default::default()
+        };
+        let err = service
+            .update_config(&chain.id, update, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot be changed"));
+    }
+
+    #[tokio::test]
+    async fn save_config_records_audit_trail() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let service = ChainService::new_with_notifier(crate::test_db_pool().await, sender);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        let config = ChainConfigUpdate {
+            gas_limit: Some(500000),
+            ..Default::default()
+        }
+        .apply(&chain.config);
+        let node_id: NodeId = "1111111111111111111111111111111111111111".parse().unwrap();
+
+        let updated = service
+            .save_config(&chain, &node_id, &config)
+            .await
+            .unwrap();
+        assert_eq!(updated.config.fee.gas_limit, 500000);
+        assert_eq!(updated.node_id, node_id);
+        assert_eq!(
+            service
+                .get(&chain.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .config
+                .fee
+                .gas_limit,
+            500000
+        );
+
+        let changes = service.get_config_changes(&chain.id, 10, 0).await.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old_node_id, chain.node_id);
+        assert_eq!(changes[0].new_node_id, node_id);
+        assert_eq!(changes[0].old_config.fee.gas_limit, 300000);
+        assert_eq!(changes[0].new_config.fee.gas_limit, 500000);
+
+        assert!(matches!(
+            receiver.try_recv().unwrap(),
+            Event::ChainConfigUpdated { chain_id } if chain_id == chain.id
+        ));
+    }
+
+    #[tokio::test]
+    async fn save_config_rejects_concurrent_update() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        let first = ChainConfigUpdate {
+            gas_limit: Some(500000),
+            ..Default::default()
+        }
+        .apply(&chain.config);
+        service
+            .save_config(&chain, &chain.node_id, &first)
+            .await
+            .unwrap();
+
+        // Second update was validated against the config which was replaced by first update
+        let second = ChainConfigUpdate {
+            gas_limit: Some(600000),
+            ..Default::default()
+        }
+        .apply(&chain.config);
+        let err = service
+            .save_config(&chain, &chain.node_id, &second)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("updated concurrently"));
+
+        assert_eq!(
+            service
+                .get(&chain.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .config
+                .fee
+                .gas_limit,
+            500000
+        );
+        assert_eq!(
+            service
+                .get_config_changes(&chain.id, 10, 0)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn get_public_keys_returns_key_history() {
+        let service = ChainService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        for public_key in ["key-1", "key-2", "key-3"] {
+            chain_keys::add_chain_key(&service.db_pool, &chain.id, public_key)
+                .await
+                .unwrap();
+        }
+
+        let public_keys = |limit, offset| service.get_public_keys(&chain.id, limit, offset);
+
+        let keys = public_keys(10, 0).await.unwrap();
+        assert_eq!(
+            keys.iter()
+                .map(|key| key.public_key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["key-3", "key-2", "key-1"]
+        );
+        assert!(keys.iter().all(|key| key.chain_id == chain.id));
+
+        let keys = public_keys(1, 1).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].public_key, "key-2");
+
+        assert!(service
+            .get_public_keys(&"test-2".parse().unwrap(), 10, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,23 +1,30 @@
 use std::collections::HashMap;
-use std::str;
-
-use anyhow::{anyhow, ensure, Context, Result};
-use ibc_proto::ibc::core::{
-    channel::v1::{
-        Channel, Counterparty as ChannelCounterparty, Order as ChannelOrder, Packet,
-        State as ChannelState,
+use std::{fmt, str, time::Instant};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use ibc_proto::ibc::{
+    core::{
+        channel::v1::{
+            Channel, Counterparty as ChannelCounterparty, Order as ChannelOrder, Packet,
+            State as ChannelState,
+        },
+        client::v1::Height,
+        commitment::v1::MerklePrefix,
+        connection::v1::{
+            ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+            Version as ConnectionVersion,
+        },
     },
-    client::v1::Height,
-    commitment::v1::MerklePrefix,
-    connection::v1::{
-        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
-        Version as ConnectionVersion,
+    lightclients::tendermint::v1::{
+        ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
     },
 };
 use primitive_types::U256;
+use sha2::{Digest, Sha256};
 use sqlx::{Executor, Transaction};
 use tendermint::{
     abci::{Event as AbciEvent, EventAttribute},
+    hash::Algorithm,
     trust_threshold::TrustThresholdFraction,
     Hash as TendermintHash,
 };
@@ -26,33 +33,173 @@ use tendermint_light_client::{
     store::memory::MemoryStore, store::LightStore,
 };
 use tendermint_rpc::{
-    endpoint::broadcast::tx_commit::Response as TxCommitResponse, Client, HttpClient,
+    endpoint::broadcast::tx_commit::Response as TxCommitResponse, error::ErrorDetail, Client,
+    HttpClient,
 };
 use tokio::sync::mpsc::UnboundedSender;
 
+pub use self::{
+    dry_run::{DryRunMessage, DryRunOperation, DryRunReport, DryRunTransaction},
+    offline::SigningSessionOutcome,
+};
+
 use crate::model::ConnectionDetails;
 use crate::transaction_builder::msg_channel_close_init;
 use crate::{
     cosmos::crypto::PublicKey,
-    event::{notify_event, Event},
+    event::{record_event, Event, PendingEvents},
     ibc::core::{
         ics02_client::{client_type::ClientType, height::IHeight},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId},
     },
     model::{
-        chain::{self, chain_keys},
-        ibc as ibc_handler,
-        operation::{self, Operation},
-        Chain, ConnectionDetails as ChainConnectionDetails, OperationType,
+        chain::{self, chain_keys, RpcEndpoint},
+        ibc as ibc_handler, operation, Chain, ConnectionDetails as ChainConnectionDetails,
+        Operation, OperationFilter, OperationKind, OperationPage, OperationStatus, OperationType,
     },
     proto::proto_encode,
-    transaction_builder, Db, DbPool, Signer, ToPublicKey,
+    service::{
+        chain_service::ChainNotFound,
+        job_service::{CancellationToken, OperationCancelled},
+        policy_service::{check_mint_policy, MintRejected},
+    },
+    signer::SignaturesPending,
+    transaction_builder::{self, LightClient},
+    Db, DbPool, Signer, ToPublicKey,
 };
 
+mod dry_run;
+mod offline;
+
+/// Tendermint client of an IBC enabled chain stored on solo machine
+#[derive(Debug)]
+pub struct TendermintClient {
+    /// Client ID of IBC enabled chain on solo machine
+    pub client_id: ClientId,
+    /// Client state of IBC enabled chain
+    pub client_state: TendermintClientState,
+    /// Consensus state of IBC enabled chain at latest height of client state
+    pub consensus_state: Option<TendermintConsensusState>,
+}
+
+/// Connection with an IBC enabled chain stored on solo machine
+#[derive(Debug)]
+pub struct IbcConnection {
+    /// Connection ID of IBC enabled chain on solo machine
+    pub connection_id: ConnectionId,
+    /// Connection end on solo machine
+    pub connection: ConnectionEnd,
+}
+
+/// Channel with an IBC enabled chain stored on solo machine
+#[derive(Debug)]
+pub struct IbcChannel {
+    /// Port ID of channel
+    pub port_id: PortId,
+    /// Channel ID of IBC enabled chain on solo machine
+    pub channel_id: ChannelId,
+    /// Channel end on solo machine
+    pub channel: Channel,
+}
+
+/// Result of recording an operation before broadcasting its transaction
+enum OperationEntry {
+    /// A new pending operation is recorded with given ID
+    New(i64),
+    /// Transaction of an operation with same request ID is already committed with given transaction hash
+    Completed(String),
+}
+
+/// Error which occurs after the transaction of an operation is broadcasted (carries the transaction hash so that it
+/// can be recorded along with the failure)
+#[derive(Debug)]
+struct BroadcastError {
+    transaction_hash: String,
+    error: Error,
+    /// `true` if outcome of the transaction is unknown, i.e., broadcasting it failed without a response from IBC
+    /// enabled chain (so, the transaction may still get committed)
+    ambiguous: bool,
+}
+
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for BroadcastError {}
+
+impl BroadcastError {
+    /// Wraps an error which occurs after the transaction of an operation is committed on IBC enabled chain. It is
+    /// recorded as ambiguous so that outcome of the operation is resolved from IBC enabled chain instead of it being
+    /// retried.
+    fn after_commit(transaction_hash: &str, error: Error) -> Error {
+        Self {
+            transaction_hash: transaction_hash.to_owned(),
+            error,
+            ambiguous: true,
+        }
+        .into()
+    }
+}
+
+/// Details of a failed operation (used for building failure events)
+struct OperationFailure {
+    /// Hash of transaction on IBC enabled chain (absent if no transaction was broadcasted)
+    transaction_hash: Option<String>,
+    /// `true` if outcome of the transaction is unknown (it may still get committed later)
+    timed_out: bool,
+    /// Error message (along with its causes)
+    error: String,
+}
+
+impl OperationFailure {
+    fn mint_failed(
+        self,
+        chain_id: &ChainId,
+        request_id: &Option<String>,
+        to_address: &str,
+        amount: U256,
+        denom: &Identifier,
+    ) -> Event {
+        Event::MintFailed {
+            chain_id: chain_id.clone(),
+            request_id: request_id.clone(),
+            to_address: to_address.to_owned(),
+            amount,
+            denom: denom.clone(),
+            transaction_hash: self.transaction_hash,
+            timed_out: self.timed_out,
+            error: self.error,
+        }
+    }
+
+    fn burn_failed(
+        self,
+        chain_id: &ChainId,
+        request_id: &Option<String>,
+        from_address: &str,
+        amount: U256,
+        denom: &Identifier,
+    ) -> Event {
+        Event::BurnFailed {
+            chain_id: chain_id.clone(),
+            request_id: request_id.clone(),
+            from_address: from_address.to_owned(),
+            amount,
+            denom: denom.clone(),
+            transaction_hash: self.transaction_hash,
+            timed_out: self.timed_out,
+            error: self.error,
+        }
+    }
+}
+
 /// Used to connect, send tokens and receive tokens over IBC
 pub struct IbcService {
     db_pool: DbPool,
     notifier: Option<UnboundedSender<Event>>,
+    cancellation: Option<CancellationToken>,
 }
 
 impl IbcService {
@@ -61,6 +208,7 @@ impl IbcService {
         Self {
             db_pool,
             notifier: None,
+            cancellation: None,
         }
     }
 
@@ -69,9 +217,45 @@ impl IbcService {
         Self {
             db_pool,
             notifier: Some(notifier),
+            cancellation: None,
         }
     }
 
+    /// Sets the token used for cancelling long-running operations (i.e., connecting and closing channel) of this
+    /// service between their steps
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Returns a copy of this service whose events are collected in returned `PendingEvents` instead (to be sent to
+    /// notifier once the database transaction recording them is committed)
+    fn defer_events(&self) -> (Self, PendingEvents) {
+        let (notifier, pending_events) = PendingEvents::new(&self.notifier);
+
+        let service = Self {
+            db_pool: self.db_pool.clone(),
+            notifier,
+            cancellation: self.cancellation.clone(),
+        };
+
+        (service, pending_events)
+    }
+
+    /// Fails with `OperationCancelled` error if cancellation of current operation is requested
+    fn ensure_not_cancelled(&self) -> Result<()> {
+        ensure!(
+            !self
+                .cancellation
+                .as_ref()
+                .map(CancellationToken::is_cancelled)
+                .unwrap_or_default(),
+            OperationCancelled
+        );
+
+        Ok(())
+    }
+
     /// Establishes connection with an IBC enabled chain
     pub async fn connect(
         &self,
@@ -81,15 +265,18 @@ impl IbcService {
         memo: String,
         force: bool,
     ) -> Result<()> {
+        self.ensure_not_reserved(&chain_id).await?;
+
         let mut transaction = self
             .db_pool
             .begin()
             .await
             .context("unable to begin database transaction")?;
+        let (service, pending_events) = self.defer_events();
 
         let mut chain = chain::get_chain(&mut *transaction, &chain_id)
             .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+            .ok_or_else(|| ChainNotFound::new(&chain_id))?;
 
         let (already_established, channel_closed) = {
             match chain.connection_details {
@@ -121,10 +308,9 @@ impl IbcService {
 
         // if connection is not not established or connection is established, but need to force reconnect
         // create new client and connection first
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let rpc_client = service.rpc_client(&mut *transaction, &chain).await?;
         let (client_id, connection_id) = if !already_established {
-            let client_id = self
+            let client_id = service
                 .create_new_client(
                     &signer,
                     memo.clone(),
@@ -134,7 +320,7 @@ impl IbcService {
                     &mut transaction,
                 )
                 .await?;
-            let connection_id = self
+            let connection_id = service
                 .create_connection(
                     &mut chain,
                     &signer,
@@ -153,7 +339,7 @@ impl IbcService {
             }
             todo!("get solo_chaine_client_id and tendermint_client_id")
         };
-        let (solo_machine_channel_id, tendermint_channel_id) = self
+        let (solo_machine_channel_id, tendermint_channel_id) = service
             .open_channel(
                 &signer,
                 memo,
@@ -176,18 +362,23 @@ impl IbcService {
 
         chain::add_connection_details(&mut *transaction, &chain.id, &connection_details).await?;
 
-        notify_event(
-            &self.notifier,
+        record_event(
+            &mut *transaction,
+            &service.notifier,
             Event::ConnectionEstablished {
                 chain_id,
+                request_id,
                 connection_details,
             },
-        )?;
+        )
+        .await?;
 
         transaction
             .commit()
             .await
-            .context("unable to commit transaction for creating ibc connection")
+            .context("unable to commit transaction for creating ibc connection")?;
+
+        pending_events.notify(&self.notifier)
     }
 
     async fn create_new_client(
@@ -199,28 +390,37 @@ impl IbcService {
         rpc_client: HttpClient,
         transaction: &mut Transaction<'_, Db>,
     ) -> Result<(ClientId, ClientId)> {
-        let mut instance =
-            prepare_light_client(chain, rpc_client.clone(), Box::new(MemoryStore::new()))?;
+        let mut light_client = prepare_light_client(chain).await?;
+
+        self.ensure_not_cancelled()?;
 
         let solo_machine_client_id =
             create_solo_machine_client(signer, &rpc_client, chain, memo, request_id).await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::CreatedSoloMachineClient {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 client_id: solo_machine_client_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         let tendermint_client_id =
-            create_tendermint_client(transaction, &mut instance, chain).await?;
+            create_tendermint_client(transaction, &mut light_client, chain).await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::CreatedTendermintClient {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 client_id: tendermint_client_id.clone(),
             },
-        )?;
+        )
+        .await?;
         Ok((solo_machine_client_id, tendermint_client_id))
     }
 
@@ -236,6 +436,8 @@ impl IbcService {
         memo: String,
         request_id: Option<&str>,
     ) -> Result<(ConnectionId, ConnectionId)> {
+        self.ensure_not_cancelled()?;
+
         let solo_machine_connection_id = connection_open_init(
             &signer,
             rpc_client,
@@ -247,12 +449,16 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::InitializedConnectionOnTendermint {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 connection_id: solo_machine_connection_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         let tendermint_connection_id = connection_open_try(
             &mut **transaction,
@@ -262,12 +468,18 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::InitializedConnectionOnSoloMachine {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 connection_id: tendermint_connection_id.clone(),
             },
-        )?;
+        )
+        .await?;
+
+        self.ensure_not_cancelled()?;
 
         connection_open_ack(
             &mut *transaction,
@@ -282,21 +494,32 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        self.record_sequence(&mut **transaction, chain, request_id)
+            .await?;
+
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::ConfirmedConnectionOnTendermint {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 connection_id: solo_machine_connection_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         connection_open_confirm(&mut *transaction, &tendermint_connection_id).await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::ConfirmedConnectionOnSoloMachine {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 connection_id: tendermint_connection_id.clone(),
             },
-        )?;
+        )
+        .await?;
         Ok((solo_machine_connection_id, tendermint_connection_id))
     }
 
@@ -312,6 +535,8 @@ impl IbcService {
         tendermint_connection_id: &ConnectionId,
         transaction: &mut Transaction<'_, Db>,
     ) -> Result<(ChannelId, ChannelId)> {
+        self.ensure_not_cancelled()?;
+
         let solo_machine_channel_id = channel_open_init(
             signer,
             rpc_client,
@@ -322,12 +547,16 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::InitializedChannelOnTendermint {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 channel_id: solo_machine_channel_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         let tendermint_channel_id = channel_open_try(
             &mut **transaction,
@@ -337,12 +566,18 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::InitializedChannelOnSoloMachine {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 channel_id: tendermint_channel_id.clone(),
             },
-        )?;
+        )
+        .await?;
+
+        self.ensure_not_cancelled()?;
 
         channel_open_ack(
             &mut *transaction,
@@ -356,21 +591,32 @@ impl IbcService {
         )
         .await?;
 
-        notify_event(
+        self.record_sequence(&mut **transaction, chain, request_id)
+            .await?;
+
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::ConfirmedChannelOnTendermint {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 channel_id: solo_machine_channel_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         channel_open_confirm(transaction, &chain.config.port_id, &tendermint_channel_id).await?;
 
-        notify_event(
+        record_event(
+            &mut **transaction,
             &self.notifier,
             Event::ConfirmedChannelOnSoloMachine {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
                 channel_id: tendermint_channel_id.clone(),
             },
-        )?;
+        )
+        .await?;
         Ok((solo_machine_channel_id, tendermint_channel_id))
     }
 
@@ -382,14 +628,17 @@ impl IbcService {
         request_id: Option<String>,
         memo: String,
     ) -> Result<()> {
+        self.ensure_not_reserved(chain_id).await?;
+
         let mut transaction = self
             .db_pool
             .begin()
             .await
             .context("unable to begin database transaction")?;
+        let (service, pending_events) = self.defer_events();
         let chain = chain::get_chain(&mut *transaction, chain_id)
             .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
         ensure!(
             chain.connection_details.is_some(),
             "chain connection details is empty"
@@ -414,8 +663,10 @@ impl IbcService {
             &new_connection_details,
         )
         .await?;
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let rpc_client = service.rpc_client(&mut *transaction, &chain).await?;
+
+        self.ensure_not_cancelled()?;
+
         let closed_solo_machine_channel_id = channel_close_init(
             &mut transaction,
             &rpc_client,
@@ -432,23 +683,40 @@ impl IbcService {
                 solo_machine_channel_id, closed_solo_machine_channel_id
             ),
         );
-        notify_event(
-            &self.notifier,
+        record_event(
+            &mut *transaction,
+            &service.notifier,
             Event::CloseChannelInitOnSoloMachine {
-                chain_id: chain_id.to_string(),
-                channel_id: solo_machine_channel_id,
+                chain_id: chain_id.clone(),
+                request_id: request_id.clone(),
+                channel_id: solo_machine_channel_id.clone(),
+            },
+        )
+        .await?;
+
+        record_event(
+            &mut *transaction,
+            &service.notifier,
+            Event::ChannelClosed {
+                chain_id: chain_id.clone(),
+                request_id,
+                solo_machine_channel_id,
+                tendermint_channel_id: tendermint_channel_id.clone(),
             },
-        )?;
+        )
+        .await?;
 
         transaction
             .commit()
             .await
             .context("unable to commit transaction for creating ibc connection")?;
 
-        Ok(())
+        pending_events.notify(&self.notifier)
     }
 
-    /// Mint some tokens on IBC enabled chain
+    /// Mint some tokens on IBC enabled chain. If the transaction of an operation with same `request_id` was already
+    /// committed, returns its transaction hash without broadcasting a new transaction. Minting is rejected (before
+    /// signing any transaction) if it violates mint policies of the chain.
     #[allow(clippy::too_many_arguments)]
     pub async fn mint(
         &self,
@@ -460,267 +728,1331 @@ impl IbcService {
         receiver: Option<String>,
         memo: String,
     ) -> Result<String> {
+        self.ensure_not_reserved(&chain_id).await?;
+
         let mut chain = chain::get_chain(&self.db_pool, &chain_id)
             .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+            .ok_or_else(|| ChainNotFound::new(&chain_id))?;
 
         let address = signer.to_account_address()?;
         let receiver = receiver.unwrap_or_else(|| address.clone());
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let operation_id = match self
+            .begin_operation(
+                request_id.as_deref(),
+                &receiver,
+                Some(&denom),
+                Some(amount),
+                &OperationType::Mint {
+                    chain_id: chain_id.clone(),
+                },
+            )
+            .await?
+        {
+            OperationEntry::New(operation_id) => operation_id,
+            OperationEntry::Completed(transaction_hash) => return Ok(transaction_hash),
+        };
 
-        let mut transaction = self
-            .db_pool
-            .begin()
-            .await
-            .context("unable to begin database transaction")?;
+        self.ensure_mint_allowed(
+            operation_id,
+            &chain_id,
+            &request_id,
+            &receiver,
+            amount,
+            &denom,
+        )
+        .await?;
 
-        let msg = transaction_builder::msg_token_send(
-            &mut transaction,
+        self.complete_mint(
             signer,
-            &rpc_client,
             &mut chain,
+            operation_id,
+            request_id,
             amount,
-            &denom,
-            receiver.clone(),
+            denom,
+            receiver,
             memo,
-            request_id.as_deref(),
+            None,
         )
-        .await?;
-
-        let response = rpc_client.broadcast_tx_commit(proto_encode(&msg)?).await?;
-
-        let transaction_hash = ensure_response_success(&response)?;
-
-        transaction
-            .commit()
-            .await
-            .context("unable to commit transaction for sending tokens over IBC")?;
+        .await
+    }
 
-        let success: bool = extract_attribute(
-            &response.tx_result.events,
-            "fungible_token_packet",
-            "success",
-        )?
-        .parse()?;
+    /// Fails the pending mint operation (and returns an error) if it violates mint policies of the chain
+    async fn ensure_mint_allowed(
+        &self,
+        operation_id: i64,
+        chain_id: &ChainId,
+        request_id: &Option<String>,
+        receiver: &str,
+        amount: U256,
+        denom: &Identifier,
+    ) -> Result<()> {
+        let rejection =
+            match check_mint_policy(&self.db_pool, chain_id, denom, receiver, amount).await {
+                Ok(rejection) => rejection,
+                Err(err) => {
+                    return self
+                        .fail_operation(operation_id, err, |failure| {
+                            Some(failure.mint_failed(chain_id, request_id, receiver, amount, denom))
+                        })
+                        .await
+                }
+            };
 
-        if success {
-            operation::add_operation(
-                &self.db_pool,
-                request_id.as_deref(),
-                &receiver,
-                &denom,
+        if let Some(reason) = rejection {
+            let event = Event::MintRejected {
+                chain_id: chain_id.clone(),
+                request_id: request_id.clone(),
+                to_address: receiver.to_owned(),
                 amount,
-                &OperationType::Mint {
-                    chain_id: chain_id.clone(),
-                },
-                &transaction_hash,
-            )
-            .await?;
-
-            notify_event(
-                &self.notifier,
-                Event::TokensMinted {
-                    chain_id,
-                    request_id,
-                    to_address: receiver,
-                    amount,
-                    denom,
-                    transaction_hash: transaction_hash.clone(),
-                },
-            )?;
-
-            Ok(transaction_hash)
-        } else {
-            let data = hex::decode(extract_attribute(
-                &response.tx_result.events,
-                "write_acknowledgement",
-                "packet_ack_hex",
-            )?)?;
-            let error = str::from_utf8(&data)?;
-
-            Err(anyhow!(
-                "Failed to mint tokens on IBC enabled chain: {}",
-                error
-            ))
+                denom: denom.clone(),
+                reason: reason.clone(),
+            };
+
+            // `MintRejected` is recorded instead of a failure event (along with the status of operation)
+            return self
+                .fail_operation(operation_id, MintRejected { reason }.into(), |_| {
+                    Some(event)
+                })
+                .await;
         }
+
+        Ok(())
     }
 
-    /// Burn some tokens on IBC enabled chain
+    /// Sends tokens of a pending mint operation and marks it as acknowledged. `timeout_height` of packet is computed
+    /// from latest height of IBC enabled chain if it is not provided.
     #[allow(clippy::too_many_arguments)]
-    pub async fn burn(
+    async fn complete_mint(
         &self,
         signer: impl Signer,
-        chain_id: ChainId,
+        chain: &mut Chain,
+        operation_id: i64,
         request_id: Option<String>,
         amount: U256,
         denom: Identifier,
+        receiver: String,
         memo: String,
+        timeout_height: Option<u64>,
     ) -> Result<String> {
-        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
-            .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+        let chain_id = chain.id.clone();
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let transaction_hash = match self
+            .send_tokens(
+                signer,
+                chain,
+                operation_id,
+                amount,
+                &denom,
+                receiver.clone(),
+                memo,
+                timeout_height,
+                request_id.as_deref(),
+            )
+            .await
+        {
+            Ok(transaction_hash) => transaction_hash,
+            Err(err) => {
+                return self
+                    .fail_operation(operation_id, err, |failure| {
+                        Some(failure.mint_failed(&chain_id, &request_id, &receiver, amount, &denom))
+                    })
+                    .await
+            }
+        };
 
-        // TODO: Remove this code once fully tested that updating solo machine client is not needed
-        //
-        // let mut transaction = self
-        //     .db_pool
-        //     .begin()
-        //     .await
-        //     .context("unable to begin database transaction")?;
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+        let (service, pending_events) = self.defer_events();
 
-        // let msg = transaction_builder::msg_update_solo_machine_client(
-        //     &mut transaction,
-        //     &signer,
-        //     &mut chain,
-        //     None,
-        //     memo.clone(),
-        // )
-        // .await?;
+        operation::set_operation_status(
+            &mut *transaction,
+            operation_id,
+            OperationStatus::Acknowledged,
+            None,
+            None,
+        )
+        .await?;
 
-        // let response = rpc_client
-        //     .broadcast_tx_commit(proto_encode(&msg)?.into())
-        //     .await?;
+        record_event(
+            &mut *transaction,
+            &service.notifier,
+            Event::TokensMinted {
+                chain_id,
+                request_id,
+                to_address: receiver,
+                amount,
+                denom,
+                transaction_hash: transaction_hash.clone(),
+            },
+        )
+        .await?;
 
-        // ensure_response_success(&response)?;
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for minting tokens over IBC")?;
 
-        // transaction
-        //     .commit()
-        //     .await
-        //     .context("unable to commit transaction for receiving tokens over IBC")?;
+        pending_events.notify(&self.notifier)?;
 
-        let address = signer.to_account_address()?;
+        Ok(transaction_hash)
+    }
 
-        let msg = transaction_builder::msg_token_receive(
-            &signer,
-            &chain,
-            amount,
-            &denom,
-            address.clone(),
-            memo.clone(),
-            request_id.as_deref(),
-        )
-        .await?;
+    #[allow(clippy::too_many_arguments)]
+    async fn send_tokens(
+        &self,
+        signer: impl Signer,
+        chain: &mut Chain,
+        operation_id: i64,
+        amount: U256,
+        denom: &Identifier,
+        receiver: String,
+        memo: String,
+        timeout_height: Option<u64>,
+        request_id: Option<&str>,
+    ) -> Result<String> {
+        let rpc_client = self.rpc_client(&self.db_pool, chain).await?;
 
-        let response = rpc_client.broadcast_tx_commit(proto_encode(&msg)?).await?;
+        let timeout_height = match timeout_height {
+            Some(revision_height) => Height::new(chain.id.version(), revision_height),
+            None => {
+                transaction_builder::get_timeout_height(
+                    chain,
+                    &rpc_client,
+                    transaction_builder::DEFAULT_TIMEOUT_HEIGHT_OFFSET,
+                )
+                .await?
+            }
+        };
 
-        let transaction_hash = ensure_response_success(&response)?;
+        let previous = (chain.sequence, chain.packet_sequence);
 
-        operation::add_operation(
-            &self.db_pool,
-            request_id.as_deref(),
-            &address,
-            &denom,
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let msg = transaction_builder::msg_token_send(
+            &mut transaction,
+            signer,
+            chain,
             amount,
-            &OperationType::Burn {
-                chain_id: chain_id.clone(),
-            },
-            &transaction_hash,
+            denom,
+            receiver,
+            memo,
+            timeout_height,
+            request_id,
         )
         .await?;
 
-        notify_event(
-            &self.notifier,
-            Event::TokensBurnt {
-                chain_id,
-                request_id: request_id.clone(),
-                from_address: address,
+        let tx_bytes = proto_encode(&msg)?;
+        let transaction_hash = self
+            .record_broadcast(&mut *transaction, operation_id, &tx_bytes, Some(previous))
+            .await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for signing tokens transfer")?;
+
+        let operation_type = OperationType::Mint {
+            chain_id: chain.id.clone(),
+        };
+
+        let response = match self
+            .broadcast_operation(
+                &rpc_client,
+                chain,
+                OperationKind::Mint,
+                request_id,
+                transaction_hash.clone(),
+                tx_bytes,
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => return Err(self.revert_rejected(&operation_type, previous, err).await),
+        };
+
+        let committed: Result<()> = async {
+            let mut transaction = self
+                .db_pool
+                .begin()
+                .await
+                .context("unable to begin database transaction")?;
+            let (service, pending_events) = self.defer_events();
+
+            service
+                .record_sequence(&mut *transaction, chain, request_id)
+                .await?;
+            self.commit_operation(&mut *transaction, operation_id, chain, &response)
+                .await?;
+
+            transaction
+                .commit()
+                .await
+                .context("unable to commit transaction for sending tokens over IBC")?;
+
+            pending_events.notify(&self.notifier)
+        }
+        .await;
+        committed.map_err(|error| BroadcastError::after_commit(&transaction_hash, error))?;
+
+        let success: bool = extract_attribute(
+            &response.tx_result.events,
+            "fungible_token_packet",
+            "success",
+        )?
+        .parse()?;
+
+        if success {
+            Ok(transaction_hash)
+        } else {
+            let data = hex::decode(extract_attribute(
+                &response.tx_result.events,
+                "write_acknowledgement",
+                "packet_ack_hex",
+            )?)?;
+            let error = str::from_utf8(&data)?;
+
+            Err(anyhow!(
+                "Failed to mint tokens on IBC enabled chain: {}",
+                error
+            ))
+        }
+    }
+
+    /// Burn some tokens on IBC enabled chain. If the transaction of an operation with same `request_id` was already
+    /// committed, returns its transaction hash without broadcasting a new transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn burn(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        amount: U256,
+        denom: Identifier,
+        memo: String,
+    ) -> Result<String> {
+        self.ensure_not_reserved(&chain_id).await?;
+
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(&chain_id))?;
+
+        let rpc_client = self.rpc_client(&self.db_pool, &chain).await?;
+
+        // TODO: Remove this code once fully tested that updating solo machine client is not needed
+        //
+        // let mut transaction = self
+        //     .db_pool
+        //     .begin()
+        //     .await
+        //     .context("unable to begin database transaction")?;
+
+        // let msg = transaction_builder::msg_update_solo_machine_client(
+        //     &mut transaction,
+        //     &signer,
+        //     &mut chain,
+        //     None,
+        //     memo.clone(),
+        // )
+        // .await?;
+
+        // let response = rpc_client
+        //     .broadcast_tx_commit(proto_encode(&msg)?.into())
+        //     .await?;
+
+        // ensure_response_success(&response)?;
+
+        // transaction
+        //     .commit()
+        //     .await
+        //     .context("unable to commit transaction for receiving tokens over IBC")?;
+
+        let address = signer.to_account_address()?;
+
+        let operation_id = match self
+            .begin_operation(
+                request_id.as_deref(),
+                &address,
+                Some(&denom),
+                Some(amount),
+                &OperationType::Burn {
+                    chain_id: chain_id.clone(),
+                },
+            )
+            .await?
+        {
+            OperationEntry::New(operation_id) => operation_id,
+            OperationEntry::Completed(transaction_hash) => return Ok(transaction_hash),
+        };
+
+        let response = self
+            .transfer_burn(
+                &signer,
+                &rpc_client,
+                &chain,
+                operation_id,
+                &request_id,
                 amount,
-                denom,
-                transaction_hash: transaction_hash.clone(),
-            },
-        )?;
+                &denom,
+                &address,
+                memo.clone(),
+            )
+            .await?;
 
-        if let Err(e) = self
-            .process_packets(
+        let transaction_hash = response.hash.to_string();
+
+        let result = match extract_packets(&response) {
+            Ok(packets) => {
+                self.process_packets(signer, &rpc_client, &mut chain, packets, memo, request_id)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        self.finish_burn(operation_id, result).await?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Transfers tokens of a pending burn operation from IBC enabled chain (fails the operation if transfer fails)
+    #[allow(clippy::too_many_arguments)]
+    async fn transfer_burn<C>(
+        &self,
+        signer: impl Signer,
+        rpc_client: &C,
+        chain: &Chain,
+        operation_id: i64,
+        request_id: &Option<String>,
+        amount: U256,
+        denom: &Identifier,
+        address: &str,
+        memo: String,
+    ) -> Result<TxCommitResponse>
+    where
+        C: Client + Send + Sync,
+    {
+        match self
+            .receive_tokens(
                 signer,
-                &rpc_client,
-                &mut chain,
-                extract_packets(&response)?,
+                rpc_client,
+                chain,
+                operation_id,
+                amount,
+                denom,
+                address.to_owned(),
                 memo,
-                request_id,
+                request_id.as_deref(),
             )
             .await
         {
+            Ok(response) => Ok(response),
+            Err(err) => {
+                self.fail_operation(operation_id, err, |failure| {
+                    Some(failure.burn_failed(&chain.id, request_id, address, amount, denom))
+                })
+                .await
+            }
+        }
+    }
+
+    /// Marks a burn operation as acknowledged once the packets of its transfer are acknowledged (`result`)
+    async fn finish_burn(&self, operation_id: i64, result: Result<()>) -> Result<()> {
+        // Operation remains committed (with error detail) if processing of packets fails so that it is not retried
+        let (status, error) = match result {
+            Ok(()) => (OperationStatus::Acknowledged, None),
+            Err(ref e) => (OperationStatus::Committed, Some(e.to_string())),
+        };
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+        let (service, pending_events) = self.defer_events();
+
+        operation::set_operation_status(
+            &mut *transaction,
+            operation_id,
+            status,
+            error.as_deref(),
+            None,
+        )
+        .await?;
+
+        if let Some(error) = error {
             // Create a warning instead of returning an error because IBC transfer is successful even if processing of
             // packets (i.e., sending acks) fails
-            notify_event(
-                &self.notifier,
-                Event::Warning {
-                    message: e.to_string(),
-                },
-            )?;
+            record_event(
+                &mut *transaction,
+                &service.notifier,
+                Event::Warning { message: error },
+            )
+            .await?;
         }
 
-        Ok(transaction_hash)
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for finishing burn operation")?;
+
+        pending_events.notify(&self.notifier)
+    }
+
+    /// Updates signer for future IBC transactions. If the transaction of an operation with same `request_id` was
+    /// already committed, returns its transaction hash without broadcasting a new transaction.
+    pub async fn update_signer(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        new_public_key: PublicKey,
+        memo: String,
+    ) -> Result<String> {
+        self.ensure_not_reserved(&chain_id).await?;
+
+        let address = signer.to_account_address()?;
+
+        let operation_id = match self
+            .begin_operation(
+                request_id.as_deref(),
+                &address,
+                None,
+                None,
+                &OperationType::UpdateSigner {
+                    chain_id: chain_id.clone(),
+                    new_public_key: new_public_key.encode(),
+                },
+            )
+            .await?
+        {
+            OperationEntry::New(operation_id) => operation_id,
+            OperationEntry::Completed(transaction_hash) => return Ok(transaction_hash),
+        };
+
+        let transaction_hash = match self
+            .send_update_signer(
+                &signer,
+                &chain_id,
+                operation_id,
+                &new_public_key,
+                memo,
+                request_id.as_deref(),
+            )
+            .await
+        {
+            Ok(transaction_hash) => transaction_hash,
+            Err(err) => return self.fail_operation(operation_id, err, |_| None).await,
+        };
+
+        Ok(transaction_hash)
+    }
+
+    async fn send_update_signer(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        operation_id: i64,
+        new_public_key: &PublicKey,
+        memo: String,
+        request_id: Option<&str>,
+    ) -> Result<String> {
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+        let previous = (chain.sequence, chain.packet_sequence);
+
+        let rpc_client = self.rpc_client(&self.db_pool, &chain).await?;
+        let old_public_key = signer.to_public_key()?;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let msg = transaction_builder::msg_update_solo_machine_client(
+            &mut *transaction,
+            signer,
+            &mut chain,
+            Some(new_public_key),
+            memo,
+            request_id,
+        )
+        .await?;
+
+        // New public key is recorded before broadcasting (along with the advanced sequences) so that it isn't lost if
+        // outcome of the transaction is unknown. Both are reverted if the transaction is rejected.
+        chain_keys::add_chain_key(&mut *transaction, chain_id, &new_public_key.encode()).await?;
+
+        let tx_bytes = proto_encode(&msg)?;
+        let transaction_hash = self
+            .record_broadcast(&mut *transaction, operation_id, &tx_bytes, Some(previous))
+            .await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for signing solo machine client update")?;
+
+        let operation_type = OperationType::UpdateSigner {
+            chain_id: chain_id.clone(),
+            new_public_key: new_public_key.encode(),
+        };
+
+        let response = match self
+            .broadcast_operation(
+                &rpc_client,
+                &chain,
+                OperationKind::UpdateSigner,
+                request_id,
+                transaction_hash.clone(),
+                tx_bytes,
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => return Err(self.revert_rejected(&operation_type, previous, err).await),
+        };
+
+        let committed: Result<()> = async {
+            let mut transaction = self
+                .db_pool
+                .begin()
+                .await
+                .context("unable to begin database transaction")?;
+            let (service, pending_events) = self.defer_events();
+
+            service
+                .record_sequence(&mut *transaction, &chain, request_id)
+                .await?;
+
+            if let Some(ref connection_details) = chain.connection_details {
+                record_event(
+                    &mut *transaction,
+                    &service.notifier,
+                    Event::SoloMachineClientUpdated {
+                        chain_id: chain_id.clone(),
+                        request_id: request_id.map(ToOwned::to_owned),
+                        client_id: connection_details.solo_machine_client_id.clone(),
+                        transaction_hash: transaction_hash.clone(),
+                    },
+                )
+                .await?;
+            }
+
+            record_event(
+                &mut *transaction,
+                &service.notifier,
+                Event::SignerUpdated {
+                    chain_id: chain_id.clone(),
+                    request_id: request_id.map(ToOwned::to_owned),
+                    old_public_key,
+                    new_public_key: new_public_key.clone(),
+                },
+            )
+            .await?;
+
+            self.commit_operation(&mut *transaction, operation_id, &chain, &response)
+                .await?;
+
+            transaction
+                .commit()
+                .await
+                .context("unable to commit transaction for updating signer")?;
+
+            pending_events.notify(&self.notifier)
+        }
+        .await;
+        committed.map_err(|error| BroadcastError::after_commit(&transaction_hash, error))?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Records a pending operation before any transaction is signed for it. If `request_id` belongs to an operation
+    /// whose transaction is already committed, its transaction hash is returned instead. Operations which failed can be
+    /// retried with same `request_id`. Outcome of an operation whose transaction was broadcasted without a response
+    /// (or by a process which crashed) is resolved by querying its transaction from IBC enabled chain before allowing a
+    /// retry.
+    async fn begin_operation(
+        &self,
+        request_id: Option<&str>,
+        address: &str,
+        denom: Option<&Identifier>,
+        amount: Option<U256>,
+        operation_type: &OperationType,
+    ) -> Result<OperationEntry> {
+        if let Some(request_id) = request_id {
+            if let Some(operation) =
+                operation::get_operation_by_request_id(&self.db_pool, request_id).await?
+            {
+                ensure!(
+                    operation.operation_type == *operation_type
+                        && operation.address == address
+                        && operation.denom.as_ref() == denom
+                        && operation.amount == amount,
+                    "request ID `{}` is already used by a different operation",
+                    request_id
+                );
+
+                let status = match (operation.status, operation.transaction_hash.as_deref()) {
+                    (
+                        OperationStatus::Broadcast | OperationStatus::TimedOut,
+                        Some(transaction_hash),
+                    ) => self.resolve_operation(&operation, transaction_hash).await?,
+                    (status, _) => status,
+                };
+
+                match status {
+                    OperationStatus::Pending => {
+                        return Err(anyhow!(
+                            "operation with request ID `{}` is already in progress",
+                            request_id
+                        ))
+                    }
+                    OperationStatus::Broadcast | OperationStatus::TimedOut => {
+                        return Err(anyhow!(
+                            "outcome of operation with request ID `{}` is unknown and its transaction {} may still be committed",
+                            request_id,
+                            operation.transaction_hash.as_deref().unwrap_or("-")
+                        ))
+                    }
+                    OperationStatus::Committed | OperationStatus::Acknowledged => {
+                        return operation
+                            .transaction_hash
+                            .map(OperationEntry::Completed)
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "transaction hash missing for committed operation with request ID `{}`",
+                                    request_id
+                                )
+                            })
+                    }
+                    OperationStatus::Failed => {}
+                }
+            }
+        }
+
+        match operation::add_operation(
+            &self.db_pool,
+            request_id,
+            address,
+            denom,
+            amount,
+            operation_type,
+            OperationStatus::Pending,
+            None,
+        )
+        .await
+        {
+            Ok(operation_id) => Ok(OperationEntry::New(operation_id)),
+            Err(err) if is_unique_violation(&err) => Err(anyhow!(
+                "operation with request ID `{}` is already in progress",
+                request_id.unwrap_or_default()
+            )),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Resolves the outcome of an operation with unknown outcome by querying its transaction from IBC enabled chain and
+    /// returns its updated status. Status remains unchanged if the transaction can't be found (it may still get
+    /// committed). Sequences (and new public key of a signer update) recorded before broadcasting are kept if the
+    /// transaction is committed and reverted if it is rejected.
+    async fn resolve_operation(
+        &self,
+        operation: &Operation,
+        transaction_hash: &str,
+    ) -> Result<OperationStatus> {
+        let chain_id = operation.operation_type.chain_id();
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        let rpc_client = self.rpc_client(&self.db_pool, &chain).await?;
+        let hash = TendermintHash::from_hex_upper(Algorithm::Sha256, transaction_hash)
+            .context("invalid transaction hash of operation")?;
+
+        let response = match rpc_client.tx(hash, false).await {
+            Ok(response) => response,
+            Err(_) => return Ok(operation.status),
+        };
+
+        if response.tx_result.code.is_ok() {
+            operation::set_operation_committed(
+                &self.db_pool,
+                operation.id,
+                response.height.value().try_into()?,
+                response.tx_result.gas_used,
+                &format!("{}{}", chain.config.fee.amount, chain.config.fee.denom),
+            )
+            .await?;
+
+            Ok(OperationStatus::Committed)
+        } else {
+            let mut transaction = self
+                .db_pool
+                .begin()
+                .await
+                .context("unable to begin database transaction")?;
+
+            operation::set_operation_status(
+                &mut *transaction,
+                operation.id,
+                OperationStatus::Failed,
+                Some(&format!(
+                    "deliver_tx response contains error code: {}",
+                    response.tx_result.log
+                )),
+                None,
+            )
+            .await?;
+
+            if let Some(previous) =
+                operation::get_operation_sequences(&mut *transaction, operation.id).await?
+            {
+                self.revert_broadcast(&mut transaction, &operation.operation_type, previous)
+                    .await?;
+            }
+
+            transaction
+                .commit()
+                .await
+                .context("unable to commit transaction for resolving operation")?;
+
+            Ok(OperationStatus::Failed)
+        }
+    }
+
+    /// Returns RPC client of the first healthy RPC endpoint of chain. Records a warning (using given executor) if
+    /// primary RPC endpoint is not healthy and a fallback RPC endpoint is used instead.
+    async fn rpc_client<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Db>,
+        chain: &Chain,
+    ) -> Result<HttpClient> {
+        let endpoint = chain.rpc_endpoint().await?;
+
+        if !endpoint.is_primary {
+            record_event(
+                executor,
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "primary rpc endpoint of {} is not healthy, failing over to {}",
+                        chain.id, endpoint.addr
+                    ),
+                },
+            )
+            .await?;
+        }
+
+        Ok(endpoint.client)
+    }
+
+    /// Marks the operation as broadcast (along with the hash of given transaction and `previous` sequences of solo
+    /// machine, if it is signed by solo machine) before its transaction is broadcasted. This should be committed (along
+    /// with the sequences advanced for signing the transaction) before broadcasting so that the transaction hash of
+    /// operation is known even if the process crashes while broadcasting.
+    async fn record_broadcast<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Db>,
+        operation_id: i64,
+        tx_bytes: &[u8],
+        previous: Option<(u32, u32)>,
+    ) -> Result<String> {
+        let transaction_hash = hex::encode_upper(Sha256::digest(tx_bytes));
+
+        operation::set_operation_broadcast(executor, operation_id, &transaction_hash, previous)
+            .await?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Reverts the changes recorded before broadcasting the transaction of an operation (see `revert_broadcast`) if it
+    /// was rejected by IBC enabled chain and returns the error. Changes are kept if outcome of the transaction is
+    /// unknown (they're reverted by `resolve_operation` if the transaction turns out to be rejected).
+    async fn revert_rejected(
+        &self,
+        operation_type: &OperationType,
+        previous: (u32, u32),
+        err: Error,
+    ) -> Error {
+        let rejected = matches!(
+            err.downcast_ref::<BroadcastError>(),
+            Some(broadcast_error) if !broadcast_error.ambiguous
+        );
+
+        if rejected {
+            let reverted: Result<()> = async {
+                let mut transaction = self
+                    .db_pool
+                    .begin()
+                    .await
+                    .context("unable to begin database transaction")?;
+
+                self.revert_broadcast(&mut transaction, operation_type, previous)
+                    .await?;
+
+                transaction
+                    .commit()
+                    .await
+                    .context("unable to commit transaction for reverting rejected operation")
+            }
+            .await;
+
+            if let Err(revert_err) = reverted {
+                log::warn!("{:#}", revert_err);
+            }
+        }
+
+        err
+    }
+
+    /// Rewinds sequences of solo machine to `previous` (i.e., the ones before signing a transaction which was rejected
+    /// by IBC enabled chain) and removes the public key added by a rejected signer update. Sequences are only rewound if
+    /// no other transaction was signed after the rejected one.
+    async fn revert_broadcast(
+        &self,
+        transaction: &mut Transaction<'_, Db>,
+        operation_type: &OperationType,
+        previous: (u32, u32),
+    ) -> Result<()> {
+        let chain_id = operation_type.chain_id();
+
+        if !chain::revert_sequences(&mut **transaction, chain_id, previous).await? {
+            log::warn!(
+                "sequences of chain {} were advanced by another operation and can't be reverted",
+                chain_id
+            );
+        }
+
+        if let OperationType::UpdateSigner { new_public_key, .. } = operation_type {
+            chain_keys::remove_chain_key(&mut **transaction, chain_id, new_public_key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Broadcasts the transaction of an operation (recorded using `record_broadcast`) and ensures that it is
+    /// successfully committed
+    async fn broadcast_operation<C>(
+        &self,
+        rpc_client: &C,
+        chain: &Chain,
+        operation_kind: OperationKind,
+        request_id: Option<&str>,
+        transaction_hash: String,
+        tx_bytes: Vec<u8>,
+    ) -> Result<TxCommitResponse>
+    where
+        C: Client + Send + Sync,
+    {
+        // Any error while broadcasting is ambiguous because the transaction may already be accepted by IBC enabled
+        // chain
+        let response = self
+            .broadcast_transaction(
+                &self.db_pool,
+                rpc_client,
+                chain,
+                request_id,
+                &operation_kind.to_string(),
+                &transaction_hash,
+                tx_bytes,
+            )
+            .await
+            .map_err(|error| BroadcastError {
+                transaction_hash: transaction_hash.clone(),
+                error,
+                ambiguous: true,
+            })?;
+
+        ensure_response_success(&response).map_err(|error| BroadcastError {
+            transaction_hash,
+            error,
+            ambiguous: false,
+        })?;
+
+        Ok(response)
+    }
+
+    /// Marks the operation as committed (using the same database transaction which records the events of operation)
+    async fn commit_operation<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Db>,
+        operation_id: i64,
+        chain: &Chain,
+        response: &TxCommitResponse,
+    ) -> Result<()> {
+        operation::set_operation_committed(
+            executor,
+            operation_id,
+            response.height.value().try_into()?,
+            response.tx_result.gas_used,
+            &format!("{}{}", chain.config.fee.amount, chain.config.fee.denom),
+        )
+        .await
+    }
+
+    /// Broadcasts a transaction to IBC enabled chain and records its outcome (along with time taken and gas used)
+    /// using given executor. Note that the returned response is not checked for error codes.
+    #[allow(clippy::too_many_arguments)]
+    async fn broadcast_transaction<'e, C>(
+        &self,
+        executor: impl Executor<'e, Database = Db>,
+        rpc_client: &C,
+        chain: &Chain,
+        request_id: Option<&str>,
+        transaction_type: &str,
+        transaction_hash: &str,
+        tx_bytes: Vec<u8>,
+    ) -> Result<TxCommitResponse>
+    where
+        C: Client + Send + Sync,
+    {
+        let started_at = Instant::now();
+        let result = rpc_client
+            .broadcast_tx_commit(tx_bytes)
+            .await
+            .map_err(Error::from);
+        let duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        let (gas_used, error_class) = match result {
+            Ok(ref response) => (
+                Some(response.tx_result.gas_used),
+                ensure_response_success(response).err().map(|_| "rejected"),
+            ),
+            Err(ref error) if is_timeout(error) => (None, Some("timeout")),
+            Err(_) => (None, Some("rpc")),
+        };
+
+        // Transaction is already broadcasted at this point, so, failing to record the event must not fail the
+        // operation (or its outcome would be recorded incorrectly)
+        if let Err(err) = record_event(
+            executor,
+            &self.notifier,
+            Event::TransactionBroadcast {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
+                transaction_type: transaction_type.to_owned(),
+                transaction_hash: transaction_hash.to_owned(),
+                duration_ms,
+                gas_used,
+                error_class: error_class.map(ToOwned::to_owned),
+            },
+        )
+        .await
+        {
+            log::warn!("{}", err);
+        }
+
+        result
+    }
+
+    /// Records that sequence of solo machine was incremented (`chain` should contain the incremented sequences)
+    async fn record_sequence<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Db>,
+        chain: &Chain,
+        request_id: Option<&str>,
+    ) -> Result<()> {
+        record_event(
+            executor,
+            &self.notifier,
+            Event::SequenceIncremented {
+                chain_id: chain.id.clone(),
+                request_id: request_id.map(ToOwned::to_owned),
+                sequence: chain.sequence,
+                packet_sequence: chain.packet_sequence,
+            },
+        )
+        .await
+    }
+
+    /// Marks the operation as failed (or timed out if outcome of its transaction is unknown), records the event
+    /// returned by `failure_event` (if any) and returns the error. Operations of offline signing sessions remain
+    /// pending when signatures of their messages are not available yet.
+    async fn fail_operation<T>(
+        &self,
+        operation_id: i64,
+        err: Error,
+        failure_event: impl FnOnce(OperationFailure) -> Option<Event>,
+    ) -> Result<T> {
+        if err.is::<SignaturesPending>() {
+            return Err(err);
+        }
+
+        let (err, transaction_hash, ambiguous) = match err.downcast::<BroadcastError>() {
+            Ok(broadcast_error) => (
+                broadcast_error.error,
+                Some(broadcast_error.transaction_hash),
+                broadcast_error.ambiguous,
+            ),
+            Err(err) => (err, None, false),
+        };
+
+        let status = if ambiguous {
+            OperationStatus::TimedOut
+        } else {
+            OperationStatus::Failed
+        };
+
+        let error = format!("{:#}", err);
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+        let (service, pending_events) = self.defer_events();
+
+        operation::set_operation_status(
+            &mut *transaction,
+            operation_id,
+            status,
+            Some(&error),
+            transaction_hash.as_deref(),
+        )
+        .await?;
+
+        let failure = OperationFailure {
+            transaction_hash,
+            timed_out: status == OperationStatus::TimedOut,
+            error,
+        };
+
+        if let Some(event) = failure_event(failure) {
+            record_event(&mut *transaction, &service.notifier, event).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for failing operation")?;
+
+        pending_events.notify(&self.notifier)?;
+
+        Err(err)
+    }
+
+    /// Fetches a page of history of operations matching given filter. `cursor` should be the `next_cursor` of previous
+    /// page (or `None` for first page). `cursor` and `offset` cannot be used together.
+    pub async fn history(
+        &self,
+        signer: impl ToPublicKey,
+        filter: &OperationFilter,
+        cursor: Option<i64>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<OperationPage> {
+        ensure!(limit > 0, "`limit` should be greater than zero");
+        ensure!(
+            cursor.is_none() || offset == 0,
+            "`cursor` and `offset` cannot be used together"
+        );
+
+        let account_address = signer.to_account_address()?;
+        let operations = operation::get_operations(
+            &self.db_pool,
+            &account_address,
+            filter,
+            cursor,
+            limit,
+            offset,
+        )
+        .await?;
+
+        let next_cursor = if operations.len() == usize::try_from(limit)? {
+            operations.last().map(|operation| operation.id)
+        } else {
+            None
+        };
+
+        Ok(OperationPage {
+            operations,
+            next_cursor,
+        })
+    }
+
+    /// Fetches tendermint client of IBC enabled chain (along with its consensus state at latest height) stored on
+    /// solo machine. Returns `None` if solo machine is not connected to the chain.
+    pub async fn get_tendermint_client(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<TendermintClient>> {
+        let connection_details = match self.get_connection_details(chain_id).await? {
+            Some(connection_details) => connection_details,
+            None => return Ok(None),
+        };
+
+        let client_id = connection_details.tendermint_client_id;
+
+        let client_state =
+            match ibc_handler::get_tendermint_client_state(&self.db_pool, &client_id).await? {
+                Some(client_state) => client_state,
+                None => return Ok(None),
+            };
+
+        let consensus_state = match client_state.latest_height {
+            Some(ref height) => {
+                ibc_handler::get_tendermint_consensus_state(&self.db_pool, &client_id, height)
+                    .await?
+            }
+            None => None,
+        };
+
+        Ok(Some(TendermintClient {
+            client_id,
+            client_state,
+            consensus_state,
+        }))
+    }
+
+    /// Fetches connection with IBC enabled chain stored on solo machine. Returns `None` if solo machine is not
+    /// connected to the chain.
+    pub async fn get_connection(&self, chain_id: &ChainId) -> Result<Option<IbcConnection>> {
+        let connection_details = match self.get_connection_details(chain_id).await? {
+            Some(connection_details) => connection_details,
+            None => return Ok(None),
+        };
+
+        let connection_id = connection_details.tendermint_connection_id;
+
+        Ok(ibc_handler::get_connection(&self.db_pool, &connection_id)
+            .await?
+            .map(|connection| IbcConnection {
+                connection_id,
+                connection,
+            }))
+    }
+
+    /// Fetches all the channels (including closed channels) over current connection with IBC enabled chain stored on
+    /// solo machine
+    pub async fn get_channels(&self, chain_id: &ChainId) -> Result<Vec<IbcChannel>> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        let connection_details = match chain.connection_details {
+            Some(connection_details) => connection_details,
+            None => return Ok(Vec::new()),
+        };
+
+        let port_id = chain.config.port_id;
+
+        Ok(ibc_handler::get_channels(
+            &self.db_pool,
+            &port_id,
+            &connection_details.tendermint_connection_id,
+        )
+        .await?
+        .into_iter()
+        .map(|(channel_id, channel)| IbcChannel {
+            port_id: port_id.clone(),
+            channel_id,
+            channel,
+        })
+        .collect())
+    }
+
+    async fn get_connection_details(
+        &self,
+        chain_id: &ChainId,
+    ) -> Result<Option<ChainConnectionDetails>> {
+        Ok(chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?
+            .connection_details)
     }
 
-    /// Updates signer for future IBC transactions
-    pub async fn update_signer(
+    /// Transfers tokens from signer's account on IBC enabled chain to solo machine and marks the burn operation as
+    /// committed (along with `TokensBurnt` event)
+    #[allow(clippy::too_many_arguments)]
+    async fn receive_tokens<C>(
         &self,
         signer: impl Signer,
-        chain_id: ChainId,
-        request_id: Option<String>,
-        new_public_key: PublicKey,
+        rpc_client: &C,
+        chain: &Chain,
+        operation_id: i64,
+        amount: U256,
+        denom: &Identifier,
+        receiver: String,
         memo: String,
-    ) -> Result<()> {
-        let mut transaction = self
-            .db_pool
-            .begin()
-            .await
-            .context("unable to begin database transaction")?;
+        request_id: Option<&str>,
+    ) -> Result<TxCommitResponse>
+    where
+        C: Client + Send + Sync,
+    {
+        let msg = transaction_builder::msg_token_receive(
+            signer,
+            chain,
+            amount,
+            denom,
+            receiver.clone(),
+            memo,
+            request_id,
+        )
+        .await?;
 
-        let mut chain = chain::get_chain(&mut *transaction, &chain_id)
-            .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+        let tx_bytes = proto_encode(&msg)?;
+        let transaction_hash = self
+            .record_broadcast(&self.db_pool, operation_id, &tx_bytes, None)
+            .await?;
 
-        chain_keys::add_chain_key(&mut *transaction, &chain_id, &new_public_key.encode()).await?;
+        let response = self
+            .broadcast_operation(
+                rpc_client,
+                chain,
+                OperationKind::Burn,
+                request_id,
+                transaction_hash,
+                tx_bytes,
+            )
+            .await?;
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let transaction_hash = response.hash.to_string();
 
-        let msg = transaction_builder::msg_update_solo_machine_client(
-            &mut *transaction,
-            &signer,
-            &mut chain,
-            Some(&new_public_key),
-            memo.clone(),
-            request_id.as_deref(),
-        )
-        .await?;
+        let committed: Result<()> = async {
+            let mut transaction = self
+                .db_pool
+                .begin()
+                .await
+                .context("unable to begin database transaction")?;
+            let (service, pending_events) = self.defer_events();
 
-        let response = rpc_client.broadcast_tx_commit(proto_encode(&msg)?).await?;
+            self.commit_operation(&mut *transaction, operation_id, chain, &response)
+                .await?;
 
-        ensure_response_success(&response)?;
+            record_event(
+                &mut *transaction,
+                &service.notifier,
+                Event::TokensBurnt {
+                    chain_id: chain.id.clone(),
+                    request_id: request_id.map(ToOwned::to_owned),
+                    from_address: receiver.clone(),
+                    amount,
+                    denom: denom.clone(),
+                    transaction_hash: transaction_hash.clone(),
+                },
+            )
+            .await?;
 
-        transaction
-            .commit()
-            .await
-            .context("unable to commit transaction for receiving tokens over IBC")?;
+            transaction
+                .commit()
+                .await
+                .context("unable to commit transaction for burning tokens")?;
 
-        notify_event(
-            &self.notifier,
-            Event::SignerUpdated {
-                chain_id,
-                old_public_key: signer.to_public_key()?,
-                new_public_key,
-            },
-        )
-    }
+            pending_events.notify(&self.notifier)
+        }
+        .await;
+        committed.map_err(|error| BroadcastError::after_commit(&transaction_hash, error))?;
 
-    /// Fetches history of all operations
-    pub async fn history(
-        &self,
-        signer: impl ToPublicKey,
-        limit: i32,
-        offset: i32,
-    ) -> Result<Vec<Operation>> {
-        let account_address = signer.to_account_address()?;
-        operation::get_operations(&self.db_pool, &account_address, limit, offset).await
+        Ok(response)
     }
 
     async fn process_packets<C>(
@@ -775,6 +2107,9 @@ impl IbcService {
                 .begin()
                 .await
                 .context("unable to begin database transaction")?;
+            let (service, pending_events) = self.defer_events();
+
+            let packet_sequence = packet.sequence;
 
             let msg = transaction_builder::msg_token_receive_ack(
                 &mut *transaction,
@@ -786,14 +2121,50 @@ impl IbcService {
             )
             .await?;
 
-            let response = rpc_client.broadcast_tx_commit(proto_encode(&msg)?).await?;
+            let tx_bytes = proto_encode(&msg)?;
+            let transaction_hash = hex::encode_upper(Sha256::digest(&tx_bytes));
+
+            let response = service
+                .broadcast_transaction(
+                    &mut *transaction,
+                    rpc_client,
+                    chain,
+                    request_id.as_deref(),
+                    "acknowledgement",
+                    &transaction_hash,
+                    tx_bytes,
+                )
+                .await?;
+
+            // Sequence is consumed even if acknowledgement is rejected by IBC enabled chain
+            service
+                .record_sequence(&mut *transaction, chain, request_id.as_deref())
+                .await?;
+
+            let acknowledged = ensure_response_success(&response);
+
+            if acknowledged.is_ok() {
+                record_event(
+                    &mut *transaction,
+                    &service.notifier,
+                    Event::AcknowledgementSent {
+                        chain_id: chain.id.clone(),
+                        request_id: request_id.clone(),
+                        packet_sequence,
+                        transaction_hash,
+                    },
+                )
+                .await?;
+            }
 
             transaction
                 .commit()
                 .await
                 .context("unable to commit transaction for processing IBC packets")?;
 
-            ensure_response_success(&response)?;
+            pending_events.notify(&self.notifier)?;
+
+            acknowledged?;
         }
 
         Ok(())
@@ -822,11 +2193,11 @@ where
 
 async fn create_tendermint_client(
     transaction: &mut Transaction<'_, Db>,
-    instance: &mut Instance,
+    light_client: &mut LightClient,
     chain: &Chain,
 ) -> Result<ClientId> {
     let (client_state, consensus_state) =
-        transaction_builder::msg_create_tendermint_client(chain, instance).await?;
+        transaction_builder::msg_create_tendermint_client(chain, light_client).await?;
 
     let client_id = ClientId::generate(ClientType::Tendermint);
     let latest_height = client_state
@@ -1075,7 +2446,7 @@ where
 {
     let chain = chain::get_chain(&mut **transaction, &chain_id)
         .await?
-        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+        .ok_or_else(|| ChainNotFound::new(&chain_id))?;
     ensure!(
         chain.connection_details.is_some(),
         "connection is not established with given chain"
@@ -1120,14 +2491,37 @@ async fn close_channel_confirm(
     Ok(())
 }
 
-fn prepare_light_client(
+/// Prepares light client with the first healthy RPC endpoint of chain as primary and all the other healthy RPC
+/// endpoints as witnesses. Headers need to be verified by a witness if chain has fallback RPC endpoints.
+async fn prepare_light_client(chain: &Chain) -> Result<LightClient> {
+    let mut instances = Vec::new();
+
+    for endpoint in chain.rpc_endpoints().await? {
+        let instance =
+            prepare_light_client_instance(chain, &endpoint, Box::new(MemoryStore::new()))?;
+        instances.push((endpoint.addr, instance));
+    }
+
+    let mut instances = instances.into_iter();
+    let (_, primary) = instances
+        .next()
+        .ok_or_else(|| anyhow!("no healthy rpc endpoint found for chain {}", chain.id))?;
+
+    Ok(LightClient {
+        primary,
+        witnesses: instances.collect(),
+        requires_witness: !chain.config.fallback_rpc_addrs.is_empty(),
+    })
+}
+
+fn prepare_light_client_instance(
     chain: &Chain,
-    rpc_client: HttpClient,
+    endpoint: &RpcEndpoint,
     light_store: Box<dyn LightStore>,
 ) -> Result<Instance> {
     let builder = LightClientBuilder::prod(
-        chain.node_id,
-        rpc_client,
+        endpoint.node_id,
+        endpoint.client.clone(),
         light_store,
         Options {
             trust_threshold: TrustThresholdFraction::new(
@@ -1248,3 +2642,568 @@ fn get_attribute(tags: &[EventAttribute], key: &str) -> Result<String> {
 
     Err(anyhow!("{} not found in tags: {:?}", key, tags))
 }
+
+fn is_unique_violation(err: &Error) -> bool {
+    match err.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(err)) => err.is_unique_violation(),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the error is caused by a timeout while waiting for a transaction to be committed
+fn is_timeout(err: &Error) -> bool {
+    err.chain().any(
+        |cause| match cause.downcast_ref::<tendermint_rpc::Error>() {
+            Some(err) => matches!(
+                err.detail(),
+                ErrorDetail::Timeout(_) | ErrorDetail::WebSocketTimeout(_)
+            ),
+            None => false,
+        },
+    )
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "cosmos1address";
+
+    fn mint_operation_type() -> OperationType {
+        OperationType::Mint {
+            chain_id: "test-1".parse().unwrap(),
+        }
+    }
+
+    async fn begin_mint(service: &IbcService, request_id: &str) -> Result<OperationEntry> {
+        service
+            .begin_operation(
+                Some(request_id),
+                ADDRESS,
+                Some(&"gld".parse().unwrap()),
+                Some(U256::from(100)),
+                &mint_operation_type(),
+            )
+            .await
+    }
+
+    async fn new_operation(service: &IbcService, request_id: &str) -> i64 {
+        match begin_mint(service, request_id).await.unwrap() {
+            OperationEntry::New(operation_id) => operation_id,
+            OperationEntry::Completed(_) => panic!("expected a new operation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn begin_operation_rejects_pending_operation() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        new_operation(&service, "request-1").await;
+
+        let err = begin_mint(&service, "request-1").await.err().unwrap();
+        assert!(err.to_string().contains("already in progress"));
+    }
+
+    #[tokio::test]
+    async fn begin_operation_rejects_unresolved_broadcast_operation() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let operation_id = new_operation(&service, "request-1").await;
+        operation::set_operation_broadcast(&service.db_pool, operation_id, "ABCD", None)
+            .await
+            .unwrap();
+
+        // Outcome of transaction can't be resolved (chain is unknown), so, the operation must not be retried
+        assert!(begin_mint(&service, "request-1").await.is_err());
+
+        let operation = operation::get_operation_by_request_id(&service.db_pool, "request-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(operation.id, operation_id);
+        assert_eq!(operation.status, OperationStatus::Broadcast);
+    }
+
+    #[tokio::test]
+    async fn begin_operation_returns_committed_transaction() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let operation_id = new_operation(&service, "request-1").await;
+        operation::set_operation_broadcast(&service.db_pool, operation_id, "ABCD", None)
+            .await
+            .unwrap();
+        operation::set_operation_committed(&service.db_pool, operation_id, 10, 1000, "10stake")
+            .await
+            .unwrap();
+
+        match begin_mint(&service, "request-1").await.unwrap() {
+            OperationEntry::Completed(transaction_hash) => assert_eq!(transaction_hash, "ABCD"),
+            OperationEntry::New(_) => panic!("committed operation must not be retried"),
+        }
+    }
+
+    #[tokio::test]
+    async fn begin_operation_retries_failed_operation() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let operation_id = new_operation(&service, "request-1").await;
+        operation::set_operation_status(
+            &service.db_pool,
+            operation_id,
+            OperationStatus::Failed,
+            Some("rejected"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let retry_id = new_operation(&service, "request-1").await;
+        assert_ne!(retry_id, operation_id);
+    }
+
+    #[tokio::test]
+    async fn revert_rejected_reverts_sequences_and_new_chain_key() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        let previous = (chain.sequence, chain.packet_sequence);
+
+        chain::increment_sequence(&service.db_pool, &chain.id)
+            .await
+            .unwrap();
+        chain_keys::add_chain_key(&service.db_pool, &chain.id, "new-key")
+            .await
+            .unwrap();
+
+        let operation_type = OperationType::UpdateSigner {
+            chain_id: chain.id.clone(),
+            new_public_key: "new-key".to_owned(),
+        };
+
+        // Outcome of transaction is unknown, so, nothing is reverted
+        let err = BroadcastError {
+            transaction_hash: "ABCD".to_owned(),
+            error: anyhow!("timed out"),
+            ambiguous: true,
+        };
+        service
+            .revert_rejected(&operation_type, previous, err.into())
+            .await;
+
+        let updated = chain::get_chain(&service.db_pool, &chain.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.sequence, previous.0 + 1);
+
+        // Transaction is rejected, so, sequence and new chain key are reverted
+        let err = BroadcastError {
+            transaction_hash: "ABCD".to_owned(),
+            error: anyhow!("rejected"),
+            ambiguous: false,
+        };
+        service
+            .revert_rejected(&operation_type, previous, err.into())
+            .await;
+
+        let reverted = chain::get_chain(&service.db_pool, &chain.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((reverted.sequence, reverted.packet_sequence), previous);
+        assert!(
+            chain_keys::get_chain_keys(&service.db_pool, &chain.id, 10, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_rejected_keeps_sequences_advanced_by_other_operations() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        let previous = (chain.sequence, chain.packet_sequence);
+
+        chain::increment_sequence(&service.db_pool, &chain.id)
+            .await
+            .unwrap();
+        chain::increment_sequence(&service.db_pool, &chain.id)
+            .await
+            .unwrap();
+
+        let err = BroadcastError {
+            transaction_hash: "ABCD".to_owned(),
+            error: anyhow!("rejected"),
+            ambiguous: false,
+        };
+        service
+            .revert_rejected(
+                &OperationType::Mint {
+                    chain_id: chain.id.clone(),
+                },
+                previous,
+                err.into(),
+            )
+            .await;
+
+        let updated = chain::get_chain(&service.db_pool, &chain.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.sequence, previous.0 + 2);
+    }
+
+    #[tokio::test]
+    async fn begin_operation_rejects_different_operation_with_same_request_id() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        new_operation(&service, "request-1").await;
+
+        let err = service
+            .begin_operation(
+                Some("request-1"),
+                ADDRESS,
+                Some(&"gld".parse().unwrap()),
+                Some(U256::from(200)),
+                &mint_operation_type(),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("different operation"));
+    }
+
+    fn history_signer() -> crate::signer::OfflineSigner {
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+        crate::signer::OfflineSigner::new(
+            PublicKey::Secp256k1(*signing_key.verifying_key()),
+            "cosmos".to_owned(),
+            &[],
+        )
+    }
+
+    /// Adds operations of history signer (with IDs 1 to 4) and an operation of another account
+    async fn add_history(service: &IbcService) {
+        let address = history_signer().to_account_address().unwrap();
+        let operations = [
+            (
+                "request-1",
+                "test-1",
+                OperationKind::Mint,
+                OperationStatus::Committed,
+            ),
+            (
+                "request-2",
+                "test-1",
+                OperationKind::Burn,
+                OperationStatus::Failed,
+            ),
+            (
+                "request-3",
+                "test-2",
+                OperationKind::Mint,
+                OperationStatus::Acknowledged,
+            ),
+            (
+                "request-4",
+                "test-1",
+                OperationKind::Mint,
+                OperationStatus::Committed,
+            ),
+        ];
+
+        for (request_id, chain_id, kind, status) in operations {
+            let chain_id: ChainId = chain_id.parse().unwrap();
+            let operation_type = match kind {
+                OperationKind::Mint => OperationType::Mint { chain_id },
+                _ => OperationType::Burn { chain_id },
+            };
+
+            operation::add_operation(
+                &service.db_pool,
+                Some(request_id),
+                &address,
+                Some(&"gld".parse().unwrap()),
+                Some(U256::from(100)),
+                &operation_type,
+                status,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        operation::add_operation(
+            &service.db_pool,
+            Some("request-5"),
+            ADDRESS,
+            Some(&"gld".parse().unwrap()),
+            Some(U256::from(100)),
+            &mint_operation_type(),
+            OperationStatus::Committed,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn history_ids(service: &IbcService, filter: &OperationFilter) -> Vec<i64> {
+        service
+            .history(history_signer(), filter, None, 10, 0)
+            .await
+            .unwrap()
+            .operations
+            .into_iter()
+            .map(|operation| operation.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn history_applies_filters() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        add_history(&service).await;
+
+        assert_eq!(
+            history_ids(&service, &OperationFilter::default()).await,
+            vec![4, 3, 2, 1]
+        );
+
+        let filter = OperationFilter {
+            chain_id: Some("test-1".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(history_ids(&service, &filter).await, vec![4, 2, 1]);
+
+        let filter = OperationFilter {
+            chain_id: Some("test-1".parse().unwrap()),
+            kind: Some(OperationKind::Mint),
+            status: Some(OperationStatus::Committed),
+            ..Default::default()
+        };
+        assert_eq!(history_ids(&service, &filter).await, vec![4, 1]);
+
+        let filter = OperationFilter {
+            request_id: Some("request-3".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(history_ids(&service, &filter).await, vec![3]);
+
+        let filter = OperationFilter {
+            denom: Some("stake".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(history_ids(&service, &filter).await.is_empty());
+
+        let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+        let filter = OperationFilter {
+            created_before: Some(tomorrow),
+            ..Default::default()
+        };
+        assert_eq!(history_ids(&service, &filter).await, vec![4, 3, 2, 1]);
+        let filter = OperationFilter {
+            created_after: Some(tomorrow),
+            ..Default::default()
+        };
+        assert!(history_ids(&service, &filter).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn history_pages_with_cursor() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        add_history(&service).await;
+        let filter = OperationFilter::default();
+
+        let page = service
+            .history(history_signer(), &filter, None, 3, 0)
+            .await
+            .unwrap();
+        assert_eq!(page.operations.len(), 3);
+        assert_eq!(page.next_cursor, Some(2));
+
+        let page = service
+            .history(history_signer(), &filter, page.next_cursor, 3, 0)
+            .await
+            .unwrap();
+        assert_eq!(page.operations.len(), 1);
+        assert_eq!(page.operations[0].id, 1);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn history_rejects_cursor_with_offset() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        add_history(&service).await;
+
+        let err = service
+            .history(history_signer(), &OperationFilter::default(), Some(4), 3, 1)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot be used together"));
+    }
+
+    fn connection_details(channel_open: bool) -> ChainConnectionDetails {
+        ChainConnectionDetails {
+            solo_machine_client_id: "06-solomachine-0".parse().unwrap(),
+            tendermint_client_id: "07-tendermint-0".parse().unwrap(),
+            solo_machine_connection_id: "connection-0".parse().unwrap(),
+            tendermint_connection_id: "connection-0".parse().unwrap(),
+            solo_machine_channel_id: channel_open.then(|| "channel-0".parse().unwrap()),
+            tendermint_channel_id: channel_open.then(|| "channel-0".parse().unwrap()),
+        }
+    }
+
+    fn channel(connection_id: &str) -> Channel {
+        Channel {
+            state: ChannelState::Open.into(),
+            connection_hops: vec![connection_id.to_owned()],
+            version: "ics20-1".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn ibc_queries_return_stored_ibc_data() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+        let connection_details = connection_details(true);
+        chain::add_connection_details(&service.db_pool, &chain.id, &connection_details)
+            .await
+            .unwrap();
+
+        let height = Height {
+            revision_number: 1,
+            revision_height: 10,
+        };
+        let client_state = TendermintClientState {
+            chain_id: "test-1".to_owned(),
+            latest_height: Some(height),
+            ..Default::default()
+        };
+        let consensus_state = TendermintConsensusState {
+            next_validators_hash: vec![1; 32],
+            ..Default::default()
+        };
+        let connection = ConnectionEnd {
+            client_id: "07-tendermint-0".to_owned(),
+            state: ConnectionState::Open.into(),
+            ..Default::default()
+        };
+
+        let client_id = &connection_details.tendermint_client_id;
+        let connection_id = &connection_details.tendermint_connection_id;
+        let port_id = &chain.config.port_id;
+
+        ibc_handler::add_tendermint_client_state(&service.db_pool, client_id, &client_state)
+            .await
+            .unwrap();
+        ibc_handler::add_tendermint_consensus_state(
+            &service.db_pool,
+            client_id,
+            &height,
+            &consensus_state,
+        )
+        .await
+        .unwrap();
+        ibc_handler::add_connection(&service.db_pool, connection_id, &connection)
+            .await
+            .unwrap();
+        ibc_handler::add_channel(
+            &service.db_pool,
+            port_id,
+            &"channel-0".parse().unwrap(),
+            &channel("connection-0"),
+        )
+        .await
+        .unwrap();
+        // Channel over another connection is not returned
+        ibc_handler::add_channel(
+            &service.db_pool,
+            port_id,
+            &"channel-1".parse().unwrap(),
+            &channel("connection-1"),
+        )
+        .await
+        .unwrap();
+
+        let client = service
+            .get_tendermint_client(&chain.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&client.client_id, client_id);
+        assert_eq!(client.client_state, client_state);
+        assert_eq!(client.consensus_state, Some(consensus_state));
+
+        let stored_connection = service.get_connection(&chain.id).await.unwrap().unwrap();
+        assert_eq!(&stored_connection.connection_id, connection_id);
+        assert_eq!(stored_connection.connection, connection);
+
+        let channels = service.get_channels(&chain.id).await.unwrap();
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].channel_id.to_string(), "channel-0");
+        assert_eq!(&channels[0].port_id, port_id);
+        assert_eq!(channels[0].channel, channel("connection-0"));
+    }
+
+    #[tokio::test]
+    async fn ibc_queries_without_connection() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        assert!(service
+            .get_tendermint_client(&chain.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service.get_connection(&chain.id).await.unwrap().is_none());
+        assert!(service.get_channels(&chain.id).await.unwrap().is_empty());
+
+        let unknown_chain_id: ChainId = "test-2".parse().unwrap();
+        for err in [
+            service
+                .get_tendermint_client(&unknown_chain_id)
+                .await
+                .err()
+                .unwrap(),
+            service
+                .get_connection(&unknown_chain_id)
+                .await
+                .err()
+                .unwrap(),
+            service.get_channels(&unknown_chain_id).await.err().unwrap(),
+        ] {
+            assert!(err.is::<ChainNotFound>());
+        }
+    }
+
+    #[tokio::test]
+    async fn close_channel_requires_open_channel() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        let close_channel = || {
+            service.close_channel(
+                history_signer(),
+                &chain.id,
+                Some("request-1".to_owned()),
+                "memo".to_owned(),
+            )
+        };
+
+        let err = close_channel().await.unwrap_err();
+        assert!(err.to_string().contains("connection details is empty"));
+
+        chain::add_connection_details(&service.db_pool, &chain.id, &connection_details(false))
+            .await
+            .unwrap();
+
+        let err = close_channel().await.unwrap_err();
+        assert!(err.to_string().contains("already closed"));
+
+        let err = service
+            .close_channel(
+                history_signer(),
+                &"test-2".parse().unwrap(),
+                None,
+                "memo".to_owned(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.is::<ChainNotFound>());
+    }
+}
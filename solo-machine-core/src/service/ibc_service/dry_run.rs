@@ -0,0 +1,674 @@
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use async_trait::async_trait;
+use ibc_proto::{
+    cosmos::tx::v1beta1::{
+        service_client::ServiceClient, AuthInfo, SimulateRequest, TxBody, TxRaw,
+    },
+    google::protobuf::Any,
+    ibc::{
+        applications::transfer::v1::MsgTransfer,
+        core::{
+            channel::v1::MsgRecvPacket,
+            client::v1::{MsgCreateClient, MsgUpdateClient},
+        },
+    },
+};
+use primitive_types::U256;
+use prost::{Message as _, Name};
+use serde::Serialize;
+
+use crate::{
+    cosmos::crypto::PublicKey,
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{chain, Chain},
+    proto::{proto_encode, AnyConvert},
+    service::{
+        chain_service::ChainNotFound,
+        policy_service::{check_mint_policy, MintRejected},
+    },
+    signer::{Message, MessageDescription},
+    transaction_builder, Signer, ToPublicKey,
+};
+
+use super::IbcService;
+
+/// Operation which is built (but not broadcasted) in a dry run
+#[derive(Debug, Clone)]
+pub enum DryRunOperation {
+    /// Establishes connection with an IBC enabled chain
+    Connect {
+        /// Memo to include in transactions
+        memo: String,
+    },
+    /// Mint some tokens on IBC enabled chain
+    Mint {
+        /// Amount of tokens
+        amount: U256,
+        /// Denom of tokens
+        denom: Identifier,
+        /// Receiver address on IBC enabled chain (defaults to signer's address)
+        receiver: Option<String>,
+        /// Memo to include in transaction
+        memo: String,
+    },
+    /// Burn some tokens on IBC enabled chain
+    Burn {
+        /// Amount of tokens
+        amount: U256,
+        /// Denom of tokens
+        denom: Identifier,
+        /// Memo to include in transaction
+        memo: String,
+    },
+    /// Updates signer's public key on IBC enabled chain
+    UpdateSigner {
+        /// New public key of signer
+        new_public_key: PublicKey,
+        /// Memo to include in transaction
+        memo: String,
+    },
+}
+
+/// Report of a dry run (i.e., everything which would be signed and broadcasted by an operation)
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Current sequence of solo machine
+    pub sequence: u32,
+    /// Sequence of solo machine after the operation
+    pub next_sequence: u32,
+    /// Current packet sequence of solo machine
+    pub packet_sequence: u32,
+    /// Packet sequence of solo machine after the operation
+    pub next_packet_sequence: u32,
+    /// Decoded descriptions of all the messages signed (in the order they are signed)
+    pub signed_messages: Vec<MessageDescription>,
+    /// Transactions which would be broadcasted
+    pub transactions: Vec<DryRunTransaction>,
+    /// Steps of operation which can't be built in a dry run because they depend on the outcome of transactions above
+    pub remaining_steps: Option<String>,
+}
+
+/// Decoded transaction built in a dry run (signatures of transaction are placeholders, so, it can't be broadcasted)
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunTransaction {
+    /// Size of encoded transaction (in bytes)
+    pub size: usize,
+    /// Decoded messages of transaction body (along with their type URLs)
+    pub messages: Vec<DryRunMessage>,
+    /// Memo of transaction
+    pub memo: String,
+    /// Fee of transaction (e.g. 1000stake)
+    pub fee: Vec<String>,
+    /// Gas limit of transaction
+    pub gas_limit: u64,
+    /// Gas used when transaction was simulated on IBC enabled chain (only present if simulation was requested and
+    /// succeeded)
+    pub simulated_gas_used: Option<u64>,
+    /// Error returned when transaction was simulated on IBC enabled chain
+    pub simulation_error: Option<String>,
+}
+
+/// Decoded message of a transaction body
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunMessage {
+    /// Type URL of message
+    pub type_url: String,
+    /// Decoded message (in protobuf JSON encoding)
+    pub value: serde_json::Value,
+}
+
+/// Signer used in dry runs which never signs anything. It records the descriptions of messages which would be signed
+/// and returns zeroed signatures (of the same length as real signatures) so that nothing signed in a dry run can be
+/// broadcasted.
+struct DryRunSigner {
+    public_key: PublicKey,
+    account_prefix: String,
+    descriptions: Mutex<Vec<MessageDescription>>,
+}
+
+impl DryRunSigner {
+    fn new(signer: impl ToPublicKey) -> Result<Self> {
+        Ok(Self {
+            public_key: signer.to_public_key()?,
+            account_prefix: signer.get_account_prefix().to_owned(),
+            descriptions: Default::default(),
+        })
+    }
+
+    fn into_descriptions(self) -> Result<Vec<MessageDescription>> {
+        self.descriptions
+            .into_inner()
+            .map_err(|_| anyhow!("unable to lock message descriptions"))
+    }
+}
+
+impl ToPublicKey for DryRunSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.public_key.account_address(&self.account_prefix)
+    }
+}
+
+#[async_trait]
+impl Signer for DryRunSigner {
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        _message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        let signature_len = match self.public_key {
+            PublicKey::Secp256k1(_) => 64,
+            #[cfg(feature = "ethermint")]
+            PublicKey::EthSecp256k1(_) => 65,
+            _ => bail!("unsupported public key type for dry run"),
+        };
+
+        self.descriptions
+            .lock()
+            .map_err(|_| anyhow!("unable to lock message descriptions"))?
+            .push(description.clone());
+
+        Ok(vec![0; signature_len])
+    }
+}
+
+impl IbcService {
+    /// Builds the transactions of an operation without signing or broadcasting them (only the public key of signer is
+    /// used and signatures are zeroed placeholders). All the changes made while building transactions (e.g.
+    /// incrementing sequence of solo machine) are rolled back. Transactions are simulated on IBC enabled chain if
+    /// `simulate` is `true` (IBC enabled chain does not verify transaction signatures in simulations, but, it does
+    /// verify the signatures of solo machine proofs, so, simulations of operations which send proofs fail).
+    ///
+    /// Only the first transaction is built for `connect` and only the transfer is built for `burn` because the rest of
+    /// their transactions depend on the outcome of previous transactions.
+    pub async fn dry_run(
+        &self,
+        signer: impl ToPublicKey,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        operation: DryRunOperation,
+        simulate: bool,
+    ) -> Result<DryRunReport> {
+        self.ensure_not_reserved(&chain_id).await?;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+        // Events recorded during dry run are rolled back along with the transaction, so they're never notified
+        let (service, _pending_events) = self.defer_events();
+
+        let mut chain = chain::get_chain(&mut *transaction, &chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(&chain_id))?;
+
+        let (sequence, packet_sequence) = (chain.sequence, chain.packet_sequence);
+
+        let signer = DryRunSigner::new(signer)?;
+        let request_id = request_id.as_deref();
+
+        let (tx_raw, remaining_steps) = match operation {
+            DryRunOperation::Connect { memo } => {
+                ensure!(
+                    chain.connection_details.is_none(),
+                    "connection is already established with given chain (dry run of re-establishing a connection is \
+                    not supported)"
+                );
+
+                let tx_raw = transaction_builder::msg_create_solo_machine_client(
+                    &signer, &chain, memo, request_id,
+                )
+                .await?;
+
+                (
+                    tx_raw,
+                    Some(
+                        "connection and channel handshakes (need ID of client created above)"
+                            .to_owned(),
+                    ),
+                )
+            }
+            DryRunOperation::Mint {
+                amount,
+                denom,
+                receiver,
+                memo,
+            } => {
+                let receiver = match receiver {
+                    Some(receiver) => receiver,
+                    None => signer.to_account_address()?,
+                };
+
+                if let Some(reason) =
+                    check_mint_policy(&self.db_pool, &chain_id, &denom, &receiver, amount).await?
+                {
+                    return Err(MintRejected { reason }.into());
+                }
+
+                let rpc_client = service.rpc_client(&mut *transaction, &chain).await?;
+                let timeout_height = transaction_builder::get_timeout_height(
+                    &chain,
+                    &rpc_client,
+                    transaction_builder::DEFAULT_TIMEOUT_HEIGHT_OFFSET,
+                )
+                .await?;
+
+                let tx_raw = transaction_builder::msg_token_send(
+                    &mut transaction,
+                    &signer,
+                    &mut chain,
+                    amount,
+                    &denom,
+                    receiver,
+                    memo,
+                    timeout_height,
+                    request_id,
+                )
+                .await?;
+
+                (tx_raw, None)
+            }
+            DryRunOperation::Burn {
+                amount,
+                denom,
+                memo,
+            } => {
+                let address = signer.to_account_address()?;
+
+                let tx_raw = transaction_builder::msg_token_receive(
+                    &signer, &chain, amount, &denom, address, memo, request_id,
+                )
+                .await?;
+
+                (
+                    tx_raw,
+                    Some(
+                        "acknowledgements of packets (need packets committed by transfer)"
+                            .to_owned(),
+                    ),
+                )
+            }
+            DryRunOperation::UpdateSigner {
+                new_public_key,
+                memo,
+            } => {
+                let tx_raw = transaction_builder::msg_update_solo_machine_client(
+                    &mut *transaction,
+                    &signer,
+                    &mut chain,
+                    Some(&new_public_key),
+                    memo,
+                    request_id,
+                )
+                .await?;
+
+                (tx_raw, None)
+            }
+        };
+
+        let (next_sequence, next_packet_sequence) = (chain.sequence, chain.packet_sequence);
+
+        transaction
+            .rollback()
+            .await
+            .context("unable to roll back database transaction of dry run")?;
+
+        let transaction = describe_transaction(&chain, &tx_raw, simulate).await?;
+
+        Ok(DryRunReport {
+            chain_id,
+            sequence,
+            next_sequence,
+            packet_sequence,
+            next_packet_sequence,
+            signed_messages: signer.into_descriptions()?,
+            transactions: vec![transaction],
+            remaining_steps,
+        })
+    }
+}
+
+/// Decodes a transaction built in a dry run (and simulates it on IBC enabled chain if `simulate` is `true`)
+async fn describe_transaction(
+    chain: &Chain,
+    tx_raw: &TxRaw,
+    simulate: bool,
+) -> Result<DryRunTransaction> {
+    let tx_body = TxBody::decode(tx_raw.body_bytes.as_slice())
+        .context("unable to decode transaction body")?;
+    let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+        .context("unable to decode auth info of transaction")?;
+    let fee = auth_info
+        .fee
+        .ok_or_else(|| anyhow!("fee not found in auth info of transaction"))?;
+
+    let tx_bytes = proto_encode(tx_raw)?;
+
+    let messages = tx_body
+        .messages
+        .iter()
+        .map(decode_message)
+        .collect::<Result<_>>()?;
+
+    let (simulated_gas_used, simulation_error) = if simulate {
+        match simulate_transaction(chain, tx_bytes.clone()).await {
+            Ok(gas_used) => (Some(gas_used), None),
+            Err(err) => (None, Some(format!("{:#}", err))),
+        }
+    } else {
+        (None, None)
+    };
+
+    Ok(DryRunTransaction {
+        size: tx_bytes.len(),
+        messages,
+        memo: tx_body.memo,
+        fee: fee
+            .amount
+            .iter()
+            .map(|coin| format!("{}{}", coin.amount, coin.denom))
+            .collect(),
+        gas_limit: fee.gas_limit,
+        simulated_gas_used,
+        simulation_error,
+    })
+}
+
+/// Simulates a transaction on IBC enabled chain and returns the gas used
+async fn simulate_transaction(chain: &Chain, tx_bytes: Vec<u8>) -> Result<u64> {
+    let mut service_client = ServiceClient::new(chain.grpc_channel().await?);
+
+    #[allow(deprecated)]
+    let request = SimulateRequest { tx: None, tx_bytes };
+
+    let response = service_client
+        .simulate(request)
+        .await
+        .context("unable to simulate transaction")?
+        .into_inner();
+
+    response
+        .gas_info
+        .map(|gas_info| gas_info.gas_used)
+        .ok_or_else(|| anyhow!("gas info not found in simulation response"))
+}
+
+/// Decodes a message of transaction body into protobuf JSON encoding
+fn decode_message(message: &Any) -> Result<DryRunMessage> {
+    let value = match message.type_url.as_str() {
+        type_url if type_url == MsgCreateClient::type_url() => {
+            to_json(MsgCreateClient::from_any(message)?)
+        }
+        type_url if type_url == MsgUpdateClient::type_url() => {
+            to_json(MsgUpdateClient::from_any(message)?)
+        }
+        type_url if type_url == MsgRecvPacket::type_url() => {
+            to_json(MsgRecvPacket::from_any(message)?)
+        }
+        type_url if type_url == MsgTransfer::type_url() => to_json(MsgTransfer::from_any(message)?),
+        type_url => bail!("unable to decode message with type url {}", type_url),
+    }?;
+
+    Ok(DryRunMessage {
+        type_url: message.type_url.clone(),
+        value,
+    })
+}
+
+fn to_json<T: Serialize>(message: T) -> Result<serde_json::Value> {
+    serde_json::to_value(message).context("unable to encode message to json")
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use ibc_proto::cosmos::auth::v1beta1::{
+        query_server::{Query, QueryServer},
+        AddressBytesToStringRequest, AddressBytesToStringResponse, AddressStringToBytesRequest,
+        AddressStringToBytesResponse, BaseAccount, Bech32PrefixRequest, Bech32PrefixResponse,
+        QueryAccountAddressByIdRequest, QueryAccountAddressByIdResponse, QueryAccountInfoRequest,
+        QueryAccountInfoResponse, QueryAccountRequest, QueryAccountResponse, QueryAccountsRequest,
+        QueryAccountsResponse, QueryModuleAccountByNameRequest, QueryModuleAccountByNameResponse,
+        QueryModuleAccountsRequest, QueryModuleAccountsResponse, QueryParamsRequest,
+        QueryParamsResponse,
+    };
+    use k256::ecdsa::SigningKey;
+    use tokio::net::TcpListener;
+    use tonic::{
+        transport::{server::TcpIncoming, Server},
+        Request, Response, Status,
+    };
+
+    use super::*;
+    use crate::{
+        model::{chain::add_connection_details, ConnectionDetails},
+        signer::{MessageContent, OfflineSigner},
+    };
+
+    /// Auth query service of IBC enabled chain which only returns the account of signer
+    struct TestAuthQuery;
+
+    #[tonic::async_trait]
+    impl Query for TestAuthQuery {
+        async fn accounts(
+            &self,
+            _: Request<QueryAccountsRequest>,
+        ) -> Result<Response<QueryAccountsResponse>, Status> {
+            Err(Status::unimplemented("accounts"))
+        }
+
+        async fn account(
+            &self,
+            request: Request<QueryAccountRequest>,
+        ) -> Result<Response<QueryAccountResponse>, Status> {
+            let account = BaseAccount {
+                address: request.into_inner().address,
+                pub_key: None,
+                account_number: 7,
+                sequence: 3,
+            };
+
+            Ok(Response::new(QueryAccountResponse {
+                account: Some(
+                    account
+                        .to_any()
+                        .map_err(|err| Status::internal(err.to_string()))?,
+                ),
+            }))
+        }
+
+        async fn account_address_by_id(
+            &self,
+            _: Request<QueryAccountAddressByIdRequest>,
+        ) -> Result<Response<QueryAccountAddressByIdResponse>, Status> {
+            Err(Status::unimplemented("account_address_by_id"))
+        }
+
+        async fn params(
+            &self,
+            _: Request<QueryParamsRequest>,
+        ) -> Result<Response<QueryParamsResponse>, Status> {
+            Err(Status::unimplemented("params"))
+        }
+
+        async fn module_accounts(
+            &self,
+            _: Request<QueryModuleAccountsRequest>,
+        ) -> Result<Response<QueryModuleAccountsResponse>, Status> {
+            Err(Status::unimplemented("module_accounts"))
+        }
+
+        async fn module_account_by_name(
+            &self,
+            _: Request<QueryModuleAccountByNameRequest>,
+        ) -> Result<Response<QueryModuleAccountByNameResponse>, Status> {
+            Err(Status::unimplemented("module_account_by_name"))
+        }
+
+        async fn bech32_prefix(
+            &self,
+            _: Request<Bech32PrefixRequest>,
+        ) -> Result<Response<Bech32PrefixResponse>, Status> {
+            Err(Status::unimplemented("bech32_prefix"))
+        }
+
+        async fn address_bytes_to_string(
+            &self,
+            _: Request<AddressBytesToStringRequest>,
+        ) -> Result<Response<AddressBytesToStringResponse>, Status> {
+            Err(Status::unimplemented("address_bytes_to_string"))
+        }
+
+        async fn address_string_to_bytes(
+            &self,
+            _: Request<AddressStringToBytesRequest>,
+        ) -> Result<Response<AddressStringToBytesResponse>, Status> {
+            Err(Status::unimplemented("address_string_to_bytes"))
+        }
+
+        async fn account_info(
+            &self,
+            _: Request<QueryAccountInfoRequest>,
+        ) -> Result<Response<QueryAccountInfoResponse>, Status> {
+            Err(Status::unimplemented("account_info"))
+        }
+    }
+
+    /// Serves auth query service on a random local port and returns its url
+    async fn serve_auth_query() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(QueryServer::new(TestAuthQuery))
+                .serve_with_incoming(incoming),
+        );
+
+        format!("http://{}", addr)
+    }
+
+    fn signer(byte: u8) -> OfflineSigner {
+        let signing_key = SigningKey::from_slice(&[byte; 32]).unwrap();
+        OfflineSigner::new(
+            PublicKey::Secp256k1(*signing_key.verifying_key()),
+            "cosmos".to_owned(),
+            &[],
+        )
+    }
+
+    /// Adds a chain (with an established connection) whose grpc endpoint serves auth query service
+    async fn add_connected_chain(service: &IbcService) -> Chain {
+        let chain = crate::add_test_chain(&service.db_pool, "test-1").await;
+
+        let mut config = chain.config.clone();
+        config.grpc_addr = serve_auth_query().await;
+        chain::update_chain_config(&service.db_pool, &chain.id, &chain.node_id, &config)
+            .await
+            .unwrap();
+
+        let connection_details = ConnectionDetails {
+            solo_machine_client_id: "06-solomachine-0".parse().unwrap(),
+            tendermint_client_id: "07-tendermint-0".parse().unwrap(),
+            solo_machine_connection_id: "connection-0".parse().unwrap(),
+            tendermint_connection_id: "connection-0".parse().unwrap(),
+            solo_machine_channel_id: Some("channel-0".parse().unwrap()),
+            tendermint_channel_id: Some("channel-0".parse().unwrap()),
+        };
+        add_connection_details(&service.db_pool, &chain.id, &connection_details)
+            .await
+            .unwrap();
+
+        chain::get_chain(&service.db_pool, &chain.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn dry_run_rolls_back_sequences() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = add_connected_chain(&service).await;
+
+        let report = service
+            .dry_run(
+                signer(7),
+                chain.id.clone(),
+                Some("request-1".to_owned()),
+                DryRunOperation::UpdateSigner {
+                    new_public_key: signer(8).to_public_key().unwrap(),
+                    memo: "memo".to_owned(),
+                },
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.sequence, chain.sequence);
+        assert_eq!(report.next_sequence, chain.sequence + 1);
+        assert_eq!(report.packet_sequence, chain.packet_sequence);
+        assert_eq!(report.next_packet_sequence, chain.packet_sequence);
+
+        let stored_chain = chain::get_chain(&service.db_pool, &chain.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_chain.sequence, chain.sequence);
+        assert_eq!(stored_chain.packet_sequence, chain.packet_sequence);
+    }
+
+    #[tokio::test]
+    async fn dry_run_does_not_sign_transactions() {
+        let service = IbcService::new(crate::test_db_pool().await);
+        let chain = add_connected_chain(&service).await;
+
+        let report = service
+            .dry_run(
+                signer(7),
+                chain.id.clone(),
+                None,
+                DryRunOperation::UpdateSigner {
+                    new_public_key: signer(8).to_public_key().unwrap(),
+                    memo: "memo".to_owned(),
+                },
+                false,
+            )
+            .await
+            .unwrap();
+
+        // Proof of new public key and the transaction itself are described but never signed
+        assert_eq!(report.signed_messages.len(), 2);
+        assert!(matches!(
+            report.signed_messages[0].content,
+            MessageContent::SignBytes(_)
+        ));
+        assert!(matches!(
+            report.signed_messages[1].content,
+            MessageContent::SignDoc(_)
+        ));
+
+        assert_eq!(report.transactions.len(), 1);
+        let transaction = &report.transactions[0];
+        assert_eq!(transaction.messages.len(), 1);
+        assert_eq!(
+            transaction.messages[0].type_url,
+            MsgUpdateClient::type_url()
+        );
+        assert_eq!(transaction.memo, "memo");
+        assert_eq!(transaction.fee, vec!["1000stake"]);
+        assert_eq!(transaction.simulated_gas_used, None);
+    }
+}
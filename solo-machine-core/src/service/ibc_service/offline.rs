@@ -0,0 +1,1050 @@
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use ibc_proto::ibc::core::channel::v1::Packet;
+
+use crate::{
+    cosmos::crypto::PublicKey,
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{
+        chain::{self, Chain},
+        operation,
+        signing_session::{self, OfflineOperation, SigningSession, SigningSessionStatus},
+        OperationStatus, OperationType,
+    },
+    proto::proto_encode,
+    service::chain_service::ChainNotFound,
+    signer::{
+        MessageSignature, OfflineSigner, SignaturesPending, SigningRequests, SigningResponse,
+    },
+    transaction_builder, ToPublicKey,
+};
+
+use super::{extract_packets, is_unique_violation, IbcService, OperationEntry};
+
+/// Outcome of preparing (or finalizing a round of) an offline signing session
+#[derive(Debug)]
+pub enum SigningSessionOutcome {
+    /// Messages of next round need to be signed offline
+    AwaitingSignatures(Box<SigningRequests>),
+    /// All the messages are signed and the operation is completed with given transaction hash
+    Completed(String),
+}
+
+/// Error used for failing the operation of an aborted signing session
+#[derive(Debug)]
+struct SessionAborted(i64);
+
+impl std::fmt::Display for SessionAborted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "offline signing session {} aborted", self.0)
+    }
+}
+
+impl std::error::Error for SessionAborted {}
+
+impl IbcService {
+    /// Prepares an offline signing session for an operation and returns the messages of its first round. Sequence and
+    /// timestamp of solo machine are reserved for the session (i.e., other operations on the chain are rejected) until
+    /// it is completed, failed or aborted. Packet sent by mint operation times out `timeout_height_offset` blocks after
+    /// latest height of IBC enabled chain, so, it should leave enough time for signing all the rounds.
+    pub async fn prepare_offline(
+        &self,
+        signer: impl ToPublicKey,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        operation: OfflineOperation,
+        timeout_height_offset: u64,
+    ) -> Result<SigningSessionOutcome> {
+        self.ensure_not_reserved(&chain_id).await?;
+
+        let chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(&chain_id))?;
+
+        let public_key = signer.to_public_key()?;
+        let address = signer.to_account_address()?;
+
+        let (operation, operation_address, operation_type) = match operation {
+            OfflineOperation::Mint {
+                amount,
+                denom,
+                receiver,
+                memo,
+            } => {
+                let receiver = receiver.unwrap_or_else(|| address.clone());
+
+                (
+                    OfflineOperation::Mint {
+                        amount,
+                        denom,
+                        receiver: Some(receiver.clone()),
+                        memo,
+                    },
+                    receiver,
+                    OperationType::Mint {
+                        chain_id: chain_id.clone(),
+                    },
+                )
+            }
+            OfflineOperation::Burn { .. } => (
+                operation,
+                address.clone(),
+                OperationType::Burn {
+                    chain_id: chain_id.clone(),
+                },
+            ),
+            OfflineOperation::UpdateSigner {
+                ref new_public_key, ..
+            } => {
+                let operation_type = OperationType::UpdateSigner {
+                    chain_id: chain_id.clone(),
+                    new_public_key: new_public_key.encode(),
+                };
+
+                (operation, address.clone(), operation_type)
+            }
+        };
+
+        let (denom, amount) = match operation {
+            OfflineOperation::Mint {
+                amount, ref denom, ..
+            }
+            | OfflineOperation::Burn {
+                amount, ref denom, ..
+            } => (Some(denom.clone()), Some(amount)),
+            OfflineOperation::UpdateSigner { .. } => (None, None),
+        };
+
+        let operation_id = match self
+            .begin_operation(
+                request_id.as_deref(),
+                &operation_address,
+                denom.as_ref(),
+                amount,
+                &operation_type,
+            )
+            .await?
+        {
+            OperationEntry::New(operation_id) => operation_id,
+            OperationEntry::Completed(transaction_hash) => {
+                return Ok(SigningSessionOutcome::Completed(transaction_hash))
+            }
+        };
+
+        let timeout_height = match operation {
+            OfflineOperation::Mint {
+                amount, ref denom, ..
+            } => {
+                self.ensure_mint_allowed(
+                    operation_id,
+                    &chain_id,
+                    &request_id,
+                    &operation_address,
+                    amount,
+                    denom,
+                )
+                .await?;
+
+                let timeout_height = async {
+                    let rpc_client = self.rpc_client(&self.db_pool, &chain).await?;
+                    transaction_builder::get_timeout_height(
+                        &chain,
+                        &rpc_client,
+                        timeout_height_offset,
+                    )
+                    .await
+                }
+                .await;
+
+                match timeout_height {
+                    Ok(timeout_height) => Some(timeout_height.revision_height),
+                    Err(err) => {
+                        return self
+                            .fail_operation(operation_id, err, |failure| {
+                                Some(failure.mint_failed(
+                                    &chain_id,
+                                    &request_id,
+                                    &operation_address,
+                                    amount,
+                                    denom,
+                                ))
+                            })
+                            .await
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let session = match self
+            .reserve_sequence(
+                &chain,
+                operation_id,
+                request_id.as_deref(),
+                &operation,
+                &public_key,
+                signer.get_account_prefix(),
+                timeout_height,
+            )
+            .await
+        {
+            Ok(session) => session,
+            Err(err) => return self.fail_operation(operation_id, err, |_| None).await,
+        };
+
+        self.run_signing_session(session, Vec::new()).await
+    }
+
+    /// Imports signatures of current round of an offline signing session and continues its operation. Returns the
+    /// messages of next round if more messages need to be signed (e.g. a transaction containing a proof which is signed
+    /// in current round).
+    pub async fn finalize_offline(
+        &self,
+        response: SigningResponse,
+    ) -> Result<SigningSessionOutcome> {
+        let session = self.get_open_signing_session(response.session_id).await?;
+
+        ensure!(
+            response.round == session.round,
+            "signatures are for round {} but offline signing session {} is in round {}",
+            response.round,
+            session.id,
+            session.round
+        );
+
+        for signature in response.signatures.iter() {
+            ensure!(
+                session
+                    .requests
+                    .iter()
+                    .any(|request| request.message == signature.message),
+                "signature of a message which is not requested in round {} of offline signing session {}",
+                session.round,
+                session.id
+            );
+        }
+
+        let mut signatures = session.signatures.clone();
+
+        for request in session.requests.iter() {
+            request.description.verify(&request.message())?;
+
+            let signature = response
+                .signatures
+                .iter()
+                .find(|signature| signature.message == request.message)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "signature missing for {} message: {}",
+                        request.message_type,
+                        request.description
+                    )
+                })?;
+
+            signature.verify(&session.public_key).with_context(|| {
+                format!(
+                    "invalid signature for {} message: {}",
+                    request.message_type, request.description
+                )
+            })?;
+
+            signatures.push(signature.clone());
+        }
+
+        self.run_signing_session(session, signatures).await
+    }
+
+    /// Aborts an offline signing session and releases the sequence reserved for it. Operation of the session is marked
+    /// as failed (a burn operation whose transfer is already committed remains committed with error detail).
+    pub async fn abort_offline(&self, session_id: i64) -> Result<()> {
+        let session = self.get_open_signing_session(session_id).await?;
+
+        let error = SessionAborted(session.id).to_string();
+
+        ensure!(
+            signing_session::finish_signing_session(
+                &self.db_pool,
+                session.id,
+                SigningSessionStatus::Aborted,
+                None,
+                Some(&error),
+            )
+            .await?,
+            "offline signing session {} is not awaiting signatures",
+            session.id
+        );
+
+        match self
+            .fail_session_operation::<()>(&session, SessionAborted(session.id).into())
+            .await
+        {
+            Err(err) if err.is::<SessionAborted>() => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Reserves current sequence and timestamp of solo machine for a new offline signing session. Only one session can
+    /// await signatures on a chain (enforced by database), so, this fails if another session reserved the sequence
+    /// after `ensure_not_reserved()` was checked.
+    #[allow(clippy::too_many_arguments)]
+    async fn reserve_sequence(
+        &self,
+        chain: &Chain,
+        operation_id: i64,
+        request_id: Option<&str>,
+        operation: &OfflineOperation,
+        public_key: &PublicKey,
+        account_prefix: &str,
+        timeout_height: Option<u64>,
+    ) -> Result<SigningSession> {
+        match signing_session::add_signing_session(
+            &self.db_pool,
+            &chain.id,
+            operation_id,
+            request_id,
+            operation,
+            public_key,
+            account_prefix,
+            chain.sequence,
+            chain.consensus_timestamp,
+            timeout_height,
+        )
+        .await
+        {
+            Err(err) if is_unique_violation(&err) => Err(anyhow!(
+                "sequence of chain {} is already reserved by another offline signing session (finalize or abort it \
+                 first)",
+                chain.id
+            )),
+            result => result,
+        }
+    }
+
+    /// Fails if sequence of solo machine for given chain is reserved by an open offline signing session
+    pub(super) async fn ensure_not_reserved(&self, chain_id: &ChainId) -> Result<()> {
+        match signing_session::get_open_signing_session(&self.db_pool, chain_id).await? {
+            None => Ok(()),
+            Some(session) => Err(anyhow!(
+                "sequence of chain {} is reserved by offline signing session {} (finalize or abort it first)",
+                chain_id,
+                session.id
+            )),
+        }
+    }
+
+    async fn get_open_signing_session(&self, session_id: i64) -> Result<SigningSession> {
+        let session = signing_session::get_signing_session(&self.db_pool, session_id)
+            .await?
+            .ok_or_else(|| anyhow!("offline signing session {} not found", session_id))?;
+
+        ensure!(
+            !session.status.is_finished(),
+            "offline signing session {} is already {}",
+            session.id,
+            session.status
+        );
+
+        Ok(session)
+    }
+
+    /// Executes operation of a signing session with the signatures collected so far. Database changes made while
+    /// building a transaction are rolled back if one of its messages is not signed yet, and the unsigned message is
+    /// exported as next round of the session.
+    async fn run_signing_session(
+        &self,
+        mut session: SigningSession,
+        signatures: Vec<MessageSignature>,
+    ) -> Result<SigningSessionOutcome> {
+        let chain = chain::get_chain(&self.db_pool, &session.chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(&session.chain_id))?;
+
+        let result = if chain.sequence != session.sequence
+            || chain.consensus_timestamp != session.consensus_timestamp
+        {
+            let err = anyhow!(
+                "sequence of chain {} changed after offline signing session {} reserved it (reserved: {}, current: {})",
+                session.chain_id,
+                session.id,
+                session.sequence,
+                chain.sequence
+            );
+
+            self.fail_session_operation(&session, err).await
+        } else {
+            let signer = OfflineSigner::new(
+                session.public_key.clone(),
+                session.account_prefix.clone(),
+                &signatures,
+            );
+
+            match self.execute_signing_session(&mut session, &signer).await {
+                Err(err) if err.is::<SignaturesPending>() => {
+                    let requests = signer.take_requests()?;
+                    let round = session.round + 1;
+
+                    signing_session::set_signing_session_round(
+                        &self.db_pool,
+                        session.id,
+                        round,
+                        &requests,
+                        &signatures,
+                    )
+                    .await?;
+
+                    return Ok(SigningSessionOutcome::AwaitingSignatures(Box::new(
+                        SigningRequests {
+                            session_id: session.id,
+                            round,
+                            chain_id: session.chain_id,
+                            address: signer.to_account_address()?,
+                            public_key: session.public_key,
+                            requests,
+                        },
+                    )));
+                }
+                result => result,
+            }
+        };
+
+        match result {
+            Ok(transaction_hash) => {
+                signing_session::finish_signing_session(
+                    &self.db_pool,
+                    session.id,
+                    SigningSessionStatus::Completed,
+                    Some(&transaction_hash),
+                    None,
+                )
+                .await?;
+
+                Ok(SigningSessionOutcome::Completed(transaction_hash))
+            }
+            Err(err) => {
+                signing_session::finish_signing_session(
+                    &self.db_pool,
+                    session.id,
+                    SigningSessionStatus::Failed,
+                    None,
+                    Some(&format!("{:#}", err)),
+                )
+                .await?;
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Executes operation of a signing session using given offline signer and returns hash of its transaction
+    async fn execute_signing_session(
+        &self,
+        session: &mut SigningSession,
+        signer: &OfflineSigner,
+    ) -> Result<String> {
+        let mut chain = chain::get_chain(&self.db_pool, &session.chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(&session.chain_id))?;
+
+        match session.operation.clone() {
+            OfflineOperation::Mint {
+                amount,
+                denom,
+                receiver,
+                memo,
+            } => {
+                let receiver = match receiver {
+                    Some(receiver) => receiver,
+                    None => signer.to_account_address()?,
+                };
+
+                self.complete_mint(
+                    signer,
+                    &mut chain,
+                    session.operation_id,
+                    session.request_id.clone(),
+                    amount,
+                    denom,
+                    receiver,
+                    memo,
+                    session.timeout_height,
+                )
+                .await
+            }
+            OfflineOperation::Burn {
+                amount,
+                denom,
+                memo,
+            } => {
+                let rpc_client = match self.rpc_client(&self.db_pool, &chain).await {
+                    Ok(rpc_client) => rpc_client,
+                    Err(err) => return self.fail_session_operation(session, err).await,
+                };
+
+                let transaction_hash = match session.transaction_hash {
+                    Some(ref transaction_hash) => transaction_hash.clone(),
+                    None => {
+                        let response = self
+                            .transfer_burn(
+                                signer,
+                                &rpc_client,
+                                &chain,
+                                session.operation_id,
+                                &session.request_id,
+                                amount,
+                                &denom,
+                                &signer.to_account_address()?,
+                                memo.clone(),
+                            )
+                            .await?;
+
+                        let transaction_hash = response.hash.to_string();
+
+                        let packets = match extract_packets(&response).and_then(|packets| {
+                            packets
+                                .iter()
+                                .map(|packet| proto_encode(packet).map(hex::encode))
+                                .collect::<Result<Vec<_>>>()
+                        }) {
+                            Ok(packets) => packets,
+                            Err(err) => {
+                                self.finish_burn(session.operation_id, Err(err)).await?;
+                                return Ok(transaction_hash);
+                            }
+                        };
+
+                        session.transaction_hash = Some(transaction_hash.clone());
+                        session.packets = packets;
+
+                        self.record_session_progress(session, chain.sequence)
+                            .await?;
+
+                        transaction_hash
+                    }
+                };
+
+                while let Some(packet) = session.packets.first() {
+                    let result = match decode_packet(packet) {
+                        Ok(packet) => {
+                            self.process_packets(
+                                signer,
+                                &rpc_client,
+                                &mut chain,
+                                vec![packet],
+                                memo.clone(),
+                                session.request_id.clone(),
+                            )
+                            .await
+                        }
+                        Err(err) => Err(err),
+                    };
+
+                    match result {
+                        Ok(()) => {
+                            session.packets.remove(0);
+                            self.record_session_progress(session, chain.sequence)
+                                .await?;
+                        }
+                        Err(err) if err.is::<SignaturesPending>() => return Err(err),
+                        Err(err) => {
+                            self.finish_burn(session.operation_id, Err(err)).await?;
+                            return Ok(transaction_hash);
+                        }
+                    }
+                }
+
+                self.finish_burn(session.operation_id, Ok(())).await?;
+
+                Ok(transaction_hash)
+            }
+            OfflineOperation::UpdateSigner {
+                new_public_key,
+                memo,
+            } => {
+                match self
+                    .send_update_signer(
+                        signer,
+                        &session.chain_id,
+                        session.operation_id,
+                        &new_public_key,
+                        memo,
+                        session.request_id.as_deref(),
+                    )
+                    .await
+                {
+                    Ok(transaction_hash) => Ok(transaction_hash),
+                    Err(err) => {
+                        self.fail_operation(session.operation_id, err, |_| None)
+                            .await
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records the sequence and pending packets of a signing session after one of its transactions is broadcasted
+    async fn record_session_progress(
+        &self,
+        session: &mut SigningSession,
+        sequence: u32,
+    ) -> Result<()> {
+        signing_session::set_signing_session_progress(
+            &self.db_pool,
+            session.id,
+            sequence,
+            &session.packets,
+            session.transaction_hash.as_deref(),
+        )
+        .await?;
+
+        session.sequence = sequence;
+
+        Ok(())
+    }
+
+    /// Fails the operation of a signing session (along with its failure event) and returns the error
+    async fn fail_session_operation<T>(&self, session: &SigningSession, err: Error) -> Result<T> {
+        match session.operation {
+            OfflineOperation::Mint {
+                amount,
+                ref denom,
+                ref receiver,
+                ..
+            } => {
+                let receiver = match receiver {
+                    Some(receiver) => receiver.clone(),
+                    None => session
+                        .public_key
+                        .account_address(&session.account_prefix)?,
+                };
+
+                self.fail_operation(session.operation_id, err, |failure| {
+                    Some(failure.mint_failed(
+                        &session.chain_id,
+                        &session.request_id,
+                        &receiver,
+                        amount,
+                        denom,
+                    ))
+                })
+                .await
+            }
+            OfflineOperation::Burn {
+                amount, ref denom, ..
+            } if session.transaction_hash.is_none() => {
+                let address = session
+                    .public_key
+                    .account_address(&session.account_prefix)?;
+
+                self.fail_operation(session.operation_id, err, |failure| {
+                    Some(failure.burn_failed(
+                        &session.chain_id,
+                        &session.request_id,
+                        &address,
+                        amount,
+                        denom,
+                    ))
+                })
+                .await
+            }
+            // Transfer of burn operation is already committed, so, it remains committed (with error detail)
+            OfflineOperation::Burn { .. } => {
+                operation::set_operation_status(
+                    &self.db_pool,
+                    session.operation_id,
+                    OperationStatus::Committed,
+                    Some(&format!("{:#}", err)),
+                    None,
+                )
+                .await?;
+
+                Err(err)
+            }
+            OfflineOperation::UpdateSigner { .. } => {
+                self.fail_operation(session.operation_id, err, |_| None)
+                    .await
+            }
+        }
+    }
+}
+
+fn decode_packet(packet: &str) -> Result<Packet> {
+    let bytes = hex::decode(packet).context("invalid hex encoded packet")?;
+    <Packet as prost::Message>::decode(bytes.as_slice()).context("unable to decode packet")
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, SignDoc, SignerInfo, TxBody};
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::{
+        model::OperationType,
+        signer::{
+            AddressAlgo, MessageContent, MessageDescription, MessageKind, SignDocDescription,
+            SignatureRequest,
+        },
+    };
+
+    const REQUEST_ID: &str = "offline-1";
+
+    fn signing_key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn public_key() -> PublicKey {
+        PublicKey::Secp256k1(*signing_key(7).verifying_key())
+    }
+
+    /// A message of first round of signing session (as exported by `prepare_offline`)
+    fn signature_request() -> SignatureRequest {
+        let message = proto_encode(&SignDoc {
+            body_bytes: proto_encode(&TxBody {
+                memo: "memo".to_owned(),
+                ..Default::default()
+            })
+            .unwrap(),
+            auth_info_bytes: proto_encode(&AuthInfo {
+                signer_infos: vec![SignerInfo::default()],
+                ..Default::default()
+            })
+            .unwrap(),
+            chain_id: "test-1".to_owned(),
+            account_number: 0,
+        })
+        .unwrap();
+
+        SignatureRequest {
+            message_type: MessageKind::SignDoc,
+            message,
+            description: MessageDescription {
+                chain_id: "test-1".to_owned(),
+                content: MessageContent::SignDoc(SignDocDescription {
+                    account_number: 0,
+                    account_sequence: 0,
+                    messages: Vec::new(),
+                    memo: "memo".to_owned(),
+                    packet_data: None,
+                }),
+            },
+        }
+    }
+
+    fn sign(message: &[u8], signing_key: &SigningKey) -> MessageSignature {
+        MessageSignature {
+            message: message.to_vec(),
+            signature: AddressAlgo::Secp256k1.sign(signing_key, message).unwrap(),
+        }
+    }
+
+    fn response(session: &SigningSession, signatures: Vec<MessageSignature>) -> SigningResponse {
+        SigningResponse {
+            session_id: session.id,
+            round: session.round,
+            signatures,
+        }
+    }
+
+    /// Prepares a signing session of a signer update whose first round awaits signature of `signature_request()`
+    async fn open_session() -> (IbcService, Chain, SigningSession) {
+        let db_pool = crate::test_db_pool().await;
+        let chain = crate::add_test_chain(&db_pool, "test-1").await;
+        let service = IbcService::new(db_pool);
+
+        let operation_id = operation::add_operation(
+            &service.db_pool,
+            Some(REQUEST_ID),
+            "cosmos1address",
+            None,
+            None,
+            &OperationType::UpdateSigner {
+                chain_id: chain.id.clone(),
+                new_public_key: public_key().encode(),
+            },
+            OperationStatus::Pending,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let session = service
+            .reserve_sequence(
+                &chain,
+                operation_id,
+                Some(REQUEST_ID),
+                &OfflineOperation::UpdateSigner {
+                    new_public_key: public_key(),
+                    memo: "memo".to_owned(),
+                },
+                &public_key(),
+                "cosmos",
+                None,
+            )
+            .await
+            .unwrap();
+
+        signing_session::set_signing_session_round(
+            &service.db_pool,
+            session.id,
+            1,
+            &[signature_request()],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        let session = get_session(&service, session.id).await;
+
+        (service, chain, session)
+    }
+
+    async fn get_session(service: &IbcService, session_id: i64) -> SigningSession {
+        signing_session::get_signing_session(&service.db_pool, session_id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn operation_status(service: &IbcService) -> OperationStatus {
+        operation::get_operation_by_request_id(&service.db_pool, REQUEST_ID)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    /// Asserts that finalizing given response fails with an error mentioning `reason` and that the session still awaits
+    /// signatures of the same round (so that it can be finalized with correct signatures)
+    async fn assert_rejected(
+        service: &IbcService,
+        session: &SigningSession,
+        response: SigningResponse,
+        reason: &str,
+    ) {
+        let err = service.finalize_offline(response).await.unwrap_err();
+        assert!(
+            format!("{:#}", err).contains(reason),
+            "unexpected error: {:#}",
+            err
+        );
+
+        let current = get_session(service, session.id).await;
+        assert_eq!(current.status, SigningSessionStatus::AwaitingSignatures);
+        assert_eq!(current.round, session.round);
+    }
+
+    #[tokio::test]
+    async fn prepare_rejects_chain_reserved_by_open_session() {
+        let (service, chain, session) = open_session().await;
+
+        let err = service
+            .prepare_offline(
+                OfflineSigner::new(public_key(), "cosmos".to_owned(), &[]),
+                chain.id.clone(),
+                None,
+                OfflineOperation::UpdateSigner {
+                    new_public_key: public_key(),
+                    memo: "memo".to_owned(),
+                },
+                10,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains(&format!(
+            "is reserved by offline signing session {}",
+            session.id
+        )));
+    }
+
+    #[tokio::test]
+    async fn reserves_sequence_for_one_session_at_a_time() {
+        let (service, chain, session) = open_session().await;
+
+        let err = service
+            .reserve_sequence(
+                &chain,
+                session.operation_id,
+                None,
+                &session.operation,
+                &public_key(),
+                "cosmos",
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "sequence of chain test-1 is already reserved by another offline signing session (finalize or abort it \
+             first)"
+        );
+    }
+
+    #[tokio::test]
+    async fn finalize_rejects_signatures_of_other_round() {
+        let (service, _, session) = open_session().await;
+
+        let mut response = response(
+            &session,
+            vec![sign(&signature_request().message, &signing_key(7))],
+        );
+        response.round += 1;
+
+        assert_rejected(&service, &session, response, "signatures are for round 2").await;
+    }
+
+    #[tokio::test]
+    async fn finalize_rejects_signature_of_unrequested_message() {
+        let (service, _, session) = open_session().await;
+
+        let response = response(
+            &session,
+            vec![
+                sign(&signature_request().message, &signing_key(7)),
+                sign(b"other message", &signing_key(7)),
+            ],
+        );
+
+        assert_rejected(
+            &service,
+            &session,
+            response,
+            "signature of a message which is not requested",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn finalize_rejects_missing_signature() {
+        let (service, _, session) = open_session().await;
+
+        assert_rejected(
+            &service,
+            &session,
+            response(&session, Vec::new()),
+            "signature missing for sign-doc message",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn finalize_rejects_signature_of_other_key() {
+        let (service, _, session) = open_session().await;
+
+        let response = response(
+            &session,
+            vec![sign(&signature_request().message, &signing_key(8))],
+        );
+
+        assert_rejected(
+            &service,
+            &session,
+            response,
+            "invalid signature for sign-doc message",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn finalize_fails_session_when_sequence_changed() {
+        let (service, chain, session) = open_session().await;
+        chain::increment_sequence(&service.db_pool, &chain.id)
+            .await
+            .unwrap();
+
+        let response = response(
+            &session,
+            vec![sign(&signature_request().message, &signing_key(7))],
+        );
+        let err = service.finalize_offline(response).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("sequence of chain test-1 changed after offline signing session"));
+
+        let session = get_session(&service, session.id).await;
+        assert_eq!(session.status, SigningSessionStatus::Failed);
+        assert_eq!(operation_status(&service).await, OperationStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn finalize_fails_session_when_timestamp_changed() {
+        let (service, chain, session) = open_session().await;
+        sqlx::query("UPDATE chains SET consensus_timestamp = $1 WHERE id = $2")
+            .bind(chain.consensus_timestamp + chrono::Duration::seconds(1))
+            .bind(chain.id.to_string())
+            .execute(&service.db_pool)
+            .await
+            .unwrap();
+
+        let response = response(
+            &session,
+            vec![sign(&signature_request().message, &signing_key(7))],
+        );
+        assert!(service.finalize_offline(response).await.is_err());
+
+        let session = get_session(&service, session.id).await;
+        assert_eq!(session.status, SigningSessionStatus::Failed);
+        assert_eq!(operation_status(&service).await, OperationStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn finalize_continues_operation_with_valid_signatures() {
+        let (service, _, session) = open_session().await;
+
+        let response = response(
+            &session,
+            vec![sign(&signature_request().message, &signing_key(7))],
+        );
+
+        // Signatures are accepted and the operation continues (it fails because RPC endpoint of test chain is
+        // unreachable)
+        let err = service.finalize_offline(response).await.unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("no healthy rpc endpoint"),
+            "unexpected error: {:#}",
+            err
+        );
+
+        let session = get_session(&service, session.id).await;
+        assert_eq!(session.status, SigningSessionStatus::Failed);
+        assert_eq!(operation_status(&service).await, OperationStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn abort_releases_reserved_sequence() {
+        let (service, chain, session) = open_session().await;
+
+        service.abort_offline(session.id).await.unwrap();
+
+        let aborted = get_session(&service, session.id).await;
+        assert_eq!(aborted.status, SigningSessionStatus::Aborted);
+        assert_eq!(operation_status(&service).await, OperationStatus::Failed);
+
+        service.ensure_not_reserved(&chain.id).await.unwrap();
+
+        let err = service
+            .finalize_offline(response(
+                &session,
+                vec![sign(&signature_request().message, &signing_key(7))],
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("offline signing session {} is already aborted", session.id)
+        );
+
+        service
+            .reserve_sequence(
+                &chain,
+                session.operation_id,
+                None,
+                &session.operation,
+                &public_key(),
+                "cosmos",
+                None,
+            )
+            .await
+            .unwrap();
+    }
+}
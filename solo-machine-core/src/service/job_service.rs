@@ -0,0 +1,227 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::{ensure, Error, Result};
+use serde_json::Value;
+
+use crate::{
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{
+        chain,
+        job::{self, Job, JobFilter, JobKind, JobProgress, JobStatus},
+    },
+    service::chain_service::ChainNotFound,
+    DbPool, Event,
+};
+
+/// Used to cooperatively cancel a job. Services check it between the steps of an operation, so, a cancelled job stops
+/// before its next step instead of being interrupted in the middle of one.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new cancellation token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of the job using this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if cancellation of the job is requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Error returned by services when they stop an operation because its cancellation was requested
+#[derive(Debug)]
+pub struct OperationCancelled;
+
+impl fmt::Display for OperationCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation was cancelled")
+    }
+}
+
+impl std::error::Error for OperationCancelled {}
+
+/// Used to track long-running operations (jobs) executed in background
+#[derive(Clone)]
+pub struct JobService {
+    db_pool: DbPool,
+}
+
+impl JobService {
+    /// Creates a new instance of job service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Records start of a job on IBC enabled chain. Fails if another job is already running on the same chain.
+    pub async fn start(
+        &self,
+        kind: JobKind,
+        chain_id: &ChainId,
+        request_id: Option<&str>,
+    ) -> Result<Job> {
+        ensure!(
+            chain::get_chain(&self.db_pool, chain_id).await?.is_some(),
+            ChainNotFound::new(chain_id)
+        );
+
+        job::add_job(&self.db_pool, kind, chain_id, request_id).await
+    }
+
+    /// Records an event emitted while executing a job
+    pub async fn add_progress(&self, id: i64, event: &Event) -> Result<()> {
+        job::add_job_progress(&self.db_pool, id, event).await
+    }
+
+    /// Records result of a job: it succeeded if `result` is `Ok`, was cancelled if it stopped with
+    /// `OperationCancelled` error (i.e., it observed the cancellation request) and failed otherwise. A job whose
+    /// cancellation was requested but which finished its last step anyway is recorded as succeeded. Returns `false` if
+    /// the job is not running anymore.
+    pub async fn finish(&self, id: i64, result: &Result<Option<Value>>) -> Result<bool> {
+        match result {
+            Ok(value) => {
+                job::finish_job(
+                    &self.db_pool,
+                    id,
+                    JobStatus::Succeeded,
+                    value.as_ref(),
+                    None,
+                )
+                .await
+            }
+            Err(err) if is_cancelled(err) => {
+                job::finish_job(
+                    &self.db_pool,
+                    id,
+                    JobStatus::Cancelled,
+                    None,
+                    Some(&err.to_string()),
+                )
+                .await
+            }
+            Err(err) => {
+                job::finish_job(
+                    &self.db_pool,
+                    id,
+                    JobStatus::Failed,
+                    None,
+                    Some(&format!("{:#}", err)),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Marks all the running jobs as failed (should be called on startup as jobs do not survive restarts) and returns
+    /// the number of affected jobs
+    pub async fn fail_interrupted(&self) -> Result<u64> {
+        job::fail_running_jobs(
+            &self.db_pool,
+            "job was interrupted by restart of solo machine",
+        )
+        .await
+    }
+
+    /// Fetches a job
+    pub async fn get(&self, id: i64) -> Result<Option<Job>> {
+        job::get_job(&self.db_pool, id).await
+    }
+
+    /// Fetches jobs matching given filter (latest first)
+    pub async fn list(&self, filter: &JobFilter, limit: i32, offset: i32) -> Result<Vec<Job>> {
+        job::get_jobs(&self.db_pool, filter, limit, offset).await
+    }
+
+    /// Fetches all the events emitted while executing a job (in the order they were emitted)
+    pub async fn get_progress(&self, id: i64) -> Result<Vec<JobProgress>> {
+        job::get_job_progress(&self.db_pool, id).await
+    }
+}
+
+/// Returns `true` if error (or any of its causes) is `OperationCancelled`
+fn is_cancelled(err: &Error) -> bool {
+    err.chain()
+        .any(|cause| cause.downcast_ref::<OperationCancelled>().is_some())
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+
+    async fn start_job() -> (JobService, Job) {
+        let db_pool = crate::test_db_pool().await;
+        crate::add_test_chain(&db_pool, "test-1").await;
+
+        let job_service = JobService::new(db_pool);
+        let job = job_service
+            .start(JobKind::Connect, &"test-1".parse().unwrap(), None)
+            .await
+            .unwrap();
+
+        (job_service, job)
+    }
+
+    async fn status(job_service: &JobService, id: i64) -> JobStatus {
+        job_service.get(id).await.unwrap().unwrap().status
+    }
+
+    #[tokio::test]
+    async fn records_cancelled_when_job_stops_on_cancellation() {
+        let (job_service, job) = start_job().await;
+
+        let result = Err(Error::new(OperationCancelled).context("unable to connect"));
+        assert!(job_service.finish(job.id, &result).await.unwrap());
+        assert_eq!(status(&job_service, job.id).await, JobStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn records_succeeded_when_job_completes() {
+        let (job_service, job) = start_job().await;
+
+        assert!(job_service.finish(job.id, &Ok(None)).await.unwrap());
+        assert_eq!(status(&job_service, job.id).await, JobStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn records_failed_for_other_errors() {
+        let (job_service, job) = start_job().await;
+
+        let result = Err(anyhow!("rpc endpoint unreachable")).context("unable to connect");
+        assert!(job_service.finish(job.id, &result).await.unwrap());
+
+        let job = job_service.get(job.id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(
+            job.error.as_deref(),
+            Some("unable to connect: rpc endpoint unreachable")
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_finish_job_twice() {
+        let (job_service, job) = start_job().await;
+
+        assert!(job_service.finish(job.id, &Ok(None)).await.unwrap());
+        assert!(!job_service
+            .finish(job.id, &Err(Error::new(OperationCancelled)))
+            .await
+            .unwrap());
+        assert_eq!(status(&job_service, job.id).await, JobStatus::Succeeded);
+    }
+}
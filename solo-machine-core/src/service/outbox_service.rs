@@ -0,0 +1,206 @@
+use anyhow::{ensure, Context, Result};
+
+use crate::{
+    model::outbox::{self, HandlerCursor, OutboxEvent},
+    DbPool,
+};
+
+/// Used to deliver events recorded in event outbox to event handlers (each event handler tracks its position in
+/// outbox using a cursor)
+#[derive(Clone)]
+pub struct OutboxService {
+    db_pool: DbPool,
+}
+
+impl OutboxService {
+    /// Creates a new instance of outbox service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Fetches events recorded in outbox after event with given ID (in the order of their IDs)
+    pub async fn get_events(&self, after_id: i64, limit: i32) -> Result<Vec<OutboxEvent>> {
+        outbox::get_events(&self.db_pool, after_id, limit).await
+    }
+
+    /// Fetches events to be delivered after given position (in the order they were committed). Positions are assigned
+    /// to newly committed events before fetching them.
+    pub async fn get_pending_events(
+        &self,
+        after_position: i64,
+        limit: i32,
+    ) -> Result<Vec<OutboxEvent>> {
+        self.assign_positions().await?;
+        outbox::get_events_after_position(&self.db_pool, after_position, limit).await
+    }
+
+    /// Returns the position of the last event delivered to given event handler. New event handlers start from the
+    /// latest event in outbox (i.e., only the events committed after first registration of an event handler are
+    /// delivered to it).
+    pub async fn register(&self, handler: &str) -> Result<i64> {
+        if let Some(cursor) = outbox::get_handler_cursor(&self.db_pool, handler).await? {
+            return Ok(cursor.position);
+        }
+
+        self.assign_positions().await?;
+
+        let position = outbox::get_latest_position(&self.db_pool).await?;
+        outbox::set_handler_cursor(&self.db_pool, handler, position).await?;
+
+        Ok(position)
+    }
+
+    /// Records that all the events till given position are delivered to given event handler
+    pub async fn acknowledge(&self, handler: &str, position: i64) -> Result<()> {
+        outbox::set_handler_cursor(&self.db_pool, handler, position).await
+    }
+
+    /// Moves cursor of given event handler so that events are delivered to it again starting from given event ID
+    /// (on next start of solo machine). Events with lower IDs which were committed after given event are delivered
+    /// again too.
+    pub async fn replay(&self, handler: &str, from_event_id: i64) -> Result<()> {
+        ensure!(from_event_id > 0, "event id should be greater than zero");
+
+        self.assign_positions().await?;
+
+        let position = match outbox::get_first_position_from(&self.db_pool, from_event_id).await? {
+            Some(position) => position - 1,
+            None => outbox::get_latest_position(&self.db_pool).await?,
+        };
+
+        outbox::set_handler_cursor(&self.db_pool, handler, position).await
+    }
+
+    /// Fetches cursors of all the event handlers
+    pub async fn cursors(&self) -> Result<Vec<HandlerCursor>> {
+        outbox::get_handler_cursors(&self.db_pool).await
+    }
+
+    /// Assigns positions to the events committed since last assignment
+    async fn assign_positions(&self) -> Result<()> {
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        outbox::assign_event_positions(&mut transaction).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for assigning positions to events")
+    }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use sqlx::types::Json;
+
+    use super::*;
+    use crate::Event;
+
+    fn warning(message: &str) -> Event {
+        Event::Warning {
+            message: message.to_owned(),
+        }
+    }
+
+    /// Records an event with given ID (IDs are allocated at insert time, so, a transaction may commit an event with a
+    /// lower ID than the events committed before it)
+    async fn add_event_with_id(db_pool: &DbPool, id: i64, message: &str) {
+        sqlx::query("INSERT INTO event_outbox (id, event_type, event) VALUES ($1, $2, $3)")
+            .bind(id)
+            .bind("Warning")
+            .bind(Json(serde_json::to_value(warning(message)).unwrap()))
+            .execute(db_pool)
+            .await
+            .unwrap();
+    }
+
+    /// Delivers all the pending events to given event handler and returns their IDs
+    async fn deliver(outbox_service: &OutboxService, handler: &str) -> Vec<i64> {
+        let mut cursor = outbox_service.register(handler).await.unwrap();
+        let events = outbox_service
+            .get_pending_events(cursor, 100)
+            .await
+            .unwrap();
+
+        for event in events.iter() {
+            cursor = event.position.unwrap();
+        }
+
+        outbox_service.acknowledge(handler, cursor).await.unwrap();
+
+        events.into_iter().map(|event| event.id).collect()
+    }
+
+    #[tokio::test]
+    async fn delivers_event_with_lower_id_committed_later() {
+        let db_pool = crate::test_db_pool().await;
+        let outbox_service = OutboxService::new(db_pool.clone());
+        outbox_service.register("hook").await.unwrap();
+
+        add_event_with_id(&db_pool, 10, "committed first").await;
+        assert_eq!(deliver(&outbox_service, "hook").await, vec![10]);
+
+        add_event_with_id(&db_pool, 5, "committed later").await;
+        add_event_with_id(&db_pool, 11, "committed last").await;
+        assert_eq!(deliver(&outbox_service, "hook").await, vec![5, 11]);
+
+        assert!(deliver(&outbox_service, "hook").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn starts_new_handler_after_existing_events() {
+        let db_pool = crate::test_db_pool().await;
+        let outbox_service = OutboxService::new(db_pool.clone());
+
+        outbox::add_event(&db_pool, &warning("before"))
+            .await
+            .unwrap();
+        outbox_service.register("hook").await.unwrap();
+        let id = outbox::add_event(&db_pool, &warning("after"))
+            .await
+            .unwrap();
+
+        assert_eq!(deliver(&outbox_service, "hook").await, vec![id]);
+    }
+
+    #[tokio::test]
+    async fn replays_events_from_given_id() {
+        let db_pool = crate::test_db_pool().await;
+        let outbox_service = OutboxService::new(db_pool.clone());
+        outbox_service.register("hook").await.unwrap();
+
+        add_event_with_id(&db_pool, 1, "first").await;
+        add_event_with_id(&db_pool, 3, "second").await;
+        deliver(&outbox_service, "hook").await;
+        add_event_with_id(&db_pool, 2, "third").await;
+        deliver(&outbox_service, "hook").await;
+
+        outbox_service.replay("hook", 3).await.unwrap();
+        assert_eq!(deliver(&outbox_service, "hook").await, vec![3, 2]);
+
+        outbox_service.replay("hook", 4).await.unwrap();
+        assert!(deliver(&outbox_service, "hook").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn counts_pending_events_of_handlers() {
+        let db_pool = crate::test_db_pool().await;
+        let outbox_service = OutboxService::new(db_pool.clone());
+        outbox_service.register("hook").await.unwrap();
+
+        add_event_with_id(&db_pool, 1, "first").await;
+        deliver(&outbox_service, "hook").await;
+        add_event_with_id(&db_pool, 2, "second").await;
+        add_event_with_id(&db_pool, 3, "third").await;
+
+        let cursors = outbox_service.cursors().await.unwrap();
+        assert_eq!(cursors.len(), 1);
+        assert_eq!(cursors[0].handler, "hook");
+        assert_eq!(cursors[0].position, 1);
+        assert_eq!(cursors[0].pending_events, 2);
+    }
+}
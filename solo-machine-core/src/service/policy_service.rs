@@ -0,0 +1,510 @@
+use std::fmt;
+
+use anyhow::{anyhow, ensure, Result};
+use chrono::{Duration, Utc};
+use primitive_types::U256;
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{
+        chain, operation,
+        policy::{self, MintLimits, MintPolicy},
+    },
+    service::chain_service::ChainNotFound,
+    DbPool,
+};
+
+/// Error returned when minting is rejected by mint policies of IBC enabled chain
+#[derive(Debug)]
+pub struct MintRejected {
+    /// Reason of rejection
+    pub reason: String,
+}
+
+impl fmt::Display for MintRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mint rejected by policy: {}", self.reason)
+    }
+}
+
+impl std::error::Error for MintRejected {}
+
+/// Used to manage policies enforced by solo machine before minting tokens
+pub struct PolicyService {
+    db_pool: DbPool,
+}
+
+impl PolicyService {
+    /// Creates a new instance of policy service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Adds or replaces mint policy for given denom on IBC enabled chain. Once a chain has at least one mint policy,
+    /// only the denoms with a mint policy can be minted on that chain.
+    pub async fn set_mint_policy(
+        &self,
+        chain_id: &ChainId,
+        denom: &Identifier,
+        limits: &MintLimits,
+    ) -> Result<MintPolicy> {
+        ensure!(
+            chain::get_chain(&self.db_pool, chain_id).await?.is_some(),
+            ChainNotFound::new(chain_id)
+        );
+
+        policy::set_mint_policy(&self.db_pool, chain_id, denom, limits).await?;
+
+        policy::get_mint_policy(&self.db_pool, chain_id, denom)
+            .await?
+            .ok_or_else(|| anyhow!("mint policy for {} on {} not found", denom, chain_id))
+    }
+
+    /// Removes mint policy for given denom on IBC enabled chain
+    pub async fn remove_mint_policy(&self, chain_id: &ChainId, denom: &Identifier) -> Result<()> {
+        ensure!(
+            policy::remove_mint_policy(&self.db_pool, chain_id, denom).await?,
+            "mint policy for {} on {} not found",
+            denom,
+            chain_id
+        );
+
+        Ok(())
+    }
+
+    /// Fetches all the mint policies (optionally only for given chain)
+    pub async fn get_mint_policies(&self, chain_id: Option<&ChainId>) -> Result<Vec<MintPolicy>> {
+        policy::get_mint_policies(&self.db_pool, chain_id).await
+    }
+}
+
+/// Checks mint policies of IBC enabled chain for minting `amount` tokens of `denom` to `receiver`. The operation being
+/// checked should already be recorded (as pending) so that it is included in rolling limits. Returns the reason of
+/// rejection if minting is not allowed.
+pub(crate) async fn check_mint_policy(
+    db_pool: &DbPool,
+    chain_id: &ChainId,
+    denom: &Identifier,
+    receiver: &str,
+    amount: U256,
+) -> Result<Option<String>> {
+    let mint_policy = match policy::get_mint_policy(db_pool, chain_id, denom).await? {
+        Some(mint_policy) => mint_policy,
+        None => {
+            return if policy::has_mint_policies(db_pool, chain_id).await? {
+                Ok(Some(format!(
+                    "denom {} is not allowed to be minted on {}",
+                    denom, chain_id
+                )))
+            } else {
+                Ok(None)
+            }
+        }
+    };
+
+    let limits = mint_policy.limits;
+
+    if let Some(max_amount) = limits.max_amount {
+        if amount > max_amount {
+            return Ok(Some(format!(
+                "amount {} exceeds maximum amount {} per operation",
+                amount, max_amount
+            )));
+        }
+    }
+
+    let now = Utc::now();
+
+    let windows = [
+        ("daily", limits.daily_limit, None, Duration::days(1)),
+        ("weekly", limits.weekly_limit, None, Duration::days(7)),
+        (
+            "receiver daily",
+            limits.receiver_daily_limit,
+            Some(receiver),
+            Duration::days(1),
+        ),
+        (
+            "receiver weekly",
+            limits.receiver_weekly_limit,
+            Some(receiver),
+            Duration::days(7),
+        ),
+    ];
+
+    for (name, limit, receiver, window) in windows {
+        if let Some(limit) = limit {
+            let minted =
+                operation::get_minted_amount(db_pool, chain_id, denom, receiver, now - window)
+                    .await?;
+
+            if minted > limit {
+                return Ok(Some(format!(
+                    "minting {} {} exceeds {} limit of {} (already minted {})",
+                    amount,
+                    denom,
+                    name,
+                    limit,
+                    minted.saturating_sub(amount)
+                )));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use crate::model::{OperationStatus, OperationType};
+
+    use super::*;
+
+    const ALICE: &str = "cosmos1alice";
+    const BOB: &str = "cosmos1bob";
+
+    fn chain_id() -> ChainId {
+        "test-1".parse().unwrap()
+    }
+
+    fn denom() -> Identifier {
+        "gld".parse().unwrap()
+    }
+
+    async fn set_limits(db_pool: &DbPool, limits: MintLimits) {
+        policy::set_mint_policy(db_pool, &chain_id(), &denom(), &limits)
+            .await
+            .unwrap();
+    }
+
+    /// Records a mint operation created `age` ago
+    async fn record_mint(
+        db_pool: &DbPool,
+        receiver: &str,
+        amount: u64,
+        age: Duration,
+        status: OperationStatus,
+    ) {
+        let operation_id = operation::add_operation(
+            db_pool,
+            None,
+            receiver,
+            Some(&denom()),
+            Some(U256::from(amount)),
+            &OperationType::Mint {
+                chain_id: chain_id(),
+            },
+            status,
+            None,
+        )
+        .await
+        .unwrap();
+
+        sqlx::query("UPDATE operations SET created_at = $1 WHERE id = $2")
+            .bind(Utc::now() - age)
+            .bind(operation_id)
+            .execute(db_pool)
+            .await
+            .unwrap();
+    }
+
+    async fn check(db_pool: &DbPool, receiver: &str, amount: u64) -> Option<String> {
+        check_mint_policy(db_pool, &chain_id(), &denom(), receiver, U256::from(amount))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn allows_any_denom_without_policies() {
+        let db_pool = crate::test_db_pool().await;
+
+        assert_eq!(check(&db_pool, ALICE, 1_000_000).await, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_denom_without_policy() {
+        let db_pool = crate::test_db_pool().await;
+        policy::set_mint_policy(
+            &db_pool,
+            &chain_id(),
+            &"slv".parse().unwrap(),
+            &MintLimits::default(),
+        )
+        .await
+        .unwrap();
+
+        let reason = check(&db_pool, ALICE, 1).await.unwrap();
+        assert!(reason.contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn rejects_amount_above_max_amount() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                max_amount: Some(U256::from(100)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert_eq!(check(&db_pool, ALICE, 100).await, None);
+
+        let reason = check(&db_pool, ALICE, 101).await.unwrap();
+        assert!(reason.contains("exceeds maximum amount"));
+    }
+
+    #[tokio::test]
+    async fn rejects_mint_above_daily_limit() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                daily_limit: Some(U256::from(100)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        record_mint(
+            &db_pool,
+            BOB,
+            60,
+            Duration::hours(1),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            40,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+        assert_eq!(check(&db_pool, ALICE, 40).await, None);
+
+        record_mint(
+            &db_pool,
+            ALICE,
+            1,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+        let reason = check(&db_pool, ALICE, 1).await.unwrap();
+        assert!(reason.contains("daily limit of 100"));
+        assert!(reason.contains("already minted 100"));
+    }
+
+    #[tokio::test]
+    async fn daily_limit_ignores_mints_outside_window_and_failed_mints() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                daily_limit: Some(U256::from(100)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::days(1) + Duration::minutes(1),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::hours(1),
+            OperationStatus::Failed,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+
+        assert_eq!(check(&db_pool, ALICE, 100).await, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_mint_above_weekly_limit() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                daily_limit: Some(U256::from(100)),
+                weekly_limit: Some(U256::from(150)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::days(3),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::days(7) + Duration::minutes(1),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            60,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+
+        let reason = check(&db_pool, ALICE, 60).await.unwrap();
+        assert!(reason.contains("weekly limit of 150"));
+        assert!(reason.contains("already minted 100"));
+    }
+
+    #[tokio::test]
+    async fn receiver_limits_only_count_mints_to_receiver() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                receiver_daily_limit: Some(U256::from(100)),
+                receiver_weekly_limit: Some(U256::from(150)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        record_mint(
+            &db_pool,
+            BOB,
+            100,
+            Duration::hours(1),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+        assert_eq!(check(&db_pool, ALICE, 100).await, None);
+
+        record_mint(&db_pool, BOB, 1, Duration::zero(), OperationStatus::Pending).await;
+        let reason = check(&db_pool, BOB, 1).await.unwrap();
+        assert!(reason.contains("receiver daily limit of 100"));
+
+        record_mint(
+            &db_pool,
+            ALICE,
+            60,
+            Duration::days(2),
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        let reason = check(&db_pool, ALICE, 100).await.unwrap();
+        assert!(reason.contains("receiver weekly limit of 150"));
+    }
+
+    #[tokio::test]
+    async fn stale_pending_mints_stop_counting_against_limits() {
+        let db_pool = crate::test_db_pool().await;
+        set_limits(
+            &db_pool,
+            MintLimits {
+                daily_limit: Some(U256::from(100)),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        record_mint(
+            &db_pool,
+            ALICE,
+            100,
+            Duration::hours(2),
+            OperationStatus::Pending,
+        )
+        .await;
+        record_mint(
+            &db_pool,
+            ALICE,
+            1,
+            Duration::zero(),
+            OperationStatus::Pending,
+        )
+        .await;
+        assert!(check(&db_pool, ALICE, 1).await.is_some());
+
+        let failed = operation::fail_stale_pending_operations(
+            &db_pool,
+            &chain_id(),
+            Utc::now() - Duration::hours(1),
+            "interrupted",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(failed, 1);
+        assert_eq!(check(&db_pool, ALICE, 1).await, None);
+    }
+
+    #[tokio::test]
+    async fn minted_amount_window_includes_its_start() {
+        let db_pool = crate::test_db_pool().await;
+        let since = Utc::now() - Duration::hours(1);
+
+        let operation_id = operation::add_operation(
+            &db_pool,
+            None,
+            ALICE,
+            Some(&denom()),
+            Some(U256::from(10)),
+            &OperationType::Mint {
+                chain_id: chain_id(),
+            },
+            OperationStatus::Acknowledged,
+            None,
+        )
+        .await
+        .unwrap();
+
+        sqlx::query("UPDATE operations SET created_at = $1 WHERE id = $2")
+            .bind(since)
+            .bind(operation_id)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+
+        let (chain_id, denom) = (chain_id(), denom());
+        let minted = |since| operation::get_minted_amount(&db_pool, &chain_id, &denom, None, since);
+
+        assert_eq!(minted(since).await.unwrap(), U256::from(10));
+        assert_eq!(
+            minted(since + Duration::seconds(1)).await.unwrap(),
+            U256::zero()
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use primitive_types::U256;
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    event::record_event,
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{
+        chain,
+        operation::{self, Operation, OperationKind, OperationStatus},
+    },
+    service::chain_service::ChainNotFound,
+    DbPool, Event,
+};
+
+/// Result of reconciling supply of a denom between solo machine's ledger and IBC enabled chain
+#[derive(Debug, Serialize)]
+pub struct SupplyReport {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Denom of tokens on solo machine
+    pub denom: Identifier,
+    /// IBC denom of tokens on IBC enabled chain
+    pub ibc_denom: String,
+    /// Total amount of tokens minted (as recorded in solo machine's ledger)
+    pub total_minted: U256,
+    /// Total amount of tokens burnt (as recorded in solo machine's ledger)
+    pub total_burnt: U256,
+    /// Supply recorded in solo machine's ledger (total minted minus total burnt)
+    pub ledger_supply: U256,
+    /// Total supply of IBC denom on IBC enabled chain
+    pub on_chain_supply: U256,
+    /// Balance of IBC denom held by escrow account of transfer channel on IBC enabled chain
+    pub escrow_balance: U256,
+    /// Number of operations whose on-chain outcome is not yet known (pending, broadcast or timed out). These may
+    /// explain a discrepancy.
+    pub unsettled_operations: u64,
+    /// `true` if supply recorded in solo machine's ledger matches the total supply on IBC enabled chain
+    pub is_balanced: bool,
+}
+
+/// Number of minutes after which an operation which is still pending (i.e., its transaction was never broadcasted) is
+/// considered to be interrupted
+const STALE_PENDING_AFTER_MINUTES: i64 = 60;
+
+#[derive(Debug, Default, PartialEq)]
+struct LedgerTotals {
+    minted: U256,
+    burnt: U256,
+    unsettled: u64,
+}
+
+impl LedgerTotals {
+    /// Returns the supply recorded in ledger (total minted minus total burnt)
+    fn supply(&self) -> U256 {
+        self.minted.saturating_sub(self.burnt)
+    }
+
+    /// Returns `true` if ledger is consistent and its supply matches given on-chain supply
+    fn is_balanced(&self, on_chain_supply: U256) -> bool {
+        self.burnt <= self.minted && self.supply() == on_chain_supply
+    }
+}
+
+/// Used to reconcile supply of tokens recorded in solo machine's ledger with IBC enabled chain
+pub struct ReconciliationService {
+    db_pool: DbPool,
+    notifier: Option<UnboundedSender<Event>>,
+}
+
+impl ReconciliationService {
+    /// Creates a new instance of reconciliation service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self {
+            db_pool,
+            notifier: None,
+        }
+    }
+
+    /// Creates a new instance of reconciliation service with notifier
+    pub fn new_with_notifier(db_pool: DbPool, notifier: UnboundedSender<Event>) -> Self {
+        Self {
+            db_pool,
+            notifier: Some(notifier),
+        }
+    }
+
+    /// Compares total minted minus total burnt tokens of each denom (as recorded in solo machine's ledger) with total
+    /// supply of its IBC denom on IBC enabled chain. Balance of IBC denom held by escrow account of the transfer channel
+    /// (queried over gRPC) is reported along with the supplies. Emits a `SupplyDiscrepancy` event for each denom which
+    /// does not match. Operations left pending (without a broadcasted transaction) for more than `STALE_PENDING_AFTER_MINUTES`
+    /// are marked as failed first, so that they no longer count against rolling mint limits.
+    pub async fn reconcile(&self, chain_id: &ChainId) -> Result<Vec<SupplyReport>> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| ChainNotFound::new(chain_id))?;
+
+        let stale_operations = operation::fail_stale_pending_operations(
+            &self.db_pool,
+            chain_id,
+            Utc::now() - Duration::minutes(STALE_PENDING_AFTER_MINUTES),
+            "operation was interrupted before its transaction was broadcasted",
+        )
+        .await?;
+
+        if stale_operations > 0 {
+            record_event(
+                &self.db_pool,
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "marked {} stale pending operations on {} as failed",
+                        stale_operations, chain_id
+                    ),
+                },
+            )
+            .await?;
+        }
+
+        let operations = operation::get_token_operations(&self.db_pool, chain_id).await?;
+        let ledger = compute_ledger(operations)?;
+
+        let mut reports = Vec::with_capacity(ledger.len());
+
+        for (_, (denom, totals)) in ledger {
+            let ibc_denom = chain.get_ibc_denom(&denom)?;
+            let on_chain_supply = chain.get_total_supply(&denom).await?;
+            let escrow_balance = chain.get_escrow_balance(&denom).await?;
+
+            let ledger_supply = totals.supply();
+            let is_balanced = totals.is_balanced(on_chain_supply);
+
+            if !is_balanced {
+                record_event(
+                    &self.db_pool,
+                    &self.notifier,
+                    Event::SupplyDiscrepancy {
+                        chain_id: chain_id.clone(),
+                        denom: denom.clone(),
+                        ibc_denom: ibc_denom.clone(),
+                        ledger_supply,
+                        on_chain_supply,
+                        escrow_balance,
+                    },
+                )
+                .await?;
+            }
+
+            reports.push(SupplyReport {
+                chain_id: chain_id.clone(),
+                denom,
+                ibc_denom,
+                total_minted: totals.minted,
+                total_burnt: totals.burnt,
+                ledger_supply,
+                on_chain_supply,
+                escrow_balance,
+                unsettled_operations: totals.unsettled,
+                is_balanced,
+            });
+        }
+
+        Ok(reports)
+    }
+}
+
+/// Computes total minted and burnt tokens (and number of unsettled operations) of each denom from token operations
+fn compute_ledger(
+    operations: Vec<Operation>,
+) -> Result<BTreeMap<String, (Identifier, LedgerTotals)>> {
+    let mut ledger: BTreeMap<String, (Identifier, LedgerTotals)> = BTreeMap::new();
+
+    for operation in operations {
+        let (denom, amount) = match (operation.denom, operation.amount) {
+            (Some(denom), Some(amount)) => (denom, amount),
+            _ => continue,
+        };
+
+        let (_, totals) = ledger
+            .entry(denom.to_string())
+            .or_insert_with(|| (denom, LedgerTotals::default()));
+
+        match operation.status {
+            OperationStatus::Committed | OperationStatus::Acknowledged => {
+                match operation.operation_type.kind() {
+                    OperationKind::Mint => {
+                        totals.minted = totals
+                            .minted
+                            .checked_add(amount)
+                            .ok_or_else(|| anyhow!("overflow when computing total minted tokens"))?
+                    }
+                    OperationKind::Burn => {
+                        totals.burnt = totals
+                            .burnt
+                            .checked_add(amount)
+                            .ok_or_else(|| anyhow!("overflow when computing total burnt tokens"))?
+                    }
+                    OperationKind::UpdateSigner => {}
+                }
+            }
+            OperationStatus::Pending | OperationStatus::Broadcast | OperationStatus::TimedOut => {
+                totals.unsettled += 1
+            }
+            OperationStatus::Failed => {}
+        }
+    }
+
+    Ok(ledger)
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::model::operation::OperationType;
+
+    async fn add_operation(
+        db_pool: &DbPool,
+        kind: OperationKind,
+        denom: &str,
+        amount: u64,
+        status: OperationStatus,
+    ) {
+        let chain_id: ChainId = "test-1".parse().unwrap();
+        let operation_type = match kind {
+            OperationKind::Mint => OperationType::Mint { chain_id },
+            _ => OperationType::Burn { chain_id },
+        };
+
+        operation::add_operation(
+            db_pool,
+            None,
+            "cosmos1address",
+            Some(&denom.parse().unwrap()),
+            Some(U256::from(amount)),
+            &operation_type,
+            status,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn ledger(db_pool: &DbPool) -> BTreeMap<String, (Identifier, LedgerTotals)> {
+        let operations = operation::get_token_operations(db_pool, &"test-1".parse().unwrap())
+            .await
+            .unwrap();
+        compute_ledger(operations).unwrap()
+    }
+
+    #[tokio::test]
+    async fn ledger_counts_only_settled_operations() {
+        let db_pool = crate::test_db_pool().await;
+        add_operation(
+            &db_pool,
+            OperationKind::Mint,
+            "gld",
+            100,
+            OperationStatus::Committed,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Mint,
+            "gld",
+            50,
+            OperationStatus::Acknowledged,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Burn,
+            "gld",
+            30,
+            OperationStatus::Committed,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Mint,
+            "gld",
+            1000,
+            OperationStatus::Failed,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Mint,
+            "gld",
+            1000,
+            OperationStatus::TimedOut,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Burn,
+            "gld",
+            1000,
+            OperationStatus::Broadcast,
+        )
+        .await;
+        add_operation(
+            &db_pool,
+            OperationKind::Mint,
+            "slv",
+            7,
+            OperationStatus::Committed,
+        )
+        .await;
+
+        let ledger = ledger(&db_pool).await;
+        assert_eq!(ledger.len(), 2);
+
+        let (_, gld) = &ledger["gld"];
+        assert_eq!(
+            gld,
+            &LedgerTotals {
+                minted: U256::from(150),
+                burnt: U256::from(30),
+                unsettled: 2,
+            }
+        );
+        assert_eq!(gld.supply(), U256::from(120));
+
+        let (_, slv) = &ledger["slv"];
+        assert_eq!(slv.supply(), U256::from(7));
+        assert_eq!(slv.unsettled, 0);
+    }
+
+    #[test]
+    fn detects_supply_discrepancy() {
+        let totals = LedgerTotals {
+            minted: U256::from(150),
+            burnt: U256::from(30),
+            unsettled: 0,
+        };
+
+        assert!(totals.is_balanced(U256::from(120)));
+        assert!(!totals.is_balanced(U256::from(119)));
+        assert!(!totals.is_balanced(U256::from(150)));
+    }
+
+    #[test]
+    fn detects_more_burnt_than_minted() {
+        let totals = LedgerTotals {
+            minted: U256::from(10),
+            burnt: U256::from(30),
+            unsettled: 0,
+        };
+
+        // Ledger supply saturates at zero, so, a zero on-chain supply must not hide the inconsistency
+        assert_eq!(totals.supply(), U256::zero());
+        assert!(!totals.is_balanced(U256::zero()));
+    }
+}
@@ -1,11 +1,28 @@
 //! Utilities for signing transactions
+mod description;
+mod offline_signer;
+mod remote_signer;
+
 use std::{fmt, str::FromStr, sync::Arc};
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, Context, Error, Result};
 use async_trait::async_trait;
+use k256::ecdsa::{signature::Verifier, Signature, SigningKey};
 
 use crate::cosmos::crypto::PublicKey;
 
+pub use self::{
+    description::{
+        MessageContent, MessageDescription, PacketData, SignBytesDescription, SignBytesPath,
+        SignDocDescription,
+    },
+    offline_signer::{
+        MessageKind, MessageSignature, OfflineSigner, SignatureRequest, SignaturesPending,
+        SigningRequests, SigningResponse,
+    },
+    remote_signer::{RemoteSigner, RemoteSignerConfig},
+};
+
 #[derive(Debug, Clone, Copy)]
 /// Supported algorithms for address generation
 pub enum AddressAlgo {
@@ -16,6 +33,34 @@ pub enum AddressAlgo {
     EthSecp256k1,
 }
 
+impl AddressAlgo {
+    /// Signs given message bytes using `signing_key` in the signature format expected for current algorithm (64 byte
+    /// signature for `secp256k1` and 65 byte recoverable signature for `eth-secp256k1`)
+    pub fn sign(&self, signing_key: &SigningKey, message: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Self::Secp256k1 => Ok(
+                <SigningKey as k256::ecdsa::signature::Signer<Signature>>::sign(
+                    signing_key,
+                    message,
+                )
+                .to_bytes()
+                .to_vec(),
+            ),
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1 => {
+                let (signature, recovery_id) = signing_key.sign_recoverable(message)?;
+
+                let mut buf = signature.to_bytes().to_vec();
+                buf.push(recovery_id.to_byte());
+
+                buf.shrink_to_fit();
+
+                Ok(buf)
+            }
+        }
+    }
+}
+
 impl fmt::Display for AddressAlgo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -110,21 +155,36 @@ impl<T: ToPublicKey + ?Sized> ToPublicKey for Arc<T> {
 /// This trait must be implemented by all the transaction signers (e.g. mnemonic, ledger, etc.)
 #[async_trait]
 pub trait Signer: ToPublicKey + Send + Sync {
-    /// Signs the given message
-    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>>;
+    /// Signs the given message (`description` contains decoded contents of the message)
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>>;
 }
 
 #[async_trait]
 impl<T: Signer> Signer for &T {
-    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
-        (*self).sign(request_id, message).await
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        (*self).sign(request_id, message, description).await
     }
 }
 
 #[async_trait]
 impl<T: Signer + ?Sized> Signer for Arc<T> {
-    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
-        (**self).sign(request_id, message).await
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        (**self).sign(request_id, message, description).await
     }
 }
 
@@ -133,3 +193,45 @@ pub trait SignerRegistrar {
     /// Registers a new signer
     fn register(&mut self, signer: Arc<dyn Signer>);
 }
+
+/// Verifies that `signature` (in the format produced by local signers) is a valid signature of `message` by
+/// `public_key`
+pub(crate) fn verify_signature(
+    public_key: &PublicKey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    match public_key {
+        PublicKey::Secp256k1(ref verifying_key) => {
+            let signature = Signature::try_from(signature).context("malformed signature")?;
+            verifying_key
+                .verify(message, &signature)
+                .map_err(Into::into)
+        }
+        #[cfg(feature = "ethermint")]
+        PublicKey::EthSecp256k1(ref verifying_key) => {
+            use anyhow::ensure;
+            use k256::ecdsa::{RecoveryId, VerifyingKey};
+
+            ensure!(
+                signature.len() == 65,
+                "recoverable signature length should be equal to 65"
+            );
+
+            let recovery_id = RecoveryId::from_byte(signature[64])
+                .ok_or_else(|| anyhow!("invalid recovery id in signature"))?;
+            let signature = Signature::try_from(&signature[..64]).context("malformed signature")?;
+
+            verifying_key.verify(message, &signature)?;
+
+            let recovered_key = VerifyingKey::recover_from_msg(message, &signature, recovery_id)?;
+            ensure!(
+                &recovered_key == verifying_key,
+                "recovery id of signature does not match public key"
+            );
+
+            Ok(())
+        }
+        _ => Err(anyhow!("unsupported public key type for signer")),
+    }
+}
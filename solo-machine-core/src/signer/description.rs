@@ -0,0 +1,1049 @@
+use std::{convert::TryFrom, fmt};
+
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
+use ibc_proto::{
+    cosmos::tx::v1beta1::{AuthInfo, SignDoc, TxBody},
+    google::protobuf::Any,
+    ibc::{
+        applications::transfer::v1::MsgTransfer,
+        core::{
+            channel::v1::{MsgAcknowledgement, MsgRecvPacket, Packet},
+            client::v1::Height,
+        },
+        lightclients::solomachine::v3::{HeaderData, SignBytes},
+    },
+};
+use k256::ecdsa::VerifyingKey;
+use prost::{Message as _, Name};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cosmos::crypto::{PublicKey, PublicKeyAlgo},
+    ibc::core::{
+        ics04_channel::packet::IPacket,
+        ics24_host::path::{
+            ChannelPath, ConnectionPath, PacketAcknowledgementPath, PacketCommitmentPath, Path,
+        },
+    },
+    proto::{solo_machine::signer::v1 as proto, AnyConvert},
+    transaction_builder::TokenTransferPacketData,
+};
+
+use super::Message;
+
+/// Decoded description of a message given to a signer (can be used by signers to enforce signing policies or to show
+/// the request to a human approver before signing)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageDescription {
+    /// Chain ID of IBC enabled chain for which the message is signed
+    pub chain_id: String,
+    /// Decoded contents of message
+    pub content: MessageContent,
+}
+
+/// Decoded contents of a message given to a signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum MessageContent {
+    /// Solo machine `SignBytes` (proof of solo machine state verified by IBC enabled chain)
+    SignBytes(SignBytesDescription),
+    /// Cosmos SDK `SignDoc` (transaction sent to IBC enabled chain)
+    SignDoc(SignDocDescription),
+}
+
+/// Decoded description of solo machine `SignBytes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignBytesDescription {
+    /// Sequence of solo machine
+    pub sequence: u64,
+    /// Consensus timestamp of solo machine
+    pub timestamp: u64,
+    /// Diversifier of solo machine
+    pub diversifier: String,
+    /// Decoded path (and data) of `SignBytes`
+    pub path: SignBytesPath,
+}
+
+/// Decoded path (and data) of solo machine `SignBytes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum SignBytesPath {
+    /// Solo machine header (used for updating solo machine client and rotating its public key)
+    Header {
+        /// New public key of solo machine client
+        new_public_key: PublicKey,
+        /// New diversifier of solo machine client
+        new_diversifier: String,
+    },
+    /// State of a connection
+    Connection {
+        /// Connection ID
+        connection_id: String,
+    },
+    /// State of a channel
+    Channel {
+        /// Port ID
+        port_id: String,
+        /// Channel ID
+        channel_id: String,
+    },
+    /// Packet commitment (sending a token transfer packet)
+    PacketCommitment {
+        /// Port ID
+        port_id: String,
+        /// Channel ID
+        channel_id: String,
+        /// Sequence of packet
+        packet_sequence: u64,
+        /// Token transfer data of packet
+        packet_data: PacketData,
+        /// Revision number of timeout height of packet
+        #[serde(default)]
+        timeout_revision_number: u64,
+        /// Revision height of timeout height of packet
+        #[serde(default)]
+        timeout_revision_height: u64,
+        /// Timeout timestamp of packet
+        #[serde(default)]
+        timeout_timestamp: u64,
+    },
+    /// Packet acknowledgement (acknowledging a received token transfer packet)
+    PacketAcknowledgement {
+        /// Port ID
+        port_id: String,
+        /// Channel ID
+        channel_id: String,
+        /// Sequence of packet
+        packet_sequence: u64,
+    },
+}
+
+/// Decoded description of Cosmos SDK `SignDoc`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignDocDescription {
+    /// Account number of signer on IBC enabled chain
+    pub account_number: u64,
+    /// Account sequence of signer on IBC enabled chain
+    pub account_sequence: u64,
+    /// Type URLs of messages in transaction
+    pub messages: Vec<String>,
+    /// Memo of transaction
+    pub memo: String,
+    /// Token transfer data of packet sent (or acknowledged) by transaction (if any)
+    pub packet_data: Option<PacketData>,
+}
+
+/// Token transfer data of a packet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketData {
+    /// Denom of tokens
+    pub denom: String,
+    /// Amount of tokens
+    pub amount: String,
+    /// Sender of tokens
+    pub sender: String,
+    /// Receiver of tokens
+    pub receiver: String,
+    /// Memo of token transfer
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl MessageDescription {
+    /// Checks that current description matches the contents of given message. Signers should call this before using a
+    /// description (received along with the message) for enforcing signing policies or showing it to an approver.
+    ///
+    /// Note: `SignBytes` do not contain chain ID, so, it is only verified for `SignDoc` messages (see
+    /// `is_chain_bound()`).
+    pub fn verify(&self, message: &Message<'_>) -> Result<()> {
+        match (message, &self.content) {
+            (Message::SignBytes(bytes), MessageContent::SignBytes(description)) => {
+                description.verify(bytes)
+            }
+            (Message::SignDoc(bytes), MessageContent::SignDoc(description)) => {
+                description.verify(&self.chain_id, bytes)
+            }
+            (message, _) => bail!(
+                "description does not match message type: {}",
+                message.message_type()
+            ),
+        }
+        .context("message description does not match message")
+    }
+
+    /// Returns `true` if chain ID of current description is part of the described message (and is checked by
+    /// `verify()`). Chain ID of a `SignBytes` description can't be verified by signers, so, signers should not rely on
+    /// it (and should point this out when showing the description to an approver).
+    pub fn is_chain_bound(&self) -> bool {
+        matches!(self.content, MessageContent::SignDoc(_))
+    }
+}
+
+impl SignBytesDescription {
+    fn verify(&self, bytes: &[u8]) -> Result<()> {
+        let sign_bytes = SignBytes::decode(bytes).context("unable to decode sign bytes")?;
+
+        ensure!(
+            sign_bytes.sequence == self.sequence,
+            "sequence mismatch: {} != {}",
+            sign_bytes.sequence,
+            self.sequence
+        );
+        ensure!(
+            sign_bytes.timestamp == self.timestamp,
+            "timestamp mismatch: {} != {}",
+            sign_bytes.timestamp,
+            self.timestamp
+        );
+        ensure!(
+            sign_bytes.diversifier == self.diversifier,
+            "diversifier mismatch: {} != {}",
+            sign_bytes.diversifier,
+            self.diversifier
+        );
+
+        let path = match self.path {
+            SignBytesPath::Header {
+                ref new_public_key,
+                ref new_diversifier,
+            } => {
+                let header_data = HeaderData::decode(sign_bytes.data.as_slice())
+                    .context("unable to decode header data")?;
+
+                ensure!(
+                    header_data.new_pub_key == Some(new_public_key.to_any()?),
+                    "new public key mismatch"
+                );
+                ensure!(
+                    &header_data.new_diversifier == new_diversifier,
+                    "new diversifier mismatch: {} != {}",
+                    header_data.new_diversifier,
+                    new_diversifier
+                );
+
+                "solomachine:header".to_owned()
+            }
+            SignBytesPath::Connection { ref connection_id } => {
+                path_key(&ConnectionPath::new(&connection_id.parse()?))?
+            }
+            SignBytesPath::Channel {
+                ref port_id,
+                ref channel_id,
+            } => path_key(&ChannelPath::new(&port_id.parse()?, &channel_id.parse()?))?,
+            SignBytesPath::PacketCommitment {
+                ref port_id,
+                ref channel_id,
+                packet_sequence,
+                ref packet_data,
+                timeout_revision_number,
+                timeout_revision_height,
+                timeout_timestamp,
+            } => {
+                let packet = Packet {
+                    timeout_height: Some(Height {
+                        revision_number: timeout_revision_number,
+                        revision_height: timeout_revision_height,
+                    }),
+                    timeout_timestamp,
+                    data: serde_json::to_vec(&TokenTransferPacketData {
+                        denom: packet_data.denom.clone(),
+                        amount: packet_data.amount.clone(),
+                        sender: packet_data.sender.clone(),
+                        receiver: packet_data.receiver.clone(),
+                        memo: packet_data.memo.clone(),
+                    })?,
+                    ..Default::default()
+                };
+
+                ensure!(
+                    sign_bytes.data == packet.commitment_bytes()?,
+                    "packet commitment does not match packet data"
+                );
+
+                path_key(&PacketCommitmentPath::new(
+                    &port_id.parse()?,
+                    &channel_id.parse()?,
+                    packet_sequence,
+                ))?
+            }
+            SignBytesPath::PacketAcknowledgement {
+                ref port_id,
+                ref channel_id,
+                packet_sequence,
+            } => path_key(&PacketAcknowledgementPath::new(
+                &port_id.parse()?,
+                &channel_id.parse()?,
+                packet_sequence,
+            ))?,
+        };
+
+        ensure!(
+            sign_bytes.path == path.as_bytes(),
+            "path mismatch: {} != {}",
+            String::from_utf8_lossy(&sign_bytes.path),
+            path
+        );
+
+        Ok(())
+    }
+}
+
+impl SignDocDescription {
+    fn verify(&self, chain_id: &str, bytes: &[u8]) -> Result<()> {
+        let sign_doc = SignDoc::decode(bytes).context("unable to decode sign doc")?;
+
+        ensure!(
+            sign_doc.chain_id == chain_id,
+            "chain id mismatch: {} != {}",
+            sign_doc.chain_id,
+            chain_id
+        );
+        ensure!(
+            sign_doc.account_number == self.account_number,
+            "account number mismatch: {} != {}",
+            sign_doc.account_number,
+            self.account_number
+        );
+
+        let auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())
+            .context("unable to decode auth info")?;
+        ensure!(
+            auth_info.signer_infos.len() == 1
+                && auth_info.signer_infos[0].sequence == self.account_sequence,
+            "account sequence mismatch"
+        );
+
+        let tx_body = TxBody::decode(sign_doc.body_bytes.as_slice())
+            .context("unable to decode transaction body")?;
+        ensure!(
+            tx_body.memo == self.memo,
+            "memo mismatch: {} != {}",
+            tx_body.memo,
+            self.memo
+        );
+        ensure!(
+            tx_body
+                .messages
+                .iter()
+                .map(|message| &message.type_url)
+                .eq(self.messages.iter()),
+            "messages mismatch"
+        );
+
+        let packet_data = tx_body
+            .messages
+            .iter()
+            .map(PacketData::from_message)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .next();
+        ensure!(packet_data == self.packet_data, "packet data mismatch");
+
+        Ok(())
+    }
+}
+
+impl PacketData {
+    /// Decodes token transfer data of a packet
+    pub(crate) fn from_packet(packet: &Packet) -> Result<Self> {
+        serde_json::from_slice(&packet.data).context("unable to decode token transfer packet data")
+    }
+
+    /// Returns token transfer data of packet sent (or acknowledged) by given transaction message (if any)
+    fn from_message(message: &Any) -> Result<Option<Self>> {
+        match message.type_url.as_str() {
+            type_url if type_url == MsgRecvPacket::type_url() => MsgRecvPacket::from_any(message)?
+                .packet
+                .as_ref()
+                .map(Self::from_packet)
+                .transpose(),
+            // Acknowledged packets are sent by IBC enabled chain, so, their data is described on a best effort basis
+            type_url if type_url == MsgAcknowledgement::type_url() => {
+                Ok(MsgAcknowledgement::from_any(message)?
+                    .packet
+                    .as_ref()
+                    .and_then(|packet| Self::from_packet(packet).ok()))
+            }
+            type_url if type_url == MsgTransfer::type_url() => {
+                let message = MsgTransfer::from_any(message)?;
+                let token = message
+                    .token
+                    .ok_or_else(|| anyhow!("token of transfer message is missing"))?;
+
+                Ok(Some(Self {
+                    denom: token.denom,
+                    amount: token.amount,
+                    sender: message.sender,
+                    receiver: message.receiver,
+                    memo: message.memo,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Returns key of given path (without `ibc` prefix) as used in `SignBytes`
+fn path_key(path: &Path) -> Result<String> {
+    path.get_key(0)
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| anyhow!("invalid path {:?}", path))
+}
+
+impl fmt::Display for MessageDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.content {
+            MessageContent::SignBytes(ref sign_bytes) => {
+                write!(
+                    f,
+                    "sign-bytes for chain {} [sequence: {}]: ",
+                    self.chain_id, sign_bytes.sequence
+                )?;
+
+                match sign_bytes.path {
+                    SignBytesPath::Header {
+                        ref new_public_key,
+                        ref new_diversifier,
+                    } => write!(
+                        f,
+                        "header [new public key: {}, new diversifier: {}]",
+                        new_public_key.encode(),
+                        new_diversifier
+                    ),
+                    SignBytesPath::Connection { ref connection_id } => {
+                        write!(f, "connection {}", connection_id)
+                    }
+                    SignBytesPath::Channel {
+                        ref port_id,
+                        ref channel_id,
+                    } => write!(f, "channel {}/{}", port_id, channel_id),
+                    SignBytesPath::PacketCommitment {
+                        ref port_id,
+                        ref channel_id,
+                        packet_sequence,
+                        ref packet_data,
+                        ..
+                    } => write!(
+                        f,
+                        "packet commitment {}/{}/{} ({})",
+                        port_id, channel_id, packet_sequence, packet_data
+                    ),
+                    SignBytesPath::PacketAcknowledgement {
+                        ref port_id,
+                        ref channel_id,
+                        packet_sequence,
+                    } => write!(
+                        f,
+                        "packet acknowledgement {}/{}/{}",
+                        port_id, channel_id, packet_sequence
+                    ),
+                }
+            }
+            MessageContent::SignDoc(ref sign_doc) => {
+                write!(
+                    f,
+                    "sign-doc for chain {} [account number: {}, account sequence: {}]: {}",
+                    self.chain_id,
+                    sign_doc.account_number,
+                    sign_doc.account_sequence,
+                    sign_doc.messages.join(", ")
+                )?;
+
+                match sign_doc.packet_data {
+                    Some(ref packet_data) => write!(f, " ({})", packet_data),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PacketData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} from {} to {}",
+            self.amount, self.denom, self.sender, self.receiver
+        )?;
+
+        if !self.memo.is_empty() {
+            write!(f, " [memo: {}]", self.memo)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&MessageDescription> for proto::MessageDescription {
+    type Error = Error;
+
+    fn try_from(description: &MessageDescription) -> Result<Self, Self::Error> {
+        let content = match description.content {
+            MessageContent::SignBytes(ref sign_bytes) => {
+                let path = match sign_bytes.path {
+                    SignBytesPath::Header {
+                        ref new_public_key,
+                        ref new_diversifier,
+                    } => {
+                        let (algo, verifying_key) = match new_public_key {
+                            PublicKey::Secp256k1(verifying_key) => ("secp256k1", verifying_key),
+                            #[cfg(feature = "ethermint")]
+                            PublicKey::EthSecp256k1(verifying_key) => {
+                                ("eth-secp256k1", verifying_key)
+                            }
+                            _ => bail!("unsupported public key algorithm in header"),
+                        };
+
+                        proto::sign_bytes_description::Path::Header(proto::HeaderPath {
+                            new_public_key_algo: algo.to_owned(),
+                            new_public_key: verifying_key.to_sec1_bytes().to_vec(),
+                            new_diversifier: new_diversifier.clone(),
+                        })
+                    }
+                    SignBytesPath::Connection { ref connection_id } => {
+                        proto::sign_bytes_description::Path::Connection(proto::ConnectionPath {
+                            connection_id: connection_id.clone(),
+                        })
+                    }
+                    SignBytesPath::Channel {
+                        ref port_id,
+                        ref channel_id,
+                    } => proto::sign_bytes_description::Path::Channel(proto::ChannelPath {
+                        port_id: port_id.clone(),
+                        channel_id: channel_id.clone(),
+                    }),
+                    SignBytesPath::PacketCommitment {
+                        ref port_id,
+                        ref channel_id,
+                        packet_sequence,
+                        ref packet_data,
+                        timeout_revision_number,
+                        timeout_revision_height,
+                        timeout_timestamp,
+                    } => proto::sign_bytes_description::Path::PacketCommitment(
+                        proto::PacketCommitmentPath {
+                            port_id: port_id.clone(),
+                            channel_id: channel_id.clone(),
+                            packet_sequence,
+                            packet_data: Some(packet_data.into()),
+                            timeout_revision_number,
+                            timeout_revision_height,
+                            timeout_timestamp,
+                        },
+                    ),
+                    SignBytesPath::PacketAcknowledgement {
+                        ref port_id,
+                        ref channel_id,
+                        packet_sequence,
+                    } => proto::sign_bytes_description::Path::PacketAcknowledgement(
+                        proto::PacketAcknowledgementPath {
+                            port_id: port_id.clone(),
+                            channel_id: channel_id.clone(),
+                            packet_sequence,
+                        },
+                    ),
+                };
+
+                proto::message_description::Content::SignBytes(proto::SignBytesDescription {
+                    sequence: sign_bytes.sequence,
+                    timestamp: sign_bytes.timestamp,
+                    diversifier: sign_bytes.diversifier.clone(),
+                    path: Some(path),
+                })
+            }
+            MessageContent::SignDoc(ref sign_doc) => {
+                proto::message_description::Content::SignDoc(proto::SignDocDescription {
+                    account_number: sign_doc.account_number,
+                    account_sequence: sign_doc.account_sequence,
+                    messages: sign_doc.messages.clone(),
+                    memo: sign_doc.memo.clone(),
+                    packet_data: sign_doc.packet_data.as_ref().map(Into::into),
+                })
+            }
+        };
+
+        Ok(Self {
+            chain_id: description.chain_id.clone(),
+            content: Some(content),
+        })
+    }
+}
+
+impl TryFrom<proto::MessageDescription> for MessageDescription {
+    type Error = Error;
+
+    fn try_from(description: proto::MessageDescription) -> Result<Self, Self::Error> {
+        let content = match description
+            .content
+            .ok_or_else(|| anyhow!("content of message description is missing"))?
+        {
+            proto::message_description::Content::SignBytes(sign_bytes) => {
+                let path = match sign_bytes
+                    .path
+                    .ok_or_else(|| anyhow!("path of sign bytes description is missing"))?
+                {
+                    proto::sign_bytes_description::Path::Header(header) => {
+                        let verifying_key = VerifyingKey::from_sec1_bytes(&header.new_public_key)
+                            .context("invalid new public key in header")?;
+
+                        let new_public_key = match header.new_public_key_algo.parse()? {
+                            PublicKeyAlgo::Secp256k1 => PublicKey::Secp256k1(verifying_key),
+                            #[cfg(feature = "ethermint")]
+                            PublicKeyAlgo::EthSecp256k1 => PublicKey::EthSecp256k1(verifying_key),
+                        };
+
+                        SignBytesPath::Header {
+                            new_public_key,
+                            new_diversifier: header.new_diversifier,
+                        }
+                    }
+                    proto::sign_bytes_description::Path::Connection(connection) => {
+                        SignBytesPath::Connection {
+                            connection_id: connection.connection_id,
+                        }
+                    }
+                    proto::sign_bytes_description::Path::Channel(channel) => {
+                        SignBytesPath::Channel {
+                            port_id: channel.port_id,
+                            channel_id: channel.channel_id,
+                        }
+                    }
+                    proto::sign_bytes_description::Path::PacketCommitment(commitment) => {
+                        SignBytesPath::PacketCommitment {
+                            port_id: commitment.port_id,
+                            channel_id: commitment.channel_id,
+                            packet_sequence: commitment.packet_sequence,
+                            packet_data: commitment
+                                .packet_data
+                                .ok_or_else(|| anyhow!("packet data of commitment is missing"))?
+                                .into(),
+                            timeout_revision_number: commitment.timeout_revision_number,
+                            timeout_revision_height: commitment.timeout_revision_height,
+                            timeout_timestamp: commitment.timeout_timestamp,
+                        }
+                    }
+                    proto::sign_bytes_description::Path::PacketAcknowledgement(acknowledgement) => {
+                        SignBytesPath::PacketAcknowledgement {
+                            port_id: acknowledgement.port_id,
+                            channel_id: acknowledgement.channel_id,
+                            packet_sequence: acknowledgement.packet_sequence,
+                        }
+                    }
+                };
+
+                MessageContent::SignBytes(SignBytesDescription {
+                    sequence: sign_bytes.sequence,
+                    timestamp: sign_bytes.timestamp,
+                    diversifier: sign_bytes.diversifier,
+                    path,
+                })
+            }
+            proto::message_description::Content::SignDoc(sign_doc) => {
+                MessageContent::SignDoc(SignDocDescription {
+                    account_number: sign_doc.account_number,
+                    account_sequence: sign_doc.account_sequence,
+                    messages: sign_doc.messages,
+                    memo: sign_doc.memo,
+                    packet_data: sign_doc.packet_data.map(Into::into),
+                })
+            }
+        };
+
+        Ok(Self {
+            chain_id: description.chain_id,
+            content,
+        })
+    }
+}
+
+impl From<&PacketData> for proto::PacketData {
+    fn from(packet_data: &PacketData) -> Self {
+        Self {
+            denom: packet_data.denom.clone(),
+            amount: packet_data.amount.clone(),
+            sender: packet_data.sender.clone(),
+            receiver: packet_data.receiver.clone(),
+            memo: packet_data.memo.clone(),
+        }
+    }
+}
+
+impl From<proto::PacketData> for PacketData {
+    fn from(packet_data: proto::PacketData) -> Self {
+        Self {
+            denom: packet_data.denom,
+            amount: packet_data.amount,
+            sender: packet_data.sender,
+            receiver: packet_data.receiver,
+            memo: packet_data.memo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::{base::v1beta1::Coin, tx::v1beta1::SignerInfo};
+
+    use super::*;
+    use crate::proto::proto_encode;
+
+    const CONNECTION_ID: &str = "connection-0";
+
+    fn packet_data() -> PacketData {
+        PacketData {
+            denom: "stake".to_owned(),
+            amount: "100".to_owned(),
+            sender: "cosmos1sender".to_owned(),
+            receiver: "cosmos1receiver".to_owned(),
+            memo: String::new(),
+        }
+    }
+
+    fn connection_description() -> MessageDescription {
+        MessageDescription {
+            chain_id: "test-1".to_owned(),
+            content: MessageContent::SignBytes(SignBytesDescription {
+                sequence: 3,
+                timestamp: 1_700_000_000,
+                diversifier: "solo-machine".to_owned(),
+                path: SignBytesPath::Connection {
+                    connection_id: CONNECTION_ID.to_owned(),
+                },
+            }),
+        }
+    }
+
+    fn connection_sign_bytes() -> Vec<u8> {
+        proto_encode(&SignBytes {
+            sequence: 3,
+            timestamp: 1_700_000_000,
+            diversifier: "solo-machine".to_owned(),
+            path: path_key(&ConnectionPath::new(&CONNECTION_ID.parse().unwrap()))
+                .unwrap()
+                .into_bytes(),
+            data: b"connection".to_vec(),
+        })
+        .unwrap()
+    }
+
+    fn packet_commitment_description() -> MessageDescription {
+        MessageDescription {
+            chain_id: "test-1".to_owned(),
+            content: MessageContent::SignBytes(SignBytesDescription {
+                sequence: 4,
+                timestamp: 1_700_000_000,
+                diversifier: "solo-machine".to_owned(),
+                path: SignBytesPath::PacketCommitment {
+                    port_id: "transfer".to_owned(),
+                    channel_id: "channel-0".to_owned(),
+                    packet_sequence: 1,
+                    packet_data: packet_data(),
+                    timeout_revision_number: 1,
+                    timeout_revision_height: 100,
+                    timeout_timestamp: 0,
+                },
+            }),
+        }
+    }
+
+    fn packet_commitment_sign_bytes() -> Vec<u8> {
+        let packet_data = packet_data();
+        let packet = Packet {
+            timeout_height: Some(Height {
+                revision_number: 1,
+                revision_height: 100,
+            }),
+            data: serde_json::to_vec(&TokenTransferPacketData {
+                denom: packet_data.denom,
+                amount: packet_data.amount,
+                sender: packet_data.sender,
+                receiver: packet_data.receiver,
+                memo: packet_data.memo,
+            })
+            .unwrap(),
+            ..Default::default()
+        };
+
+        proto_encode(&SignBytes {
+            sequence: 4,
+            timestamp: 1_700_000_000,
+            diversifier: "solo-machine".to_owned(),
+            path: path_key(&PacketCommitmentPath::new(
+                &"transfer".parse().unwrap(),
+                &"channel-0".parse().unwrap(),
+                1,
+            ))
+            .unwrap()
+            .into_bytes(),
+            data: packet.commitment_bytes().unwrap(),
+        })
+        .unwrap()
+    }
+
+    fn sign_doc_description() -> MessageDescription {
+        MessageDescription {
+            chain_id: "test-1".to_owned(),
+            content: MessageContent::SignDoc(SignDocDescription {
+                account_number: 7,
+                account_sequence: 5,
+                messages: vec![MsgTransfer::type_url()],
+                memo: "memo".to_owned(),
+                packet_data: Some(packet_data()),
+            }),
+        }
+    }
+
+    fn sign_doc() -> Vec<u8> {
+        let packet_data = packet_data();
+        let message = MsgTransfer {
+            source_port: "transfer".to_owned(),
+            source_channel: "channel-0".to_owned(),
+            token: Some(Coin {
+                denom: packet_data.denom,
+                amount: packet_data.amount,
+            }),
+            sender: packet_data.sender,
+            receiver: packet_data.receiver,
+            memo: packet_data.memo,
+            ..Default::default()
+        };
+
+        proto_encode(&SignDoc {
+            body_bytes: proto_encode(&TxBody {
+                messages: vec![Any {
+                    type_url: MsgTransfer::type_url(),
+                    value: proto_encode(&message).unwrap(),
+                }],
+                memo: "memo".to_owned(),
+                ..Default::default()
+            })
+            .unwrap(),
+            auth_info_bytes: proto_encode(&AuthInfo {
+                signer_infos: vec![SignerInfo {
+                    sequence: 5,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .unwrap(),
+            chain_id: "test-1".to_owned(),
+            account_number: 7,
+        })
+        .unwrap()
+    }
+
+    fn sign_bytes_content(description: &mut MessageDescription) -> &mut SignBytesDescription {
+        match description.content {
+            MessageContent::SignBytes(ref mut content) => content,
+            _ => unreachable!(),
+        }
+    }
+
+    fn sign_doc_content(description: &mut MessageDescription) -> &mut SignDocDescription {
+        match description.content {
+            MessageContent::SignDoc(ref mut content) => content,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Asserts that given description is rejected for given message with an error mentioning `reason`
+    fn assert_rejected(description: &MessageDescription, message: Message<'_>, reason: &str) {
+        let err = description.verify(&message).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains(reason),
+            "unexpected error: {:#}",
+            err
+        );
+    }
+
+    #[test]
+    fn accepts_matching_descriptions() {
+        connection_description()
+            .verify(&Message::SignBytes(&connection_sign_bytes()))
+            .unwrap();
+        packet_commitment_description()
+            .verify(&Message::SignBytes(&packet_commitment_sign_bytes()))
+            .unwrap();
+        sign_doc_description()
+            .verify(&Message::SignDoc(&sign_doc()))
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_description_of_other_message_type() {
+        assert_rejected(
+            &sign_doc_description(),
+            Message::SignBytes(&connection_sign_bytes()),
+            "description does not match message type",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_sequence() {
+        let mut description = connection_description();
+        sign_bytes_content(&mut description).sequence = 4;
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&connection_sign_bytes()),
+            "sequence mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_timestamp() {
+        let mut description = connection_description();
+        sign_bytes_content(&mut description).timestamp += 1;
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&connection_sign_bytes()),
+            "timestamp mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_diversifier() {
+        let mut description = connection_description();
+        sign_bytes_content(&mut description).diversifier = "other".to_owned();
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&connection_sign_bytes()),
+            "diversifier mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_path() {
+        let mut description = connection_description();
+        sign_bytes_content(&mut description).path = SignBytesPath::Connection {
+            connection_id: "connection-1".to_owned(),
+        };
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&connection_sign_bytes()),
+            "path mismatch",
+        );
+
+        let mut description = connection_description();
+        sign_bytes_content(&mut description).path = SignBytesPath::Channel {
+            port_id: "transfer".to_owned(),
+            channel_id: "channel-0".to_owned(),
+        };
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&connection_sign_bytes()),
+            "path mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_packet_commitment() {
+        let mut description = packet_commitment_description();
+        if let SignBytesPath::PacketCommitment {
+            ref mut packet_data,
+            ..
+        } = sign_bytes_content(&mut description).path
+        {
+            packet_data.amount = "1000".to_owned();
+        }
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&packet_commitment_sign_bytes()),
+            "packet commitment does not match packet data",
+        );
+
+        let mut description = packet_commitment_description();
+        if let SignBytesPath::PacketCommitment {
+            ref mut timeout_revision_height,
+            ..
+        } = sign_bytes_content(&mut description).path
+        {
+            *timeout_revision_height = 200;
+        }
+
+        assert_rejected(
+            &description,
+            Message::SignBytes(&packet_commitment_sign_bytes()),
+            "packet commitment does not match packet data",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_sign_doc_chain_id() {
+        let mut description = sign_doc_description();
+        description.chain_id = "test-2".to_owned();
+
+        assert_rejected(
+            &description,
+            Message::SignDoc(&sign_doc()),
+            "chain id mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_sign_doc_account() {
+        let mut description = sign_doc_description();
+        sign_doc_content(&mut description).account_number = 8;
+
+        assert_rejected(
+            &description,
+            Message::SignDoc(&sign_doc()),
+            "account number mismatch",
+        );
+
+        let mut description = sign_doc_description();
+        sign_doc_content(&mut description).account_sequence = 6;
+
+        assert_rejected(
+            &description,
+            Message::SignDoc(&sign_doc()),
+            "account sequence mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_sign_doc_memo() {
+        let mut description = sign_doc_description();
+        sign_doc_content(&mut description).memo = "other memo".to_owned();
+
+        assert_rejected(&description, Message::SignDoc(&sign_doc()), "memo mismatch");
+    }
+
+    #[test]
+    fn rejects_tampered_sign_doc_type_url() {
+        let mut description = sign_doc_description();
+        sign_doc_content(&mut description).messages = vec![MsgRecvPacket::type_url()];
+
+        assert_rejected(
+            &description,
+            Message::SignDoc(&sign_doc()),
+            "messages mismatch",
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_sign_doc_packet_data() {
+        let mut description = sign_doc_description();
+        if let Some(ref mut packet_data) = sign_doc_content(&mut description).packet_data {
+            packet_data.receiver = "cosmos1attacker".to_owned();
+        }
+
+        assert_rejected(
+            &description,
+            Message::SignDoc(&sign_doc()),
+            "packet data mismatch",
+        );
+    }
+
+    #[test]
+    fn sign_bytes_are_not_bound_to_chain() {
+        assert!(sign_doc_description().is_chain_bound());
+        assert!(!connection_description().is_chain_bound());
+    }
+}
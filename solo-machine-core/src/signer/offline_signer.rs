@@ -0,0 +1,197 @@
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::{cosmos::crypto::PublicKey, ibc::core::ics24_host::identifier::ChainId};
+
+use super::{verify_signature, Message, MessageDescription, Signer, ToPublicKey};
+
+/// Type of message which needs to be signed offline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MessageKind {
+    /// [ibc_proto::ibc::lightclients::solomachine::v3::SignBytes]
+    SignBytes,
+    /// [ibc_proto::cosmos::tx::v1beta1::SignDoc]
+    SignDoc,
+}
+
+impl fmt::Display for MessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SignBytes => write!(f, "sign-bytes"),
+            Self::SignDoc => write!(f, "sign-doc"),
+        }
+    }
+}
+
+/// A message which needs to be signed offline (along with its decoded contents)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureRequest {
+    /// Type of message
+    pub message_type: MessageKind,
+    /// Hex encoded bytes of message
+    #[serde(with = "hex")]
+    pub message: Vec<u8>,
+    /// Decoded contents of message
+    pub description: MessageDescription,
+}
+
+impl SignatureRequest {
+    /// Returns the message to sign
+    pub fn message(&self) -> Message<'_> {
+        match self.message_type {
+            MessageKind::SignBytes => Message::SignBytes(&self.message),
+            MessageKind::SignDoc => Message::SignDoc(&self.message),
+        }
+    }
+}
+
+/// Signature of a message signed offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSignature {
+    /// Hex encoded bytes of signed message
+    #[serde(with = "hex")]
+    pub message: Vec<u8>,
+    /// Hex encoded signature
+    #[serde(with = "hex")]
+    pub signature: Vec<u8>,
+}
+
+impl MessageSignature {
+    /// Verifies the signature using given public key
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        verify_signature(public_key, &self.message, &self.signature)
+            .context("signature verification failed")
+    }
+}
+
+/// Messages of an offline signing session which need to be signed in current round (written to a file and carried to
+/// the offline machine)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningRequests {
+    /// ID of offline signing session
+    pub session_id: i64,
+    /// Round of offline signing session (messages of a round can only be built once previous round is signed)
+    pub round: u32,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Account address of signer
+    pub address: String,
+    /// Public key of signer
+    pub public_key: PublicKey,
+    /// Messages to sign
+    pub requests: Vec<SignatureRequest>,
+}
+
+/// Signatures of messages of a round of an offline signing session (carried back from the offline machine)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningResponse {
+    /// ID of offline signing session
+    pub session_id: i64,
+    /// Round of offline signing session
+    pub round: u32,
+    /// Signatures of messages
+    pub signatures: Vec<MessageSignature>,
+}
+
+/// Error returned by [OfflineSigner] when a message is not signed yet
+#[derive(Debug)]
+pub struct SignaturesPending;
+
+impl fmt::Display for SignaturesPending {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "signatures of offline signing session are pending")
+    }
+}
+
+impl std::error::Error for SignaturesPending {}
+
+/// Signer which only knows the public key of signer and returns signatures which were collected offline. Messages
+/// which are not signed yet are recorded (see [OfflineSigner::take_requests]) and signing fails with
+/// [SignaturesPending].
+#[derive(Debug)]
+pub struct OfflineSigner {
+    public_key: PublicKey,
+    account_prefix: String,
+    signatures: HashMap<Vec<u8>, Vec<u8>>,
+    requests: Mutex<Vec<SignatureRequest>>,
+}
+
+impl OfflineSigner {
+    /// Creates a new offline signer with given (already verified) signatures
+    pub fn new(
+        public_key: PublicKey,
+        account_prefix: String,
+        signatures: &[MessageSignature],
+    ) -> Self {
+        Self {
+            public_key,
+            account_prefix,
+            signatures: signatures
+                .iter()
+                .map(|signature| (signature.message.clone(), signature.signature.clone()))
+                .collect(),
+            requests: Default::default(),
+        }
+    }
+
+    /// Returns the messages which were not signed yet
+    pub fn take_requests(&self) -> Result<Vec<SignatureRequest>> {
+        let mut requests = self
+            .requests
+            .lock()
+            .map_err(|_| anyhow!("unable to lock signature requests"))?;
+
+        Ok(std::mem::take(&mut *requests))
+    }
+}
+
+impl ToPublicKey for OfflineSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.public_key.account_address(&self.account_prefix)
+    }
+}
+
+#[async_trait]
+impl Signer for OfflineSigner {
+    async fn sign(
+        &self,
+        _request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        if let Some(signature) = self.signatures.get(message.as_ref()) {
+            return Ok(signature.clone());
+        }
+
+        // Exported descriptions are shown to the approver on offline machine, so, they should match the messages
+        description.verify(&message)?;
+
+        let message_type = match message {
+            Message::SignBytes(_) => MessageKind::SignBytes,
+            Message::SignDoc(_) => MessageKind::SignDoc,
+        };
+
+        self.requests
+            .lock()
+            .map_err(|_| anyhow!("unable to lock signature requests"))?
+            .push(SignatureRequest {
+                message_type,
+                message: message.as_ref().to_vec(),
+                description: description.clone(),
+            });
+
+        Err(SignaturesPending.into())
+    }
+}
@@ -0,0 +1,359 @@
+use std::{convert::TryInto, time::Duration};
+
+use anyhow::{ensure, Context, Error, Result};
+use async_trait::async_trait;
+use k256::ecdsa::VerifyingKey;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+
+use crate::{
+    cosmos::crypto::{PublicKey, PublicKeyAlgo},
+    proto::solo_machine::signer::v1::{
+        signer_client::SignerClient, GetPublicKeyRequest, MessageType, SignRequest,
+    },
+};
+
+use super::{verify_signature, Message, MessageDescription, Signer, ToPublicKey};
+
+/// Configuration of a remote signer
+#[derive(Debug, Clone)]
+pub struct RemoteSignerConfig {
+    /// URL of remote signing service (e.g. `https://signer.example.com:9100`)
+    pub url: String,
+    /// PEM encoded CA certificate used to verify certificate of remote signing service (system roots are used when
+    /// `None`)
+    pub ca_cert: Option<Vec<u8>>,
+    /// PEM encoded client certificate and private key presented to remote signing service (mutual TLS)
+    pub identity: Option<(Vec<u8>, Vec<u8>)>,
+    /// Domain name used to verify certificate of remote signing service (defaults to host of URL)
+    pub domain: Option<String>,
+    /// Timeout of each request to remote signing service
+    pub timeout: Duration,
+    /// Allows connecting to remote signing service without `https` and a client identity (only meant for local
+    /// testing)
+    pub insecure: bool,
+}
+
+/// Signer which signs messages using a remote signing service (over `solo_machine.signer.v1.Signer` gRPC protocol)
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: SignerClient<Channel>,
+    public_key: PublicKey,
+    account_prefix: String,
+}
+
+impl RemoteSigner {
+    /// Connects to remote signing service and fetches public key of signer
+    pub async fn connect(config: RemoteSignerConfig) -> Result<Self> {
+        ensure!(
+            config.insecure || config.url.starts_with("https://"),
+            "remote signer url should use `https` scheme (use `--remote-signer-insecure` to allow plaintext connections)"
+        );
+        ensure!(
+            config.insecure || config.identity.is_some(),
+            "remote signer requires a client certificate and private key for mutual tls (use `--remote-signer-insecure` to allow connections without client identity)"
+        );
+
+        let mut endpoint = Endpoint::from_shared(config.url)
+            .map_err(Error::from)
+            .context("invalid remote signer url")?
+            .connect_timeout(config.timeout)
+            .timeout(config.timeout);
+
+        if endpoint.uri().scheme_str() == Some("https") {
+            let mut tls_config = match config.ca_cert {
+                Some(ca_cert) => {
+                    ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca_cert))
+                }
+                None => ClientTlsConfig::new().with_enabled_roots(),
+            };
+
+            if let Some((cert, key)) = config.identity {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+
+            if let Some(domain) = config.domain {
+                tls_config = tls_config.domain_name(domain);
+            }
+
+            endpoint = endpoint
+                .tls_config(tls_config)
+                .context("invalid tls configuration for remote signer")?;
+        } else {
+            ensure!(
+                config.ca_cert.is_none() && config.identity.is_none(),
+                "remote signer url should use `https` scheme when tls certificates are provided"
+            );
+        }
+
+        let channel = endpoint
+            .connect()
+            .await
+            .context("unable to connect to remote signer")?;
+        let mut client = SignerClient::new(channel);
+
+        let response = client
+            .get_public_key(GetPublicKeyRequest {})
+            .await
+            .context("unable to get public key from remote signer")?
+            .into_inner();
+
+        let verifying_key = VerifyingKey::from_sec1_bytes(&response.public_key)
+            .context("invalid public key returned by remote signer")?;
+
+        let public_key = match response.algo.parse()? {
+            PublicKeyAlgo::Secp256k1 => PublicKey::Secp256k1(verifying_key),
+            #[cfg(feature = "ethermint")]
+            PublicKeyAlgo::EthSecp256k1 => PublicKey::EthSecp256k1(verifying_key),
+        };
+
+        Ok(Self {
+            client,
+            public_key,
+            account_prefix: response.account_prefix,
+        })
+    }
+}
+
+impl ToPublicKey for RemoteSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.public_key.account_address(&self.account_prefix)
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        let message_type = match message {
+            Message::SignBytes(_) => MessageType::SignBytes,
+            Message::SignDoc(_) => MessageType::SignDoc,
+        };
+
+        let request = SignRequest {
+            request_id: request_id.map(ToOwned::to_owned),
+            message_type: message_type.into(),
+            message: message.as_ref().to_vec(),
+            description: Some(description.try_into()?),
+        };
+
+        let response = self
+            .client
+            .clone()
+            .sign(request)
+            .await
+            .with_context(|| {
+                format!(
+                    "remote signer failed to sign {} message",
+                    message.message_type()
+                )
+            })?
+            .into_inner();
+
+        verify_signature(&self.public_key, message.as_ref(), &response.signature).with_context(
+            || {
+                format!(
+                    "remote signer returned invalid signature for {} message",
+                    message.message_type()
+                )
+            },
+        )?;
+
+        Ok(response.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::SigningKey;
+    use tokio::net::TcpListener;
+    use tonic::{
+        transport::{server::TcpIncoming, Server},
+        Request, Response, Status,
+    };
+
+    use super::*;
+    use crate::{
+        proto::solo_machine::signer::v1::{
+            signer_server::{Signer as SignerService, SignerServer},
+            GetPublicKeyResponse, SignResponse,
+        },
+        signer::{AddressAlgo, MessageContent, SignBytesDescription, SignBytesPath},
+    };
+
+    /// Signing service which signs messages with `signing_key` but advertises public key of `public_key`
+    struct TestSigningService {
+        signing_key: SigningKey,
+        public_key: VerifyingKey,
+    }
+
+    #[tonic::async_trait]
+    impl SignerService for TestSigningService {
+        async fn get_public_key(
+            &self,
+            _request: Request<GetPublicKeyRequest>,
+        ) -> Result<Response<GetPublicKeyResponse>, Status> {
+            Ok(Response::new(GetPublicKeyResponse {
+                algo: "secp256k1".to_owned(),
+                public_key: self.public_key.to_sec1_bytes().to_vec(),
+                account_prefix: "cosmos".to_owned(),
+            }))
+        }
+
+        async fn sign(
+            &self,
+            request: Request<SignRequest>,
+        ) -> Result<Response<SignResponse>, Status> {
+            let signature = AddressAlgo::Secp256k1
+                .sign(&self.signing_key, &request.into_inner().message)
+                .map_err(|err| Status::internal(format!("{:#}", err)))?;
+
+            Ok(Response::new(SignResponse { signature }))
+        }
+    }
+
+    fn signing_key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn config(url: &str) -> RemoteSignerConfig {
+        RemoteSignerConfig {
+            url: url.to_owned(),
+            ca_cert: None,
+            identity: None,
+            domain: None,
+            timeout: Duration::from_secs(5),
+            insecure: false,
+        }
+    }
+
+    fn description() -> MessageDescription {
+        MessageDescription {
+            chain_id: "test-1".to_owned(),
+            content: MessageContent::SignBytes(SignBytesDescription {
+                sequence: 1,
+                timestamp: 1_700_000_000,
+                diversifier: "solo-machine".to_owned(),
+                path: SignBytesPath::Connection {
+                    connection_id: "connection-0".to_owned(),
+                },
+            }),
+        }
+    }
+
+    /// Serves given signing service on a random local port and returns its (plaintext) url
+    async fn serve(service: TestSigningService) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(SignerServer::new(service))
+                .serve_with_incoming(incoming),
+        );
+
+        format!("http://{}", addr)
+    }
+
+    async fn connect_insecure(service: TestSigningService) -> RemoteSigner {
+        let mut config = config(&serve(service).await);
+        config.insecure = true;
+
+        RemoteSigner::connect(config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn connect_requires_https() {
+        let mut config = config("http://127.0.0.1:1");
+        config.identity = Some((b"cert".to_vec(), b"key".to_vec()));
+
+        let err = RemoteSigner::connect(config).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("remote signer url should use `https` scheme"));
+    }
+
+    #[tokio::test]
+    async fn connect_requires_client_identity() {
+        let err = RemoteSigner::connect(config("https://127.0.0.1:1"))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("remote signer requires a client certificate and private key"));
+    }
+
+    #[tokio::test]
+    async fn insecure_connection_rejects_tls_certificates() {
+        let mut config = config("http://127.0.0.1:1");
+        config.insecure = true;
+        config.ca_cert = Some(b"ca".to_vec());
+
+        let err = RemoteSigner::connect(config).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "remote signer url should use `https` scheme when tls certificates are provided"
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_returns_verified_signature() {
+        let signing_key = signing_key(7);
+        let public_key = *signing_key.verifying_key();
+
+        let signer = connect_insecure(TestSigningService {
+            signing_key,
+            public_key,
+        })
+        .await;
+
+        assert_eq!(
+            signer.to_account_address().unwrap(),
+            PublicKey::Secp256k1(public_key)
+                .account_address("cosmos")
+                .unwrap()
+        );
+
+        let signature = signer
+            .sign(
+                Some("request-1"),
+                Message::SignBytes(b"message"),
+                &description(),
+            )
+            .await
+            .unwrap();
+
+        verify_signature(&signer.to_public_key().unwrap(), b"message", &signature).unwrap();
+    }
+
+    #[tokio::test]
+    async fn remote_signer_rejects_signature_of_different_key() {
+        let signer = connect_insecure(TestSigningService {
+            signing_key: signing_key(8),
+            public_key: *signing_key(7).verifying_key(),
+        })
+        .await;
+
+        let err = signer
+            .sign(None, Message::SignBytes(b"message"), &description())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "remote signer returned invalid signature for sign-bytes message"
+        );
+    }
+}
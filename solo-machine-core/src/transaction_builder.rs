@@ -21,7 +21,7 @@ use ibc_proto::{
             },
         },
     },
-    google::protobuf::{Any, Duration},
+    google::protobuf::Duration,
     ibc::{
         applications::transfer::v1::MsgTransfer,
         core::{
@@ -54,7 +54,10 @@ use serde::Serialize;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use sqlx::{Executor, Transaction};
-use tendermint::block::header::Header;
+use tendermint::{
+    block::{header::Header, Height as BlockHeight},
+    Hash as TendermintHash,
+};
 use tendermint_light_client::instance::Instance;
 use tendermint_rpc::Client;
 
@@ -76,11 +79,15 @@ use crate::{
     },
     model::{chain, ibc as ibc_handler, Chain},
     proto::{proto_encode, AnyConvert},
-    signer::Message,
+    signer::{
+        Message, MessageContent, MessageDescription, PacketData, SignBytesDescription,
+        SignBytesPath, SignDocDescription,
+    },
     Db, Signer, ToPublicKey,
 };
 
-const DEFAULT_TIMEOUT_HEIGHT_OFFSET: u64 = 10;
+/// Number of blocks after latest height of IBC enabled chain at which packets sent by solo machine time out
+pub const DEFAULT_TIMEOUT_HEIGHT_OFFSET: u64 = 10;
 
 /// Builds a transaction to create a solo machine client on IBC enabled chain
 pub async fn msg_create_solo_machine_client(
@@ -111,7 +118,7 @@ pub async fn msg_create_solo_machine_client(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 /// Builds a transaction to update solo machine client on IBC enabled chain
@@ -130,15 +137,16 @@ pub async fn msg_update_solo_machine_client<'e>(
         );
     }
 
-    let any_public_key = match new_public_key {
-        Some(new_public_key) => new_public_key.to_any()?,
-        None => signer.to_public_key()?.to_any()?,
+    let new_public_key = match new_public_key {
+        Some(new_public_key) => new_public_key.clone(),
+        None => signer.to_public_key()?,
     };
+    let any_public_key = new_public_key.to_any()?;
 
     let signature = get_header_proof(
         &signer,
         chain,
-        Some(any_public_key.clone()),
+        new_public_key,
         chain.config.diversifier.clone(),
         request_id,
     )
@@ -168,13 +176,24 @@ pub async fn msg_update_solo_machine_client<'e>(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
+}
+
+/// Light client instances of primary and witness nodes of an IBC enabled chain
+pub struct LightClient {
+    /// Light client instance of primary node
+    pub primary: Instance,
+    /// Light client instances of witness nodes (along with their RPC addresses)
+    pub witnesses: Vec<(String, Instance)>,
+    /// `true` if at least one witness should verify headers of primary node (i.e., fallback RPC endpoints are
+    /// configured for chain), even if none of the witnesses are healthy
+    pub requires_witness: bool,
 }
 
 /// Builds a transaction to create a tendermint client on IBC enabled solo machine
 pub async fn msg_create_tendermint_client(
     chain: &Chain,
-    instance: &mut Instance,
+    light_client: &mut LightClient,
 ) -> Result<(TendermintClientState, TendermintConsensusState)> {
     let trust_level = Some(Fraction {
         numerator: *chain.config.trust_level.numer(),
@@ -182,7 +201,7 @@ pub async fn msg_create_tendermint_client(
     });
 
     let unbonding_period = Some(get_unbonding_period(chain).await?);
-    let latest_header = get_latest_header(instance)?;
+    let latest_header = get_latest_header(light_client)?;
     let latest_height = get_block_height(chain, &latest_header);
 
     #[allow(deprecated)]
@@ -230,7 +249,7 @@ pub async fn msg_connection_open_init(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -281,7 +300,7 @@ pub async fn msg_connection_open_ack(
         host_consensus_state_proof: Vec::new(),
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 pub async fn msg_channel_open_init(
@@ -307,7 +326,7 @@ pub async fn msg_channel_open_init(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 pub async fn msg_channel_close_init(
@@ -332,7 +351,7 @@ pub async fn msg_channel_close_init(
         channel_id: solo_machine_channel_id.unwrap(),
         signer: signer.to_account_address()?,
     };
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 pub async fn msg_channel_open_ack(
@@ -366,24 +385,21 @@ pub async fn msg_channel_open_ack(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, chain, &[message], None, memo, request_id).await
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn msg_token_send<C>(
+pub async fn msg_token_send(
     transaction: &mut Transaction<'_, Db>,
     signer: impl Signer,
-    rpc_client: &C,
     chain: &mut Chain,
     amount: U256,
     denom: &Identifier,
     receiver: String,
     memo: String,
+    timeout_height: Height,
     request_id: Option<&str>,
-) -> Result<TxRaw>
-where
-    C: Client + Send + Sync,
-{
+) -> Result<TxRaw> {
     let connection_details = chain.connection_details.as_ref().ok_or_else(|| {
         anyhow!(
             "connection details not found for chain with id {}",
@@ -424,12 +440,7 @@ where
             .unwrap()
             .to_string(),
         data: serde_json::to_vec(&packet_data)?,
-        timeout_height: Some(
-            get_latest_height(chain, rpc_client)
-                .await?
-                .checked_add(DEFAULT_TIMEOUT_HEIGHT_OFFSET)
-                .ok_or_else(|| anyhow!("height addition overflow"))?,
-        ),
+        timeout_height: Some(timeout_height),
         timeout_timestamp: 0,
     };
 
@@ -440,6 +451,8 @@ where
     *chain = chain::increment_sequence(&mut **transaction, &chain.id).await?;
     *chain = chain::increment_packet_sequence(&mut **transaction, &chain.id).await?;
 
+    let description_data = PacketData::from_packet(&packet)?;
+
     let message = MsgRecvPacket {
         packet: Some(packet),
         proof_commitment,
@@ -447,7 +460,15 @@ where
         signer: sender,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(
+        signer,
+        chain,
+        &[message],
+        Some(description_data),
+        memo,
+        request_id,
+    )
+    .await
 }
 
 pub async fn msg_token_receive(
@@ -474,6 +495,14 @@ pub async fn msg_token_receive(
 
     let sender = signer.to_account_address()?;
 
+    let description_data = PacketData {
+        denom: denom.clone(),
+        amount: amount.to_string(),
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        memo: memo.clone(),
+    };
+
     let message = MsgTransfer {
         source_port: chain.config.port_id.to_string(),
         source_channel: connection_details
@@ -492,7 +521,15 @@ pub async fn msg_token_receive(
         memo: memo.clone(),
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(
+        signer,
+        chain,
+        &[message],
+        Some(description_data),
+        memo,
+        request_id,
+    )
+    .await
 }
 
 pub async fn msg_token_receive_ack<'e>(
@@ -518,6 +555,9 @@ pub async fn msg_token_receive_ack<'e>(
 
     *chain = chain::increment_sequence(executor, &chain.id).await?;
 
+    // Acknowledged packets are sent by IBC enabled chain, so, their data is described on a best effort basis
+    let description_data = PacketData::from_packet(&packet).ok();
+
     let message = MsgAcknowledgement {
         packet: Some(packet),
         acknowledgement,
@@ -526,13 +566,22 @@ pub async fn msg_token_receive_ack<'e>(
         signer: signer.to_account_address()?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(
+        signer,
+        chain,
+        &[message],
+        description_data,
+        memo,
+        request_id,
+    )
+    .await
 }
 
 async fn build<T>(
     signer: impl Signer,
     chain: &Chain,
     messages: &[T],
+    packet_data: Option<PacketData>,
     memo: String,
     request_id: Option<&str>,
 ) -> Result<TxRaw>
@@ -548,12 +597,28 @@ where
         build_auth_info(&signer, chain, account_sequence).context("unable to build auth info")?;
     let auth_info_bytes = proto_encode(&auth_info)?;
 
+    let description = MessageDescription {
+        chain_id: chain.id.to_string(),
+        content: MessageContent::SignDoc(SignDocDescription {
+            account_number,
+            account_sequence,
+            messages: tx_body
+                .messages
+                .iter()
+                .map(|message| message.type_url.clone())
+                .collect(),
+            memo: tx_body.memo.clone(),
+            packet_data,
+        }),
+    };
+
     let signature = build_signature(
         signer,
         tx_body_bytes.clone(),
         auth_info_bytes.clone(),
         chain.id.to_string(),
         account_number,
+        &description,
         request_id,
     )
     .await
@@ -621,6 +686,7 @@ async fn build_signature(
     auth_info_bytes: Vec<u8>,
     chain_id: String,
     account_number: u64,
+    description: &MessageDescription,
     request_id: Option<&str>,
 ) -> Result<Vec<u8>> {
     let sign_doc = SignDoc {
@@ -633,17 +699,12 @@ async fn build_signature(
     let sign_doc_bytes = proto_encode(&sign_doc)?;
 
     signer
-        .sign(request_id, Message::SignDoc(&sign_doc_bytes))
+        .sign(request_id, Message::SignDoc(&sign_doc_bytes), description)
         .await
 }
 
 async fn get_account_details(signer: impl ToPublicKey, chain: &Chain) -> Result<(u64, u64)> {
-    let mut query_client = AuthQueryClient::connect(chain.config.grpc_addr.clone())
-        .await
-        .context(format!(
-            "unable to connect to grpc query client at {}",
-            chain.config.grpc_addr
-        ))?;
+    let mut query_client = AuthQueryClient::new(chain.grpc_channel().await?);
 
     let account_address = signer.to_account_address()?;
 
@@ -665,12 +726,7 @@ async fn get_account_details(signer: impl ToPublicKey, chain: &Chain) -> Result<
 }
 
 async fn get_unbonding_period(chain: &Chain) -> Result<Duration> {
-    let mut query_client = StakingQueryClient::connect(chain.config.grpc_addr.clone())
-        .await
-        .context(format!(
-            "unable to connect to grpc query client at {}",
-            chain.config.grpc_addr
-        ))?;
+    let mut query_client = StakingQueryClient::new(chain.grpc_channel().await?);
 
     query_client
         .params(QueryParamsRequest::default())
@@ -682,6 +738,17 @@ async fn get_unbonding_period(chain: &Chain) -> Result<Duration> {
         .ok_or_else(|| anyhow!("missing unbonding period in staking params"))
 }
 
+/// Returns the height (`offset` blocks after latest height of IBC enabled chain) at which a packet sent now times out
+pub async fn get_timeout_height<C>(chain: &Chain, rpc_client: &C, offset: u64) -> Result<Height>
+where
+    C: Client + Send + Sync,
+{
+    get_latest_height(chain, rpc_client)
+        .await?
+        .checked_add(offset)
+        .ok_or_else(|| anyhow!("height addition overflow"))
+}
+
 async fn get_latest_height<C>(chain: &Chain, rpc_client: &C) -> Result<Height>
 where
     C: Client + Send + Sync,
@@ -690,8 +757,7 @@ where
 
     ensure!(
         !response.sync_info.catching_up,
-        "node at {} running chain {} not caught up",
-        chain.config.rpc_addr,
+        "node running chain {} not caught up",
         chain.id,
     );
 
@@ -710,14 +776,76 @@ where
     })
 }
 
-fn get_latest_header(instance: &mut Instance) -> Result<Header> {
-    let light_block = instance
+/// Verifies the highest header of primary node and cross-checks it with witness nodes
+fn get_latest_header(light_client: &mut LightClient) -> Result<Header> {
+    let light_block = light_client
+        .primary
         .light_client
-        .verify_to_highest(&mut instance.state)?;
+        .verify_to_highest(&mut light_client.primary.state)?;
+
+    let witnesses = light_client.witnesses.iter_mut().map(|(addr, witness)| {
+        let hash = witness
+            .light_client
+            .verify_to_target(light_block.height(), &mut witness.state)
+            .map(|witness_block| witness_block.signed_header.header.hash())
+            .map_err(|err| err.to_string());
+
+        (addr.as_str(), hash)
+    });
+
+    cross_check_header(
+        light_block.height(),
+        light_block.signed_header.header.hash(),
+        witnesses,
+        light_client.requires_witness,
+    )?;
 
     Ok(light_block.signed_header.header)
 }
 
+/// Cross-checks hash of header of primary node with the hashes of headers verified by witness nodes at the same
+/// height. Witnesses which are not able to verify the header (e.g., unreachable or lagging behind) are skipped, but at
+/// least one witness should verify it when `requires_witness` is `true` (i.e., it fails closed if none of the witnesses
+/// are reachable).
+fn cross_check_header<'a>(
+    height: BlockHeight,
+    hash: TendermintHash,
+    witnesses: impl IntoIterator<Item = (&'a str, Result<TendermintHash, String>)>,
+    requires_witness: bool,
+) -> Result<()> {
+    let mut verified = false;
+    let mut errors = Vec::new();
+
+    for (addr, witness_hash) in witnesses {
+        match witness_hash {
+            Ok(witness_hash) => {
+                ensure!(
+                    witness_hash == hash,
+                    "witness at {} reports a different header than primary node at height {} (possible fork)",
+                    addr,
+                    height
+                );
+
+                verified = true;
+            }
+            Err(err) => errors.push(format!("{}: {}", addr, err)),
+        }
+    }
+
+    ensure!(
+        verified || !requires_witness,
+        "none of the witnesses could verify header of primary node at height {}: [{}]",
+        height,
+        if errors.is_empty() {
+            "no healthy witness found".to_owned()
+        } else {
+            errors.join(", ")
+        }
+    );
+
+    Ok(())
+}
+
 fn get_block_height(chain: &Chain, header: &Header) -> Height {
     let revision_number = chain.id.version();
     let revision_height = header.height.value();
@@ -766,7 +894,17 @@ async fn get_packet_acknowledgement_proof(
         data: acknowledgement_bytes,
     };
 
-    timestamped_sign(signer, chain, sign_bytes, request_id).await
+    let path = SignBytesPath::PacketAcknowledgement {
+        port_id: chain.config.port_id.to_string(),
+        channel_id: connection_details
+            .tendermint_channel_id
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        packet_sequence,
+    };
+
+    timestamped_sign(signer, chain, sign_bytes, path, request_id).await
 }
 
 async fn get_packet_commitment_proof(
@@ -806,7 +944,22 @@ async fn get_packet_commitment_proof(
         data: commitment_bytes,
     };
 
-    timestamped_sign(signer, chain, sign_bytes, request_id).await
+    let timeout_height = packet
+        .timeout_height
+        .as_ref()
+        .ok_or_else(|| anyhow!("timeout height is not set"))?;
+
+    let path = SignBytesPath::PacketCommitment {
+        port_id: packet.source_port.clone(),
+        channel_id: packet.source_channel.clone(),
+        packet_sequence: packet.sequence,
+        packet_data: PacketData::from_packet(packet)?,
+        timeout_revision_number: timeout_height.revision_number,
+        timeout_revision_height: timeout_height.revision_height,
+        timeout_timestamp: packet.timeout_timestamp,
+    };
+
+    timestamped_sign(signer, chain, sign_bytes, path, request_id).await
 }
 
 async fn get_channel_proof<'e>(
@@ -843,7 +996,12 @@ async fn get_channel_proof<'e>(
         data: channel_bytes,
     };
 
-    timestamped_sign(signer, chain, sign_bytes, request_id).await
+    let path = SignBytesPath::Channel {
+        port_id: chain.config.port_id.to_string(),
+        channel_id: channel_id.to_string(),
+    };
+
+    timestamped_sign(signer, chain, sign_bytes, path, request_id).await
 }
 
 async fn get_connection_proof<'e>(
@@ -874,19 +1032,23 @@ async fn get_connection_proof<'e>(
         data: connection_bytes,
     };
 
-    timestamped_sign(signer, chain, sign_bytes, request_id).await
+    let path = SignBytesPath::Connection {
+        connection_id: connection_id.to_string(),
+    };
+
+    timestamped_sign(signer, chain, sign_bytes, path, request_id).await
 }
 
 async fn get_header_proof(
     signer: impl Signer,
     chain: &Chain,
-    new_public_key: Option<Any>,
+    new_public_key: PublicKey,
     new_diversifier: String,
     request_id: Option<&str>,
 ) -> Result<Vec<u8>> {
     let header_data = HeaderData {
-        new_pub_key: new_public_key,
-        new_diversifier,
+        new_pub_key: Some(new_public_key.to_any()?),
+        new_diversifier: new_diversifier.clone(),
     };
 
     let header_data_bytes = proto_encode(&header_data)?;
@@ -899,16 +1061,22 @@ async fn get_header_proof(
         data: header_data_bytes,
     };
 
-    sign(signer, request_id, sign_bytes).await
+    let path = SignBytesPath::Header {
+        new_public_key,
+        new_diversifier,
+    };
+
+    sign(signer, chain, sign_bytes, path, request_id).await
 }
 
 async fn timestamped_sign(
     signer: impl Signer,
     chain: &Chain,
     sign_bytes: SignBytes,
+    path: SignBytesPath,
     request_id: Option<&str>,
 ) -> Result<Vec<u8>> {
-    let signature_data = sign(signer, request_id, sign_bytes).await?;
+    let signature_data = sign(signer, chain, sign_bytes, path, request_id).await?;
 
     let timestamped_signature_data = TimestampedSignatureData {
         signature_data,
@@ -920,12 +1088,24 @@ async fn timestamped_sign(
 
 async fn sign(
     signer: impl Signer,
-    request_id: Option<&str>,
+    chain: &Chain,
     sign_bytes: SignBytes,
+    path: SignBytesPath,
+    request_id: Option<&str>,
 ) -> Result<Vec<u8>> {
+    let description = MessageDescription {
+        chain_id: chain.id.to_string(),
+        content: MessageContent::SignBytes(SignBytesDescription {
+            sequence: sign_bytes.sequence,
+            timestamp: sign_bytes.timestamp,
+            diversifier: sign_bytes.diversifier.clone(),
+            path,
+        }),
+    };
+
     let sign_bytes = proto_encode(&sign_bytes)?;
     let signature = signer
-        .sign(request_id, Message::SignBytes(&sign_bytes))
+        .sign(request_id, Message::SignBytes(&sign_bytes), &description)
         .await?;
 
     let signature_data = SignatureData {
@@ -955,3 +1135,55 @@ pub struct TokenTransferPacketData {
     pub receiver: String,
     pub memo: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> TendermintHash {
+        TendermintHash::Sha256([byte; 32])
+    }
+
+    fn height() -> BlockHeight {
+        10u32.into()
+    }
+
+    #[test]
+    fn cross_check_accepts_header_verified_by_a_witness() {
+        let witnesses = [
+            ("http://witness-1", Err("unreachable".to_owned())),
+            ("http://witness-2", Ok(hash(1))),
+        ];
+
+        assert!(cross_check_header(height(), hash(1), witnesses, true).is_ok());
+    }
+
+    #[test]
+    fn cross_check_rejects_fork() {
+        let witnesses = [
+            ("http://witness-1", Ok(hash(1))),
+            ("http://witness-2", Ok(hash(2))),
+        ];
+
+        let err = cross_check_header(height(), hash(1), witnesses, false).unwrap_err();
+        assert!(err.to_string().contains("possible fork"));
+    }
+
+    #[test]
+    fn cross_check_fails_closed_without_witness() {
+        let err = cross_check_header(height(), hash(1), [], true).unwrap_err();
+        assert!(err.to_string().contains("no healthy witness found"));
+
+        let witnesses = [("http://witness-1", Err("lagging behind".to_owned()))];
+        let err = cross_check_header(height(), hash(1), witnesses, true).unwrap_err();
+        assert!(err.to_string().contains("lagging behind"));
+    }
+
+    #[test]
+    fn cross_check_does_not_require_witness_without_fallback_endpoints() {
+        assert!(cross_check_header(height(), hash(1), [], false).is_ok());
+
+        let witnesses = [("http://witness-1", Err("unreachable".to_owned()))];
+        assert!(cross_check_header(height(), hash(1), witnesses, false).is_ok());
+    }
+}
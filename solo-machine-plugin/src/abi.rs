@@ -0,0 +1,210 @@
+//! C ABI between solo machine and its plugins
+//!
+//! Every plugin exports a function named [`ABI_VERSION_SYMBOL`] returning the [`ABI_VERSION`] it was built with and a
+//! registration function ([`REGISTER_SIGNER_SYMBOL`] or [`REGISTER_HANDLER_SYMBOL`]). Solo machine refuses to load a
+//! plugin whose ABI version does not match its own before calling any other function of the plugin.
+//!
+//! All the functions return [`STATUS_OK`] on success and [`STATUS_ERROR`] on failure. Results (or UTF-8 error
+//! messages) are written to the [`FfiOutput`] passed by the caller, so, memory is always freed by the side which
+//! allocated it.
+use std::{convert::TryFrom, ffi::c_void, time::Duration};
+
+use anyhow::{anyhow, Error, Result};
+use solo_machine_core::event::{ErrorPolicy, HandlerOptions};
+
+/// Version of plugin ABI (incremented on every incompatible change to this module)
+pub const ABI_VERSION: u32 = 2;
+
+/// Name of the function returning ABI version of a plugin ([`AbiVersionFn`])
+pub const ABI_VERSION_SYMBOL: &str = "solo_machine_plugin_abi_version";
+/// Name of the function registering signers of a plugin ([`RegisterSignerFn`])
+pub const REGISTER_SIGNER_SYMBOL: &str = "solo_machine_register_signer";
+/// Name of the function registering event handlers of a plugin ([`RegisterHandlerFn`])
+pub const REGISTER_HANDLER_SYMBOL: &str = "solo_machine_register_handler";
+
+/// Status returned by a successful call
+pub const STATUS_OK: i32 = 0;
+/// Status returned by a failed call (error message is written to output)
+pub const STATUS_ERROR: i32 = 1;
+
+/// Message type of [`solo_machine_core::signer::Message::SignBytes`]
+pub const MESSAGE_TYPE_SIGN_BYTES: u32 = 0;
+/// Message type of [`solo_machine_core::signer::Message::SignDoc`]
+pub const MESSAGE_TYPE_SIGN_DOC: u32 = 1;
+
+/// Error policy [`solo_machine_core::event::ErrorPolicy::Retry`]
+pub const ERROR_POLICY_RETRY: u32 = 0;
+/// Error policy [`solo_machine_core::event::ErrorPolicy::Skip`]
+pub const ERROR_POLICY_SKIP: u32 = 1;
+/// Error policy [`solo_machine_core::event::ErrorPolicy::Halt`]
+pub const ERROR_POLICY_HALT: u32 = 2;
+
+/// Function returning ABI version of a plugin
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+/// Function registering signers of a plugin (error message is written to output)
+pub type RegisterSignerFn = unsafe extern "C" fn(FfiSignerRegistrar, FfiOutput) -> i32;
+/// Function registering event handlers of a plugin (error message is written to output)
+pub type RegisterHandlerFn = unsafe extern "C" fn(FfiHandlerRegistrar, FfiOutput) -> i32;
+
+/// Borrowed slice of bytes (valid only for the duration of a call)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiSlice {
+    /// Pointer to first byte (null for [`FfiSlice::none`])
+    pub ptr: *const u8,
+    /// Number of bytes
+    pub len: usize,
+}
+
+impl FfiSlice {
+    /// Creates a slice borrowing given bytes
+    pub fn new(bytes: &[u8]) -> Self {
+        Self {
+            ptr: bytes.as_ptr(),
+            len: bytes.len(),
+        }
+    }
+
+    /// Creates a null slice (used for optional values)
+    pub fn none() -> Self {
+        Self {
+            ptr: std::ptr::null(),
+            len: 0,
+        }
+    }
+
+    /// Returns borrowed bytes (`None` for a null slice)
+    ///
+    /// # Safety
+    ///
+    /// Slice should point to `len` valid bytes (or be null)
+    pub unsafe fn as_bytes<'a>(&self) -> Option<&'a [u8]> {
+        if self.ptr.is_null() {
+            None
+        } else {
+            Some(std::slice::from_raw_parts(self.ptr, self.len))
+        }
+    }
+}
+
+/// Output of a call provided by the caller (callee writes its result by calling `write` any number of times)
+#[repr(C)]
+pub struct FfiOutput {
+    /// Opaque context of caller
+    pub context: *mut c_void,
+    /// Appends bytes to output
+    pub write: unsafe extern "C" fn(context: *mut c_void, bytes: FfiSlice),
+}
+
+/// Signer exported by a plugin
+#[repr(C)]
+pub struct SignerVTable {
+    /// Opaque signer instance owned by plugin
+    pub instance: *const c_void,
+    /// Writes public key of signer (serialized as JSON) to output
+    pub public_key: unsafe extern "C" fn(instance: *const c_void, output: FfiOutput) -> i32,
+    /// Writes account prefix of signer to output
+    pub account_prefix: unsafe extern "C" fn(instance: *const c_void, output: FfiOutput) -> i32,
+    /// Writes account address of signer to output
+    pub account_address: unsafe extern "C" fn(instance: *const c_void, output: FfiOutput) -> i32,
+    /// Signs a message and writes signature to output (`request_id` is a null slice when there is no request ID and
+    /// `description` is the decoded description of message serialized as JSON)
+    pub sign: unsafe extern "C" fn(
+        instance: *const c_void,
+        request_id: FfiSlice,
+        message_type: u32,
+        message: FfiSlice,
+        description: FfiSlice,
+        output: FfiOutput,
+    ) -> i32,
+    /// Drops signer instance (no other function is called after this)
+    pub drop: unsafe extern "C" fn(instance: *const c_void),
+}
+
+/// Event handler exported by a plugin
+#[repr(C)]
+pub struct HandlerVTable {
+    /// Opaque event handler instance owned by plugin
+    pub instance: *const c_void,
+    /// Handles an event (serialized as JSON). Events which can't be deserialized by plugin (e.g. event types added to
+    /// solo machine after the plugin was built) are skipped with [`STATUS_OK`] and the reason is written to output, so
+    /// that they don't block the delivery of later events.
+    pub handle:
+        unsafe extern "C" fn(instance: *const c_void, event: FfiSlice, output: FfiOutput) -> i32,
+    /// Drops event handler instance (no other function is called after this)
+    pub drop: unsafe extern "C" fn(instance: *const c_void),
+}
+
+/// Options of an event handler
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiHandlerOptions {
+    /// Maximum number of events queued for event handler
+    pub queue_size: u64,
+    /// Action taken when event handler fails to handle an event (one of `ERROR_POLICY_*` constants)
+    pub error_policy: u32,
+    /// Maximum time (in milliseconds) event handler can take to handle a single event
+    pub timeout_millis: u64,
+    /// Maximum number of retries for an event
+    pub max_retries: u32,
+}
+
+impl From<HandlerOptions> for FfiHandlerOptions {
+    fn from(options: HandlerOptions) -> Self {
+        let error_policy = match options.error_policy {
+            ErrorPolicy::Retry => ERROR_POLICY_RETRY,
+            ErrorPolicy::Skip => ERROR_POLICY_SKIP,
+            ErrorPolicy::Halt => ERROR_POLICY_HALT,
+        };
+
+        Self {
+            queue_size: u64::try_from(options.queue_size).unwrap_or(u64::MAX),
+            error_policy,
+            timeout_millis: u64::try_from(options.timeout.as_millis()).unwrap_or(u64::MAX),
+            max_retries: options.max_retries,
+        }
+    }
+}
+
+impl TryFrom<FfiHandlerOptions> for HandlerOptions {
+    type Error = Error;
+
+    fn try_from(options: FfiHandlerOptions) -> Result<Self, Self::Error> {
+        let error_policy = match options.error_policy {
+            ERROR_POLICY_RETRY => ErrorPolicy::Retry,
+            ERROR_POLICY_SKIP => ErrorPolicy::Skip,
+            ERROR_POLICY_HALT => ErrorPolicy::Halt,
+            _ => return Err(anyhow!("invalid error policy: {}", options.error_policy)),
+        };
+
+        Ok(Self {
+            queue_size: usize::try_from(options.queue_size).unwrap_or(usize::MAX),
+            error_policy,
+            timeout: Duration::from_millis(options.timeout_millis),
+            max_retries: options.max_retries,
+        })
+    }
+}
+
+/// Registrar of signers provided by solo machine
+#[repr(C)]
+pub struct FfiSignerRegistrar {
+    /// Opaque context of solo machine
+    pub context: *mut c_void,
+    /// Registers a signer (ownership of signer instance is transferred to solo machine)
+    pub register: unsafe extern "C" fn(context: *mut c_void, signer: SignerVTable),
+}
+
+/// Registrar of event handlers provided by solo machine
+#[repr(C)]
+pub struct FfiHandlerRegistrar {
+    /// Opaque context of solo machine
+    pub context: *mut c_void,
+    /// Registers an event handler (ownership of event handler instance is transferred to solo machine). `options` is
+    /// null when event handler should use default options.
+    pub register: unsafe extern "C" fn(
+        context: *mut c_void,
+        handler: HandlerVTable,
+        options: *const FfiHandlerOptions,
+    ),
+}
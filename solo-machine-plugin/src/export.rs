@@ -0,0 +1,287 @@
+//! Plugin side of the ABI (used by [`export_signer`](crate::export_signer) and
+//! [`export_handler`](crate::export_handler) macros)
+use std::{
+    any::Any,
+    ffi::c_void,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use solo_machine_core::{
+    event::{EventHandler, HandlerOptions, HandlerRegistrar},
+    signer::{Message, MessageDescription, SignerRegistrar},
+    Event, Signer,
+};
+use tokio::runtime::{Builder, Runtime};
+
+use crate::abi::{
+    FfiHandlerOptions, FfiHandlerRegistrar, FfiOutput, FfiSignerRegistrar, FfiSlice, HandlerVTable,
+    SignerVTable, MESSAGE_TYPE_SIGN_BYTES, MESSAGE_TYPE_SIGN_DOC, STATUS_ERROR, STATUS_OK,
+};
+
+/// Exports a signer registration function (`fn(&mut dyn SignerRegistrar) -> anyhow::Result<()>`) of a plugin
+#[macro_export]
+macro_rules! export_signer {
+    ($register_fn:path) => {
+        /// Returns ABI version of plugin
+        #[no_mangle]
+        pub extern "C" fn solo_machine_plugin_abi_version() -> u32 {
+            $crate::abi::ABI_VERSION
+        }
+
+        /// Registers signers of plugin
+        ///
+        /// # Safety
+        ///
+        /// Should only be called by solo machine after checking ABI version of plugin
+        #[no_mangle]
+        pub unsafe extern "C" fn solo_machine_register_signer(
+            registrar: $crate::abi::FfiSignerRegistrar,
+            output: $crate::abi::FfiOutput,
+        ) -> i32 {
+            $crate::export::register_signer(registrar, output, $register_fn)
+        }
+    };
+}
+
+/// Exports an event handler registration function (`fn(&mut dyn HandlerRegistrar) -> anyhow::Result<()>`) of a
+/// plugin
+#[macro_export]
+macro_rules! export_handler {
+    ($register_fn:path) => {
+        /// Returns ABI version of plugin
+        #[no_mangle]
+        pub extern "C" fn solo_machine_plugin_abi_version() -> u32 {
+            $crate::abi::ABI_VERSION
+        }
+
+        /// Registers event handlers of plugin
+        ///
+        /// # Safety
+        ///
+        /// Should only be called by solo machine after checking ABI version of plugin
+        #[no_mangle]
+        pub unsafe extern "C" fn solo_machine_register_handler(
+            registrar: $crate::abi::FfiHandlerRegistrar,
+            output: $crate::abi::FfiOutput,
+        ) -> i32 {
+            $crate::export::register_handler(registrar, output, $register_fn)
+        }
+    };
+}
+
+/// Runtime used for executing async functions of signers and event handlers of plugin
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = match RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = Builder::new_multi_thread()
+                .enable_all()
+                .thread_name("solo-machine-plugin")
+                .build()
+                .context("unable to start runtime of plugin")?;
+
+            RUNTIME.get_or_init(|| runtime)
+        }
+    };
+
+    Ok(runtime.block_on(future))
+}
+
+/// Calls given function and writes its result (or error) to output. Panics are caught (unwinding across FFI boundary
+/// is undefined behaviour) and returned as errors.
+fn call(output: FfiOutput, f: impl FnOnce() -> Result<Vec<u8>>) -> i32 {
+    let result = catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|panic| Err(anyhow!("plugin panicked: {}", panic_message(&panic))));
+
+    let (status, bytes) = match result {
+        Ok(bytes) => (STATUS_OK, bytes),
+        Err(err) => (STATUS_ERROR, format!("{:#}", err).into_bytes()),
+    };
+
+    unsafe { (output.write)(output.context, FfiSlice::new(&bytes)) };
+
+    status
+}
+
+fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Calls registration function of a signer plugin
+///
+/// # Safety
+///
+/// Registrar and output should be valid for the duration of this call
+pub unsafe fn register_signer(
+    registrar: FfiSignerRegistrar,
+    output: FfiOutput,
+    register_fn: fn(&mut dyn SignerRegistrar) -> Result<()>,
+) -> i32 {
+    call(output, || {
+        register_fn(&mut PluginSignerRegistrar { registrar })?;
+        Ok(Vec::new())
+    })
+}
+
+/// Calls registration function of an event hook plugin
+///
+/// # Safety
+///
+/// Registrar and output should be valid for the duration of this call
+pub unsafe fn register_handler(
+    registrar: FfiHandlerRegistrar,
+    output: FfiOutput,
+    register_fn: fn(&mut dyn HandlerRegistrar) -> Result<()>,
+) -> i32 {
+    call(output, || {
+        register_fn(&mut PluginHandlerRegistrar { registrar })?;
+        Ok(Vec::new())
+    })
+}
+
+struct PluginSignerRegistrar {
+    registrar: FfiSignerRegistrar,
+}
+
+impl SignerRegistrar for PluginSignerRegistrar {
+    fn register(&mut self, signer: Arc<dyn Signer>) {
+        let vtable = SignerVTable {
+            instance: Box::into_raw(Box::new(signer)) as *const c_void,
+            public_key: signer_public_key,
+            account_prefix: signer_account_prefix,
+            account_address: signer_account_address,
+            sign: signer_sign,
+            drop: signer_drop,
+        };
+
+        unsafe { (self.registrar.register)(self.registrar.context, vtable) }
+    }
+}
+
+unsafe fn signer<'a>(instance: *const c_void) -> &'a Arc<dyn Signer> {
+    &*(instance as *const Arc<dyn Signer>)
+}
+
+unsafe extern "C" fn signer_public_key(instance: *const c_void, output: FfiOutput) -> i32 {
+    call(output, || {
+        let public_key = signer(instance).to_public_key()?;
+        serde_json::to_vec(&public_key).context("unable to serialize public key")
+    })
+}
+
+unsafe extern "C" fn signer_account_prefix(instance: *const c_void, output: FfiOutput) -> i32 {
+    call(output, || {
+        Ok(signer(instance).get_account_prefix().as_bytes().to_vec())
+    })
+}
+
+unsafe extern "C" fn signer_account_address(instance: *const c_void, output: FfiOutput) -> i32 {
+    call(output, || {
+        Ok(signer(instance).to_account_address()?.into_bytes())
+    })
+}
+
+unsafe extern "C" fn signer_sign(
+    instance: *const c_void,
+    request_id: FfiSlice,
+    message_type: u32,
+    message: FfiSlice,
+    description: FfiSlice,
+    output: FfiOutput,
+) -> i32 {
+    call(output, || {
+        let request_id = request_id
+            .as_bytes()
+            .map(std::str::from_utf8)
+            .transpose()
+            .context("invalid request ID")?;
+
+        let message_bytes = message.as_bytes().unwrap_or_default();
+
+        let message = match message_type {
+            MESSAGE_TYPE_SIGN_BYTES => Message::SignBytes(message_bytes),
+            MESSAGE_TYPE_SIGN_DOC => Message::SignDoc(message_bytes),
+            _ => bail!("unknown message type: {}", message_type),
+        };
+
+        let description: MessageDescription =
+            serde_json::from_slice(description.as_bytes().unwrap_or_default())
+                .context("unable to deserialize message description")?;
+
+        block_on(signer(instance).sign(request_id, message, &description))?
+    })
+}
+
+unsafe extern "C" fn signer_drop(instance: *const c_void) {
+    drop(Box::from_raw(instance as *mut Arc<dyn Signer>))
+}
+
+struct PluginHandlerRegistrar {
+    registrar: FfiHandlerRegistrar,
+}
+
+impl PluginHandlerRegistrar {
+    fn register_vtable(
+        &mut self,
+        handler: Box<dyn EventHandler>,
+        options: Option<FfiHandlerOptions>,
+    ) {
+        let vtable = HandlerVTable {
+            instance: Box::into_raw(Box::new(handler)) as *const c_void,
+            handle: handler_handle,
+            drop: handler_drop,
+        };
+
+        let options = options
+            .as_ref()
+            .map_or(std::ptr::null(), |options| options as *const _);
+
+        unsafe { (self.registrar.register)(self.registrar.context, vtable, options) }
+    }
+}
+
+impl HandlerRegistrar for PluginHandlerRegistrar {
+    fn register(&mut self, handler: Box<dyn EventHandler>) {
+        self.register_vtable(handler, None)
+    }
+
+    fn register_with_options(&mut self, handler: Box<dyn EventHandler>, options: HandlerOptions) {
+        self.register_vtable(handler, Some(options.into()))
+    }
+}
+
+unsafe extern "C" fn handler_handle(
+    instance: *const c_void,
+    event: FfiSlice,
+    output: FfiOutput,
+) -> i32 {
+    call(output, || {
+        let handler = &*(instance as *const Box<dyn EventHandler>);
+        let event: Event = match serde_json::from_slice(event.as_bytes().unwrap_or_default()) {
+            Ok(event) => event,
+            Err(err) => {
+                return Ok(
+                    format!("skipped event which is unknown to the plugin: {}", err).into_bytes(),
+                )
+            }
+        };
+
+        block_on(handler.handle(event))??;
+        Ok(Vec::new())
+    })
+}
+
+unsafe extern "C" fn handler_drop(instance: *const c_void) {
+    drop(Box::from_raw(instance as *mut Box<dyn EventHandler>))
+}
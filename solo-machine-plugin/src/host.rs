@@ -0,0 +1,356 @@
+//! Host side of the ABI (used by solo machine for loading plugins)
+use std::{
+    convert::TryFrom,
+    ffi::{c_void, OsStr},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use async_trait::async_trait;
+use libloading::{Library, Symbol};
+use solo_machine_core::{
+    cosmos::crypto::PublicKey,
+    event::{EventHandler, HandlerOptions, HandlerRegistrar},
+    signer::{Message, MessageDescription, SignerRegistrar, ToPublicKey},
+    Event, Signer,
+};
+
+use crate::abi::{
+    AbiVersionFn, FfiHandlerOptions, FfiHandlerRegistrar, FfiOutput, FfiSignerRegistrar, FfiSlice,
+    HandlerVTable, RegisterHandlerFn, RegisterSignerFn, SignerVTable, ABI_VERSION,
+    ABI_VERSION_SYMBOL, MESSAGE_TYPE_SIGN_BYTES, MESSAGE_TYPE_SIGN_DOC, REGISTER_HANDLER_SYMBOL,
+    REGISTER_SIGNER_SYMBOL, STATUS_ERROR, STATUS_OK,
+};
+
+/// A plugin (signer or event hook) loaded from a dynamic library
+pub struct Plugin {
+    library: Arc<Library>,
+}
+
+impl Plugin {
+    /// Loads a plugin from given file and checks that it is built for the same ABI version as solo machine
+    // TODO: remove conditional compilation when this issue is fixed:
+    // https://github.com/nagisa/rust_libloading/issues/41
+    pub fn load(file: impl AsRef<OsStr>) -> Result<Self> {
+        unsafe {
+            #[cfg(target_os = "linux")]
+            let library: Library = {
+                // Load library with `RTLD_NOW | RTLD_NODELETE` to fix a SIGSEGV
+                libloading::os::unix::Library::open(
+                    Some(file),
+                    libloading::os::unix::RTLD_NOW | 0x1000,
+                )
+                .context("unable to load plugin")?
+                .into()
+            };
+            #[cfg(not(target_os = "linux"))]
+            let library = Library::new(file).context("unable to load plugin")?;
+
+            let abi_version_fn: Symbol<AbiVersionFn> =
+                library.get(ABI_VERSION_SYMBOL.as_bytes()).context(
+                    "plugin is not built using `solo-machine-plugin` (ABI version not found)",
+                )?;
+
+            let abi_version = abi_version_fn();
+
+            ensure!(
+                abi_version == ABI_VERSION,
+                "plugin is built for ABI version {} but solo machine supports ABI version {} (rebuild the plugin \
+                 with a compatible version of `solo-machine-plugin`)",
+                abi_version,
+                ABI_VERSION
+            );
+
+            Ok(Self {
+                library: Arc::new(library),
+            })
+        }
+    }
+
+    /// Registers signers exported by plugin
+    pub fn register_signer(&self, registrar: &mut dyn SignerRegistrar) -> Result<()> {
+        let mut context = SignerContext {
+            registrar,
+            library: self.library.clone(),
+            error: None,
+        };
+
+        unsafe {
+            let register_fn: Symbol<RegisterSignerFn> = self
+                .library
+                .get(REGISTER_SIGNER_SYMBOL.as_bytes())
+                .with_context(|| {
+                    format!(
+                        "unable to load `{}` function from plugin",
+                        REGISTER_SIGNER_SYMBOL
+                    )
+                })?;
+
+            let ffi_registrar = FfiSignerRegistrar {
+                context: &mut context as *mut SignerContext<'_> as *mut c_void,
+                register: register_signer,
+            };
+
+            call(|output| register_fn(ffi_registrar, output))?;
+        }
+
+        context.error.map_or(Ok(()), Err)
+    }
+
+    /// Registers event handlers exported by plugin
+    pub fn register_handlers(&self, registrar: &mut dyn HandlerRegistrar) -> Result<()> {
+        let mut context = HandlerContext {
+            registrar,
+            library: self.library.clone(),
+            error: None,
+        };
+
+        unsafe {
+            let register_fn: Symbol<RegisterHandlerFn> = self
+                .library
+                .get(REGISTER_HANDLER_SYMBOL.as_bytes())
+                .with_context(|| {
+                    format!(
+                        "unable to load `{}` function from plugin",
+                        REGISTER_HANDLER_SYMBOL
+                    )
+                })?;
+
+            let ffi_registrar = FfiHandlerRegistrar {
+                context: &mut context as *mut HandlerContext<'_> as *mut c_void,
+                register: register_handler,
+            };
+
+            call(|output| register_fn(ffi_registrar, output))?;
+        }
+
+        context.error.map_or(Ok(()), Err)
+    }
+}
+
+/// Calls a function of plugin and returns the bytes written to output (or the error message written by plugin)
+fn call(f: impl FnOnce(FfiOutput) -> i32) -> Result<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let status = f(FfiOutput {
+        context: &mut buffer as *mut Vec<u8> as *mut c_void,
+        write: write_output,
+    });
+
+    match status {
+        STATUS_OK => Ok(buffer),
+        STATUS_ERROR => Err(anyhow!(String::from_utf8_lossy(&buffer).into_owned())),
+        _ => Err(anyhow!("plugin returned invalid status: {}", status)),
+    }
+}
+
+unsafe extern "C" fn write_output(context: *mut c_void, bytes: FfiSlice) {
+    let buffer = &mut *(context as *mut Vec<u8>);
+    buffer.extend_from_slice(bytes.as_bytes().unwrap_or_default());
+}
+
+struct SignerContext<'a> {
+    registrar: &'a mut dyn SignerRegistrar,
+    library: Arc<Library>,
+    /// First error encountered while registering signers
+    error: Option<Error>,
+}
+
+unsafe extern "C" fn register_signer(context: *mut c_void, vtable: SignerVTable) {
+    let context = &mut *(context as *mut SignerContext<'_>);
+
+    let instance = Arc::new(SignerInstance {
+        vtable,
+        _library: context.library.clone(),
+    });
+
+    match PluginSigner::new(instance) {
+        Ok(signer) => context.registrar.register(Arc::new(signer)),
+        Err(err) => {
+            context.error.get_or_insert(err);
+        }
+    }
+}
+
+struct HandlerContext<'a> {
+    registrar: &'a mut dyn HandlerRegistrar,
+    library: Arc<Library>,
+    /// First error encountered while registering event handlers
+    error: Option<Error>,
+}
+
+unsafe extern "C" fn register_handler(
+    context: *mut c_void,
+    vtable: HandlerVTable,
+    options: *const FfiHandlerOptions,
+) {
+    let context = &mut *(context as *mut HandlerContext<'_>);
+
+    let handler = Box::new(PluginHandler {
+        instance: Arc::new(HandlerInstance {
+            vtable,
+            _library: context.library.clone(),
+        }),
+    });
+
+    if options.is_null() {
+        return context.registrar.register(handler);
+    }
+
+    match HandlerOptions::try_from(*options) {
+        Ok(options) => context.registrar.register_with_options(handler, options),
+        Err(err) => {
+            context
+                .error
+                .get_or_insert(err.context("invalid options of event handler"));
+        }
+    }
+}
+
+/// Signer instance owned by plugin (dropped using plugin's drop function)
+struct SignerInstance {
+    vtable: SignerVTable,
+    /// Library is kept loaded as long as there are instances owned by it
+    _library: Arc<Library>,
+}
+
+// Signers are `Send + Sync` on plugin side
+unsafe impl Send for SignerInstance {}
+unsafe impl Sync for SignerInstance {}
+
+impl Drop for SignerInstance {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.drop)(self.vtable.instance) }
+    }
+}
+
+/// Signer exported by a plugin
+struct PluginSigner {
+    instance: Arc<SignerInstance>,
+    account_prefix: String,
+}
+
+impl PluginSigner {
+    fn new(instance: Arc<SignerInstance>) -> Result<Self> {
+        let account_prefix = call(|output| unsafe {
+            (instance.vtable.account_prefix)(instance.vtable.instance, output)
+        })
+        .and_then(|bytes| String::from_utf8(bytes).map_err(Into::into))
+        .context("unable to get account prefix from signer")?;
+
+        Ok(Self {
+            instance,
+            account_prefix,
+        })
+    }
+}
+
+impl ToPublicKey for PluginSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        let bytes = call(|output| unsafe {
+            (self.instance.vtable.public_key)(self.instance.vtable.instance, output)
+        })?;
+
+        serde_json::from_slice(&bytes).context("invalid public key returned by signer")
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        let bytes = call(|output| unsafe {
+            (self.instance.vtable.account_address)(self.instance.vtable.instance, output)
+        })?;
+
+        String::from_utf8(bytes).context("invalid account address returned by signer")
+    }
+}
+
+#[async_trait]
+impl Signer for PluginSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        let instance = self.instance.clone();
+        let request_id = request_id.map(ToOwned::to_owned);
+        let description =
+            serde_json::to_vec(description).context("unable to serialize message description")?;
+
+        let message_type = match message {
+            Message::SignBytes(_) => MESSAGE_TYPE_SIGN_BYTES,
+            Message::SignDoc(_) => MESSAGE_TYPE_SIGN_DOC,
+        };
+        let message = message.as_ref().to_vec();
+
+        // Plugins block the calling thread while executing async functions on their own runtime
+        tokio::task::spawn_blocking(move || {
+            let request_id = request_id
+                .as_deref()
+                .map_or(FfiSlice::none(), |request_id| {
+                    FfiSlice::new(request_id.as_bytes())
+                });
+
+            call(|output| unsafe {
+                (instance.vtable.sign)(
+                    instance.vtable.instance,
+                    request_id,
+                    message_type,
+                    FfiSlice::new(&message),
+                    FfiSlice::new(&description),
+                    output,
+                )
+            })
+        })
+        .await
+        .context("unable to join signer task")?
+    }
+}
+
+/// Event handler instance owned by plugin (dropped using plugin's drop function)
+struct HandlerInstance {
+    vtable: HandlerVTable,
+    /// Library is kept loaded as long as there are instances owned by it
+    _library: Arc<Library>,
+}
+
+// Event handlers are `Send + Sync` on plugin side
+unsafe impl Send for HandlerInstance {}
+unsafe impl Sync for HandlerInstance {}
+
+impl Drop for HandlerInstance {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.drop)(self.vtable.instance) }
+    }
+}
+
+/// Event handler exported by a plugin
+struct PluginHandler {
+    instance: Arc<HandlerInstance>,
+}
+
+#[async_trait]
+impl EventHandler for PluginHandler {
+    async fn handle(&self, event: Event) -> Result<()> {
+        let instance = self.instance.clone();
+        let event = serde_json::to_vec(&event).context("unable to serialize event")?;
+
+        // Plugins block the calling thread while executing async functions on their own runtime
+        let skipped = tokio::task::spawn_blocking(move || {
+            call(|output| unsafe {
+                (instance.vtable.handle)(instance.vtable.instance, FfiSlice::new(&event), output)
+            })
+        })
+        .await
+        .context("unable to join event handler task")??;
+
+        if !skipped.is_empty() {
+            log::warn!("{}", String::from_utf8_lossy(&skipped));
+        }
+
+        Ok(())
+    }
+}
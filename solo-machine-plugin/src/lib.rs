@@ -0,0 +1,37 @@
+#![deny(missing_docs)]
+//! # Solo machine plugins
+//!
+//! Signers and event hooks are loaded by solo machine at runtime from dynamic libraries. Rust trait objects do not
+//! have a stable ABI, so, solo machine and its plugins communicate using a versioned C ABI (see [`abi`]) in which
+//! events and public keys are exchanged as JSON and signatures as raw bytes. This makes it possible to load plugins
+//! built with a different compiler (or different versions of dependencies) than solo machine.
+//!
+//! Plugin authors don't have to deal with the C ABI directly. A plugin implements `Signer` or `EventHandler` traits
+//! of `solo-machine-core` as usual and exports its registration function using [`export_signer`] or
+//! [`export_handler`] macros. Note that a plugin should be compiled as a `cdylib`:
+//!
+//! ```toml
+//! [lib]
+//! crate-type = ["cdylib"]
+//! ```
+//!
+//! For example,
+//!
+//! ```ignore
+//! use solo_machine_core::event::HandlerRegistrar;
+//!
+//! fn register_handler(registrar: &mut dyn HandlerRegistrar) -> anyhow::Result<()> {
+//!     registrar.register(Box::new(MyEventHandler::new()));
+//!     Ok(())
+//! }
+//!
+//! solo_machine_plugin::export_handler!(register_handler);
+//! ```
+//!
+//! Async functions of signers and event handlers are executed on a runtime owned by the plugin, so, plugins can use
+//! `tokio` without sharing a runtime with solo machine.
+pub mod abi;
+#[doc(hidden)]
+pub mod export;
+#[cfg(feature = "host")]
+pub mod host;
@@ -1,4 +1,5 @@
 use std::{
+    env,
     error::Error,
     fs::{read_dir, DirEntry},
     path::PathBuf,
@@ -18,9 +19,16 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut config = Config::default();
     config.protoc_arg("--experimental_allow_proto3_optional");
 
+    let descriptor_path = PathBuf::from(env::var("OUT_DIR")?).join("solo_machine_descriptor.bin");
+
     tonic_build::configure()
         .build_client(false)
-        .compile_protos_with_config(config, &files, &["proto"])?;
+        .file_descriptor_set_path(descriptor_path)
+        .extern_path(
+            ".solo_machine.signer.v1",
+            "::solo_machine_core::proto::solo_machine::signer::v1",
+        )
+        .compile_protos_with_config(config, &files, &["proto", "../solo-machine-core/proto"])?;
 
     Ok(())
 }
@@ -1,29 +1,41 @@
 mod chain;
+mod events;
 mod ibc;
+mod policy;
 
 use std::{
-    convert::TryFrom,
     fmt::Display,
     io::{stdout, Write},
     net::SocketAddr,
     path::PathBuf,
+    sync::Arc,
 };
 
 use anyhow::{ensure, Context, Result};
 use cli_table::{Cell, Row, RowStruct, Style};
 use serde_json::json;
-use solo_machine_core::{connect_db, event::HandlerRegistrar as _, init_db, run_migrations};
+use solo_machine_core::{
+    connect_db,
+    event::{ErrorPolicy, HandlerOptions},
+    init_db, run_migrations,
+    service::IbcService,
+    Signer,
+};
 use structopt::{clap::Shell, StructOpt};
 use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{
-    event::{cli_event_handler::CliEventHandler, env_logger::EnvLogger, HandlerRegistrar},
+    event::{
+        broadcaster::EventBroadcaster, cli_event_handler::CliEventHandler, env_logger::EnvLogger,
+        metrics_collector::MetricsCollector, HandlerRegistrar,
+    },
+    metrics::{Metrics, MetricsSigner},
     output::OutputType,
-    server::start_grpc,
-    signer::SignerRegistrar,
+    server::{start_grpc, ApiKeys, GrpcConfig, MetricsConfig, TlsConfig},
+    signer::SignerOptions,
 };
 
-use self::{chain::ChainCommand, ibc::IbcCommand};
+use self::{chain::ChainCommand, events::EventsCommand, ibc::IbcCommand, policy::PolicyCommand};
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -45,14 +57,24 @@ pub struct Command {
     )]
     #[structopt(long, env = "SOLO_DB_URI", hide_env_values = true)]
     db_uri: Option<String>,
-    /// Register a signer (path to signer's `*.so` file)
-    #[structopt(long, env = "SOLO_SIGNER", hide_env_values = true)]
-    signer: Option<PathBuf>,
-    /// Register an event handler. Multiple event handlers can be registered and they're executed in order they're
-    /// provided in CLI. Also, if an event handler returns an error when handling a message, all the future event
-    /// handlers will not get executed.
+    #[structopt(flatten)]
+    signer: SignerOptions,
+    /// Register an event handler. Multiple event handlers can be registered and each of them runs independently of
+    /// others. Events are delivered to event handlers from event outbox (at-least-once) and failed deliveries are
+    /// retried without blocking other event handlers (`events` subcommand can be used to inspect and replay events).
     #[structopt(long)]
     handler: Vec<PathBuf>,
+    /// Maximum number of events queued for each event handler
+    #[structopt(long, env = "SOLO_HANDLER_QUEUE_SIZE", default_value = "1024")]
+    handler_queue_size: usize,
+    /// Maximum time an event handler can take to handle a single event
+    #[structopt(long, env = "SOLO_HANDLER_TIMEOUT", default_value = "30s")]
+    handler_timeout: humantime::Duration,
+    /// Action taken when an event handler fails to handle an event (`retry`: retry with exponential backoff, `skip`:
+    /// log the error and move on to next event, `halt`: stop delivering events to the event handler). Event hooks can
+    /// override this for their own event handlers.
+    #[structopt(long, env = "SOLO_HANDLER_ERROR_POLICY", default_value = "retry", possible_values = &["retry", "skip", "halt"])]
+    handler_error_policy: ErrorPolicy,
     #[structopt(subcommand)]
     subcommand: SubCommand,
     /// Output format
@@ -65,6 +87,8 @@ pub struct Command {
 pub enum SubCommand {
     /// Chain operations (managing chain state and metadata)
     Chain(ChainSubCommand),
+    /// Event outbox operations (inspecting and replaying events delivered to event handlers)
+    Events(EventsSubCommand),
     /// Generate completion scripts for solo-machine-cli
     GenCompletion {
         #[structopt(long, default_value = "bash")]
@@ -74,11 +98,50 @@ pub enum SubCommand {
     Ibc(IbcSubCommand),
     /// Initializes database for solo machine
     Init,
+    /// Mint policy operations (denom allowlists and mint limits)
+    Policy(PolicySubCommand),
     /// Starts gRPC server for solo machine
     Start {
         /// gRPC server address
         #[structopt(short, long, env = "SOLO_GRPC_ADDR", default_value = "0.0.0.0:9000")]
         addr: SocketAddr,
+        /// Path to PEM encoded TLS certificate of gRPC server (enables TLS)
+        #[structopt(long, env = "SOLO_GRPC_TLS_CERT", requires = "tls-key")]
+        tls_cert: Option<PathBuf>,
+        /// Path to PEM encoded TLS private key of gRPC server
+        #[structopt(
+            long,
+            env = "SOLO_GRPC_TLS_KEY",
+            hide_env_values = true,
+            requires = "tls-cert"
+        )]
+        tls_key: Option<PathBuf>,
+        /// Path to PEM encoded CA certificate used to verify client certificates (enables mutual TLS)
+        #[structopt(long, env = "SOLO_GRPC_TLS_CLIENT_CA", requires = "tls-cert")]
+        tls_client_ca: Option<PathBuf>,
+        /// Path to JSON file containing API keys and the gRPC methods each key is allowed to call (enables API key
+        /// authentication, e.g. `[{ "name": "reader", "key": "<secret>", "methods": ["ibc.Ibc/QueryHistory"] }]`)
+        #[structopt(long, env = "SOLO_GRPC_API_KEYS", hide_env_values = true)]
+        api_keys: Option<PathBuf>,
+        /// Interval between health checks of database and configured chains (reported by `grpc.health.v1.Health`)
+        #[structopt(long, env = "SOLO_HEALTH_CHECK_INTERVAL", default_value = "30s")]
+        health_check_interval: humantime::Duration,
+        /// Maximum time gRPC server takes to respond to a request. Synchronous `Mint`, `Burn` and
+        /// `UpdateSigner` requests broadcast transactions before responding, so, this should be greater than the time
+        /// taken to broadcast and commit their transactions (see `--rpc-timeout`)
+        #[structopt(long, env = "SOLO_GRPC_TIMEOUT", default_value = "5m")]
+        grpc_timeout: humantime::Duration,
+        /// Address to serve HTTP/JSON REST gateway on (REST gateway is disabled if not provided). REST gateway uses
+        /// the same API keys and TLS configuration as gRPC server.
+        #[structopt(long, env = "SOLO_REST_ADDR")]
+        rest_addr: Option<SocketAddr>,
+        /// Address to serve Prometheus metrics on (`/metrics` endpoint is disabled if not provided)
+        #[structopt(long, env = "SOLO_METRICS_ADDR")]
+        metrics_addr: Option<SocketAddr>,
+        /// Interval between refreshes of metrics fetched from database and configured chains (sequences, tendermint
+        /// client age and fee account balance)
+        #[structopt(long, env = "SOLO_METRICS_INTERVAL", default_value = "30s")]
+        metrics_interval: humantime::Duration,
     },
 }
 
@@ -88,12 +151,24 @@ pub struct ChainSubCommand {
     subcommand: ChainCommand,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct EventsSubCommand {
+    #[structopt(subcommand)]
+    subcommand: EventsCommand,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct IbcSubCommand {
     #[structopt(subcommand)]
     subcommand: IbcCommand,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct PolicySubCommand {
+    #[structopt(subcommand)]
+    subcommand: PolicyCommand,
+}
+
 impl Command {
     pub async fn execute(self) -> Result<()> {
         let color_choice = if self.no_style {
@@ -124,22 +199,31 @@ impl Command {
     }
 
     async fn run(self, color_choice: ColorChoice) -> Result<()> {
+        let handler_options = HandlerOptions {
+            queue_size: self.handler_queue_size,
+            error_policy: self.handler_error_policy,
+            timeout: self.handler_timeout.into(),
+            ..Default::default()
+        };
+
         match self.subcommand {
             SubCommand::Chain(chain) => {
                 ensure!(
-                    self.signer.is_some(),
-                    "`signer` is required for chain commands"
+                    self.signer.is_configured(),
+                    "`signer` or `remote-signer` is required for chain commands"
                 );
                 ensure!(self.db_uri.is_some(), "`db-uri` is required");
 
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
 
-                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar
-                    .register(Box::new(CliEventHandler::new(color_choice, self.output)));
-                let (sender, handle) = handler_registrar.spawn();
+                let mut handler_registrar = HandlerRegistrar::new(self.handler, handler_options)?;
+                handler_registrar.register_builtin(
+                    "cli",
+                    Box::new(CliEventHandler::new(color_choice, self.output)),
+                );
+                let (sender, handle) = handler_registrar.spawn(db_pool.clone()).await?;
 
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                let signer = self.signer.load().await?;
 
                 chain
                     .subcommand
@@ -150,29 +234,57 @@ impl Command {
                     .await
                     .context("unable to join event hook registrar task")?
             }
+            SubCommand::Events(events) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                events
+                    .subcommand
+                    .execute(db_pool, color_choice, self.output)
+                    .await
+            }
             SubCommand::GenCompletion { shell } => {
                 Self::clap().gen_completions_to("solo-machine", shell, &mut stdout());
                 Ok(())
             }
             SubCommand::Ibc(ibc) => {
+                // Offline signing sessions only need public key of offline signer
+                let offline = matches!(ibc.subcommand, IbcCommand::Offline(_));
+
                 ensure!(
-                    self.signer.is_some(),
-                    "`signer` is required for ibc commands"
+                    offline || self.signer.is_configured(),
+                    "`signer` or `remote-signer` is required for ibc commands"
                 );
                 ensure!(self.db_uri.is_some(), "`db-uri` is required");
 
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
 
-                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar
-                    .register(Box::new(CliEventHandler::new(color_choice, self.output)));
-                let (sender, handle) = handler_registrar.spawn();
-
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                let mut handler_registrar = HandlerRegistrar::new(self.handler, handler_options)?;
+                handler_registrar.register_builtin(
+                    "cli",
+                    Box::new(CliEventHandler::new(color_choice, self.output)),
+                );
+                let (sender, handle) = handler_registrar.spawn(db_pool.clone()).await?;
+
+                match ibc.subcommand {
+                    IbcCommand::Offline(command) => {
+                        command
+                            .execute(
+                                &IbcService::new_with_notifier(db_pool, sender),
+                                color_choice,
+                                self.output,
+                            )
+                            .await?
+                    }
+                    subcommand => {
+                        let signer = self.signer.load().await?;
 
-                ibc.subcommand
-                    .execute(db_pool, signer, sender, color_choice, self.output)
-                    .await?;
+                        subcommand
+                            .execute(db_pool, signer, sender, color_choice, self.output)
+                            .await?
+                    }
+                }
 
                 handle
                     .await
@@ -206,21 +318,89 @@ impl Command {
                     ),
                 }
             }
-            SubCommand::Start { addr } => {
+            SubCommand::Policy(policy) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                policy
+                    .subcommand
+                    .execute(db_pool, color_choice, self.output)
+                    .await
+            }
+            SubCommand::Start {
+                addr,
+                tls_cert,
+                tls_key,
+                tls_client_ca,
+                api_keys,
+                health_check_interval,
+                grpc_timeout,
+                rest_addr,
+                metrics_addr,
+                metrics_interval,
+            } => {
                 ensure!(
-                    self.signer.is_some(),
-                    "`signer` is required for gRPC server"
+                    self.signer.is_configured(),
+                    "`signer` or `remote-signer` is required for gRPC server"
                 );
                 ensure!(self.db_uri.is_some(), "`db-uri` is required");
 
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
-                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar.register(Box::new(EnvLogger::new()));
-                let (sender, handle) = handler_registrar.spawn();
+                let mut handler_registrar = HandlerRegistrar::new(self.handler, handler_options)?;
+                handler_registrar.register_builtin("env-logger", Box::new(EnvLogger::new()));
+
+                let broadcaster = EventBroadcaster::new();
+                handler_registrar.register_builtin("broadcaster", Box::new(broadcaster.clone()));
+
+                let metrics = metrics_addr.map(|_| Arc::new(Metrics::new()));
+
+                if let Some(ref metrics) = metrics {
+                    handler_registrar.register_builtin(
+                        "metrics",
+                        Box::new(MetricsCollector::new(metrics.clone())),
+                    );
+                }
+
+                let (sender, handle) = handler_registrar.spawn(db_pool.clone()).await?;
 
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                let mut signer = self.signer.load().await?;
+
+                if let Some(ref metrics) = metrics {
+                    signer =
+                        Arc::new(MetricsSigner::new(signer, metrics.clone())) as Arc<dyn Signer>;
+                }
+
+                let tls_config = match (tls_cert, tls_key) {
+                    (Some(cert), Some(key)) => Some(TlsConfig {
+                        cert,
+                        key,
+                        client_ca: tls_client_ca,
+                    }),
+                    _ => None,
+                };
+                let api_keys = match api_keys {
+                    Some(path) => Some(ApiKeys::from_file(&path).await?),
+                    None => None,
+                };
+
+                let config = GrpcConfig {
+                    addr,
+                    tls_config,
+                    api_keys,
+                    health_check_interval: health_check_interval.into(),
+                    request_timeout: grpc_timeout.into(),
+                    rest_addr,
+                    metrics_config: metrics_addr.zip(metrics).map(|(addr, metrics)| {
+                        MetricsConfig {
+                            addr,
+                            refresh_interval: metrics_interval.into(),
+                            metrics,
+                        }
+                    }),
+                };
 
-                start_grpc(db_pool, signer, sender, addr).await?;
+                start_grpc(db_pool, signer, sender, broadcaster, config).await?;
 
                 handle
                     .await
@@ -5,11 +5,14 @@ use cli_table::{format::Justify, print_stdout, Cell, Row, RowStruct, Style, Tabl
 use humantime::format_duration;
 use num_rational::Ratio;
 use rust_decimal::Decimal;
-use serde_json::json;
+use serde_json::{json, Value};
 use solo_machine_core::{
     ibc::core::ics24_host::identifier::{ChainId, Identifier, PortId},
-    model::{ChainConfig, ChainKey, Fee},
-    service::ChainService,
+    model::{
+        Chain, ChainConfig, ChainConfigChange, ChainConfigUpdate, ChainKey, ConnectionStatus,
+        EndpointHealth, Fee,
+    },
+    service::{ChainService, ReconciliationService, SupplyReport},
     DbPool, Event, ToPublicKey,
 };
 use structopt::StructOpt;
@@ -17,7 +20,10 @@ use tendermint::block::Height as BlockHeight;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{command::add_row, output::OutputType};
+use crate::{
+    command::add_row,
+    output::{chains_json, success_json, OutputType},
+};
 
 use super::print_json;
 
@@ -41,6 +47,22 @@ pub enum ChainCommand {
             hide_env_values = true
         )]
         rpc_addr: String,
+        /// Fallback gRPC addresses of IBC enabled chain (comma separated)
+        #[structopt(
+            long,
+            env = "SOLO_FALLBACK_GRPC_ADDRESSES",
+            hide_env_values = true,
+            use_delimiter = true
+        )]
+        fallback_grpc_addrs: Vec<String>,
+        /// Fallback RPC addresses of IBC enabled chain (comma separated, also used as light client witnesses)
+        #[structopt(
+            long,
+            env = "SOLO_FALLBACK_RPC_ADDRESSES",
+            hide_env_values = true,
+            use_delimiter = true
+        )]
+        fallback_rpc_addrs: Vec<String>,
         /// Fee amount
         #[structopt(
             long,
@@ -123,6 +145,81 @@ pub enum ChainCommand {
         #[structopt(long, env = "SOLO_TRUSTED_HASH", hide_env_values = true, parse(try_from_str = parse_trusted_hash))]
         trusted_hash: [u8; 32],
     },
+    /// Updates metadata of an IBC enabled chain (only the provided values are changed)
+    Update {
+        chain_id: ChainId,
+        /// gRPC address of IBC enabled chain
+        #[structopt(long)]
+        grpc_addr: Option<String>,
+        /// RPC address of IBC enabled chain
+        #[structopt(long)]
+        rpc_addr: Option<String>,
+        /// Fallback gRPC addresses of IBC enabled chain (comma separated, pass without values to remove all)
+        #[structopt(long, use_delimiter = true, min_values = 0)]
+        fallback_grpc_addrs: Option<Vec<String>>,
+        /// Fallback RPC addresses of IBC enabled chain (comma separated, pass without values to remove all)
+        #[structopt(long, use_delimiter = true, min_values = 0)]
+        fallback_rpc_addrs: Option<Vec<String>>,
+        /// Fee amount
+        #[structopt(long)]
+        fee_amount: Option<Decimal>,
+        /// Fee denom
+        #[structopt(long)]
+        fee_denom: Option<Identifier>,
+        /// Gas limit
+        #[structopt(long)]
+        gas_limit: Option<u64>,
+        /// Trust level (e.g. 1/3)
+        #[structopt(long)]
+        trust_level: Option<Ratio<u64>>,
+        /// Trusting period
+        #[structopt(long, parse(try_from_str = humantime::parse_duration))]
+        trusting_period: Option<Duration>,
+        /// Maximum clock drift
+        #[structopt(long, parse(try_from_str = humantime::parse_duration))]
+        max_clock_drift: Option<Duration>,
+        /// RPC timeout duration
+        #[structopt(long, parse(try_from_str = humantime::parse_duration))]
+        rpc_timeout: Option<Duration>,
+        /// Diversifier used in transactions for chain (only before establishing connection)
+        #[structopt(long)]
+        diversifier: Option<String>,
+        /// Port ID used to create connection with chain (only before establishing connection)
+        #[structopt(long)]
+        port_id: Option<PortId>,
+        /// Trusted height of the chain (only before establishing connection)
+        #[structopt(long)]
+        trusted_height: Option<BlockHeight>,
+        /// Block hash at trusted height of the chain (only before establishing connection)
+        #[structopt(long, parse(try_from_str = parse_trusted_hash))]
+        trusted_hash: Option<[u8; 32]>,
+        /// Allows the RPC endpoint to report a different node ID than the one stored for the chain
+        #[structopt(long)]
+        allow_node_change: bool,
+    },
+    /// Checks health of all the RPC and gRPC endpoints (primary and fallback) of an IBC enabled chain
+    Endpoints { chain_id: ChainId },
+    /// Fetches audit trail of changes in metadata of an IBC enabled chain
+    ConfigHistory {
+        chain_id: ChainId,
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Lists all the IBC enabled chains along with their connection status
+    List {
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Moves an IBC enabled chain (along with its IBC data, public keys and operations) to archive. Channel with the
+    /// chain should be closed before archiving it.
+    Archive { chain_id: ChainId },
+    /// Permanently removes an IBC enabled chain (along with its IBC data, public keys and operations). Channel with the
+    /// chain should be closed before removing it.
+    Remove { chain_id: ChainId },
     /// Fetches current state and metadata for an IBC enabled chain
     Get { chain_id: ChainId },
     /// Fetches all the public keys associated with solo machine client on given chain
@@ -143,6 +240,14 @@ pub enum ChainCommand {
         chain_id: ChainId,
         denom: Identifier,
     },
+    /// Compares total minted minus total burnt tokens of each denom with total supply of its IBC denom on IBC enabled
+    /// chain (operations left pending by interrupted processes for more than an hour are marked as failed first)
+    Reconcile {
+        chain_id: ChainId,
+        /// Exit with an error if supply of any denom does not match
+        #[structopt(long)]
+        strict: bool,
+    },
 }
 
 impl ChainCommand {
@@ -154,12 +259,14 @@ impl ChainCommand {
         color_choice: ColorChoice,
         output: OutputType,
     ) -> Result<()> {
-        let chain_service = ChainService::new_with_notifier(db_pool, sender);
+        let chain_service = ChainService::new_with_notifier(db_pool.clone(), sender.clone());
 
         match self {
             Self::Add {
                 grpc_addr,
                 rpc_addr,
+                fallback_grpc_addrs,
+                fallback_rpc_addrs,
                 fee_amount,
                 fee_denom,
                 gas_limit,
@@ -175,6 +282,8 @@ impl ChainCommand {
                 let config = ChainConfig {
                     grpc_addr,
                     rpc_addr,
+                    fallback_grpc_addrs,
+                    fallback_rpc_addrs,
                     fee: Fee {
                         amount: fee_amount,
                         denom: fee_denom,
@@ -195,6 +304,148 @@ impl ChainCommand {
                     .await
                     .map(|_| ())
             }
+            Self::Update {
+                chain_id,
+                grpc_addr,
+                rpc_addr,
+                fallback_grpc_addrs,
+                fallback_rpc_addrs,
+                fee_amount,
+                fee_denom,
+                gas_limit,
+                trust_level,
+                trusting_period,
+                max_clock_drift,
+                rpc_timeout,
+                diversifier,
+                port_id,
+                trusted_height,
+                trusted_hash,
+                allow_node_change,
+            } => {
+                let update = ChainConfigUpdate {
+                    grpc_addr,
+                    rpc_addr,
+                    fallback_grpc_addrs,
+                    fallback_rpc_addrs,
+                    fee_amount,
+                    fee_denom,
+                    gas_limit,
+                    trust_level,
+                    trusting_period,
+                    max_clock_drift,
+                    rpc_timeout,
+                    diversifier,
+                    port_id,
+                    trusted_height,
+                    trusted_hash,
+                };
+
+                chain_service
+                    .update_config(&chain_id, update, allow_node_change)
+                    .await
+                    .map(|_| ())
+            }
+            Self::Endpoints { ref chain_id } => {
+                let endpoints = chain_service.check_endpoints(chain_id).await?;
+
+                match output {
+                    OutputType::Text => {
+                        let table = endpoints
+                            .into_iter()
+                            .map(into_endpoint_row)
+                            .collect::<Vec<RowStruct>>()
+                            .table()
+                            .title(vec![
+                                "Type".cell().bold(true),
+                                "Address".cell().bold(true),
+                                "Primary".cell().bold(true),
+                                "Status".cell().bold(true),
+                                "Node ID".cell().bold(true),
+                                "Latest height".cell().bold(true),
+                                "Error".cell().bold(true),
+                            ])
+                            .color_choice(color_choice);
+
+                        print_stdout(table).context("unable to print table to stdout")
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": endpoints
+                        }),
+                    ),
+                }
+            }
+            Self::ConfigHistory {
+                ref chain_id,
+                limit,
+                offset,
+            } => {
+                let limit = i32::try_from(limit).or(Err(anyhow!("invalid `limit`")))?;
+                let offset = i32::try_from(offset).or(Err(anyhow!("invalid `offset`")))?;
+
+                let changes = chain_service
+                    .get_config_changes(chain_id, limit, offset)
+                    .await?;
+
+                match output {
+                    OutputType::Text => {
+                        let table = changes
+                            .into_iter()
+                            .map(into_config_change_row)
+                            .collect::<Result<Vec<RowStruct>>>()?
+                            .table()
+                            .title(vec![
+                                "ID".cell().bold(true),
+                                "Chain ID".cell().bold(true),
+                                "Changes".cell().bold(true),
+                                "Created at".cell().bold(true),
+                            ])
+                            .color_choice(color_choice);
+
+                        print_stdout(table).context("unable to print table to stdout")
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": changes
+                        }),
+                    ),
+                }
+            }
+            Self::List { limit, offset } => {
+                let limit = i32::try_from(limit).or(Err(anyhow!("invalid `limit`")))?;
+                let offset = i32::try_from(offset).or(Err(anyhow!("invalid `offset`")))?;
+
+                let chains = chain_service.list(limit, offset).await?;
+
+                match output {
+                    OutputType::Text => {
+                        let table = chains
+                            .into_iter()
+                            .map(into_chain_row)
+                            .collect::<Vec<RowStruct>>()
+                            .table()
+                            .title(vec![
+                                "Chain ID".cell().bold(true),
+                                "Node ID".cell().bold(true),
+                                "RPC address".cell().bold(true),
+                                "gRPC address".cell().bold(true),
+                                "Connection status".cell().bold(true),
+                                "Updated at".cell().bold(true),
+                            ])
+                            .color_choice(color_choice);
+
+                        print_stdout(table).context("unable to print table to stdout")
+                    }
+                    OutputType::Json => print_json(color_choice, chains_json(chains)?),
+                }
+            }
+            Self::Archive { chain_id } => chain_service.archive(&chain_id).await,
+            Self::Remove { chain_id } => chain_service.remove(&chain_id).await,
             Self::Get { ref chain_id } => {
                 let chain = chain_service.get(chain_id).await?;
 
@@ -225,6 +476,16 @@ impl ChainCommand {
                             add_row(&mut table, "Node ID", chain.node_id);
                             add_row(&mut table, "gRPC address", &chain.config.grpc_addr);
                             add_row(&mut table, "RPC address", &chain.config.rpc_addr);
+                            add_row(
+                                &mut table,
+                                "Fallback gRPC addresses",
+                                chain.config.fallback_grpc_addrs.join("\n"),
+                            );
+                            add_row(
+                                &mut table,
+                                "Fallback RPC addresses",
+                                chain.config.fallback_rpc_addrs.join("\n"),
+                            );
                             add_row(&mut table, "Fee amount", chain.config.fee.amount);
                             add_row(&mut table, "Fee denom", &chain.config.fee.denom);
                             add_row(&mut table, "Gas limit", chain.config.fee.gas_limit);
@@ -315,13 +576,7 @@ impl ChainCommand {
                             print_stdout(table.table().color_choice(color_choice))
                                 .context("unable to print table to stdout")
                         }
-                        OutputType::Json => print_json(
-                            color_choice,
-                            json!({
-                                "result": "success",
-                                "data": chain
-                            }),
-                        ),
+                        OutputType::Json => print_json(color_choice, success_json(chain)),
                     },
                 }
             }
@@ -354,13 +609,7 @@ impl ChainCommand {
 
                         print_stdout(table).context("unable to print table to stdout")
                     }
-                    OutputType::Json => print_json(
-                        color_choice,
-                        json!({
-                            "result": "success",
-                            "data": keys
-                        }),
-                    ),
+                    OutputType::Json => print_json(color_choice, success_json(keys)),
                 }
             }
             Self::GetIbcDenom {
@@ -419,6 +668,53 @@ impl ChainCommand {
                     ),
                 }
             }
+            Self::Reconcile { chain_id, strict } => {
+                let reconciliation_service =
+                    ReconciliationService::new_with_notifier(db_pool, sender);
+                let reports = reconciliation_service.reconcile(&chain_id).await?;
+
+                let discrepancies = reports.iter().filter(|report| !report.is_balanced).count();
+
+                match output {
+                    OutputType::Text => {
+                        let table = reports
+                            .into_iter()
+                            .map(into_reconciliation_row)
+                            .collect::<Vec<RowStruct>>()
+                            .table()
+                            .title(vec![
+                                "Denom".cell().bold(true),
+                                "IBC denom".cell().bold(true),
+                                "Total minted".cell().bold(true),
+                                "Total burnt".cell().bold(true),
+                                "Ledger supply".cell().bold(true),
+                                "On-chain supply".cell().bold(true),
+                                "Escrow balance".cell().bold(true),
+                                "Unsettled operations".cell().bold(true),
+                                "Status".cell().bold(true),
+                            ])
+                            .color_choice(color_choice);
+
+                        print_stdout(table).context("unable to print table to stdout")?;
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": reports
+                        }),
+                    )?,
+                }
+
+                ensure!(
+                    !strict || discrepancies == 0,
+                    "supply discrepancy found for {} denom(s) on chain {}",
+                    discrepancies,
+                    chain_id
+                );
+
+                Ok(())
+            }
         }
     }
 }
@@ -444,3 +740,118 @@ fn into_row(key: ChainKey) -> RowStruct {
     ]
     .row()
 }
+
+fn into_chain_row(chain: Chain) -> RowStruct {
+    let connection_status = chain.connection_status();
+    let color = match connection_status {
+        ConnectionStatus::Open => Color::Green,
+        ConnectionStatus::ChannelClosed => Color::Yellow,
+        ConnectionStatus::NotConnected => Color::Red,
+    };
+
+    vec![
+        chain.id.cell(),
+        chain.node_id.cell(),
+        chain.config.rpc_addr.cell(),
+        chain.config.grpc_addr.cell(),
+        connection_status.cell().foreground_color(Some(color)),
+        chain.updated_at.cell(),
+    ]
+    .row()
+}
+
+fn into_endpoint_row(endpoint: EndpointHealth) -> RowStruct {
+    let (status, color) = if endpoint.is_healthy {
+        ("Healthy", Color::Green)
+    } else {
+        ("Unhealthy", Color::Red)
+    };
+
+    vec![
+        endpoint.kind.cell(),
+        endpoint.addr.cell(),
+        endpoint.is_primary.cell(),
+        status.cell().foreground_color(Some(color)),
+        endpoint
+            .node_id
+            .map(|node_id| node_id.to_string())
+            .unwrap_or_default()
+            .cell(),
+        endpoint
+            .latest_height
+            .map(|height| height.to_string())
+            .unwrap_or_default()
+            .cell()
+            .justify(Justify::Right),
+        endpoint.error.unwrap_or_default().cell(),
+    ]
+    .row()
+}
+
+fn into_config_change_row(change: ChainConfigChange) -> Result<RowStruct> {
+    let mut changes = Vec::new();
+
+    if change.old_node_id != change.new_node_id {
+        changes.push(format!(
+            "node_id: {} -> {}",
+            change.old_node_id, change.new_node_id
+        ));
+    }
+
+    diff_json(
+        "",
+        &serde_json::to_value(&change.old_config)?,
+        &serde_json::to_value(&change.new_config)?,
+        &mut changes,
+    );
+
+    Ok(vec![
+        change.id.cell().justify(Justify::Right),
+        change.chain_id.cell(),
+        changes.join("\n").cell(),
+        change.created_at.cell(),
+    ]
+    .row())
+}
+
+fn diff_json(prefix: &str, old: &Value, new: &Value, changes: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, new_value) in new {
+                let path = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+
+                match old.get(key) {
+                    Some(old_value) => diff_json(&path, old_value, new_value, changes),
+                    None => changes.push(format!("{}: - -> {}", path, new_value)),
+                }
+            }
+        }
+        _ if old != new => changes.push(format!("{}: {} -> {}", prefix, old, new)),
+        _ => {}
+    }
+}
+
+fn into_reconciliation_row(report: SupplyReport) -> RowStruct {
+    let (status, color) = if report.is_balanced {
+        ("Balanced", Color::Green)
+    } else {
+        ("Mismatch", Color::Red)
+    };
+
+    vec![
+        report.denom.cell(),
+        report.ibc_denom.cell(),
+        report.total_minted.cell().justify(Justify::Right),
+        report.total_burnt.cell().justify(Justify::Right),
+        report.ledger_supply.cell().justify(Justify::Right),
+        report.on_chain_supply.cell().justify(Justify::Right),
+        report.escrow_balance.cell().justify(Justify::Right),
+        report.unsettled_operations.cell().justify(Justify::Right),
+        status.cell().foreground_color(Some(color)),
+    ]
+    .row()
+}
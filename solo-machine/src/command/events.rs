@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use cli_table::{print_stdout, Cell, Color, Row, RowStruct, Style, Table};
+use serde_json::json;
+use solo_machine_core::{
+    model::{HandlerCursor, OutboxEvent},
+    service::OutboxService,
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::{ColorChoice, ColorSpec, StandardStream};
+
+use crate::output::OutputType;
+
+use super::{print_json, print_stream};
+
+#[derive(Debug, StructOpt)]
+pub enum EventsCommand {
+    /// Lists events recorded in event outbox
+    List {
+        /// Only list events recorded after event with given ID
+        #[structopt(long, default_value = "0")]
+        after: i64,
+        /// Maximum number of events to list
+        #[structopt(long, default_value = "20")]
+        limit: i32,
+    },
+    /// Lists cursors of event handlers (i.e., position of the last event delivered to each event handler in outbox)
+    Cursors,
+    /// Delivers events to an event handler again starting from given event ID (takes effect on next delivery round
+    /// of running solo machine or on next start)
+    Replay {
+        /// Name of event handler (file name of event hook without extension, e.g., `libstdout_logger`)
+        handler: String,
+        /// ID of the first event to deliver again
+        #[structopt(long)]
+        from: i64,
+    },
+}
+
+impl EventsCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputType,
+    ) -> Result<()> {
+        let outbox_service = OutboxService::new(db_pool);
+
+        match self {
+            Self::List { after, limit } => {
+                let events = outbox_service.get_events(after, limit).await?;
+
+                match output {
+                    OutputType::Text => print_events(events, color_choice),
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": events,
+                        }),
+                    ),
+                }
+            }
+            Self::Cursors => {
+                let cursors = outbox_service.cursors().await?;
+
+                match output {
+                    OutputType::Text => print_cursors(cursors, color_choice),
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": cursors,
+                        }),
+                    ),
+                }
+            }
+            Self::Replay { handler, from } => {
+                outbox_service.replay(&handler, from).await?;
+
+                match output {
+                    OutputType::Text => {
+                        let mut stdout = StandardStream::stdout(color_choice);
+                        print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_bold(true),
+                            format!(
+                                "Events from {} will be delivered to {} again!",
+                                from, handler
+                            ),
+                        )
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": {
+                                "handler": handler,
+                                "from": from,
+                            },
+                        }),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+fn print_events(events: Vec<OutboxEvent>, color_choice: ColorChoice) -> Result<()> {
+    let table = events
+        .into_iter()
+        .map(|event| -> Result<RowStruct> {
+            let payload =
+                serde_json::to_value(&event.event).context("unable to serialize event")?;
+            let event_type = payload["type"].as_str().unwrap_or_default().to_string();
+
+            Ok(vec![
+                event.id.cell(),
+                event_type.cell().foreground_color(Some(Color::Green)),
+                payload.to_string().cell(),
+                event.created_at.cell(),
+            ]
+            .row())
+        })
+        .collect::<Result<Vec<RowStruct>>>()?
+        .table()
+        .title(vec![
+            "ID".cell().bold(true),
+            "Type".cell().bold(true),
+            "Event".cell().bold(true),
+            "Created at".cell().bold(true),
+        ])
+        .color_choice(color_choice);
+
+    print_stdout(table).context("unable to print table to stdout")
+}
+
+fn print_cursors(cursors: Vec<HandlerCursor>, color_choice: ColorChoice) -> Result<()> {
+    let table = cursors
+        .into_iter()
+        .map(|cursor| {
+            vec![
+                cursor.handler.cell().foreground_color(Some(Color::Green)),
+                cursor.position.cell(),
+                cursor.pending_events.cell(),
+                cursor.updated_at.cell(),
+            ]
+            .row()
+        })
+        .collect::<Vec<RowStruct>>()
+        .table()
+        .title(vec![
+            "Handler".cell().bold(true),
+            "Position".cell().bold(true),
+            "Pending events".cell().bold(true),
+            "Updated at".cell().bold(true),
+        ])
+        .color_choice(color_choice);
+
+    print_stdout(table).context("unable to print table to stdout")
+}
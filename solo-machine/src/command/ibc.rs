@@ -1,26 +1,41 @@
+use std::{
+    fs::File,
+    io::{stdout, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
 use anyhow::{anyhow, Context, Result};
 use cli_table::{
     format::Justify, print_stdout, Cell, Color, ColorChoice, Row, RowStruct, Style, Table,
 };
 use k256::ecdsa::VerifyingKey;
 use primitive_types::U256;
+use serde::Serialize;
 use serde_json::json;
 use solo_machine_core::{
     cosmos::crypto::{PublicKey, PublicKeyAlgo},
     ibc::core::ics24_host::identifier::{ChainId, Identifier},
-    model::{Operation, OperationType},
-    service::IbcService,
-    DbPool, Event, Signer,
+    model::{
+        OfflineOperation, Operation, OperationFilter, OperationKind, OperationStatus,
+        OperationType, SigningSessionStatus,
+    },
+    service::{DryRunOperation, DryRunReport, IbcService, SigningSessionOutcome},
+    signer::SigningResponse,
+    DbPool, Event, Signer, ToPublicKey,
 };
 use structopt::StructOpt;
+use termcolor::{ColorSpec, StandardStream};
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::output::OutputType;
+use crate::output::{history_json, ExportFormat, OutputType};
 
-use super::print_json;
+use super::{print_json, print_stream};
 
 const PUBLIC_KEY_ALGO_VARIANTS: [&str; 2] = ["secp256k1", "eth-secp256k1"];
 
+/// Number of operations fetched from database at once when exporting history
+const EXPORT_PAGE_SIZE: i32 = 100;
+
 #[derive(Debug, StructOpt)]
 pub enum IbcCommand {
     /// Establishes connection with an IBC enabled chain
@@ -41,6 +56,13 @@ pub enum IbcCommand {
         /// Force create a new connection even if one already exists
         #[structopt(long)]
         force: bool,
+        /// Builds and prints the transactions without signing or broadcasting them (changes to solo machine's state are
+        /// rolled back)
+        #[structopt(long, conflicts_with = "force")]
+        dry_run: bool,
+        /// Simulates the transactions of dry run on IBC enabled chain (to estimate gas)
+        #[structopt(long, requires = "dry-run")]
+        simulate: bool,
     },
     /// Close solomachine channel
     CloseChannel {
@@ -80,6 +102,13 @@ pub enum IbcCommand {
         /// Optional request ID (for tracking purposes)
         #[structopt(long)]
         request_id: Option<String>,
+        /// Builds and prints the transactions without signing or broadcasting them (changes to solo machine's state are
+        /// rolled back)
+        #[structopt(long)]
+        dry_run: bool,
+        /// Simulates the transactions of dry run on IBC enabled chain (to estimate gas)
+        #[structopt(long, requires = "dry-run")]
+        simulate: bool,
     },
     /// Burn some tokens on IBC enabled chain
     Burn {
@@ -101,6 +130,13 @@ pub enum IbcCommand {
         /// Optional request ID (for tracking purposes)
         #[structopt(long)]
         request_id: Option<String>,
+        /// Builds and prints the transactions without signing or broadcasting them (changes to solo machine's state are
+        /// rolled back)
+        #[structopt(long)]
+        dry_run: bool,
+        /// Simulates the transactions of dry run on IBC enabled chain (to estimate gas)
+        #[structopt(long, requires = "dry-run")]
+        simulate: bool,
     },
     /// Updates signer's public key on IBC enabled chain for future messages from solo machine
     UpdateSigner {
@@ -123,16 +159,552 @@ pub enum IbcCommand {
         /// Optional request ID (for tracking purposes)
         #[structopt(long)]
         request_id: Option<String>,
+        /// Builds and prints the transactions without signing or broadcasting them (changes to solo machine's state are
+        /// rolled back)
+        #[structopt(long)]
+        dry_run: bool,
+        /// Simulates the transactions of dry run on IBC enabled chain (to estimate gas)
+        #[structopt(long, requires = "dry-run")]
+        simulate: bool,
     },
+    #[structopt(flatten)]
+    Offline(OfflineCommand),
     /// Check history of operations on solo machine
     History {
         #[structopt(long, default_value = "10")]
         limit: u32,
         #[structopt(long, default_value)]
         offset: u32,
+        /// Only show operations older than given cursor (use the cursor printed with previous page, cannot be used
+        /// with `--offset`)
+        #[structopt(long, conflicts_with = "offset")]
+        cursor: Option<i64>,
+        #[structopt(flatten)]
+        filter: HistoryFilter,
+        #[structopt(subcommand)]
+        subcommand: Option<HistoryCommand>,
+    },
+}
+
+/// Commands for signing messages of operations offline (e.g. on an air-gapped machine)
+#[derive(Debug, StructOpt)]
+pub enum OfflineCommand {
+    /// Prepares an offline signing session for an operation and writes the messages to sign to a file (sequence of
+    /// solo machine is reserved for the session until it is completed or aborted)
+    Prepare {
+        /// Hex encoded public key of offline signer
+        #[structopt(long, env = "SOLO_OFFLINE_PUBLIC_KEY", hide_env_values = true)]
+        public_key: String,
+        /// Type of public key of offline signer
+        #[structopt(long, possible_values = &PUBLIC_KEY_ALGO_VARIANTS, default_value = "secp256k1", env = "SOLO_PUBLIC_KEY_ALGO", hide_env_values = true)]
+        public_key_algo: PublicKeyAlgo,
+        /// Account prefix of offline signer
+        #[structopt(long, env = "SOLO_ACCOUNT_PREFIX", default_value = "cosmos")]
+        account_prefix: String,
+        /// Number of blocks (after latest height of IBC enabled chain) after which the packet sent by mint operation
+        /// times out (should leave enough time for signing all the rounds of session)
+        #[structopt(long, default_value = "10000")]
+        timeout_height_offset: u64,
+        /// File to write the messages to sign
+        #[structopt(short, long)]
+        file: PathBuf,
+        #[structopt(subcommand)]
+        operation: PrepareCommand,
+    },
+    /// Imports signatures of current round of an offline signing session and continues its operation (messages of
+    /// next round are written to a file if more messages need to be signed)
+    Finalize {
+        /// File containing signatures of current round (e.g. created by `solo-keystore sign`)
+        signatures: PathBuf,
+        /// File to write the messages of next round
+        #[structopt(short, long)]
+        file: PathBuf,
+    },
+    /// Aborts an offline signing session (releasing the sequence reserved for it)
+    Abort {
+        /// ID of offline signing session
+        session_id: i64,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PrepareCommand {
+    /// Mint some tokens on IBC enabled chain
+    Mint {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Amount to send to IBC enabled chain
+        #[structopt(parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        amount: U256,
+        /// Denom of tokens to send to IBC enabled chain
+        denom: Identifier,
+        /// Optional receiver address (if this is not provided, tokens will be sent to signer's address)
+        receiver: Option<String>,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+        /// Optional request ID (for tracking purposes)
+        #[structopt(long)]
+        request_id: Option<String>,
+    },
+    /// Burn some tokens on IBC enabled chain
+    Burn {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Amount to receive from IBC enabled chain
+        #[structopt(parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        amount: U256,
+        /// Denom of tokens to receive from IBC enabled chain
+        denom: Identifier,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+        /// Optional request ID (for tracking purposes)
+        #[structopt(long)]
+        request_id: Option<String>,
+    },
+    /// Updates signer's public key on IBC enabled chain for future messages from solo machine
+    UpdateSigner {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Hex encoded public key
+        #[structopt(long, env = "SOLO_NEW_PUBLIC_KEY", hide_env_values = true)]
+        new_public_key: String,
+        /// Type of new public key
+        #[structopt(long, possible_values = &PUBLIC_KEY_ALGO_VARIANTS, default_value = "secp256k1")]
+        new_public_key_algo: PublicKeyAlgo,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+        /// Optional request ID (for tracking purposes)
+        #[structopt(long)]
+        request_id: Option<String>,
+    },
+}
+
+impl PrepareCommand {
+    fn into_parts(self) -> Result<(ChainId, Option<String>, OfflineOperation)> {
+        match self {
+            Self::Mint {
+                chain_id,
+                amount,
+                denom,
+                receiver,
+                memo,
+                request_id,
+            } => Ok((
+                chain_id,
+                request_id,
+                OfflineOperation::Mint {
+                    amount,
+                    denom,
+                    receiver,
+                    memo,
+                },
+            )),
+            Self::Burn {
+                chain_id,
+                amount,
+                denom,
+                memo,
+                request_id,
+            } => Ok((
+                chain_id,
+                request_id,
+                OfflineOperation::Burn {
+                    amount,
+                    denom,
+                    memo,
+                },
+            )),
+            Self::UpdateSigner {
+                chain_id,
+                new_public_key,
+                new_public_key_algo,
+                memo,
+                request_id,
+            } => Ok((
+                chain_id,
+                request_id,
+                OfflineOperation::UpdateSigner {
+                    new_public_key: parse_public_key(&new_public_key, new_public_key_algo)?,
+                    memo,
+                },
+            )),
+        }
+    }
+}
+
+/// Watch-only signer of offline signing sessions (only knows the public key of offline signer)
+struct OfflinePublicKey {
+    public_key: PublicKey,
+    account_prefix: String,
+}
+
+impl ToPublicKey for OfflinePublicKey {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.public_key.account_address(&self.account_prefix)
+    }
+}
+
+impl OfflineCommand {
+    pub async fn execute(
+        self,
+        ibc_service: &IbcService,
+        color_choice: ColorChoice,
+        output: OutputType,
+    ) -> Result<()> {
+        match self {
+            Self::Prepare {
+                public_key,
+                public_key_algo,
+                account_prefix,
+                timeout_height_offset,
+                file,
+                operation,
+            } => {
+                let signer = OfflinePublicKey {
+                    public_key: parse_public_key(&public_key, public_key_algo)?,
+                    account_prefix,
+                };
+                let (chain_id, request_id, operation) = operation.into_parts()?;
+
+                let outcome = ibc_service
+                    .prepare_offline(
+                        signer,
+                        chain_id,
+                        request_id,
+                        operation,
+                        timeout_height_offset,
+                    )
+                    .await?;
+
+                print_outcome(outcome, &file, color_choice, output)
+            }
+            Self::Finalize { signatures, file } => {
+                let bytes = std::fs::read(&signatures).with_context(|| {
+                    format!("unable to read signatures from {}", signatures.display())
+                })?;
+                let response: SigningResponse =
+                    serde_json::from_slice(&bytes).with_context(|| {
+                        format!("invalid signatures file: {}", signatures.display())
+                    })?;
+
+                let outcome = ibc_service.finalize_offline(response).await?;
+
+                print_outcome(outcome, &file, color_choice, output)
+            }
+            Self::Abort { session_id } => {
+                ibc_service.abort_offline(session_id).await?;
+
+                let message = format!("Offline signing session {} aborted!", session_id);
+
+                match output {
+                    OutputType::Text => {
+                        let mut stdout = StandardStream::stdout(color_choice);
+                        print_stream(&mut stdout, ColorSpec::new().set_bold(true), message)
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": message,
+                        }),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Writes the messages of next round (if any) to given file and prints the outcome of offline signing session
+fn print_outcome(
+    outcome: SigningSessionOutcome,
+    file: &Path,
+    color_choice: ColorChoice,
+    output: OutputType,
+) -> Result<()> {
+    match outcome {
+        SigningSessionOutcome::AwaitingSignatures(requests) => {
+            let json = serde_json::to_vec_pretty(&requests)
+                .context("unable to serialize messages to sign")?;
+            std::fs::write(file, json).with_context(|| {
+                format!("unable to write messages to sign to {}", file.display())
+            })?;
+
+            match output {
+                OutputType::Text => {
+                    let mut stdout = StandardStream::stdout(color_choice);
+                    print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        format!(
+                            "Round {} of offline signing session {} is awaiting signatures (written to {})",
+                            requests.round,
+                            requests.session_id,
+                            file.display()
+                        ),
+                    )?;
+
+                    for request in requests.requests.iter() {
+                        print_stream(
+                            &mut stdout,
+                            &ColorSpec::new(),
+                            format!("- {}", request.description),
+                        )?;
+                    }
+
+                    Ok(())
+                }
+                OutputType::Json => print_json(
+                    color_choice,
+                    json!({
+                        "result": "success",
+                        "data": {
+                            "status": SigningSessionStatus::AwaitingSignatures,
+                            "session_id": requests.session_id,
+                            "round": requests.round,
+                            "file": file,
+                            "requests": requests.requests,
+                        },
+                    }),
+                ),
+            }
+        }
+        SigningSessionOutcome::Completed(transaction_hash) => match output {
+            OutputType::Text => {
+                let mut stdout = StandardStream::stdout(color_choice);
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Offline signing session completed! [transaction hash: {}]",
+                        transaction_hash
+                    ),
+                )
+            }
+            OutputType::Json => print_json(
+                color_choice,
+                json!({
+                    "result": "success",
+                    "data": {
+                        "status": SigningSessionStatus::Completed,
+                        "transaction_hash": transaction_hash,
+                    },
+                }),
+            ),
+        },
+    }
+}
+
+fn print_dry_run_report(
+    report: DryRunReport,
+    color_choice: ColorChoice,
+    output: OutputType,
+) -> Result<()> {
+    match output {
+        OutputType::Text => {
+            let mut stdout = StandardStream::stdout(color_choice);
+            print_stream(
+                &mut stdout,
+                ColorSpec::new().set_bold(true),
+                format!(
+                    "Dry run on chain {} (nothing is signed or broadcasted)",
+                    report.chain_id
+                ),
+            )?;
+
+            print_stream(
+                &mut stdout,
+                &ColorSpec::new(),
+                format!("Sequence: {} -> {}", report.sequence, report.next_sequence),
+            )?;
+            print_stream(
+                &mut stdout,
+                &ColorSpec::new(),
+                format!(
+                    "Packet sequence: {} -> {}",
+                    report.packet_sequence, report.next_packet_sequence
+                ),
+            )?;
+
+            print_stream(&mut stdout, &ColorSpec::new(), "Messages to sign:")?;
+            for description in report.signed_messages.iter() {
+                print_stream(&mut stdout, &ColorSpec::new(), format!("- {}", description))?;
+            }
+
+            for (index, transaction) in report.transactions.iter().enumerate() {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Transaction {} [size: {} bytes]",
+                        index + 1,
+                        transaction.size
+                    ),
+                )?;
+
+                print_stream(
+                    &mut stdout,
+                    &ColorSpec::new(),
+                    format!(
+                        "Fee: {} [gas limit: {}]",
+                        transaction.fee.join(", "),
+                        transaction.gas_limit
+                    ),
+                )?;
+
+                if let Some(gas_used) = transaction.simulated_gas_used {
+                    print_stream(
+                        &mut stdout,
+                        &ColorSpec::new(),
+                        format!("Simulated gas used: {}", gas_used),
+                    )?;
+                }
+
+                if let Some(ref error) = transaction.simulation_error {
+                    print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                        format!("Simulation failed: {}", error),
+                    )?;
+                }
+
+                print_stream(
+                    &mut stdout,
+                    &ColorSpec::new(),
+                    format!("Memo: {}", transaction.memo),
+                )?;
+                print_stream(&mut stdout, &ColorSpec::new(), "Messages:")?;
+
+                for message in transaction.messages.iter() {
+                    print_stream(
+                        &mut stdout,
+                        &ColorSpec::new(),
+                        format!("- {}", message.type_url),
+                    )?;
+                    print_stream(
+                        &mut stdout,
+                        &ColorSpec::new(),
+                        serde_json::to_string_pretty(&message.value)
+                            .context("unable to serialize message")?,
+                    )?;
+                }
+            }
+
+            if let Some(ref remaining_steps) = report.remaining_steps {
+                print_stream(
+                    &mut stdout,
+                    &ColorSpec::new(),
+                    format!(
+                        "Not built (depends on broadcasted transactions): {}",
+                        remaining_steps
+                    ),
+                )?;
+            }
+
+            Ok(())
+        }
+        OutputType::Json => print_json(
+            color_choice,
+            json!({
+                "result": "success",
+                "data": report,
+            }),
+        ),
+    }
+}
+
+fn parse_public_key(public_key: &str, public_key_algo: PublicKeyAlgo) -> Result<PublicKey> {
+    let public_key_bytes = hex::decode(public_key).context("unable to decode hex bytes")?;
+
+    let verifying_key =
+        VerifyingKey::from_sec1_bytes(&public_key_bytes).context("invalid secp256k1 bytes")?;
+
+    match public_key_algo {
+        PublicKeyAlgo::Secp256k1 => Ok(PublicKey::Secp256k1(verifying_key)),
+        #[cfg(feature = "ethermint")]
+        PublicKeyAlgo::EthSecp256k1 => Ok(PublicKey::EthSecp256k1(verifying_key)),
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub enum HistoryCommand {
+    /// Exports all the operations matching given filters
+    Export {
+        /// Export format
+        #[structopt(long, default_value = "csv", possible_values = &["csv", "ndjson"])]
+        format: ExportFormat,
+        /// Write exported operations to given file instead of stdout
+        #[structopt(long)]
+        file: Option<PathBuf>,
+        #[structopt(flatten)]
+        filter: HistoryFilter,
     },
 }
 
+#[derive(Debug, StructOpt)]
+pub struct HistoryFilter {
+    /// Only include operations on IBC enabled chain with given chain ID
+    #[structopt(long)]
+    chain_id: Option<ChainId>,
+    /// Only include operations with given denom
+    #[structopt(long)]
+    denom: Option<Identifier>,
+    /// Only include operations of given type
+    #[structopt(long = "type", possible_values = &["mint", "burn", "update-signer"])]
+    operation_type: Option<OperationKind>,
+    /// Only include operation with given request ID
+    #[structopt(long)]
+    request_id: Option<String>,
+    /// Only include operations with given status
+    #[structopt(long, possible_values = &["pending", "broadcast", "committed", "acknowledged", "failed", "timed-out"])]
+    status: Option<OperationStatus>,
+    /// Only include operations created at or after given time (in RFC 3339 format, e.g. `2021-07-01T00:00:00Z`)
+    #[structopt(long)]
+    from: Option<humantime::Timestamp>,
+    /// Only include operations created before given time (in RFC 3339 format, e.g. `2021-07-01T00:00:00Z`)
+    #[structopt(long)]
+    to: Option<humantime::Timestamp>,
+}
+
+impl From<HistoryFilter> for OperationFilter {
+    fn from(filter: HistoryFilter) -> Self {
+        Self {
+            chain_id: filter.chain_id,
+            denom: filter.denom,
+            kind: filter.operation_type,
+            request_id: filter.request_id,
+            status: filter.status,
+            created_after: filter.from.map(|time| (*time).into()),
+            created_before: filter.to.map(|time| (*time).into()),
+        }
+    }
+}
+
 impl IbcCommand {
     pub async fn execute(
         self,
@@ -150,7 +722,23 @@ impl IbcCommand {
                 memo,
                 request_id,
                 force,
+                dry_run,
+                simulate,
             } => {
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run(
+                            signer,
+                            chain_id,
+                            request_id,
+                            DryRunOperation::Connect { memo },
+                            simulate,
+                        )
+                        .await?;
+
+                    return print_dry_run_report(report, color_choice, output);
+                }
+
                 ibc_service
                     .connect(signer, chain_id, request_id, memo, force)
                     .await
@@ -171,51 +759,125 @@ impl IbcCommand {
                 receiver,
                 memo,
                 request_id,
-            } => ibc_service
-                .mint(signer, chain_id, request_id, amount, denom, receiver, memo)
-                .await
-                .map(|_| ()),
+                dry_run,
+                simulate,
+            } => {
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run(
+                            signer,
+                            chain_id,
+                            request_id,
+                            DryRunOperation::Mint {
+                                amount,
+                                denom,
+                                receiver,
+                                memo,
+                            },
+                            simulate,
+                        )
+                        .await?;
+
+                    return print_dry_run_report(report, color_choice, output);
+                }
+
+                ibc_service
+                    .mint(signer, chain_id, request_id, amount, denom, receiver, memo)
+                    .await
+                    .map(|_| ())
+            }
             Self::Burn {
                 chain_id,
                 amount,
                 denom,
                 memo,
                 request_id,
-            } => ibc_service
-                .burn(signer, chain_id, request_id, amount, denom, memo)
-                .await
-                .map(|_| ()),
+                dry_run,
+                simulate,
+            } => {
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run(
+                            signer,
+                            chain_id,
+                            request_id,
+                            DryRunOperation::Burn {
+                                amount,
+                                denom,
+                                memo,
+                            },
+                            simulate,
+                        )
+                        .await?;
+
+                    return print_dry_run_report(report, color_choice, output);
+                }
+
+                ibc_service
+                    .burn(signer, chain_id, request_id, amount, denom, memo)
+                    .await
+                    .map(|_| ())
+            }
             Self::UpdateSigner {
                 chain_id,
                 new_public_key,
                 public_key_algo,
                 memo,
                 request_id,
+                dry_run,
+                simulate,
             } => {
-                let new_public_key_bytes =
-                    hex::decode(&new_public_key).context("unable to decode hex bytes")?;
+                let new_public_key = parse_public_key(&new_public_key, public_key_algo)?;
 
-                let new_verifying_key = VerifyingKey::from_sec1_bytes(&new_public_key_bytes)
-                    .context("invalid secp256k1 bytes")?;
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run(
+                            signer,
+                            chain_id,
+                            request_id,
+                            DryRunOperation::UpdateSigner {
+                                new_public_key,
+                                memo,
+                            },
+                            simulate,
+                        )
+                        .await?;
 
-                let new_public_key = match public_key_algo {
-                    PublicKeyAlgo::Secp256k1 => PublicKey::Secp256k1(new_verifying_key),
-                    #[cfg(feature = "ethermint")]
-                    PublicKeyAlgo::EthSecp256k1 => PublicKey::EthSecp256k1(new_verifying_key),
-                };
+                    return print_dry_run_report(report, color_choice, output);
+                }
 
                 ibc_service
                     .update_signer(signer, chain_id, request_id, new_public_key, memo)
                     .await
+                    .map(|_| ())
             }
-            Self::History { limit, offset } => {
+            Self::History {
+                subcommand:
+                    Some(HistoryCommand::Export {
+                        format,
+                        file,
+                        filter,
+                    }),
+                ..
+            } => export_history(&ibc_service, signer, filter.into(), format, file).await,
+            Self::Offline(command) => command.execute(&ibc_service, color_choice, output).await,
+            Self::History {
+                limit,
+                offset,
+                cursor,
+                filter,
+                subcommand: None,
+            } => {
                 let limit = i32::try_from(limit).or(Err(anyhow!("invalid `limit`")))?;
                 let offset = i32::try_from(offset).or(Err(anyhow!("invalid `offset`")))?;
-                let history = ibc_service.history(signer, limit, offset).await?;
+                let history = ibc_service
+                    .history(signer, &filter.into(), cursor, limit, offset)
+                    .await?;
 
                 match output {
                     OutputType::Text => {
                         let table = history
+                            .operations
                             .into_iter()
                             .map(into_row)
                             .collect::<Vec<RowStruct>>()
@@ -227,28 +889,145 @@ impl IbcCommand {
                                 "Denom".cell().bold(true),
                                 "Amount".cell().bold(true),
                                 "Type".cell().bold(true),
+                                "Status".cell().bold(true),
+                                "Error".cell().bold(true),
                                 "Transaction Hash".cell().bold(true),
+                                "Block Height".cell().bold(true),
                                 "Time".cell().bold(true),
                             ])
                             .color_choice(color_choice);
 
-                        print_stdout(table).context("unable to print table to stdout")
+                        print_stdout(table).context("unable to print table to stdout")?;
+
+                        if let Some(next_cursor) = history.next_cursor {
+                            println!("Next cursor: {}", next_cursor);
+                        }
+
+                        Ok(())
                     }
-                    OutputType::Json => print_json(
-                        color_choice,
-                        json!({
-                            "result": "success",
-                            "data": history,
-                        }),
-                    ),
+                    OutputType::Json => print_json(color_choice, history_json(&history)),
                 }
             }
         }
     }
 }
 
+/// Flattened operation used for CSV export
+#[derive(Serialize)]
+struct ExportRecord {
+    id: i64,
+    request_id: Option<String>,
+    address: String,
+    operation_type: String,
+    chain_id: String,
+    denom: Option<String>,
+    amount: Option<String>,
+    status: String,
+    error: Option<String>,
+    transaction_hash: Option<String>,
+    block_height: Option<i64>,
+    gas_used: Option<i64>,
+    fee_paid: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Operation> for ExportRecord {
+    fn from(operation: Operation) -> Self {
+        Self {
+            id: operation.id,
+            request_id: operation.request_id,
+            address: operation.address,
+            operation_type: operation.operation_type.kind().to_string(),
+            chain_id: operation.operation_type.chain_id().to_string(),
+            denom: operation.denom.map(|denom| denom.to_string()),
+            amount: operation.amount.map(|amount| amount.to_string()),
+            status: operation.status.to_string(),
+            error: operation.error,
+            transaction_hash: operation.transaction_hash,
+            block_height: operation.block_height,
+            gas_used: operation.gas_used,
+            fee_paid: operation.fee_paid,
+            created_at: operation.created_at.to_rfc3339(),
+            updated_at: operation.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Streams all the operations matching given filter (page by page) to stdout or given file
+async fn export_history(
+    ibc_service: &IbcService,
+    signer: impl Signer,
+    filter: OperationFilter,
+    format: ExportFormat,
+    file: Option<PathBuf>,
+) -> Result<()> {
+    let writer: Box<dyn Write> = match file {
+        Some(path) => {
+            Box::new(BufWriter::new(File::create(&path).with_context(|| {
+                format!("unable to create file: {}", path.display())
+            })?))
+        }
+        None => Box::new(stdout()),
+    };
+
+    let mut writer = match format {
+        ExportFormat::Csv => ExportWriter::Csv(csv::Writer::from_writer(writer)),
+        ExportFormat::Ndjson => ExportWriter::Ndjson(writer),
+    };
+
+    let mut cursor = None;
+
+    loop {
+        let page = ibc_service
+            .history(&signer, &filter, cursor, EXPORT_PAGE_SIZE, 0)
+            .await?;
+
+        for operation in page.operations {
+            writer.write(operation)?;
+        }
+
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    writer.flush()
+}
+
+#[allow(clippy::large_enum_variant)]
+enum ExportWriter {
+    Csv(csv::Writer<Box<dyn Write>>),
+    Ndjson(Box<dyn Write>),
+}
+
+impl ExportWriter {
+    fn write(&mut self, operation: Operation) -> Result<()> {
+        match self {
+            Self::Csv(writer) => writer
+                .serialize(ExportRecord::from(operation))
+                .context("unable to write operation as csv"),
+            Self::Ndjson(writer) => {
+                serde_json::to_writer(&mut *writer, &operation)
+                    .context("unable to write operation as json")?;
+                writeln!(writer).context("unable to write operation as json")
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Csv(writer) => writer.flush(),
+            Self::Ndjson(writer) => writer.flush(),
+        }
+        .context("unable to flush exported operations")
+    }
+}
+
 fn into_row(operation: Operation) -> RowStruct {
     let color = get_color_for_operation_type(&operation.operation_type);
+    let status_color = get_color_for_operation_status(operation.status);
 
     vec![
         operation.id.cell().justify(Justify::Right),
@@ -257,13 +1036,33 @@ fn into_row(operation: Operation) -> RowStruct {
             .unwrap_or_else(|| "-".to_string())
             .cell(),
         operation.address.cell(),
-        operation.denom.cell(),
-        operation.amount.cell().justify(Justify::Right),
+        operation
+            .denom
+            .map(|denom| denom.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .cell(),
+        operation
+            .amount
+            .map(|amount| amount.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .cell()
+            .justify(Justify::Right),
         operation
             .operation_type
             .cell()
             .foreground_color(Some(color)),
-        operation.transaction_hash.cell(),
+        operation.status.cell().foreground_color(Some(status_color)),
+        operation.error.unwrap_or_else(|| "-".to_string()).cell(),
+        operation
+            .transaction_hash
+            .unwrap_or_else(|| "-".to_string())
+            .cell(),
+        operation
+            .block_height
+            .map(|block_height| block_height.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .cell()
+            .justify(Justify::Right),
         operation.created_at.cell(),
     ]
     .row()
@@ -273,5 +1072,120 @@ fn get_color_for_operation_type(operation_type: &OperationType) -> Color {
     match operation_type {
         OperationType::Mint { .. } => Color::Green,
         OperationType::Burn { .. } => Color::Red,
+        OperationType::UpdateSigner { .. } => Color::Blue,
+    }
+}
+
+fn get_color_for_operation_status(status: OperationStatus) -> Color {
+    match status {
+        OperationStatus::Pending | OperationStatus::Broadcast => Color::Yellow,
+        OperationStatus::Committed | OperationStatus::Acknowledged => Color::Green,
+        OperationStatus::Failed | OperationStatus::TimedOut => Color::Red,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    /// Writer which can be read after it is moved into an export writer
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn operation(id: i64) -> Operation {
+        Operation {
+            id,
+            request_id: Some(format!("request-{}", id)),
+            address: "cosmos1address".to_owned(),
+            denom: Some("gld".parse().unwrap()),
+            amount: Some(U256::from(100)),
+            operation_type: OperationType::Mint {
+                chain_id: "test-1".parse().unwrap(),
+            },
+            status: OperationStatus::Failed,
+            error: Some("insufficient fee, \"1000stake\"".to_owned()),
+            transaction_hash: None,
+            block_height: None,
+            gas_used: None,
+            fee_paid: None,
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            updated_at: "2024-01-01T00:00:01Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn csv_export_flattens_operations() {
+        let buffer = SharedBuffer::default();
+        let mut writer = ExportWriter::Csv(csv::Writer::from_writer(Box::new(buffer.clone())));
+
+        writer.write(operation(1)).unwrap();
+        writer.write(operation(2)).unwrap();
+        writer.flush().unwrap();
+
+        let lines: Vec<String> = buffer.contents().lines().map(ToOwned::to_owned).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "id,request_id,address,operation_type,chain_id,denom,amount,status,error,transaction_hash,block_height,gas_used,fee_paid,created_at,updated_at",
+                "1,request-1,cosmos1address,mint,test-1,gld,100,failed,\"insufficient fee, \"\"1000stake\"\"\",,,,,2024-01-01T00:00:00+00:00,2024-01-01T00:00:01+00:00",
+                "2,request-2,cosmos1address,mint,test-1,gld,100,failed,\"insufficient fee, \"\"1000stake\"\"\",,,,,2024-01-01T00:00:00+00:00,2024-01-01T00:00:01+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn ndjson_export_writes_one_operation_per_line() {
+        let buffer = SharedBuffer::default();
+        let mut writer = ExportWriter::Ndjson(Box::new(buffer.clone()));
+
+        writer.write(operation(1)).unwrap();
+        writer.write(operation(2)).unwrap();
+        writer.flush().unwrap();
+
+        let contents = buffer.contents();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        for (line, id) in lines.into_iter().zip([1, 2]) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value, serde_json::to_value(operation(id)).unwrap());
+        }
+    }
+
+    #[test]
+    fn history_rejects_cursor_with_offset() {
+        let result =
+            IbcCommand::from_iter_safe(["ibc", "history", "--cursor", "4", "--offset", "1"]);
+        assert!(result.is_err());
+
+        let result = IbcCommand::from_iter_safe(["ibc", "history", "--cursor", "4"]);
+        assert!(matches!(
+            result,
+            Ok(IbcCommand::History {
+                cursor: Some(4),
+                ..
+            })
+        ));
     }
 }
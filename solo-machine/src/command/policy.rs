@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use cli_table::{format::Justify, print_stdout, Cell, Color, Row, RowStruct, Style, Table};
+use primitive_types::U256;
+use serde_json::json;
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{MintLimits, MintPolicy},
+    service::PolicyService,
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::{ColorChoice, ColorSpec, StandardStream};
+
+use crate::output::OutputType;
+
+use super::{print_json, print_stream};
+
+#[derive(Debug, StructOpt)]
+pub enum PolicyCommand {
+    /// Adds or replaces mint policy for a denom on an IBC enabled chain. Once a chain has at least one mint policy, only
+    /// the denoms with a mint policy can be minted on that chain.
+    Set {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Denom of tokens
+        denom: Identifier,
+        /// Maximum amount of tokens that can be minted in a single operation
+        #[structopt(long, parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        max_amount: Option<U256>,
+        /// Maximum amount of tokens that can be minted in last 24 hours
+        #[structopt(long, parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        daily_limit: Option<U256>,
+        /// Maximum amount of tokens that can be minted in last 7 days
+        #[structopt(long, parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        weekly_limit: Option<U256>,
+        /// Maximum amount of tokens that can be minted to a single receiver in last 24 hours
+        #[structopt(long, parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        receiver_daily_limit: Option<U256>,
+        /// Maximum amount of tokens that can be minted to a single receiver in last 7 days
+        #[structopt(long, parse(try_from_str = ::primitive_types::U256::from_dec_str))]
+        receiver_weekly_limit: Option<U256>,
+    },
+    /// Removes mint policy for a denom on an IBC enabled chain
+    Remove {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Denom of tokens
+        denom: Identifier,
+    },
+    /// Lists all the mint policies
+    List {
+        /// Only list mint policies of IBC enabled chain with given chain ID
+        #[structopt(long)]
+        chain_id: Option<ChainId>,
+    },
+}
+
+impl PolicyCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputType,
+    ) -> Result<()> {
+        let policy_service = PolicyService::new(db_pool);
+
+        match self {
+            Self::Set {
+                chain_id,
+                denom,
+                max_amount,
+                daily_limit,
+                weekly_limit,
+                receiver_daily_limit,
+                receiver_weekly_limit,
+            } => {
+                let limits = MintLimits {
+                    max_amount,
+                    daily_limit,
+                    weekly_limit,
+                    receiver_daily_limit,
+                    receiver_weekly_limit,
+                };
+
+                let policy = policy_service
+                    .set_mint_policy(&chain_id, &denom, &limits)
+                    .await?;
+
+                match output {
+                    OutputType::Text => {
+                        let mut stdout = StandardStream::stdout(color_choice);
+                        print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_bold(true),
+                            "Mint policy updated!",
+                        )?;
+
+                        print_policies(vec![policy], color_choice)
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": policy,
+                        }),
+                    ),
+                }
+            }
+            Self::Remove { chain_id, denom } => {
+                policy_service.remove_mint_policy(&chain_id, &denom).await?;
+
+                match output {
+                    OutputType::Text => {
+                        let mut stdout = StandardStream::stdout(color_choice);
+                        print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_bold(true),
+                            "Mint policy removed!",
+                        )
+                    }
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": "Mint policy removed!",
+                        }),
+                    ),
+                }
+            }
+            Self::List { chain_id } => {
+                let policies = policy_service.get_mint_policies(chain_id.as_ref()).await?;
+
+                match output {
+                    OutputType::Text => print_policies(policies, color_choice),
+                    OutputType::Json => print_json(
+                        color_choice,
+                        json!({
+                            "result": "success",
+                            "data": policies,
+                        }),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+fn print_policies(policies: Vec<MintPolicy>, color_choice: ColorChoice) -> Result<()> {
+    let table = policies
+        .into_iter()
+        .map(into_row)
+        .collect::<Vec<RowStruct>>()
+        .table()
+        .title(vec![
+            "Chain ID".cell().bold(true),
+            "Denom".cell().bold(true),
+            "Max amount".cell().bold(true),
+            "Daily limit".cell().bold(true),
+            "Weekly limit".cell().bold(true),
+            "Receiver daily limit".cell().bold(true),
+            "Receiver weekly limit".cell().bold(true),
+            "Updated at".cell().bold(true),
+        ])
+        .color_choice(color_choice);
+
+    print_stdout(table).context("unable to print table to stdout")
+}
+
+fn into_row(policy: MintPolicy) -> RowStruct {
+    vec![
+        policy.chain_id.cell(),
+        policy.denom.cell().foreground_color(Some(Color::Green)),
+        limit_cell(policy.limits.max_amount),
+        limit_cell(policy.limits.daily_limit),
+        limit_cell(policy.limits.weekly_limit),
+        limit_cell(policy.limits.receiver_daily_limit),
+        limit_cell(policy.limits.receiver_weekly_limit),
+        policy.updated_at.cell(),
+    ]
+    .row()
+}
+
+fn limit_cell(limit: Option<U256>) -> cli_table::CellStruct {
+    limit
+        .map(|limit| limit.to_string())
+        .unwrap_or_else(|| "-".to_string())
+        .cell()
+        .justify(Justify::Right)
+}
@@ -1,98 +1,345 @@
+pub mod broadcaster;
 pub mod cli_event_handler;
 pub mod env_logger;
+pub mod handler;
+pub mod metrics_collector;
+pub mod outbox;
 
-use std::{convert::TryFrom, ffi::OsStr, path::PathBuf};
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Error, Result};
-use async_trait::async_trait;
-use libloading::{Library, Symbol};
+use anyhow::{anyhow, ensure, Context, Result};
 use solo_machine_core::{
-    event::{EventHandler, HandlerRegistrar as IHandlerRegistrar},
-    Event,
+    event::{EventHandler, HandlerOptions, HandlerRegistrar as IHandlerRegistrar},
+    DbPool, Event,
 };
+use solo_machine_plugin::host::Plugin;
 use tokio::{
-    sync::mpsc::{unbounded_channel, UnboundedSender},
+    sync::mpsc::{channel, error::TrySendError, unbounded_channel, Sender, UnboundedSender},
     task::JoinHandle,
 };
 
-#[derive(Default)]
+use self::{handler::NamedHandler, outbox::OutboxDispatcher};
+
+/// Registrar of event handlers. Each event handler runs in its own task (with its own queue, error policy and
+/// timeout), so, a slow or failing event handler does not delay or stop other event handlers. Event handlers
+/// registered by solo machine itself receive events as soon as they're emitted, whereas event handlers loaded from
+/// event hooks are durable, i.e., they receive events from event outbox (so that no event is lost if an event hook
+/// fails or solo machine crashes).
 pub struct HandlerRegistrar {
-    event_handlers: Vec<Box<dyn EventHandler>>,
+    event_handlers: Vec<NamedHandler>,
+    durable_handlers: Vec<NamedHandler>,
+    /// Options used for event handlers registered without explicit options
+    default_options: HandlerOptions,
+    /// Name of event hook currently being loaded and number of handlers registered by it (handlers registered while
+    /// loading an event hook are durable)
+    loading_hook: Option<(String, usize)>,
 }
 
 impl HandlerRegistrar {
-    pub fn spawn(self) -> (UnboundedSender<Event>, JoinHandle<Result<()>>) {
-        let (sender, mut receiver) = unbounded_channel();
+    /// Creates a new registrar and registers event handlers from given event hooks
+    pub fn new(files: Vec<PathBuf>, default_options: HandlerOptions) -> Result<Self> {
+        let mut registrar = Self {
+            event_handlers: Vec::new(),
+            durable_handlers: Vec::new(),
+            default_options,
+            loading_hook: None,
+        };
+
+        for file in files.iter() {
+            registrar.register_handler(file)?;
+        }
+
+        Ok(registrar)
+    }
+
+    /// Registers an event handler of solo machine with given name (used in logs)
+    pub fn register_builtin(&mut self, name: &str, handler: Box<dyn EventHandler>) {
+        self.event_handlers.push(NamedHandler {
+            name: name.to_string(),
+            handler,
+            options: self.default_options,
+        })
+    }
+
+    /// Spawns a task for each event handler and returns a sender for emitting events. Returned task finishes once the
+    /// sender is dropped and all the event handlers are done (it returns an error if any event handler of solo machine
+    /// halted). Errors of durable event handlers are only logged because their events remain in event outbox.
+    pub async fn spawn(
+        self,
+        db_pool: DbPool,
+    ) -> Result<(UnboundedSender<Event>, JoinHandle<Result<()>>)> {
+        let (sender, mut receiver) = unbounded_channel::<Event>();
+
+        let mut tasks = Vec::new();
+        let mut durable_tasks = Vec::new();
+        let mut queues: Vec<(String, Sender<Event>)> = Vec::new();
+        let mut wakers: Vec<Sender<()>> = Vec::new();
+
+        for handler in self.event_handlers {
+            let (queue, queue_receiver) = channel(handler.options.queue_size.max(1));
+            queues.push((handler.name.clone(), queue));
+            tasks.push(tokio::spawn(handler.run(queue_receiver)));
+        }
+
+        for handler in self.durable_handlers {
+            // Wake-ups are coalesced because dispatcher fetches all the pending events from outbox anyway
+            let (waker, wake_receiver) = channel(1);
+            wakers.push(waker);
+            let dispatcher = OutboxDispatcher::new(db_pool.clone(), handler).await?;
+            durable_tasks.push(tokio::spawn(dispatcher.run(wake_receiver)));
+        }
 
         let handle = tokio::spawn(async move {
             while let Some(event) = receiver.recv().await {
-                self.handle(event).await?;
+                for (name, queue) in queues.iter() {
+                    if let Err(TrySendError::Full(event)) = queue.try_send(event.clone()) {
+                        log::warn!(
+                            "queue of event handler {} is full (dropping event): {:?}",
+                            name,
+                            event
+                        );
+                    }
+                }
+
+                for waker in wakers.iter() {
+                    let _ = waker.try_send(());
+                }
             }
 
-            Ok(())
+            // Dropping queues and wakers lets event handlers finish (durable event handlers deliver remaining events
+            // from outbox before finishing)
+            drop(queues);
+            drop(wakers);
+
+            for task in durable_tasks {
+                let task_result = task
+                    .await
+                    .context("unable to join event handler task")
+                    .and_then(|task_result| task_result);
+
+                if let Err(err) = task_result {
+                    log::error!("{:#} (pending events will be delivered on next start)", err);
+                }
+            }
+
+            let mut result = Ok(());
+
+            for task in tasks {
+                let task_result = task
+                    .await
+                    .context("unable to join event handler task")
+                    .and_then(|task_result| task_result);
+
+                if let Err(err) = task_result {
+                    log::error!("{:#}", err);
+
+                    if result.is_ok() {
+                        result = Err(err);
+                    }
+                }
+            }
+
+            result
         });
 
-        (sender, handle)
+        Ok((sender, handle))
     }
 
-    // TODO: remove conditional compilation when this issue is fixed:
-    // https://github.com/nagisa/rust_libloading/issues/41
-    fn register_handler(&mut self, file: impl AsRef<OsStr>) -> Result<()> {
-        unsafe {
-            #[cfg(target_os = "linux")]
-            let library: Library = {
-                // Load library with `RTLD_NOW | RTLD_NODELETE` to fix a SIGSEGV
-                libloading::os::unix::Library::open(
-                    Some(file),
-                    libloading::os::unix::RTLD_NOW | 0x1000,
-                )
-                .context("unable to load event handler")?
-                .into()
-            };
-            #[cfg(not(target_os = "linux"))]
-            let library = Library::new(file).context("unable to load event handler")?;
-
-            let register_fn: Symbol<
-                unsafe extern "C" fn(&mut dyn IHandlerRegistrar) -> Result<()>,
-            > = library
-                .get("register_handler".as_bytes())
-                .context("unable to load `register_handler` function from event hook")?;
-
-            register_fn(self).context("unable to register event handler")?;
-        }
+    fn register_handler(&mut self, file: &Path) -> Result<()> {
+        let name = file
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| anyhow!("invalid event hook file name: {}", file.display()))?;
+
+        ensure!(
+            !self
+                .durable_handlers
+                .iter()
+                .any(|durable| durable.name == name),
+            "event hook with name {} is already registered",
+            name
+        );
+
+        self.loading_hook = Some((name.to_string(), 0));
+        let result = self.load_library(file);
+        self.loading_hook = None;
 
-        Ok(())
+        result
     }
-}
 
-#[async_trait]
-impl EventHandler for HandlerRegistrar {
-    async fn handle(&self, event: Event) -> Result<()> {
-        // TODO: parallelise this
-        for handler in self.event_handlers.iter() {
-            handler.handle(event.clone()).await?;
-        }
+    fn load_library(&mut self, file: &Path) -> Result<()> {
+        let plugin = Plugin::load(file)
+            .with_context(|| format!("unable to load event hook: {}", file.display()))?;
 
-        Ok(())
+        plugin
+            .register_handlers(self)
+            .context("unable to register event handler")
     }
 }
 
 impl IHandlerRegistrar for HandlerRegistrar {
     fn register(&mut self, handler: Box<dyn EventHandler>) {
-        self.event_handlers.push(handler)
+        self.register_with_options(handler, self.default_options)
+    }
+
+    fn register_with_options(&mut self, handler: Box<dyn EventHandler>, options: HandlerOptions) {
+        match self.loading_hook {
+            Some((ref hook, ref mut count)) => {
+                // Event hooks registering multiple handlers get a separate cursor for each handler
+                let name = if *count == 0 {
+                    hook.clone()
+                } else {
+                    format!("{}-{}", hook, *count + 1)
+                };
+                *count += 1;
+
+                self.durable_handlers.push(NamedHandler {
+                    name,
+                    handler,
+                    options,
+                })
+            }
+            None => {
+                let name = format!("handler-{}", self.event_handlers.len() + 1);
+
+                self.event_handlers.push(NamedHandler {
+                    name,
+                    handler,
+                    options,
+                })
+            }
+        }
     }
 }
 
-impl TryFrom<Vec<PathBuf>> for HandlerRegistrar {
-    type Error = Error;
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
 
-    fn try_from(files: Vec<PathBuf>) -> Result<Self, Self::Error> {
-        let mut registrar = Self::default();
+    use async_trait::async_trait;
+    use primitive_types::U256;
+    use solo_machine_core::event::ErrorPolicy;
 
-        for file in files.iter() {
-            registrar.register_handler(file)?;
+    use super::*;
+
+    fn tokens_minted(request_id: &str) -> Event {
+        Event::TokensMinted {
+            chain_id: "test-1".parse().unwrap(),
+            request_id: Some(request_id.to_owned()),
+            to_address: "cosmos1address".to_owned(),
+            amount: U256::from(100),
+            denom: "gld".parse().unwrap(),
+            transaction_hash: "ABCD".to_owned(),
         }
+    }
 
-        Ok(registrar)
+    /// Records request IDs of handled events
+    struct RecordingHandler(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn handle(&self, event: Event) -> Result<()> {
+            if let Event::TokensMinted { request_id, .. } = event {
+                self.0.lock().unwrap().push(request_id.unwrap());
+            }
+
+            Ok(())
+        }
+    }
+
+    struct FailingHandler;
+
+    #[async_trait]
+    impl EventHandler for FailingHandler {
+        async fn handle(&self, _: Event) -> Result<()> {
+            Err(anyhow!("unable to handle event"))
+        }
+    }
+
+    struct StuckHandler;
+
+    #[async_trait]
+    impl EventHandler for StuckHandler {
+        async fn handle(&self, _: Event) -> Result<()> {
+            std::future::pending().await
+        }
+    }
+
+    fn registrar_with(
+        handler: Box<dyn EventHandler>,
+        options: HandlerOptions,
+    ) -> (HandlerRegistrar, Arc<Mutex<Vec<String>>>) {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+
+        let mut registrar = HandlerRegistrar::new(Vec::new(), HandlerOptions::default()).unwrap();
+        registrar.register_with_options(handler, options);
+        registrar.register(Box::new(RecordingHandler(handled.clone())));
+
+        (registrar, handled)
+    }
+
+    #[tokio::test]
+    async fn halted_handler_does_not_stop_other_handlers() {
+        let options = HandlerOptions {
+            error_policy: ErrorPolicy::Halt,
+            ..Default::default()
+        };
+        let (registrar, handled) = registrar_with(Box::new(FailingHandler), options);
+
+        let (sender, handle) = registrar
+            .spawn(crate::server::test_db_pool().await)
+            .await
+            .unwrap();
+
+        for request_id in ["1", "2", "3"] {
+            sender.send(tokens_minted(request_id)).unwrap();
+        }
+
+        drop(sender);
+
+        let err = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("event handlers did not finish")
+            .unwrap()
+            .unwrap_err();
+
+        assert!(format!("{:#}", err).contains("event handler handler-1 halted"));
+        assert_eq!(*handled.lock().unwrap(), vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn stuck_handler_does_not_delay_other_handlers() {
+        let options = HandlerOptions {
+            timeout: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let (registrar, handled) = registrar_with(Box::new(StuckHandler), options);
+
+        let (sender, handle) = registrar
+            .spawn(crate::server::test_db_pool().await)
+            .await
+            .unwrap();
+
+        for request_id in ["1", "2", "3"] {
+            sender.send(tokens_minted(request_id)).unwrap();
+        }
+
+        let delivered = tokio::time::timeout(Duration::from_secs(5), async {
+            while handled.lock().unwrap().len() < 3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+
+        handle.abort();
+
+        assert!(
+            delivered.is_ok(),
+            "events were not delivered to other event handlers"
+        );
+        assert_eq!(*handled.lock().unwrap(), vec!["1", "2", "3"]);
     }
 }
@@ -0,0 +1,48 @@
+use std::time::SystemTime;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{event::EventHandler, Event};
+use tokio::sync::broadcast::{self, Receiver, Sender};
+
+/// Number of events buffered for each subscriber before it starts lagging behind
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// An event along with the time at which it was emitted
+#[derive(Debug, Clone)]
+pub struct EmittedEvent {
+    pub emitted_at: SystemTime,
+    pub event: Event,
+}
+
+/// Event handler which fans out all the events to its subscribers (used for streaming events over gRPC)
+#[derive(Clone)]
+pub struct EventBroadcaster {
+    sender: Sender<EmittedEvent>,
+}
+
+impl EventBroadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        Self { sender }
+    }
+
+    /// Returns a receiver which receives all the events emitted after subscribing
+    pub fn subscribe(&self) -> Receiver<EmittedEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[async_trait]
+impl EventHandler for EventBroadcaster {
+    async fn handle(&self, event: Event) -> Result<()> {
+        // Sending only fails when there are no subscribers, in which case the event can be dropped
+        let _ = self.sender.send(EmittedEvent {
+            emitted_at: SystemTime::now(),
+            event,
+        });
+
+        Ok(())
+    }
+}
@@ -53,8 +53,75 @@ impl CliEventHandler {
 
                 print_stdout(table).context("unable to print table to stdout")?;
             }
+            Event::ChainConfigUpdated { chain_id } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Chain config updated!",
+                )?;
+
+                writeln!(stdout)?;
+
+                let table = vec![vec![
+                    "Chain ID".cell().bold(true),
+                    format!("{}", chain_id)
+                        .cell()
+                        .bold(true)
+                        .foreground_color(Some(Color::Green))
+                        .justify(Justify::Right),
+                ]]
+                .table()
+                .color_choice(self.color_choice);
+
+                print_stdout(table).context("unable to print table to stdout")?;
+            }
+            Event::ChainArchived { chain_id } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Chain archived!",
+                )?;
+
+                writeln!(stdout)?;
+
+                let table = vec![vec![
+                    "Chain ID".cell().bold(true),
+                    format!("{}", chain_id)
+                        .cell()
+                        .bold(true)
+                        .foreground_color(Some(Color::Green))
+                        .justify(Justify::Right),
+                ]]
+                .table()
+                .color_choice(self.color_choice);
+
+                print_stdout(table).context("unable to print table to stdout")?;
+            }
+            Event::ChainRemoved { chain_id } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Chain removed!",
+                )?;
+
+                writeln!(stdout)?;
+
+                let table = vec![vec![
+                    "Chain ID".cell().bold(true),
+                    format!("{}", chain_id)
+                        .cell()
+                        .bold(true)
+                        .foreground_color(Some(Color::Green))
+                        .justify(Justify::Right),
+                ]]
+                .table()
+                .color_choice(self.color_choice);
+
+                print_stdout(table).context("unable to print table to stdout")?;
+            }
             Event::CloseChannelInitOnSoloMachine {
                 chain_id,
+                request_id,
                 channel_id,
             } => {
                 print_stream(
@@ -66,6 +133,11 @@ impl CliEventHandler {
                 let mut table = Vec::new();
 
                 add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Request ID",
+                    request_id.as_deref().unwrap_or("-"),
+                );
                 add_row(&mut table, "Solo Machine Channel Id", channel_id);
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
@@ -132,7 +204,160 @@ impl CliEventHandler {
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
             }
-            Event::SignerUpdated { chain_id, .. } => {
+            Event::MintRejected {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                reason,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    "Mint rejected by policy!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Request ID",
+                    request_id.as_deref().unwrap_or("-"),
+                );
+                add_row(&mut table, "To", to_address);
+                add_row(&mut table, "Amount", amount);
+                add_row(&mut table, "Denom", denom);
+                add_row(&mut table, "Reason", reason);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::MintFailed {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    if timed_out {
+                        "Mint timed out!"
+                    } else {
+                        "Mint failed!"
+                    },
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Request ID",
+                    request_id.as_deref().unwrap_or("-"),
+                );
+                add_row(&mut table, "To", to_address);
+                add_row(&mut table, "Amount", amount);
+                add_row(&mut table, "Denom", denom);
+                add_row(
+                    &mut table,
+                    "Transaction Hash",
+                    transaction_hash.as_deref().unwrap_or("-"),
+                );
+                add_row(&mut table, "Error", error);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::BurnFailed {
+                chain_id,
+                request_id,
+                from_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    if timed_out {
+                        "Burn timed out!"
+                    } else {
+                        "Burn failed!"
+                    },
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Request ID",
+                    request_id.as_deref().unwrap_or("-"),
+                );
+                add_row(&mut table, "From", from_address);
+                add_row(&mut table, "Amount", amount);
+                add_row(&mut table, "Denom", denom);
+                add_row(
+                    &mut table,
+                    "Transaction Hash",
+                    transaction_hash.as_deref().unwrap_or("-"),
+                );
+                add_row(&mut table, "Error", error);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::AcknowledgementSent {
+                packet_sequence,
+                transaction_hash,
+                ..
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Sent acknowledgement to IBC enabled chain [Packet Sequence = {}] [Transaction Hash = {}]",
+                        packet_sequence, transaction_hash
+                    ),
+                )?;
+            }
+            Event::SoloMachineClientUpdated { client_id, .. } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Updated solo machine client on IBC enabled chain [Client ID = {}]",
+                        client_id
+                    ),
+                )?;
+            }
+            Event::SequenceIncremented { sequence, .. } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Incremented solo machine sequence [Sequence = {}]",
+                        sequence
+                    ),
+                )?;
+            }
+            Event::SignerUpdated {
+                chain_id,
+                request_id,
+                ..
+            } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -143,11 +368,17 @@ impl CliEventHandler {
                 let mut table = Vec::new();
 
                 add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Request ID",
+                    request_id.as_deref().unwrap_or("-"),
+                );
 
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
             }
-            Event::CreatedSoloMachineClient { client_id } => {
+            Event::TransactionBroadcast { .. } => {}
+            Event::CreatedSoloMachineClient { client_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -157,7 +388,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::CreatedTendermintClient { client_id } => {
+            Event::CreatedTendermintClient { client_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -167,7 +398,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::InitializedConnectionOnTendermint { connection_id } => {
+            Event::InitializedConnectionOnTendermint { connection_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -177,7 +408,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::InitializedConnectionOnSoloMachine { connection_id } => {
+            Event::InitializedConnectionOnSoloMachine { connection_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -187,7 +418,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::ConfirmedConnectionOnTendermint { connection_id } => {
+            Event::ConfirmedConnectionOnTendermint { connection_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -197,7 +428,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::ConfirmedConnectionOnSoloMachine { connection_id } => {
+            Event::ConfirmedConnectionOnSoloMachine { connection_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -207,7 +438,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::InitializedChannelOnTendermint { channel_id } => {
+            Event::InitializedChannelOnTendermint { channel_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -217,7 +448,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::InitializedChannelOnSoloMachine { channel_id } => {
+            Event::InitializedChannelOnSoloMachine { channel_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -227,7 +458,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::ConfirmedChannelOnTendermint { channel_id } => {
+            Event::ConfirmedChannelOnTendermint { channel_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -237,7 +468,7 @@ impl CliEventHandler {
                     ),
                 )?;
             }
-            Event::ConfirmedChannelOnSoloMachine { channel_id } => {
+            Event::ConfirmedChannelOnSoloMachine { channel_id, .. } => {
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
@@ -247,9 +478,36 @@ impl CliEventHandler {
                     ),
                 )?;
             }
+            Event::ChannelClosed {
+                chain_id,
+                solo_machine_channel_id,
+                tendermint_channel_id,
+                ..
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Channel closed!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(
+                    &mut table,
+                    "Solo machine channel ID",
+                    solo_machine_channel_id,
+                );
+                add_row(&mut table, "Tendermint channel ID", tendermint_channel_id);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
             Event::ConnectionEstablished {
                 chain_id,
                 connection_details,
+                ..
             } => {
                 print_stream(
                     &mut stdout,
@@ -295,6 +553,33 @@ impl CliEventHandler {
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
             }
+            Event::SupplyDiscrepancy {
+                chain_id,
+                denom,
+                ibc_denom,
+                ledger_supply,
+                on_chain_supply,
+                escrow_balance,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    "Supply discrepancy found!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "Denom", denom);
+                add_row(&mut table, "IBC denom", ibc_denom);
+                add_row(&mut table, "Ledger supply", ledger_supply);
+                add_row(&mut table, "On-chain supply", on_chain_supply);
+                add_row(&mut table, "Escrow balance", escrow_balance);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
             Event::Warning { message } => {
                 print_stream(
                     &mut stdout,
@@ -309,6 +594,7 @@ impl CliEventHandler {
 
     fn handle_json_output(&self, event: Event) -> Result<()> {
         match event {
+            Event::TransactionBroadcast { .. } => Ok(()),
             Event::Warning { message } => print_json(
                 self.color_choice,
                 json!({
@@ -316,6 +602,16 @@ impl CliEventHandler {
                     "data": message,
                 }),
             ),
+            Event::SupplyDiscrepancy { .. }
+            | Event::MintRejected { .. }
+            | Event::MintFailed { .. }
+            | Event::BurnFailed { .. } => print_json(
+                self.color_choice,
+                json!({
+                    "result": "warning",
+                    "data": event,
+                }),
+            ),
             _ => print_json(
                 self.color_choice,
                 json!({
@@ -48,65 +48,198 @@ impl EventHandler for EnvLogger {
                 denom,
                 transaction_hash,
             ),
+            Event::MintRejected {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                reason,
+            } => log::warn!(
+                "Mint rejected by policy [Chain ID = {}] [Request ID = {}] [Address = {}] [Amount = {} {}] [Reason = {}]",
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
+                to_address,
+                amount,
+                denom,
+                reason,
+            ),
+            Event::MintFailed {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => log::error!(
+                "{} [Chain ID = {}] [Request ID = {}] [Address = {}] [Amount = {} {}] [Transaction Hash = {}] [Error = {}]",
+                if timed_out { "Mint timed out" } else { "Failed to mint tokens" },
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
+                to_address,
+                amount,
+                denom,
+                transaction_hash.unwrap_or_else(|| "None".to_string()),
+                error,
+            ),
+            Event::BurnFailed {
+                chain_id,
+                request_id,
+                from_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => log::error!(
+                "{} [Chain ID = {}] [Request ID = {}] [Address = {}] [Amount = {} {}] [Transaction Hash = {}] [Error = {}]",
+                if timed_out { "Burn timed out" } else { "Failed to burn tokens" },
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
+                from_address,
+                amount,
+                denom,
+                transaction_hash.unwrap_or_else(|| "None".to_string()),
+                error,
+            ),
+            Event::AcknowledgementSent {
+                chain_id,
+                request_id,
+                packet_sequence,
+                transaction_hash,
+            } => log::info!(
+                "Sent acknowledgement to IBC enabled chain [Chain ID = {}] [Request ID = {}] [Packet Sequence = {}] [Transaction Hash = {}]",
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
+                packet_sequence,
+                transaction_hash,
+            ),
+            Event::SoloMachineClientUpdated {
+                chain_id,
+                client_id,
+                transaction_hash,
+                ..
+            } => log::info!(
+                "Updated solo machine client on IBC enabled chain [Chain ID = {}] [Client ID = {}] [Transaction Hash = {}]",
+                chain_id,
+                client_id,
+                transaction_hash,
+            ),
+            Event::SequenceIncremented {
+                chain_id,
+                sequence,
+                packet_sequence,
+                ..
+            } => log::debug!(
+                "Incremented solo machine sequence [Chain ID = {}] [Sequence = {}] [Packet Sequence = {}]",
+                chain_id,
+                sequence,
+                packet_sequence,
+            ),
             Event::SignerUpdated {
                 chain_id,
+                request_id,
                 old_public_key: _,
                 new_public_key: _,
             } => log::info!(
-                "Successfully updated signer's public key [Chain ID: {}]",
-                chain_id
+                "Successfully updated signer's public key [Chain ID = {}] [Request ID = {}]",
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
             ),
+            Event::TransactionBroadcast {
+                chain_id,
+                transaction_type,
+                transaction_hash,
+                duration_ms,
+                error_class,
+                ..
+            } => match error_class {
+                None => log::debug!(
+                    "Broadcasted {} transaction [Chain ID: {}] [Hash: {}] [Duration: {}ms]",
+                    transaction_type,
+                    chain_id,
+                    transaction_hash,
+                    duration_ms
+                ),
+                Some(error_class) => log::debug!(
+                    "Failed to broadcast {} transaction [Chain ID: {}] [Hash: {}] [Duration: {}ms] [Error: {}]",
+                    transaction_type,
+                    chain_id,
+                    transaction_hash,
+                    duration_ms,
+                    error_class
+                ),
+            },
 
-            Event::CreatedSoloMachineClient { client_id } => {
+            Event::CreatedSoloMachineClient { client_id, .. } => {
                 log::info!(
                     "Created solo machine client on IBC enabled chain [Client ID = {}]",
                     client_id
                 )
             }
-            Event::CreatedTendermintClient { client_id } => log::info!(
+            Event::CreatedTendermintClient { client_id, .. } => log::info!(
                 "Created tendermint client on solo machine [Client ID = {}]",
                 client_id
             ),
-            Event::InitializedConnectionOnTendermint { connection_id } => log::info!(
+            Event::InitializedConnectionOnTendermint { connection_id, .. } => log::info!(
                 "Initialized connection on IBC enabled chain [Connection ID = {}]",
                 connection_id
             ),
-            Event::InitializedConnectionOnSoloMachine { connection_id } => log::info!(
+            Event::InitializedConnectionOnSoloMachine { connection_id, .. } => log::info!(
                 "Initialized connection on solo machine [Connection ID = {}]",
                 connection_id
             ),
-            Event::CloseChannelInitOnSoloMachine { chain_id, channel_id } => log::info!(
-                "Close channel initialized on solo machine [Chain ID = {}] [Channel ID = {}]",
+            Event::CloseChannelInitOnSoloMachine {
                 chain_id,
+                request_id,
+                channel_id,
+            } => log::info!(
+                "Close channel initialized on solo machine [Chain ID = {}] [Request ID = {}] [Channel ID = {}]",
+                chain_id,
+                request_id.unwrap_or_else(|| "None".to_string()),
                 channel_id,
             ),
-            Event::ConfirmedConnectionOnTendermint { connection_id } => log::info!(
+            Event::ConfirmedConnectionOnTendermint { connection_id, .. } => log::info!(
                 "Confirmed connection on IBC enabled chain [Connection ID = {}]",
                 connection_id
             ),
-            Event::ConfirmedConnectionOnSoloMachine { connection_id } => log::info!(
+            Event::ConfirmedConnectionOnSoloMachine { connection_id, .. } => log::info!(
                 "Confirmed connection on solo machine [Connection ID = {}]",
                 connection_id
             ),
-            Event::InitializedChannelOnTendermint { channel_id } => log::info!(
+            Event::InitializedChannelOnTendermint { channel_id, .. } => log::info!(
                 "Initialized channel on IBC enabled chain [Channel ID = {}]",
                 channel_id
             ),
-            Event::InitializedChannelOnSoloMachine { channel_id } => log::info!(
+            Event::InitializedChannelOnSoloMachine { channel_id, .. } => log::info!(
                 "Initialized channel on solo machine [Channel ID = {}]",
                 channel_id
             ),
-            Event::ConfirmedChannelOnTendermint { channel_id } => log::info!(
+            Event::ConfirmedChannelOnTendermint { channel_id, .. } => log::info!(
                 "Confirmed channel on IBC enabled chain [Channel ID = {}]",
                 channel_id
             ),
-            Event::ConfirmedChannelOnSoloMachine { channel_id } => log::info!(
+            Event::ConfirmedChannelOnSoloMachine { channel_id, .. } => log::info!(
                 "Confirmed channel on solo machine [Channel ID = {}]",
                 channel_id
             ),
+            Event::ChannelClosed {
+                chain_id,
+                solo_machine_channel_id,
+                tendermint_channel_id,
+                ..
+            } => log::info!(
+                "Closed channel [Chain ID = {}] [Solo Machine Channel ID = {}] [Tendermint Channel ID = {}]",
+                chain_id,
+                solo_machine_channel_id,
+                tendermint_channel_id,
+            ),
             Event::ConnectionEstablished {
                 chain_id,
                 connection_details,
+                ..
             } => log::info!(
                 "Connection successfully established [Chain ID = {}] [Details = {}]",
                 chain_id,
@@ -115,6 +248,31 @@ impl EventHandler for EnvLogger {
             Event::ChainAdded { chain_id } => {
                 log::info!("Added new chain [Chain ID = {}]", chain_id)
             }
+            Event::ChainConfigUpdated { chain_id } => {
+                log::info!("Updated chain config [Chain ID = {}]", chain_id)
+            }
+            Event::ChainArchived { chain_id } => {
+                log::info!("Archived chain [Chain ID = {}]", chain_id)
+            }
+            Event::ChainRemoved { chain_id } => {
+                log::info!("Removed chain [Chain ID = {}]", chain_id)
+            }
+            Event::SupplyDiscrepancy {
+                chain_id,
+                denom,
+                ibc_denom,
+                ledger_supply,
+                on_chain_supply,
+                escrow_balance,
+            } => log::warn!(
+                "Supply discrepancy found [Chain ID = {}] [Denom = {}] [IBC Denom = {}] [Ledger Supply = {}] [On-chain Supply = {}] [Escrow Balance = {}]",
+                chain_id,
+                denom,
+                ibc_denom,
+                ledger_supply,
+                on_chain_supply,
+                escrow_balance,
+            ),
             Event::Warning { message } => log::warn!("{}", message),
         }
 
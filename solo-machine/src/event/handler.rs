@@ -0,0 +1,245 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use solo_machine_core::{
+    event::{ErrorPolicy, EventHandler, HandlerOptions},
+    Event,
+};
+use tokio::sync::mpsc::Receiver;
+
+/// Delay before first retry (doubled after every retry)
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Maximum delay between two retries
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// An event handler along with its name (used in logs and as key of its cursor in event outbox) and options
+pub struct NamedHandler {
+    pub name: String,
+    pub handler: Box<dyn EventHandler>,
+    pub options: HandlerOptions,
+}
+
+impl NamedHandler {
+    /// Handles an event within configured timeout. When error policy is `retry`, failed attempts are retried with
+    /// exponential backoff and the error of last attempt is returned.
+    pub async fn handle(&self, event: &Event) -> Result<()> {
+        let max_retries = match self.options.error_policy {
+            ErrorPolicy::Retry => self.options.max_retries,
+            ErrorPolicy::Skip | ErrorPolicy::Halt => 0,
+        };
+
+        let mut backoff = INITIAL_BACKOFF;
+        let mut retries = 0;
+
+        loop {
+            match self.try_handle(event).await {
+                Ok(()) => return Ok(()),
+                Err(err) if retries >= max_retries => {
+                    return Err(err.context(format!("failed after {} retries", retries)))
+                }
+                Err(err) => {
+                    log::warn!(
+                        "event handler {} failed to handle event (retrying in {}): {:#}",
+                        self.name,
+                        humantime::format_duration(backoff),
+                        err
+                    );
+
+                    tokio::time::sleep(backoff).await;
+
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    retries += 1;
+                }
+            }
+        }
+    }
+
+    async fn try_handle(&self, event: &Event) -> Result<()> {
+        tokio::time::timeout(self.options.timeout, self.handler.handle(event.clone()))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "timed out after {}",
+                    humantime::format_duration(self.options.timeout)
+                )
+            })?
+    }
+
+    /// Handles events from given queue until it is closed (or event handler halts)
+    pub async fn run(self, mut receiver: Receiver<Event>) -> Result<()> {
+        while let Some(event) = receiver.recv().await {
+            if let Err(err) = self.handle(&event).await {
+                match self.options.error_policy {
+                    ErrorPolicy::Retry | ErrorPolicy::Skip => log::error!(
+                        "event handler {} failed to handle event {:?} (dropping event): {:#}",
+                        self.name,
+                        event,
+                        err
+                    ),
+                    ErrorPolicy::Halt => {
+                        return Err(err.context(format!("event handler {} halted", self.name)))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    };
+
+    use async_trait::async_trait;
+    use primitive_types::U256;
+    use tokio::sync::mpsc::channel;
+
+    use super::*;
+
+    fn tokens_minted(request_id: &str) -> Event {
+        Event::TokensMinted {
+            chain_id: "test-1".parse().unwrap(),
+            request_id: Some(request_id.to_owned()),
+            to_address: "cosmos1address".to_owned(),
+            amount: U256::from(100),
+            denom: "gld".parse().unwrap(),
+            transaction_hash: "ABCD".to_owned(),
+        }
+    }
+
+    fn request_id(event: &Event) -> String {
+        match event {
+            Event::TokensMinted { request_id, .. } => request_id.clone().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Records handled events. Never finishes handling events with request ID `slow` and fails to handle events with
+    /// request ID `fail` (and the first `failures` events).
+    #[derive(Default)]
+    struct TestHandler {
+        handled: Arc<Mutex<Vec<String>>>,
+        failures: AtomicU32,
+    }
+
+    #[async_trait]
+    impl EventHandler for TestHandler {
+        async fn handle(&self, event: Event) -> Result<()> {
+            let request_id = request_id(&event);
+
+            match request_id.as_str() {
+                "slow" => std::future::pending().await,
+                "fail" => Err(anyhow!("unable to handle event")),
+                _ if self
+                    .failures
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| {
+                        failures.checked_sub(1)
+                    })
+                    .is_ok() =>
+                {
+                    Err(anyhow!("temporary failure"))
+                }
+                _ => {
+                    self.handled.lock().unwrap().push(request_id);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn named_handler(
+        error_policy: ErrorPolicy,
+        failures: u32,
+    ) -> (NamedHandler, Arc<Mutex<Vec<String>>>) {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = NamedHandler {
+            name: "test".to_owned(),
+            handler: Box::new(TestHandler {
+                handled: handled.clone(),
+                failures: AtomicU32::new(failures),
+            }),
+            options: HandlerOptions {
+                error_policy,
+                timeout: Duration::from_millis(50),
+                max_retries: 1,
+                ..Default::default()
+            },
+        };
+
+        (handler, handled)
+    }
+
+    async fn run(handler: NamedHandler, request_ids: &[&str]) -> Result<()> {
+        let (sender, receiver) = channel(request_ids.len());
+
+        for request_id in request_ids {
+            sender.send(tokens_minted(request_id)).await.unwrap();
+        }
+
+        drop(sender);
+
+        tokio::time::timeout(Duration::from_secs(5), handler.run(receiver))
+            .await
+            .expect("event handler did not finish")
+    }
+
+    #[tokio::test]
+    async fn handling_is_cut_off_after_timeout() {
+        let (handler, _) = named_handler(ErrorPolicy::Skip, 0);
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            handler.handle(&tokens_minted("slow")),
+        )
+        .await
+        .expect("timeout is not applied")
+        .unwrap_err();
+
+        assert!(format!("{:#}", err).contains("timed out after 50ms"));
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_retried() {
+        let (handler, handled) = named_handler(ErrorPolicy::Retry, 1);
+        handler.handle(&tokens_minted("1")).await.unwrap();
+        assert_eq!(*handled.lock().unwrap(), vec!["1"]);
+
+        let (handler, handled) = named_handler(ErrorPolicy::Retry, 2);
+        let err = handler.handle(&tokens_minted("1")).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("failed after 1 retries"));
+        assert!(handled.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_not_retried_with_skip_or_halt() {
+        for error_policy in [ErrorPolicy::Skip, ErrorPolicy::Halt] {
+            let (handler, _) = named_handler(error_policy, 1);
+            let err = handler.handle(&tokens_minted("1")).await.unwrap_err();
+            assert!(format!("{:#}", err).contains("failed after 0 retries"));
+        }
+    }
+
+    #[tokio::test]
+    async fn skip_policy_moves_on_to_next_event() {
+        let (handler, handled) = named_handler(ErrorPolicy::Skip, 0);
+
+        run(handler, &["1", "slow", "fail", "2"]).await.unwrap();
+
+        assert_eq!(*handled.lock().unwrap(), vec!["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn halt_policy_stops_handling_events() {
+        let (handler, handled) = named_handler(ErrorPolicy::Halt, 0);
+
+        let err = run(handler, &["1", "slow", "2"]).await.unwrap_err();
+
+        assert!(format!("{:#}", err).contains("event handler test halted"));
+        assert_eq!(*handled.lock().unwrap(), vec!["1"]);
+    }
+}
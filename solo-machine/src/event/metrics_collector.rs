@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use primitive_types::U256;
+use solo_machine_core::{event::EventHandler, Event};
+
+use crate::metrics::Metrics;
+
+/// Event handler which updates counters and histograms of metrics based on events emitted by solo machine
+pub struct MetricsCollector {
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsCollector {
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        Self { metrics }
+    }
+
+    fn handshake_step(&self, chain_id: &str, step: &str) {
+        self.metrics.handshake_steps.inc(&[chain_id, step]);
+    }
+}
+
+#[async_trait]
+impl EventHandler for MetricsCollector {
+    async fn handle(&self, event: Event) -> Result<()> {
+        let metrics = &self.metrics;
+
+        match event {
+            Event::TokensMinted {
+                chain_id,
+                amount,
+                denom,
+                ..
+            } => {
+                let labels: [&str; 2] = [&chain_id, &denom];
+
+                metrics.mints.inc(&labels);
+                metrics.minted_amount.inc_by(&labels, u256_to_f64(amount));
+            }
+            Event::TokensBurnt {
+                chain_id,
+                amount,
+                denom,
+                ..
+            } => {
+                let labels: [&str; 2] = [&chain_id, &denom];
+
+                metrics.burns.inc(&labels);
+                metrics.burnt_amount.inc_by(&labels, u256_to_f64(amount));
+            }
+            Event::MintRejected { chain_id, .. } => {
+                metrics.failures.inc(&[&chain_id, "policy-rejected"])
+            }
+            Event::TransactionBroadcast {
+                chain_id,
+                transaction_type,
+                duration_ms,
+                gas_used,
+                error_class,
+                ..
+            } => {
+                let labels: [&str; 2] = [&chain_id, transaction_type.as_str()];
+
+                metrics
+                    .broadcast_duration
+                    .observe(&labels, duration_ms as f64 / 1000.0);
+
+                if let Some(gas_used) = gas_used {
+                    metrics.gas_used.observe(&labels, gas_used as f64);
+                }
+
+                match error_class {
+                    Some(error_class) => metrics.failures.inc(&[&chain_id, error_class.as_str()]),
+                    None if transaction_type == "acknowledgement" => {
+                        metrics.acknowledgements.inc(&[&chain_id])
+                    }
+                    None => {}
+                }
+            }
+            Event::CreatedSoloMachineClient { chain_id, .. } => {
+                self.handshake_step(&chain_id, "create-solo-machine-client")
+            }
+            Event::CreatedTendermintClient { chain_id, .. } => {
+                self.handshake_step(&chain_id, "create-tendermint-client")
+            }
+            Event::InitializedConnectionOnTendermint { chain_id, .. } => {
+                self.handshake_step(&chain_id, "connection-open-init")
+            }
+            Event::InitializedConnectionOnSoloMachine { chain_id, .. } => {
+                self.handshake_step(&chain_id, "connection-open-try")
+            }
+            Event::ConfirmedConnectionOnTendermint { chain_id, .. } => {
+                self.handshake_step(&chain_id, "connection-open-ack")
+            }
+            Event::ConfirmedConnectionOnSoloMachine { chain_id, .. } => {
+                self.handshake_step(&chain_id, "connection-open-confirm")
+            }
+            Event::InitializedChannelOnTendermint { chain_id, .. } => {
+                self.handshake_step(&chain_id, "channel-open-init")
+            }
+            Event::InitializedChannelOnSoloMachine { chain_id, .. } => {
+                self.handshake_step(&chain_id, "channel-open-try")
+            }
+            Event::ConfirmedChannelOnTendermint { chain_id, .. } => {
+                self.handshake_step(&chain_id, "channel-open-ack")
+            }
+            Event::ConfirmedChannelOnSoloMachine { chain_id, .. } => {
+                self.handshake_step(&chain_id, "channel-open-confirm")
+            }
+            Event::CloseChannelInitOnSoloMachine { chain_id, .. } => {
+                self.handshake_step(&chain_id, "channel-close-init")
+            }
+            Event::SupplyDiscrepancy { chain_id, .. } => {
+                metrics.failures.inc(&[&chain_id, "supply-discrepancy"])
+            }
+            // Failures and acknowledgements are counted using `TransactionBroadcast` events
+            Event::MintFailed { .. }
+            | Event::BurnFailed { .. }
+            | Event::AcknowledgementSent { .. }
+            | Event::SoloMachineClientUpdated { .. }
+            | Event::SequenceIncremented { .. }
+            | Event::ChannelClosed { .. }
+            | Event::SignerUpdated { .. }
+            | Event::ConnectionEstablished { .. }
+            | Event::ChainAdded { .. }
+            | Event::ChainConfigUpdated { .. }
+            | Event::ChainArchived { .. }
+            | Event::ChainRemoved { .. }
+            | Event::Warning { .. } => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts given amount to `f64` (losing precision for very large amounts)
+fn u256_to_f64(amount: U256) -> f64 {
+    amount
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |acc, word| acc * 2f64.powi(64) + *word as f64)
+}
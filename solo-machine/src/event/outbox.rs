@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use anyhow::{Error, Result};
+use solo_machine_core::{event::ErrorPolicy, service::OutboxService, DbPool};
+use tokio::sync::mpsc::Receiver;
+
+use super::handler::NamedHandler;
+
+/// Interval after which outbox is checked for new events (even if no event is emitted by this process)
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Maximum time taken for delivering remaining events once `wake` channel is closed (so that a slow event handler
+/// does not delay exit of solo machine)
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delivers events recorded in event outbox to a durable event handler. Each durable event handler has its own
+/// dispatcher and its own cursor in outbox which is only moved forward once an event is handled (or skipped), so,
+/// events which are not delivered (because of errors or a crash) are delivered again later.
+pub struct OutboxDispatcher {
+    outbox: OutboxService,
+    handler: NamedHandler,
+}
+
+impl OutboxDispatcher {
+    /// Creates a new dispatcher for given event handler and registers its cursor in outbox
+    pub async fn new(db_pool: DbPool, handler: NamedHandler) -> Result<Self> {
+        let outbox = OutboxService::new(db_pool);
+        outbox.register(&handler.name).await?;
+
+        Ok(Self { outbox, handler })
+    }
+
+    /// Runs the dispatcher until `wake` channel is closed (or event handler halts). Every message on `wake` channel
+    /// (or a tick of poll interval) triggers a delivery round. Remaining events are delivered once more after the
+    /// channel is closed (events which are not delivered within flush timeout are delivered on next start).
+    pub async fn run(self, mut wake: Receiver<()>) -> Result<()> {
+        let mut failed = false;
+
+        loop {
+            let closed = tokio::select! {
+                message = wake.recv() => message.is_none(),
+                _ = tokio::time::sleep(POLL_INTERVAL) => false,
+            };
+
+            // Retrying a failing event handler once more would only delay shutdown (pending events are delivered on
+            // next start)
+            if closed && failed {
+                return Ok(());
+            }
+
+            failed = true;
+
+            let result = if closed {
+                match tokio::time::timeout(FLUSH_TIMEOUT, self.dispatch()).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        log::warn!(
+                            "event handler {} did not handle remaining events within {} (they will be delivered on \
+                             next start)",
+                            self.handler.name,
+                            humantime::format_duration(FLUSH_TIMEOUT)
+                        );
+                        return Ok(());
+                    }
+                }
+            } else {
+                self.dispatch().await
+            };
+
+            match result {
+                Ok(None) => failed = false,
+                Ok(Some(err)) => {
+                    return Err(err.context(format!("event handler {} halted", self.handler.name)))
+                }
+                Err(err) => log::error!(
+                    "unable to deliver events to event handler {} (will be retried): {:#}",
+                    self.handler.name,
+                    err
+                ),
+            }
+
+            if closed {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Delivers pending events to event handler (at most `queue_size` events are fetched from outbox at a time).
+    /// Returns the error of event handler if it halted.
+    async fn dispatch(&self) -> Result<Option<Error>> {
+        let name = &self.handler.name;
+        let batch_size = i32::try_from(self.handler.options.queue_size.max(1)).unwrap_or(i32::MAX);
+
+        // Cursor is fetched from database in every round so that replays are picked up by a running solo machine
+        let mut cursor = self.outbox.register(name).await?;
+
+        loop {
+            let events = self.outbox.get_pending_events(cursor, batch_size).await?;
+            let fetched = events.len();
+
+            for event in events {
+                if let Err(err) = self.handler.handle(&event.event).await {
+                    let err = err.context(format!("unable to handle event {}", event.id));
+
+                    match self.handler.options.error_policy {
+                        ErrorPolicy::Skip => {
+                            log::error!("event handler {} skipped event: {:#}", name, err)
+                        }
+                        ErrorPolicy::Retry => return Err(err),
+                        ErrorPolicy::Halt => return Ok(Some(err)),
+                    }
+                }
+
+                // Pending events are always positioned
+                cursor = event.position.unwrap_or(cursor);
+                self.outbox.acknowledge(name, cursor).await?;
+            }
+
+            if fetched < batch_size as usize {
+                return Ok(None);
+            }
+        }
+    }
+}
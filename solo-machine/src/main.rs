@@ -5,6 +5,7 @@ use self::command::Command;
 
 mod command;
 mod event;
+mod metrics;
 mod output;
 mod server;
 mod signer;
@@ -0,0 +1,565 @@
+//! Prometheus metrics of solo machine (encoded using Prometheus text exposition format)
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{
+    cosmos::crypto::PublicKey,
+    signer::{Message, MessageDescription, ToPublicKey},
+    Signer,
+};
+
+/// Content type of metrics encoded using Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Buckets (in seconds) of broadcast latency histogram
+const BROADCAST_DURATION_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+/// Buckets of gas used histogram
+const GAS_USED_BUCKETS: &[f64] = &[
+    50_000.0,
+    100_000.0,
+    200_000.0,
+    300_000.0,
+    500_000.0,
+    1_000_000.0,
+    2_000_000.0,
+];
+/// Buckets (in seconds) of signer latency histogram
+const SIGNER_DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+/// All the metrics collected by solo machine
+pub struct Metrics {
+    /// Number of successful mint operations
+    pub mints: Counter,
+    /// Amount of tokens minted
+    pub minted_amount: Counter,
+    /// Number of successful burn operations
+    pub burns: Counter,
+    /// Amount of tokens burnt
+    pub burnt_amount: Counter,
+    /// Number of packet acknowledgements sent to IBC enabled chain
+    pub acknowledgements: Counter,
+    /// Number of completed connection handshake steps
+    pub handshake_steps: Counter,
+    /// Number of failures (by class of error)
+    pub failures: Counter,
+    /// Time taken to broadcast transactions to IBC enabled chain
+    pub broadcast_duration: Histogram,
+    /// Gas used by transactions on IBC enabled chain
+    pub gas_used: Histogram,
+    /// Time taken by signer to sign messages
+    pub signer_duration: Histogram,
+    /// Number of failed signing attempts
+    pub signer_errors: Counter,
+    /// Sequence of solo machine on IBC enabled chain
+    pub chain_sequence: Gauge,
+    /// Packet sequence of solo machine on IBC enabled chain
+    pub packet_sequence: Gauge,
+    /// Age of latest consensus state of tendermint client
+    pub tendermint_client_age: Gauge,
+    /// Balance of fee denom of signer's account on IBC enabled chain
+    pub fee_account_balance: Gauge,
+}
+
+impl Metrics {
+    /// Creates a new instance of metrics with all the values set to zero
+    pub fn new() -> Self {
+        Self {
+            mints: Counter::new(
+                "solo_machine_mints_total",
+                "Number of successful mint operations",
+                &["chain_id", "denom"],
+            ),
+            minted_amount: Counter::new(
+                "solo_machine_minted_amount_total",
+                "Amount of tokens minted on IBC enabled chain",
+                &["chain_id", "denom"],
+            ),
+            burns: Counter::new(
+                "solo_machine_burns_total",
+                "Number of successful burn operations",
+                &["chain_id", "denom"],
+            ),
+            burnt_amount: Counter::new(
+                "solo_machine_burnt_amount_total",
+                "Amount of tokens burnt on IBC enabled chain",
+                &["chain_id", "denom"],
+            ),
+            acknowledgements: Counter::new(
+                "solo_machine_packet_acknowledgements_total",
+                "Number of packet acknowledgements sent to IBC enabled chain",
+                &["chain_id"],
+            ),
+            handshake_steps: Counter::new(
+                "solo_machine_handshake_steps_total",
+                "Number of completed IBC handshake steps",
+                &["chain_id", "step"],
+            ),
+            failures: Counter::new(
+                "solo_machine_failures_total",
+                "Number of failures by class of error",
+                &["chain_id", "class"],
+            ),
+            broadcast_duration: Histogram::new(
+                "solo_machine_broadcast_duration_seconds",
+                "Time taken to broadcast a transaction and wait for it to be committed",
+                &["chain_id", "transaction_type"],
+                BROADCAST_DURATION_BUCKETS,
+            ),
+            gas_used: Histogram::new(
+                "solo_machine_gas_used",
+                "Gas used by transactions on IBC enabled chain",
+                &["chain_id", "transaction_type"],
+                GAS_USED_BUCKETS,
+            ),
+            signer_duration: Histogram::new(
+                "solo_machine_signer_duration_seconds",
+                "Time taken by signer to sign a message",
+                &["message_type"],
+                SIGNER_DURATION_BUCKETS,
+            ),
+            signer_errors: Counter::new(
+                "solo_machine_signer_errors_total",
+                "Number of failed signing attempts",
+                &["message_type"],
+            ),
+            chain_sequence: Gauge::new(
+                "solo_machine_chain_sequence",
+                "Sequence of solo machine on IBC enabled chain",
+                &["chain_id"],
+            ),
+            packet_sequence: Gauge::new(
+                "solo_machine_chain_packet_sequence",
+                "Packet sequence of solo machine on IBC enabled chain",
+                &["chain_id"],
+            ),
+            tendermint_client_age: Gauge::new(
+                "solo_machine_tendermint_client_age_seconds",
+                "Age of the consensus state at latest height of tendermint client",
+                &["chain_id"],
+            ),
+            fee_account_balance: Gauge::new(
+                "solo_machine_fee_account_balance",
+                "Balance of fee denom of signer's account on IBC enabled chain",
+                &["chain_id", "denom"],
+            ),
+        }
+    }
+
+    /// Encodes all the metrics using Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut buf = String::new();
+
+        let families: [&dyn Encode; 15] = [
+            &self.mints,
+            &self.minted_amount,
+            &self.burns,
+            &self.burnt_amount,
+            &self.acknowledgements,
+            &self.handshake_steps,
+            &self.failures,
+            &self.broadcast_duration,
+            &self.gas_used,
+            &self.signer_duration,
+            &self.signer_errors,
+            &self.chain_sequence,
+            &self.packet_sequence,
+            &self.tendermint_client_age,
+            &self.fee_account_balance,
+        ];
+
+        for family in families {
+            // Writing to a `String` never fails
+            let _ = family.encode(&mut buf);
+        }
+
+        buf
+    }
+}
+
+trait Encode {
+    fn encode(&self, buf: &mut String) -> fmt::Result;
+}
+
+/// Name, help text and label names of a metric family
+struct Descriptor {
+    name: &'static str,
+    help: &'static str,
+    label_names: &'static [&'static str],
+}
+
+impl Descriptor {
+    fn encode_header(&self, buf: &mut String, metric_type: &str) -> fmt::Result {
+        writeln!(buf, "# HELP {} {}", self.name, self.help)?;
+        writeln!(buf, "# TYPE {} {}", self.name, metric_type)
+    }
+
+    fn label_values(&self, label_values: &[&str]) -> Vec<String> {
+        debug_assert_eq!(self.label_names.len(), label_values.len());
+        label_values.iter().map(ToString::to_string).collect()
+    }
+
+    fn encode_sample(
+        &self,
+        buf: &mut String,
+        suffix: &str,
+        label_values: &[String],
+        extra_label: Option<(&str, &str)>,
+        value: f64,
+    ) -> fmt::Result {
+        write!(buf, "{}{}", self.name, suffix)?;
+
+        let labels = self
+            .label_names
+            .iter()
+            .copied()
+            .zip(label_values.iter().map(String::as_str))
+            .chain(extra_label)
+            .collect::<Vec<_>>();
+
+        if !labels.is_empty() {
+            buf.push('{');
+
+            for (i, (name, value)) in labels.into_iter().enumerate() {
+                if i > 0 {
+                    buf.push(',');
+                }
+
+                write!(buf, "{}=\"{}\"", name, escape_label_value(value))?;
+            }
+
+            buf.push('}');
+        }
+
+        writeln!(buf, " {}", format_value(value))
+    }
+}
+
+/// A metric family whose values only go up (e.g. number of mints)
+pub struct Counter {
+    descriptor: Descriptor,
+    values: Mutex<BTreeMap<Vec<String>, f64>>,
+}
+
+impl Counter {
+    fn new(name: &'static str, help: &'static str, label_names: &'static [&'static str]) -> Self {
+        Self {
+            descriptor: Descriptor {
+                name,
+                help,
+                label_names,
+            },
+            values: Default::default(),
+        }
+    }
+
+    /// Increments the counter with given label values by one
+    pub fn inc(&self, label_values: &[&str]) {
+        self.inc_by(label_values, 1.0)
+    }
+
+    /// Increments the counter with given label values by given value
+    pub fn inc_by(&self, label_values: &[&str], value: f64) {
+        let key = self.descriptor.label_values(label_values);
+        *self.values.lock().unwrap().entry(key).or_default() += value;
+    }
+}
+
+impl Encode for Counter {
+    fn encode(&self, buf: &mut String) -> fmt::Result {
+        self.descriptor.encode_header(buf, "counter")?;
+
+        for (label_values, value) in self.values.lock().unwrap().iter() {
+            self.descriptor
+                .encode_sample(buf, "", label_values, None, *value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A metric family whose values can go up and down (e.g. fee account balance)
+pub struct Gauge {
+    descriptor: Descriptor,
+    values: Mutex<BTreeMap<Vec<String>, f64>>,
+}
+
+impl Gauge {
+    fn new(name: &'static str, help: &'static str, label_names: &'static [&'static str]) -> Self {
+        Self {
+            descriptor: Descriptor {
+                name,
+                help,
+                label_names,
+            },
+            values: Default::default(),
+        }
+    }
+
+    /// Replaces all the values of gauge with given values (so that values of removed chains are not reported anymore)
+    pub fn replace<'a>(&self, values: impl IntoIterator<Item = (Vec<&'a str>, f64)>) {
+        let values = values
+            .into_iter()
+            .map(|(label_values, value)| (self.descriptor.label_values(&label_values), value))
+            .collect();
+
+        *self.values.lock().unwrap() = values;
+    }
+}
+
+impl Encode for Gauge {
+    fn encode(&self, buf: &mut String) -> fmt::Result {
+        self.descriptor.encode_header(buf, "gauge")?;
+
+        for (label_values, value) in self.values.lock().unwrap().iter() {
+            self.descriptor
+                .encode_sample(buf, "", label_values, None, *value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A metric family which samples observations in configurable buckets (e.g. broadcast latency)
+pub struct Histogram {
+    descriptor: Descriptor,
+    buckets: &'static [f64],
+    values: Mutex<BTreeMap<Vec<String>, HistogramValue>>,
+}
+
+#[derive(Default)]
+struct HistogramValue {
+    /// Number of observations in each bucket (not cumulative)
+    bucket_counts: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn new(
+        name: &'static str,
+        help: &'static str,
+        label_names: &'static [&'static str],
+        buckets: &'static [f64],
+    ) -> Self {
+        Self {
+            descriptor: Descriptor {
+                name,
+                help,
+                label_names,
+            },
+            buckets,
+            values: Default::default(),
+        }
+    }
+
+    /// Records an observation in histogram with given label values
+    pub fn observe(&self, label_values: &[&str], value: f64) {
+        let key = self.descriptor.label_values(label_values);
+
+        let mut values = self.values.lock().unwrap();
+        let histogram_value = values.entry(key).or_default();
+
+        if histogram_value.bucket_counts.is_empty() {
+            histogram_value.bucket_counts = vec![0; self.buckets.len()];
+        }
+
+        if let Some(index) = self.buckets.iter().position(|bound| value <= *bound) {
+            histogram_value.bucket_counts[index] += 1;
+        }
+
+        histogram_value.count += 1;
+        histogram_value.sum += value;
+    }
+}
+
+impl Encode for Histogram {
+    fn encode(&self, buf: &mut String) -> fmt::Result {
+        self.descriptor.encode_header(buf, "histogram")?;
+
+        for (label_values, value) in self.values.lock().unwrap().iter() {
+            let mut cumulative_count = 0;
+
+            for (bound, count) in self.buckets.iter().zip(value.bucket_counts.iter()) {
+                cumulative_count += count;
+
+                self.descriptor.encode_sample(
+                    buf,
+                    "_bucket",
+                    label_values,
+                    Some(("le", &format_value(*bound))),
+                    cumulative_count as f64,
+                )?;
+            }
+
+            self.descriptor.encode_sample(
+                buf,
+                "_bucket",
+                label_values,
+                Some(("le", "+Inf")),
+                value.count as f64,
+            )?;
+            self.descriptor
+                .encode_sample(buf, "_sum", label_values, None, value.sum)?;
+            self.descriptor
+                .encode_sample(buf, "_count", label_values, None, value.count as f64)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Signer which records latency and errors of wrapped signer in metrics
+pub struct MetricsSigner {
+    signer: Arc<dyn Signer>,
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsSigner {
+    /// Creates a new instance of metrics signer
+    pub fn new(signer: Arc<dyn Signer>, metrics: Arc<Metrics>) -> Self {
+        Self { signer, metrics }
+    }
+}
+
+impl ToPublicKey for MetricsSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for MetricsSigner {
+    async fn sign(
+        &self,
+        request_id: Option<&str>,
+        message: Message<'_>,
+        description: &MessageDescription,
+    ) -> Result<Vec<u8>> {
+        let message_type = message.message_type();
+
+        let started_at = Instant::now();
+        let result = self.signer.sign(request_id, message, description).await;
+
+        self.metrics
+            .signer_duration
+            .observe(&[message_type], started_at.elapsed().as_secs_f64());
+
+        if result.is_err() {
+            self.metrics.signer_errors.inc(&[message_type]);
+        }
+
+        result
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf".to_owned()
+        } else {
+            "-Inf".to_owned()
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_escapes_label_values() {
+        let counter = Counter::new("test_total", "Test counter", &["chain_id"]);
+        counter.inc(&["a\\b\"c\nd"]);
+        counter.inc_by(&["a\\b\"c\nd"], 2.0);
+
+        let mut buf = String::new();
+        counter.encode(&mut buf).unwrap();
+
+        assert_eq!(
+            buf,
+            "# HELP test_total Test counter\n\
+             # TYPE test_total counter\n\
+             test_total{chain_id=\"a\\\\b\\\"c\\nd\"} 3\n"
+        );
+    }
+
+    #[test]
+    fn gauge_replaces_all_values() {
+        let gauge = Gauge::new("test_gauge", "Test gauge", &["chain_id"]);
+        gauge.replace(vec![(vec!["a"], 1.0), (vec!["b"], 2.5)]);
+        gauge.replace(vec![(vec!["b"], -1.0)]);
+
+        let mut buf = String::new();
+        gauge.encode(&mut buf).unwrap();
+
+        assert_eq!(
+            buf,
+            "# HELP test_gauge Test gauge\n\
+             # TYPE test_gauge gauge\n\
+             test_gauge{chain_id=\"b\"} -1\n"
+        );
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let histogram = Histogram::new(
+            "test_seconds",
+            "Test histogram",
+            &["chain_id"],
+            &[0.5, 1.0, 2.5],
+        );
+        histogram.observe(&["a"], 0.1);
+        histogram.observe(&["a"], 0.5);
+        histogram.observe(&["a"], 2.0);
+        histogram.observe(&["a"], 10.0);
+
+        let mut buf = String::new();
+        histogram.encode(&mut buf).unwrap();
+
+        assert_eq!(
+            buf,
+            "# HELP test_seconds Test histogram\n\
+             # TYPE test_seconds histogram\n\
+             test_seconds_bucket{chain_id=\"a\",le=\"0.5\"} 2\n\
+             test_seconds_bucket{chain_id=\"a\",le=\"1\"} 2\n\
+             test_seconds_bucket{chain_id=\"a\",le=\"2.5\"} 3\n\
+             test_seconds_bucket{chain_id=\"a\",le=\"+Inf\"} 4\n\
+             test_seconds_sum{chain_id=\"a\"} 12.6\n\
+             test_seconds_count{chain_id=\"a\"} 4\n"
+        );
+    }
+
+    #[test]
+    fn metrics_without_labels_are_encoded_without_braces() {
+        let counter = Counter::new("test_total", "Test counter", &[]);
+        counter.inc(&[]);
+
+        let mut buf = String::new();
+        counter.encode(&mut buf).unwrap();
+
+        assert!(buf.ends_with("\ntest_total 1\n"));
+    }
+}
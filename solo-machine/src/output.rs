@@ -1,6 +1,9 @@
 use std::{fmt, str::FromStr};
 
-use anyhow::{anyhow, Error};
+use anyhow::{anyhow, Error, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use solo_machine_core::model::{Chain, OperationPage};
 
 /// Different output formats supported by command line
 #[derive(Debug, Clone, Copy)]
@@ -31,3 +34,65 @@ impl FromStr for OutputType {
         }
     }
 }
+
+/// Different formats supported for exporting history of operations
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    /// Comma separated values (with a header row)
+    Csv,
+    /// Newline delimited json (one operation per line)
+    Ndjson,
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv => write!(f, "csv"),
+            Self::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(anyhow!("invalid export format")),
+        }
+    }
+}
+
+/// Returns JSON output of a successful command (also used as response body by REST gateway)
+pub fn success_json(data: impl Serialize) -> Value {
+    json!({
+        "result": "success",
+        "data": data,
+    })
+}
+
+/// Returns JSON output of a list of chains (along with connection status of each chain)
+pub fn chains_json(chains: Vec<Chain>) -> Result<Value> {
+    let chains = chains
+        .into_iter()
+        .map(|chain| {
+            let connection_status = chain.connection_status();
+            let mut chain = serde_json::to_value(chain)?;
+            chain["connection_status"] = json!(connection_status);
+            Ok(chain)
+        })
+        .collect::<Result<Vec<Value>>>()?;
+
+    Ok(success_json(chains))
+}
+
+/// Returns JSON output of a page of history of operations (also used as response body by REST gateway)
+pub fn history_json(history: &OperationPage) -> Value {
+    json!({
+        "result": "success",
+        "data": history.operations,
+        "next_cursor": history.next_cursor,
+    })
+}
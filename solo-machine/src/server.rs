@@ -1,35 +1,240 @@
+mod auth;
 mod chain;
+mod events;
+mod health;
 mod ibc;
+mod job_runner;
+mod metrics;
+mod operations;
+mod policy;
+mod rest;
+mod tls;
 
-use std::{net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use anyhow::{Context, Result};
-use solo_machine_core::{DbPool, Event, Signer};
+use solo_machine_core::{service::ChainNotFound, DbPool, Event, Signer};
 use tokio::sync::mpsc::UnboundedSender;
-use tonic::transport::Server as GrpcServer;
+use tonic::{transport::Server as GrpcServer, Status};
+use tower::ServiceBuilder;
+
+use crate::{event::broadcaster::EventBroadcaster, metrics::Metrics};
 
 use self::{
+    auth::{add_grpc_path, AuthInterceptor},
     chain::{chain_server::ChainServer, ChainService},
+    events::{events_server::EventsServer, EventService},
+    health::HealthChecker,
     ibc::{ibc_server::IbcServer, IbcService},
+    job_runner::JobRunner,
+    metrics::MetricsServer,
+    operations::{operations_server::OperationsServer, OperationsService},
+    policy::{policy_server::PolicyServer, PolicyService},
+    rest::RestGateway,
 };
 
-/// Starts gRPC server
+pub use self::{auth::ApiKeys, tls::TlsConfig};
+
+/// Encoded file descriptor set of all the gRPC services of solo machine (used for server reflection)
+const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("solo_machine_descriptor");
+
+/// Configuration of gRPC server
+pub struct GrpcConfig {
+    /// Address to serve gRPC server on
+    pub addr: SocketAddr,
+    /// TLS configuration (TLS is disabled when `None`)
+    pub tls_config: Option<TlsConfig>,
+    /// API keys allowed to call gRPC server (authentication is disabled when `None`)
+    pub api_keys: Option<ApiKeys>,
+    /// Interval between health checks of database and configured chains
+    pub health_check_interval: Duration,
+    /// Maximum time taken to respond to a request (requests taking longer are cancelled)
+    pub request_timeout: Duration,
+    /// Address to serve REST gateway on (REST gateway is disabled when `None`)
+    pub rest_addr: Option<SocketAddr>,
+    /// Configuration of metrics server (metrics server is disabled when `None`)
+    pub metrics_config: Option<MetricsConfig>,
+}
+
+/// Configuration of metrics server
+pub struct MetricsConfig {
+    /// Address to serve metrics on
+    pub addr: SocketAddr,
+    /// Interval between refreshes of gauges which are fetched from database and configured chains
+    pub refresh_interval: Duration,
+    /// Metrics collected by solo machine
+    pub metrics: Arc<Metrics>,
+}
+
+/// Starts gRPC server (along with REST gateway and metrics server, if enabled)
 pub async fn start_grpc(
     db_pool: DbPool,
     signer: impl Signer + Clone + 'static,
     sender: UnboundedSender<Event>,
-    addr: SocketAddr,
+    broadcaster: EventBroadcaster,
+    config: GrpcConfig,
 ) -> Result<()> {
-    let chain_service = ChainService::new(db_pool.clone(), sender.clone(), signer.clone());
-    let ibc_service = IbcService::new(db_pool, sender, signer);
+    let GrpcConfig {
+        addr,
+        tls_config,
+        api_keys,
+        health_check_interval,
+        request_timeout,
+        rest_addr,
+        metrics_config,
+    } = config;
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    HealthChecker::new(db_pool.clone(), health_reporter, health_check_interval)
+        .spawn()
+        .await;
+
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .context("unable to build grpc reflection service")?;
+    let reflection_service_v1alpha = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1alpha()
+        .context("unable to build grpc reflection service")?;
+
+    let chain_service = Arc::new(ChainService::new(
+        db_pool.clone(),
+        sender.clone(),
+        signer.clone(),
+    ));
+    let job_runner = JobRunner::new(db_pool.clone(), sender.clone()).await?;
+
+    let ibc_service = Arc::new(IbcService::new(
+        db_pool.clone(),
+        sender,
+        signer.clone(),
+        job_runner.clone(),
+    ));
+    let policy_service = PolicyService::new(db_pool.clone());
+    let event_service = EventService::new(broadcaster);
+    let operations_service = OperationsService::new(job_runner);
+
+    let mut builder = GrpcServer::builder().timeout(request_timeout);
+
+    let rest_tls_config = match (rest_addr, tls_config.as_ref()) {
+        (Some(_), Some(tls_config)) => Some(
+            tls_config
+                .load_rustls()
+                .await
+                .context("invalid tls configuration for rest gateway")?,
+        ),
+        _ => None,
+    };
+
+    match tls_config {
+        Some(tls_config) => {
+            log::info!(
+                "enabling {} for grpc server",
+                if tls_config.client_ca.is_some() {
+                    "mutual tls"
+                } else {
+                    "tls"
+                }
+            );
+
+            builder = builder
+                .tls_config(tls_config.load().await?)
+                .context("invalid tls configuration for grpc server")?;
+        }
+        None => log::warn!("tls is not enabled for grpc server"),
+    }
+
+    if api_keys.is_none() {
+        log::warn!("api key authentication is not enabled for grpc server");
+    }
+
+    let auth_interceptor = AuthInterceptor::new(api_keys);
+
+    let metrics_server = metrics_config.map(|metrics_config| {
+        let server = MetricsServer::new(
+            db_pool.clone(),
+            signer.clone(),
+            metrics_config.metrics,
+            metrics_config.refresh_interval,
+        );
+
+        (server, metrics_config.addr)
+    });
+
+    let rest_gateway = rest_addr.map(|rest_addr| {
+        let gateway = RestGateway::new(
+            chain_service.clone(),
+            ibc_service.clone(),
+            auth_interceptor.clone(),
+        );
+
+        (gateway, rest_addr)
+    });
 
     log::info!("starting grpc server at {}", addr);
 
-    GrpcServer::builder()
-        .timeout(Duration::from_secs(60))
-        .add_service(ChainServer::new(chain_service))
-        .add_service(IbcServer::new(ibc_service))
-        .serve(addr)
+    let grpc_server = builder
+        .layer(
+            ServiceBuilder::new()
+                .map_request(add_grpc_path)
+                .layer(tonic::service::interceptor(auth_interceptor)),
+        )
+        .add_service(health_service)
+        .add_service(reflection_service)
+        .add_service(reflection_service_v1alpha)
+        .add_service(ChainServer::from_arc(chain_service))
+        .add_service(IbcServer::from_arc(ibc_service))
+        .add_service(PolicyServer::new(policy_service))
+        .add_service(EventsServer::new(event_service))
+        .add_service(OperationsServer::new(operations_service))
+        .serve(addr);
+
+    let grpc_server = async {
+        grpc_server
+            .await
+            .context(format!("unable to start grpc server at: {}", addr))
+    };
+    let rest_gateway = async {
+        match rest_gateway {
+            Some((gateway, rest_addr)) => gateway.serve(rest_addr, rest_tls_config).await,
+            None => Ok(()),
+        }
+    };
+    let metrics_server = async {
+        match metrics_server {
+            Some((server, metrics_addr)) => server.serve(metrics_addr).await,
+            None => Ok(()),
+        }
+    };
+
+    tokio::try_join!(grpc_server, rest_gateway, metrics_server).map(|_| ())
+}
+
+/// Connects to a new in-memory database with all the migrations applied (used in tests)
+#[cfg(all(test, not(feature = "postgres")))]
+pub(crate) async fn test_db_pool() -> DbPool {
+    let db_pool = solo_machine_core::connect_db("sqlite::memory:")
+        .await
+        .expect("unable to connect to in-memory database");
+
+    solo_machine_core::run_migrations(&db_pool)
         .await
-        .context(format!("unable to start grpc server at: {}", addr))
+        .expect("unable to run migrations");
+
+    db_pool
+}
+
+/// Converts an error returned by core services into gRPC status (`NOT_FOUND` when chain details are not found and
+/// `INTERNAL` otherwise)
+fn into_status(err: anyhow::Error) -> Status {
+    if err.is::<ChainNotFound>() {
+        log::warn!("{}", err);
+        Status::not_found(err.to_string())
+    } else {
+        log::error!("{}", err);
+        Status::internal(err.to_string())
+    }
 }
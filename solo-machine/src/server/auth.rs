@@ -0,0 +1,349 @@
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use tonic::{metadata::MetadataMap, service::Interceptor, Request, Status};
+
+/// Minimum length of an API key
+const MIN_API_KEY_LEN: usize = 16;
+
+/// Prefix of gRPC methods which can be called without API key (so that load balancers and orchestrators can probe
+/// the server)
+const HEALTH_PATH_PREFIX: &str = "/grpc.health.v1.Health/";
+
+/// Path of gRPC method being called (e.g. `/ibc.Ibc/Mint`)
+#[derive(Debug, Clone)]
+pub struct GrpcPath(String);
+
+impl GrpcPath {
+    /// Creates path of a gRPC method from its service name (e.g. `ibc.Ibc`) and method name (e.g. `Mint`)
+    pub fn new(service: &str, method: &str) -> Self {
+        Self(format!("/{}/{}", service, method))
+    }
+}
+
+/// Adds path of gRPC method to extensions of request (tonic interceptors do not have access to request URI)
+pub fn add_grpc_path<B>(mut request: http::Request<B>) -> http::Request<B> {
+    let path = GrpcPath(request.uri().path().to_owned());
+    request.extensions_mut().insert(path);
+    request
+}
+
+/// An API key along with the gRPC methods it is allowed to call
+#[derive(Debug, Deserialize)]
+struct ApiKey {
+    /// Name of API key (used in logs)
+    name: String,
+    /// Secret value of API key
+    key: String,
+    /// gRPC methods allowed for this key (`*`, `<package>.<Service>/*` or `<package>.<Service>/<Method>`)
+    methods: Vec<String>,
+}
+
+impl ApiKey {
+    /// Returns `true` if this key is allowed to call gRPC method with given path (e.g. `/ibc.Ibc/Mint`)
+    fn allows(&self, path: &str) -> bool {
+        let method = path.trim_start_matches('/');
+
+        self.methods.iter().any(|pattern| {
+            if pattern == "*" {
+                return true;
+            }
+
+            match pattern.strip_suffix("/*") {
+                Some(service) => method
+                    .split_once('/')
+                    .map(|(method_service, _)| method_service == service)
+                    .unwrap_or(false),
+                None => pattern == method,
+            }
+        })
+    }
+}
+
+/// API keys allowed to call gRPC server
+#[derive(Debug, Clone)]
+pub struct ApiKeys {
+    keys: Arc<Vec<ApiKey>>,
+}
+
+impl ApiKeys {
+    /// Loads API keys from a JSON file
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// [
+    ///     { "name": "admin", "key": "<secret>", "methods": ["*"] },
+    ///     { "name": "reader", "key": "<secret>", "methods": ["ibc.Ibc/QueryHistory", "chain.Chain/QueryBalance"] }
+    /// ]
+    /// ```
+    pub async fn from_file(path: &Path) -> Result<Self> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("unable to read api keys file: {}", path.display()))?;
+        let keys: Vec<ApiKey> = serde_json::from_slice(&bytes)
+            .with_context(|| format!("invalid api keys file: {}", path.display()))?;
+
+        validate_api_keys(&keys)?;
+
+        Ok(Self {
+            keys: Arc::new(keys),
+        })
+    }
+
+    /// Finds API key matching given secret
+    fn find(&self, secret: &str) -> Option<&ApiKey> {
+        // Compare hashes (which have the same length) against all the keys in constant time so that response time
+        // does not leak which key matched or the length of any key
+        let secret_hash = Sha256::digest(secret.as_bytes());
+
+        self.keys.iter().fold(None, |found, api_key| {
+            let matches: bool = Sha256::digest(api_key.key.as_bytes())
+                .ct_eq(&secret_hash)
+                .into();
+
+            if matches {
+                Some(api_key)
+            } else {
+                found
+            }
+        })
+    }
+}
+
+fn validate_api_keys(keys: &[ApiKey]) -> Result<()> {
+    ensure!(!keys.is_empty(), "api keys file does not contain any key");
+
+    let mut names = HashSet::new();
+    let mut secrets = HashSet::new();
+
+    for api_key in keys {
+        ensure!(!api_key.name.is_empty(), "api key name cannot be empty");
+        ensure!(
+            names.insert(api_key.name.as_str()),
+            "duplicate api key name: {}",
+            api_key.name
+        );
+        ensure!(
+            api_key.key.len() >= MIN_API_KEY_LEN,
+            "api key `{}` should be at least {} characters long",
+            api_key.name,
+            MIN_API_KEY_LEN
+        );
+        ensure!(
+            secrets.insert(api_key.key.as_str()),
+            "api key `{}` uses the same key as another api key",
+            api_key.name
+        );
+        ensure!(
+            !api_key.methods.is_empty(),
+            "api key `{}` does not allow any method",
+            api_key.name
+        );
+
+        for pattern in api_key.methods.iter() {
+            validate_method_pattern(pattern)
+                .with_context(|| format!("invalid method for api key `{}`", api_key.name))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_method_pattern(pattern: &str) -> Result<()> {
+    if pattern == "*" {
+        return Ok(());
+    }
+
+    match pattern.split_once('/') {
+        Some((service, method))
+            if service.contains('.')
+                && !service.contains('*')
+                && !method.is_empty()
+                && !method.contains('/')
+                && (method == "*" || !method.contains('*')) =>
+        {
+            Ok(())
+        }
+        _ => bail!(
+            "`{}` should be `*`, `<package>.<Service>/*` or `<package>.<Service>/<Method>`",
+            pattern
+        ),
+    }
+}
+
+/// tonic interceptor which authenticates requests using API keys (passed in `authorization: Bearer <key>` or
+/// `x-api-key: <key>` metadata) and checks if the key is allowed to call requested method. All the requests are
+/// allowed when no API keys are configured. Health checks are always allowed.
+#[derive(Debug, Clone)]
+pub struct AuthInterceptor {
+    api_keys: Option<ApiKeys>,
+}
+
+impl AuthInterceptor {
+    /// Creates a new instance of auth interceptor
+    pub fn new(api_keys: Option<ApiKeys>) -> Self {
+        Self { api_keys }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let api_keys = match self.api_keys {
+            None => return Ok(request),
+            Some(ref api_keys) => api_keys,
+        };
+
+        let path = match request.extensions().get::<GrpcPath>() {
+            Some(GrpcPath(path)) => path,
+            None => return Err(Status::internal("unable to determine grpc method")),
+        };
+
+        if path.starts_with(HEALTH_PATH_PREFIX) {
+            return Ok(request);
+        }
+
+        let secret = match get_secret(request.metadata()) {
+            Some(secret) => secret,
+            None => return Err(Status::unauthenticated("missing api key")),
+        };
+
+        let api_key = match api_keys.find(secret) {
+            Some(api_key) => api_key,
+            None => {
+                log::warn!("rejected call to {} with invalid api key", path);
+                return Err(Status::unauthenticated("invalid api key"));
+            }
+        };
+
+        if !api_key.allows(path) {
+            log::warn!("api key `{}` is not allowed to call {}", api_key.name, path);
+            return Err(Status::permission_denied(format!(
+                "api key is not allowed to call {}",
+                path
+            )));
+        }
+
+        Ok(request)
+    }
+}
+
+fn get_secret(metadata: &MetadataMap) -> Option<&str> {
+    if let Some(value) = metadata.get("authorization") {
+        return value
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim);
+    }
+
+    metadata
+        .get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key(methods: &[&str]) -> ApiKey {
+        ApiKey {
+            name: "test".to_owned(),
+            key: "secret".to_owned(),
+            methods: methods.iter().map(|method| (*method).to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn allows_wildcard() {
+        let api_key = api_key(&["*"]);
+
+        assert!(api_key.allows("/ibc.Ibc/Mint"));
+        assert!(api_key.allows("/chain.Chain/Add"));
+    }
+
+    #[test]
+    fn allows_service() {
+        let api_key = api_key(&["ibc.Ibc/*"]);
+
+        assert!(api_key.allows("/ibc.Ibc/Mint"));
+        assert!(api_key.allows("/ibc.Ibc/Burn"));
+        assert!(!api_key.allows("/chain.Chain/Add"));
+        assert!(!api_key.allows("/ibc.IbcAdmin/Mint"));
+        assert!(!api_key.allows("/ibc.Ibc"));
+    }
+
+    #[test]
+    fn allows_method() {
+        let api_key = api_key(&["ibc.Ibc/Mint", "chain.Chain/Query"]);
+
+        assert!(api_key.allows("/ibc.Ibc/Mint"));
+        assert!(api_key.allows("/chain.Chain/Query"));
+        assert!(!api_key.allows("/ibc.Ibc/Burn"));
+        assert!(!api_key.allows("/ibc.Ibc/MintAll"));
+    }
+
+    #[test]
+    fn allows_nothing() {
+        let api_key = api_key(&[]);
+
+        assert!(!api_key.allows("/ibc.Ibc/Mint"));
+    }
+
+    #[test]
+    fn validates_method_pattern() {
+        for pattern in ["*", "ibc.Ibc/*", "ibc.Ibc/Mint"] {
+            assert!(validate_method_pattern(pattern).is_ok(), "{}", pattern);
+        }
+
+        for pattern in [
+            "",
+            "ibc.Ibc",
+            "Ibc/Mint",
+            "ibc.*/Mint",
+            "ibc.Ibc/",
+            "ibc.Ibc/Mi*",
+            "ibc.Ibc/Mint/*",
+            "*/*",
+        ] {
+            assert!(validate_method_pattern(pattern).is_err(), "{}", pattern);
+        }
+    }
+    #[test]
+    fn finds_key_matching_secret() {
+        let api_keys = ApiKeys {
+            keys: Arc::new(vec![
+                ApiKey {
+                    name: "admin".to_owned(),
+                    key: "admin-secret-0123456789".to_owned(),
+                    methods: vec!["*".to_owned()],
+                },
+                ApiKey {
+                    name: "reader".to_owned(),
+                    key: "reader-secret-0123".to_owned(),
+                    methods: vec!["ibc.Ibc/QueryHistory".to_owned()],
+                },
+            ]),
+        };
+
+        assert_eq!(
+            api_keys
+                .find("reader-secret-0123")
+                .map(|api_key| api_key.name.as_str()),
+            Some("reader")
+        );
+        assert_eq!(
+            api_keys
+                .find("admin-secret-0123456789")
+                .map(|api_key| api_key.name.as_str()),
+            Some("admin")
+        );
+        assert!(api_keys.find("reader-secret-012").is_none());
+        assert!(api_keys.find("admin-secret-0123456789-").is_none());
+        assert!(api_keys.find("").is_none());
+    }
+}
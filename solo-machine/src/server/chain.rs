@@ -7,13 +7,18 @@ use std::{
 };
 
 use solo_machine_core::{
-    model::{ChainConfig as CoreChainConfig, Fee},
+    model::{
+        Chain as CoreChain, ChainConfig as CoreChainConfig, ChainConfigUpdate,
+        ChainKey as CoreChainKey, ConnectionDetails as CoreConnectionDetails, Fee,
+    },
     service::ChainService as CoreChainService,
     DbPool, Event, Signer,
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::{Request, Response, Status};
 
+use super::into_status;
+
 use self::chain_server::Chain;
 
 const DEFAULT_GRPC_ADDR: &str = "http://0.0.0.0:9090";
@@ -43,6 +48,56 @@ impl<S> ChainService<S> {
             signer,
         }
     }
+
+    // Below methods return core types so that REST gateway can return the same JSON as `--output json` of command
+    // line
+
+    /// Fetches details of an IBC enabled chain
+    pub async fn get_chain(&self, chain_id: &str) -> Result<CoreChain, Status> {
+        let chain_id = chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        self.core_service
+            .get(&chain_id)
+            .await
+            .map_err(into_status)?
+            .ok_or_else(|| Status::not_found("chain details not found"))
+    }
+
+    /// Fetches details of IBC enabled chains
+    pub async fn list(&self, request: &ListChainsRequest) -> Result<Vec<CoreChain>, Status> {
+        let limit = i32::try_from(request.limit.unwrap_or(10))
+            .or(Err(Status::invalid_argument("invalid `limit`")))?;
+        let offset = i32::try_from(request.offset.unwrap_or(0))
+            .or(Err(Status::invalid_argument("invalid `offset`")))?;
+
+        self.core_service
+            .list(limit, offset)
+            .await
+            .map_err(into_status)
+    }
+
+    /// Fetches public keys of signer for an IBC enabled chain
+    pub async fn public_keys(
+        &self,
+        request: &GetPublicKeysRequest,
+    ) -> Result<Vec<CoreChainKey>, Status> {
+        let chain_id = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        let limit = i32::try_from(request.limit.unwrap_or(10))
+            .or(Err(Status::invalid_argument("invalid `limit`")))?;
+        let offset = i32::try_from(request.offset.unwrap_or(0))
+            .or(Err(Status::invalid_argument("invalid `offset`")))?;
+
+        self.core_service
+            .get_public_keys(&chain_id, limit, offset)
+            .await
+            .map_err(into_status)
+    }
 }
 
 #[tonic::async_trait]
@@ -150,6 +205,8 @@ where
         let core_config = CoreChainConfig {
             grpc_addr,
             rpc_addr,
+            fallback_grpc_addrs: config.fallback_grpc_addrs,
+            fallback_rpc_addrs: config.fallback_rpc_addrs,
             fee,
             trust_level,
             trusting_period,
@@ -172,19 +229,16 @@ where
                     .encode(),
             )
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
+            .map_err(into_status)?
             .to_string();
 
         Ok(Response::new(AddChainResponse { chain_id }))
     }
 
-    async fn query(
+    async fn update_chain(
         &self,
-        request: Request<QueryChainRequest>,
-    ) -> Result<Response<QueryChainResponse>, Status> {
+        request: Request<UpdateChainRequest>,
+    ) -> Result<Response<UpdateChainResponse>, Status> {
         let request = request.into_inner();
 
         let chain_id = request
@@ -192,77 +246,153 @@ where
             .parse()
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
 
+        let config = request
+            .config
+            .ok_or_else(|| Status::invalid_argument("config must be provided"))?;
+        let fee_config = config.fee_config.unwrap_or_default();
+
+        let trusted_hash = config
+            .trusted_hash
+            .map(|trusted_hash| {
+                hex::decode(&trusted_hash)
+                    .map_err(|err| err.to_string())?
+                    .try_into()
+                    .map_err(|_| "trusted_hash length should be 32".to_string())
+            })
+            .transpose()
+            .map_err(Status::invalid_argument)?;
+
+        let clear_fallback_addrs = request.clear_fallback_addrs;
+        let fallback_addrs = |addrs: Vec<String>| {
+            if !addrs.is_empty() || clear_fallback_addrs {
+                Some(addrs)
+            } else {
+                None
+            }
+        };
+
+        let update = ChainConfigUpdate {
+            grpc_addr: config.grpc_addr,
+            rpc_addr: config.rpc_addr,
+            fallback_grpc_addrs: fallback_addrs(config.fallback_grpc_addrs),
+            fallback_rpc_addrs: fallback_addrs(config.fallback_rpc_addrs),
+            fee_amount: fee_config
+                .fee_amount
+                .map(|fee_amount| fee_amount.parse())
+                .transpose()
+                .map_err(|err: rust_decimal::Error| Status::invalid_argument(err.to_string()))?,
+            fee_denom: fee_config
+                .fee_denom
+                .map(|fee_denom| fee_denom.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            gas_limit: fee_config.gas_limit,
+            trust_level: config
+                .trust_level
+                .map(|trust_level| trust_level.parse())
+                .transpose()
+                .map_err(|e: num_rational::ParseRatioError| {
+                    Status::invalid_argument(e.to_string())
+                })?,
+            trusting_period: config
+                .trusting_period
+                .map(Duration::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("negative trusting_period"))?,
+            max_clock_drift: config
+                .max_clock_drift
+                .map(Duration::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("negative max_clock_drift"))?,
+            rpc_timeout: config
+                .rpc_timeout
+                .map(Duration::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("negative rpc_timeout"))?,
+            diversifier: config.diversifier,
+            port_id: config
+                .port_id
+                .map(|port_id| port_id.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            trusted_height: config.trusted_height.map(Into::into),
+            trusted_hash,
+        };
+
         let chain = self
             .core_service
-            .get(&chain_id)
+            .update_config(&chain_id, update, request.allow_node_change)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
-            .ok_or_else(|| Status::not_found("chain details not found"))?;
+            .map_err(into_status)?;
 
-        let response = QueryChainResponse {
-            chain_id: chain.id.to_string(),
-            node_id: chain.node_id.to_string(),
-            config: Some(ChainConfig {
-                grpc_addr: Some(chain.config.grpc_addr),
-                rpc_addr: Some(chain.config.rpc_addr),
-                fee_config: Some(FeeConfig {
-                    fee_amount: Some(chain.config.fee.amount.to_string()),
-                    fee_denom: Some(chain.config.fee.denom.to_string()),
-                    gas_limit: Some(chain.config.fee.gas_limit),
-                }),
-                trust_level: Some(chain.config.trust_level.to_string()),
-                trusting_period: Some(
-                    prost_types::Duration::try_from(chain.config.trusting_period)
-                        .map_err(|err| Status::internal(err.to_string()))?,
-                ),
-                max_clock_drift: Some(
-                    prost_types::Duration::try_from(chain.config.max_clock_drift)
-                        .map_err(|err| Status::internal(err.to_string()))?,
-                ),
-                rpc_timeout: Some(
-                    prost_types::Duration::try_from(chain.config.rpc_timeout)
-                        .map_err(|err| Status::internal(err.to_string()))?,
-                ),
-                diversifier: Some(chain.config.diversifier),
-                port_id: Some(chain.config.port_id.to_string()),
-                trusted_height: Some(
-                    u64::from(chain.config.trusted_height)
-                        .try_into()
-                        .map_err(|err: TryFromIntError| Status::internal(err.to_string()))?,
-                ),
-                trusted_hash: Some(hex::encode(chain.config.trusted_hash)),
-            }),
-            consensus_timestamp: Some(SystemTime::from(chain.consensus_timestamp).into()),
-            sequence: chain.sequence,
-            packet_sequence: chain.packet_sequence,
-            connection_details: chain.connection_details.map(|connection_details| {
-                ConnectionDetails {
-                    solo_machine_client_id: connection_details.solo_machine_client_id.to_string(),
-                    tendermint_client_id: connection_details.tendermint_client_id.to_string(),
-                    solo_machine_connection_id: connection_details
-                        .solo_machine_connection_id
-                        .to_string(),
-                    tendermint_connection_id: connection_details
-                        .tendermint_connection_id
-                        .to_string(),
-                    solo_machine_channel_id: connection_details
-                        .solo_machine_channel_id
-                        .map(|s| s.to_string()),
-                    tendermint_channel_id: connection_details
-                        .tendermint_channel_id
-                        .map(|s| s.to_string()),
-                }
-            }),
-            created_at: Some(SystemTime::from(chain.created_at).into()),
-            updated_at: Some(SystemTime::from(chain.updated_at).into()),
-        };
+        Ok(Response::new(UpdateChainResponse {
+            chain: Some(chain.try_into()?),
+        }))
+    }
+
+    async fn query(
+        &self,
+        request: Request<QueryChainRequest>,
+    ) -> Result<Response<QueryChainResponse>, Status> {
+        let chain = self.get_chain(&request.into_inner().chain_id).await?;
+
+        let response = chain.try_into()?;
 
         Ok(Response::new(response))
     }
 
+    async fn list_chains(
+        &self,
+        request: Request<ListChainsRequest>,
+    ) -> Result<Response<ListChainsResponse>, Status> {
+        let chains = self
+            .list(&request.into_inner())
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Response::new(ListChainsResponse { chains }))
+    }
+
+    async fn archive_chain(
+        &self,
+        request: Request<ArchiveChainRequest>,
+    ) -> Result<Response<ArchiveChainResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        self.core_service
+            .archive(&chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(ArchiveChainResponse {}))
+    }
+
+    async fn remove_chain(
+        &self,
+        request: Request<RemoveChainRequest>,
+    ) -> Result<Response<RemoveChainResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        self.core_service
+            .remove(&chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(RemoveChainResponse {}))
+    }
+
     async fn get_ibc_denom(
         &self,
         request: Request<GetIbcDenomRequest>,
@@ -283,10 +413,7 @@ where
             .core_service
             .get_ibc_denom(&chain_id, &denom)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         let response = GetIbcDenomResponse { ibc_denom };
 
@@ -313,14 +440,105 @@ where
             .core_service
             .balance(&self.signer, &chain_id, &denom)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
+            .map_err(into_status)?
             .to_string();
 
         let response = QueryBalanceResponse { balance };
 
         Ok(Response::new(response))
     }
+
+    async fn get_public_keys(
+        &self,
+        request: Request<GetPublicKeysRequest>,
+    ) -> Result<Response<GetPublicKeysResponse>, Status> {
+        let keys = self
+            .public_keys(&request.into_inner())
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(Response::new(GetPublicKeysResponse { keys }))
+    }
+}
+
+impl TryFrom<CoreChain> for QueryChainResponse {
+    type Error = Status;
+
+    fn try_from(chain: CoreChain) -> Result<Self, Self::Error> {
+        let connection_status = chain.connection_status().to_string();
+
+        Ok(QueryChainResponse {
+            chain_id: chain.id.to_string(),
+            node_id: chain.node_id.to_string(),
+            config: Some(ChainConfig {
+                grpc_addr: Some(chain.config.grpc_addr),
+                rpc_addr: Some(chain.config.rpc_addr),
+                fallback_grpc_addrs: chain.config.fallback_grpc_addrs,
+                fallback_rpc_addrs: chain.config.fallback_rpc_addrs,
+                fee_config: Some(FeeConfig {
+                    fee_amount: Some(chain.config.fee.amount.to_string()),
+                    fee_denom: Some(chain.config.fee.denom.to_string()),
+                    gas_limit: Some(chain.config.fee.gas_limit),
+                }),
+                trust_level: Some(chain.config.trust_level.to_string()),
+                trusting_period: Some(
+                    prost_types::Duration::try_from(chain.config.trusting_period)
+                        .map_err(|err| Status::internal(err.to_string()))?,
+                ),
+                max_clock_drift: Some(
+                    prost_types::Duration::try_from(chain.config.max_clock_drift)
+                        .map_err(|err| Status::internal(err.to_string()))?,
+                ),
+                rpc_timeout: Some(
+                    prost_types::Duration::try_from(chain.config.rpc_timeout)
+                        .map_err(|err| Status::internal(err.to_string()))?,
+                ),
+                diversifier: Some(chain.config.diversifier),
+                port_id: Some(chain.config.port_id.to_string()),
+                trusted_height: Some(
+                    u64::from(chain.config.trusted_height)
+                        .try_into()
+                        .map_err(|err: TryFromIntError| Status::internal(err.to_string()))?,
+                ),
+                trusted_hash: Some(hex::encode(chain.config.trusted_hash)),
+            }),
+            consensus_timestamp: Some(SystemTime::from(chain.consensus_timestamp).into()),
+            sequence: chain.sequence,
+            packet_sequence: chain.packet_sequence,
+            connection_details: chain.connection_details.map(Into::into),
+            created_at: Some(SystemTime::from(chain.created_at).into()),
+            updated_at: Some(SystemTime::from(chain.updated_at).into()),
+            connection_status,
+        })
+    }
+}
+
+impl From<CoreConnectionDetails> for ConnectionDetails {
+    fn from(connection_details: CoreConnectionDetails) -> Self {
+        Self {
+            solo_machine_client_id: connection_details.solo_machine_client_id.to_string(),
+            tendermint_client_id: connection_details.tendermint_client_id.to_string(),
+            solo_machine_connection_id: connection_details.solo_machine_connection_id.to_string(),
+            tendermint_connection_id: connection_details.tendermint_connection_id.to_string(),
+            solo_machine_channel_id: connection_details
+                .solo_machine_channel_id
+                .map(|s| s.to_string()),
+            tendermint_channel_id: connection_details
+                .tendermint_channel_id
+                .map(|s| s.to_string()),
+        }
+    }
+}
+
+impl From<CoreChainKey> for ChainKey {
+    fn from(chain_key: CoreChainKey) -> Self {
+        Self {
+            id: chain_key.id,
+            chain_id: chain_key.chain_id.to_string(),
+            public_key: chain_key.public_key,
+            created_at: Some(SystemTime::from(chain_key.created_at).into()),
+        }
+    }
 }
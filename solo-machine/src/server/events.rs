@@ -0,0 +1,704 @@
+tonic::include_proto!("events");
+
+use std::{collections::HashSet, pin::Pin};
+
+use solo_machine_core::Event as CoreEvent;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tonic::{Request, Response, Status};
+
+use crate::event::broadcaster::{EmittedEvent, EventBroadcaster};
+
+use self::{event::Details, events_server::Events};
+
+/// Number of events buffered for each gRPC subscriber
+const STREAM_BUFFER_SIZE: usize = 128;
+
+/// Types of all the events emitted by solo machine
+const EVENT_TYPES: &[&str] = &[
+    "TokensMinted",
+    "TokensBurnt",
+    "MintRejected",
+    "MintFailed",
+    "BurnFailed",
+    "AcknowledgementSent",
+    "SignerUpdated",
+    "SoloMachineClientUpdated",
+    "SequenceIncremented",
+    "TransactionBroadcast",
+    "CreatedSoloMachineClient",
+    "CreatedTendermintClient",
+    "InitializedConnectionOnTendermint",
+    "InitializedConnectionOnSoloMachine",
+    "ConfirmedConnectionOnTendermint",
+    "ConfirmedConnectionOnSoloMachine",
+    "InitializedChannelOnTendermint",
+    "CloseChannelInitOnSoloMachine",
+    "InitializedChannelOnSoloMachine",
+    "ConfirmedChannelOnTendermint",
+    "ConfirmedChannelOnSoloMachine",
+    "ChannelClosed",
+    "ConnectionEstablished",
+    "ChainAdded",
+    "ChainConfigUpdated",
+    "ChainArchived",
+    "ChainRemoved",
+    "SupplyDiscrepancy",
+    "Warning",
+];
+
+pub struct EventService {
+    broadcaster: EventBroadcaster,
+}
+
+impl EventService {
+    /// Creates a new instance of gRPC event service
+    pub fn new(broadcaster: EventBroadcaster) -> Self {
+        Self { broadcaster }
+    }
+}
+
+#[tonic::async_trait]
+impl Events for EventService {
+    type SubscribeEventsStream = Pin<Box<dyn Stream<Item = Result<Event, Status>> + Send>>;
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        let request = request.into_inner();
+
+        if let Some(event_type) = request
+            .event_types
+            .iter()
+            .find(|event_type| !EVENT_TYPES.contains(&event_type.as_str()))
+        {
+            return Err(Status::invalid_argument(format!(
+                "invalid event type: {}",
+                event_type
+            )));
+        }
+
+        let filter = EventFilter {
+            chain_ids: request.chain_ids.into_iter().collect(),
+            request_ids: request.request_ids.into_iter().collect(),
+            event_types: request.event_types.into_iter().collect(),
+        };
+
+        let mut receiver = self.broadcaster.subscribe();
+        let (sender, stream) = mpsc::channel(STREAM_BUFFER_SIZE);
+
+        tokio::spawn(async move {
+            loop {
+                let item = match receiver.recv().await {
+                    Ok(emitted_event) => {
+                        let event = Event::from(emitted_event);
+
+                        if !filter.matches(&event) {
+                            continue;
+                        }
+
+                        Ok(event)
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!(
+                            "event subscriber lagged behind and missed {} events",
+                            skipped
+                        );
+
+                        Err(Status::resource_exhausted(format!(
+                            "subscriber lagged behind and missed {} events",
+                            skipped
+                        )))
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let is_err = item.is_err();
+
+                if sender.send(item).await.is_err() || is_err {
+                    // Either subscriber disconnected or the stream was terminated with an error
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(stream))))
+    }
+}
+
+struct EventFilter {
+    chain_ids: HashSet<String>,
+    request_ids: HashSet<String>,
+    event_types: HashSet<String>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &Event) -> bool {
+        let matches_chain_id = self.chain_ids.is_empty()
+            || event
+                .chain_id
+                .as_ref()
+                .map(|chain_id| self.chain_ids.contains(chain_id))
+                .unwrap_or(false);
+
+        let matches_request_id = self.request_ids.is_empty()
+            || event
+                .request_id
+                .as_ref()
+                .map(|request_id| self.request_ids.contains(request_id))
+                .unwrap_or(false);
+
+        let matches_event_type =
+            self.event_types.is_empty() || self.event_types.contains(&event.event_type);
+
+        matches_chain_id && matches_request_id && matches_event_type
+    }
+}
+
+impl From<EmittedEvent> for Event {
+    fn from(emitted_event: EmittedEvent) -> Self {
+        let (event_type, chain_id, request_id, details) = match emitted_event.event {
+            CoreEvent::TokensMinted {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => (
+                "TokensMinted",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::TokensMinted(TokensMinted {
+                    to_address,
+                    amount: amount.to_string(),
+                    denom: denom.to_string(),
+                    transaction_hash,
+                }),
+            ),
+            CoreEvent::TokensBurnt {
+                chain_id,
+                request_id,
+                from_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => (
+                "TokensBurnt",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::TokensBurnt(TokensBurnt {
+                    from_address,
+                    amount: amount.to_string(),
+                    denom: denom.to_string(),
+                    transaction_hash,
+                }),
+            ),
+            CoreEvent::MintRejected {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                reason,
+            } => (
+                "MintRejected",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::MintRejected(MintRejected {
+                    to_address,
+                    amount: amount.to_string(),
+                    denom: denom.to_string(),
+                    reason,
+                }),
+            ),
+            CoreEvent::MintFailed {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => (
+                "MintFailed",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::MintFailed(MintFailed {
+                    to_address,
+                    amount: amount.to_string(),
+                    denom: denom.to_string(),
+                    transaction_hash,
+                    timed_out,
+                    error,
+                }),
+            ),
+            CoreEvent::BurnFailed {
+                chain_id,
+                request_id,
+                from_address,
+                amount,
+                denom,
+                transaction_hash,
+                timed_out,
+                error,
+            } => (
+                "BurnFailed",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::BurnFailed(BurnFailed {
+                    from_address,
+                    amount: amount.to_string(),
+                    denom: denom.to_string(),
+                    transaction_hash,
+                    timed_out,
+                    error,
+                }),
+            ),
+            CoreEvent::AcknowledgementSent {
+                chain_id,
+                request_id,
+                packet_sequence,
+                transaction_hash,
+            } => (
+                "AcknowledgementSent",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::AcknowledgementSent(AcknowledgementSent {
+                    packet_sequence,
+                    transaction_hash,
+                }),
+            ),
+            CoreEvent::SoloMachineClientUpdated {
+                chain_id,
+                request_id,
+                client_id,
+                transaction_hash,
+            } => (
+                "SoloMachineClientUpdated",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::SoloMachineClientUpdated(SoloMachineClientUpdated {
+                    client_id: client_id.to_string(),
+                    transaction_hash,
+                }),
+            ),
+            CoreEvent::SequenceIncremented {
+                chain_id,
+                request_id,
+                sequence,
+                packet_sequence,
+            } => (
+                "SequenceIncremented",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::SequenceIncremented(SequenceIncremented {
+                    sequence,
+                    packet_sequence,
+                }),
+            ),
+            CoreEvent::SignerUpdated {
+                chain_id,
+                request_id,
+                old_public_key,
+                new_public_key,
+            } => (
+                "SignerUpdated",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::SignerUpdated(SignerUpdated {
+                    old_public_key: old_public_key.encode(),
+                    new_public_key: new_public_key.encode(),
+                }),
+            ),
+            CoreEvent::TransactionBroadcast {
+                chain_id,
+                request_id,
+                transaction_type,
+                transaction_hash,
+                duration_ms,
+                gas_used,
+                error_class,
+            } => (
+                "TransactionBroadcast",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::TransactionBroadcast(TransactionBroadcast {
+                    transaction_type,
+                    transaction_hash,
+                    duration_ms,
+                    gas_used,
+                    error_class,
+                }),
+            ),
+            CoreEvent::CreatedSoloMachineClient {
+                chain_id,
+                request_id,
+                client_id,
+            } => (
+                "CreatedSoloMachineClient",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::CreatedSoloMachineClient(CreatedSoloMachineClient {
+                    client_id: client_id.to_string(),
+                }),
+            ),
+            CoreEvent::CreatedTendermintClient {
+                chain_id,
+                request_id,
+                client_id,
+            } => (
+                "CreatedTendermintClient",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::CreatedTendermintClient(CreatedTendermintClient {
+                    client_id: client_id.to_string(),
+                }),
+            ),
+            CoreEvent::InitializedConnectionOnTendermint {
+                chain_id,
+                request_id,
+                connection_id,
+            } => (
+                "InitializedConnectionOnTendermint",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::InitializedConnectionOnTendermint(InitializedConnectionOnTendermint {
+                    connection_id: connection_id.to_string(),
+                }),
+            ),
+            CoreEvent::InitializedConnectionOnSoloMachine {
+                chain_id,
+                request_id,
+                connection_id,
+            } => (
+                "InitializedConnectionOnSoloMachine",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::InitializedConnectionOnSoloMachine(InitializedConnectionOnSoloMachine {
+                    connection_id: connection_id.to_string(),
+                }),
+            ),
+            CoreEvent::ConfirmedConnectionOnTendermint {
+                chain_id,
+                request_id,
+                connection_id,
+            } => (
+                "ConfirmedConnectionOnTendermint",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ConfirmedConnectionOnTendermint(ConfirmedConnectionOnTendermint {
+                    connection_id: connection_id.to_string(),
+                }),
+            ),
+            CoreEvent::ConfirmedConnectionOnSoloMachine {
+                chain_id,
+                request_id,
+                connection_id,
+            } => (
+                "ConfirmedConnectionOnSoloMachine",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ConfirmedConnectionOnSoloMachine(ConfirmedConnectionOnSoloMachine {
+                    connection_id: connection_id.to_string(),
+                }),
+            ),
+            CoreEvent::InitializedChannelOnTendermint {
+                chain_id,
+                request_id,
+                channel_id,
+            } => (
+                "InitializedChannelOnTendermint",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::InitializedChannelOnTendermint(InitializedChannelOnTendermint {
+                    channel_id: channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::CloseChannelInitOnSoloMachine {
+                chain_id,
+                request_id,
+                channel_id,
+            } => (
+                "CloseChannelInitOnSoloMachine",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::CloseChannelInitOnSoloMachine(CloseChannelInitOnSoloMachine {
+                    channel_id: channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::InitializedChannelOnSoloMachine {
+                chain_id,
+                request_id,
+                channel_id,
+            } => (
+                "InitializedChannelOnSoloMachine",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::InitializedChannelOnSoloMachine(InitializedChannelOnSoloMachine {
+                    channel_id: channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::ConfirmedChannelOnTendermint {
+                chain_id,
+                request_id,
+                channel_id,
+            } => (
+                "ConfirmedChannelOnTendermint",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ConfirmedChannelOnTendermint(ConfirmedChannelOnTendermint {
+                    channel_id: channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::ConfirmedChannelOnSoloMachine {
+                chain_id,
+                request_id,
+                channel_id,
+            } => (
+                "ConfirmedChannelOnSoloMachine",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ConfirmedChannelOnSoloMachine(ConfirmedChannelOnSoloMachine {
+                    channel_id: channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::ChannelClosed {
+                chain_id,
+                request_id,
+                solo_machine_channel_id,
+                tendermint_channel_id,
+            } => (
+                "ChannelClosed",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ChannelClosed(ChannelClosed {
+                    solo_machine_channel_id: solo_machine_channel_id.to_string(),
+                    tendermint_channel_id: tendermint_channel_id.to_string(),
+                }),
+            ),
+            CoreEvent::ConnectionEstablished {
+                chain_id,
+                request_id,
+                connection_details,
+            } => (
+                "ConnectionEstablished",
+                Some(chain_id.to_string()),
+                request_id,
+                Details::ConnectionEstablished(ConnectionEstablished {
+                    connection_details: Some(connection_details.into()),
+                }),
+            ),
+            CoreEvent::ChainAdded { chain_id } => (
+                "ChainAdded",
+                Some(chain_id.to_string()),
+                None,
+                Details::ChainAdded(ChainAdded {}),
+            ),
+            CoreEvent::ChainConfigUpdated { chain_id } => (
+                "ChainConfigUpdated",
+                Some(chain_id.to_string()),
+                None,
+                Details::ChainConfigUpdated(ChainConfigUpdated {}),
+            ),
+            CoreEvent::ChainArchived { chain_id } => (
+                "ChainArchived",
+                Some(chain_id.to_string()),
+                None,
+                Details::ChainArchived(ChainArchived {}),
+            ),
+            CoreEvent::ChainRemoved { chain_id } => (
+                "ChainRemoved",
+                Some(chain_id.to_string()),
+                None,
+                Details::ChainRemoved(ChainRemoved {}),
+            ),
+            CoreEvent::SupplyDiscrepancy {
+                chain_id,
+                denom,
+                ibc_denom,
+                ledger_supply,
+                on_chain_supply,
+                escrow_balance,
+            } => (
+                "SupplyDiscrepancy",
+                Some(chain_id.to_string()),
+                None,
+                Details::SupplyDiscrepancy(SupplyDiscrepancy {
+                    denom: denom.to_string(),
+                    ibc_denom,
+                    ledger_supply: ledger_supply.to_string(),
+                    on_chain_supply: on_chain_supply.to_string(),
+                    escrow_balance: escrow_balance.to_string(),
+                }),
+            ),
+            CoreEvent::Warning { message } => {
+                ("Warning", None, None, Details::Warning(Warning { message }))
+            }
+        };
+
+        Self {
+            event_type: event_type.to_string(),
+            chain_id,
+            request_id,
+            emitted_at: Some(emitted_event.emitted_at.into()),
+            details: Some(details),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use primitive_types::U256;
+    use solo_machine_core::event::EventHandler;
+    use tokio_stream::StreamExt;
+    use tonic::Code;
+
+    use super::*;
+
+    fn tokens_minted(chain_id: &str, request_id: &str) -> CoreEvent {
+        CoreEvent::TokensMinted {
+            chain_id: chain_id.parse().unwrap(),
+            request_id: Some(request_id.to_owned()),
+            to_address: "cosmos1address".to_owned(),
+            amount: U256::from(100),
+            denom: "gld".parse().unwrap(),
+            transaction_hash: "ABCD".to_owned(),
+        }
+    }
+
+    async fn subscribe(
+        service: &EventService,
+        request: SubscribeEventsRequest,
+    ) -> Result<<EventService as Events>::SubscribeEventsStream, Status> {
+        service
+            .subscribe_events(Request::new(request))
+            .await
+            .map(Response::into_inner)
+    }
+
+    /// Returns the next item of stream (or `None` if nothing is streamed for a while)
+    async fn next(
+        stream: &mut <EventService as Events>::SubscribeEventsStream,
+    ) -> Option<Result<Event, Status>> {
+        tokio::time::timeout(Duration::from_millis(100), stream.next())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn subscribe_rejects_unknown_event_type() {
+        let service = EventService::new(EventBroadcaster::new());
+
+        let err = subscribe(
+            &service,
+            SubscribeEventsRequest {
+                event_types: vec!["TokensMinted".to_owned(), "TokensStolen".to_owned()],
+                ..Default::default()
+            },
+        )
+        .await
+        .err()
+        .unwrap();
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("TokensStolen"));
+    }
+
+    #[tokio::test]
+    async fn stream_only_contains_matching_events() {
+        let broadcaster = EventBroadcaster::new();
+        let service = EventService::new(broadcaster.clone());
+
+        let mut stream = subscribe(
+            &service,
+            SubscribeEventsRequest {
+                chain_ids: vec!["test-1".to_owned()],
+                request_ids: vec!["request-1".to_owned(), "request-2".to_owned()],
+                event_types: vec!["TokensMinted".to_owned()],
+            },
+        )
+        .await
+        .unwrap();
+
+        for event in [
+            tokens_minted("test-2", "request-1"),
+            tokens_minted("test-1", "request-3"),
+            CoreEvent::ChainAdded {
+                chain_id: "test-1".parse().unwrap(),
+            },
+            CoreEvent::Warning {
+                message: "warning".to_owned(),
+            },
+            tokens_minted("test-1", "request-1"),
+            tokens_minted("test-1", "request-2"),
+        ] {
+            broadcaster.handle(event).await.unwrap();
+        }
+
+        for request_id in ["request-1", "request-2"] {
+            let event = next(&mut stream).await.unwrap().unwrap();
+            assert_eq!(event.event_type, "TokensMinted");
+            assert_eq!(event.chain_id.as_deref(), Some("test-1"));
+            assert_eq!(event.request_id.as_deref(), Some(request_id));
+            assert!(matches!(event.details, Some(Details::TokensMinted(_))));
+        }
+
+        assert!(next(&mut stream).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_without_filters_contains_all_events() {
+        let broadcaster = EventBroadcaster::new();
+        let service = EventService::new(broadcaster.clone());
+
+        let mut stream = subscribe(&service, Default::default()).await.unwrap();
+
+        broadcaster
+            .handle(tokens_minted("test-1", "request-1"))
+            .await
+            .unwrap();
+        broadcaster
+            .handle(CoreEvent::Warning {
+                message: "warning".to_owned(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            next(&mut stream).await.unwrap().unwrap().event_type,
+            "TokensMinted"
+        );
+
+        let event = next(&mut stream).await.unwrap().unwrap();
+        assert_eq!(event.event_type, "Warning");
+        assert_eq!(event.chain_id, None);
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_is_terminated() {
+        let broadcaster = EventBroadcaster::new();
+        let service = EventService::new(broadcaster.clone());
+
+        let mut stream = subscribe(&service, Default::default()).await.unwrap();
+
+        // Events are broadcasted without yielding, so, the subscriber falls behind before receiving any event
+        for i in 0..2000 {
+            broadcaster
+                .handle(CoreEvent::Warning {
+                    message: i.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let err = next(&mut stream).await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert!(next(&mut stream).await.is_none());
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use solo_machine_core::{model::EndpointKind, ping_db, service::ChainService, DbPool};
+use tonic_health::{server::HealthReporter, ServingStatus};
+
+use super::{chain::chain_server, ibc::ibc_server, policy::policy_server};
+
+/// Number of chains fetched from database in one go when checking chain reachability
+const CHAIN_BATCH_SIZE: i32 = 100;
+
+/// Periodically checks database connectivity and reachability of configured chains and updates the statuses served
+/// by `grpc.health.v1.Health` service.
+///
+/// - `chain.Chain` and `policy.Policy` are serving when database is reachable.
+/// - `ibc.Ibc` and the overall server status (`""`) are serving when database is reachable and all the configured
+///   chains have at least one healthy RPC and gRPC endpoint.
+pub struct HealthChecker {
+    db_pool: DbPool,
+    reporter: HealthReporter,
+    interval: Duration,
+}
+
+impl HealthChecker {
+    /// Creates a new instance of health checker
+    pub fn new(db_pool: DbPool, reporter: HealthReporter, interval: Duration) -> Self {
+        Self {
+            db_pool,
+            reporter,
+            interval,
+        }
+    }
+
+    /// Updates health statuses once and then spawns a task which keeps updating them after every `interval`
+    pub async fn spawn(mut self) {
+        self.check().await;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        });
+    }
+
+    async fn check(&mut self) {
+        let db_status = match ping_db(&self.db_pool).await {
+            Ok(()) => ServingStatus::Serving,
+            Err(err) => {
+                log::warn!("health check failed: {:#}", err);
+                ServingStatus::NotServing
+            }
+        };
+
+        let chain_status = if db_status == ServingStatus::Serving {
+            match self.check_chains().await {
+                Ok(()) => ServingStatus::Serving,
+                Err(err) => {
+                    log::warn!("health check failed: {:#}", err);
+                    ServingStatus::NotServing
+                }
+            }
+        } else {
+            ServingStatus::NotServing
+        };
+
+        self.reporter
+            .set_service_status(chain_server::SERVICE_NAME, db_status)
+            .await;
+        self.reporter
+            .set_service_status(policy_server::SERVICE_NAME, db_status)
+            .await;
+        self.reporter
+            .set_service_status(ibc_server::SERVICE_NAME, chain_status)
+            .await;
+        self.reporter.set_service_status("", chain_status).await;
+    }
+
+    async fn check_chains(&self) -> Result<()> {
+        let chain_service = ChainService::new(self.db_pool.clone());
+        let mut unreachable = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let chains = chain_service.list(CHAIN_BATCH_SIZE, offset).await?;
+
+            for chain in chains.iter() {
+                let health = chain.check_endpoints().await;
+
+                let is_reachable = |kind| {
+                    health
+                        .iter()
+                        .any(|endpoint| endpoint.kind == kind && endpoint.is_healthy)
+                };
+
+                if !is_reachable(EndpointKind::Rpc) || !is_reachable(EndpointKind::Grpc) {
+                    unreachable.push(chain.id.to_string());
+                }
+            }
+
+            if chains.len() < CHAIN_BATCH_SIZE as usize {
+                break;
+            }
+
+            offset += CHAIN_BATCH_SIZE;
+        }
+
+        if unreachable.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("unreachable chains: [{}]", unreachable.join(", ")))
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::net::TcpListener;
+    use tonic::{
+        metadata::MetadataValue,
+        transport::{server::TcpIncoming, Channel, Server},
+        Code, Request,
+    };
+    use tonic_health::pb::{
+        health_check_response::ServingStatus as ProtoServingStatus, health_client::HealthClient,
+        HealthCheckRequest,
+    };
+    use tonic_reflection::pb::v1::{
+        server_reflection_client::ServerReflectionClient,
+        server_reflection_request::MessageRequest, ServerReflectionRequest,
+    };
+    use tower::ServiceBuilder;
+
+    use super::*;
+    use crate::server::{
+        auth::{add_grpc_path, AuthInterceptor},
+        ApiKeys,
+    };
+
+    const API_KEY: &str = "admin-secret-0123456789";
+
+    async fn api_keys() -> ApiKeys {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "solo-machine-health-api-keys-{}-{}.json",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        tokio::fs::write(
+            &path,
+            format!(
+                r#"[{{ "name": "admin", "key": "{}", "methods": ["*"] }}]"#,
+                API_KEY
+            ),
+        )
+        .await
+        .unwrap();
+
+        let api_keys = ApiKeys::from_file(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        api_keys
+    }
+
+    /// Serves health (and reflection) service with API key authentication and returns a channel connected to it
+    async fn serve(db_pool: DbPool) -> Channel {
+        let (reporter, health_service) = tonic_health::server::health_reporter();
+        HealthChecker::new(db_pool, reporter, Duration::from_secs(60))
+            .spawn()
+            .await;
+
+        let reflection_service = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+            .build_v1()
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+
+        let server = Server::builder()
+            .layer(ServiceBuilder::new().map_request(add_grpc_path).layer(
+                tonic::service::interceptor(AuthInterceptor::new(Some(api_keys().await))),
+            ))
+            .add_service(health_service)
+            .add_service(reflection_service)
+            .serve_with_incoming(incoming);
+        tokio::spawn(server);
+
+        Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap()
+    }
+
+    async fn status(channel: &Channel, service: &str) -> ProtoServingStatus {
+        HealthClient::new(channel.clone())
+            .check(HealthCheckRequest {
+                service: service.to_owned(),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .status()
+    }
+
+    async fn list_services(channel: &Channel, api_key: Option<&str>) -> Result<(), tonic::Status> {
+        let mut request = Request::new(tokio_stream::iter(vec![ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        }]));
+
+        if let Some(api_key) = api_key {
+            request
+                .metadata_mut()
+                .insert("x-api-key", MetadataValue::try_from(api_key).unwrap());
+        }
+
+        ServerReflectionClient::new(channel.clone())
+            .server_reflection_info(request)
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn health_checks_do_not_require_api_key() {
+        let channel = serve(crate::server::test_db_pool().await).await;
+
+        for service in ["", ibc_server::SERVICE_NAME, chain_server::SERVICE_NAME] {
+            assert_eq!(
+                status(&channel, service).await,
+                ProtoServingStatus::Serving,
+                "{}",
+                service
+            );
+        }
+
+        // Other services still require an API key
+        assert_eq!(
+            list_services(&channel, None).await.unwrap_err().code(),
+            Code::Unauthenticated
+        );
+        assert_eq!(
+            list_services(&channel, Some("invalid-secret-0123456789"))
+                .await
+                .unwrap_err()
+                .code(),
+            Code::Unauthenticated
+        );
+        assert!(list_services(&channel, Some(API_KEY)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn services_are_not_serving_when_database_is_unreachable() {
+        let db_pool = crate::server::test_db_pool().await;
+        db_pool.close().await;
+
+        let channel = serve(db_pool).await;
+
+        for service in [
+            "",
+            ibc_server::SERVICE_NAME,
+            chain_server::SERVICE_NAME,
+            policy_server::SERVICE_NAME,
+        ] {
+            assert_eq!(
+                status(&channel, service).await,
+                ProtoServingStatus::NotServing,
+                "{}",
+                service
+            );
+        }
+    }
+}
@@ -1,43 +1,106 @@
 tonic::include_proto!("ibc");
 
-use std::time::SystemTime;
+use std::{
+    convert::{TryFrom, TryInto},
+    time::SystemTime,
+};
 
 use k256::ecdsa::VerifyingKey;
 use primitive_types::U256;
+use prost::EncodeError;
+use prost_types::{value::Kind, Any, ListValue, NullValue, Struct, Value};
 use solo_machine_core::{
     cosmos::crypto::{PublicKey, PublicKeyAlgo},
     ibc::core::ics24_host::identifier::ChainId,
-    service::IbcService as CoreIbcService,
+    model::{JobKind, OperationFilter, OperationPage},
+    service::{
+        ChainService as CoreChainService, DryRunOperation, DryRunReport as CoreDryRunReport,
+        IbcService as CoreIbcService, MintRejected,
+    },
     DbPool, Event, Signer,
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::{Request, Response, Status};
 
+use super::{into_status, job_runner::JobRunner};
+
 use self::ibc_server::Ibc;
 
 const DEFAULT_MEMO: &str = "solo-machine-memo";
 
 pub struct IbcService<S> {
+    db_pool: DbPool,
     core_service: CoreIbcService,
     signer: S,
+    job_runner: JobRunner,
 }
 
 impl<S> IbcService<S> {
     /// Creates a new instance of gRPC IBC service
-    pub fn new(db_pool: DbPool, notifier: UnboundedSender<Event>, signer: S) -> Self {
-        let core_service = CoreIbcService::new_with_notifier(db_pool, notifier);
+    pub fn new(
+        db_pool: DbPool,
+        notifier: UnboundedSender<Event>,
+        signer: S,
+        job_runner: JobRunner,
+    ) -> Self {
+        let core_service = CoreIbcService::new_with_notifier(db_pool.clone(), notifier);
 
         Self {
+            db_pool,
             core_service,
             signer,
+            job_runner,
+        }
+    }
+}
+
+impl<S> IbcService<S>
+where
+    S: Signer,
+{
+    /// Builds the transactions of an operation without signing or broadcasting them and returns the report of dry run
+    async fn dry_run(
+        &self,
+        chain_id: ChainId,
+        request_id: Option<String>,
+        operation: DryRunOperation,
+        simulate: bool,
+    ) -> Result<DryRunReport, Status> {
+        let report = self
+            .core_service
+            .dry_run(&self.signer, chain_id, request_id, operation, simulate)
+            .await
+            .map_err(into_mint_status)?;
+
+        DryRunReport::try_from(report).map_err(into_status)
+    }
+
+    /// Fetches history of operations matching given request (also used by REST gateway so that it returns the same
+    /// operations as `--output json` of command line)
+    pub async fn history(&self, request: &QueryHistoryRequest) -> Result<OperationPage, Status> {
+        let limit = i32::try_from(request.limit.unwrap_or(10))
+            .or(Err(Status::invalid_argument("invalid `limit`")))?;
+        let offset = i32::try_from(request.offset.unwrap_or(0))
+            .or(Err(Status::invalid_argument("invalid `offset`")))?;
+        let filter = OperationFilter::try_from(request)?;
+
+        if request.cursor.is_some() && request.offset.is_some() {
+            return Err(Status::invalid_argument(
+                "`cursor` and `offset` cannot be used together",
+            ));
         }
+
+        self.core_service
+            .history(&self.signer, &filter, request.cursor, limit, offset)
+            .await
+            .map_err(into_status)
     }
 }
 
 #[tonic::async_trait]
 impl<S> Ibc for IbcService<S>
 where
-    S: Signer + Send + Sync + 'static,
+    S: Signer + Clone + Send + Sync + 'static,
 {
     async fn connect(
         &self,
@@ -45,7 +108,7 @@ where
     ) -> Result<Response<ConnectResponse>, Status> {
         let request = request.into_inner();
 
-        let chain_id = request
+        let chain_id: ChainId = request
             .chain_id
             .parse()
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
@@ -53,15 +116,106 @@ where
         let request_id = request.request_id;
         let force = request.force;
 
-        self.core_service
-            .connect(&self.signer, chain_id, request_id, memo, force)
+        if request.dry_run {
+            if force {
+                return Err(Status::invalid_argument(
+                    "`force` is not supported in dry runs",
+                ));
+            }
+
+            let dry_run_report = self
+                .dry_run(
+                    chain_id,
+                    request_id,
+                    DryRunOperation::Connect { memo },
+                    request.simulate,
+                )
+                .await?;
+
+            return Ok(Response::new(ConnectResponse {
+                operation_id: 0,
+                dry_run_report: Some(dry_run_report),
+            }));
+        }
+
+        let db_pool = self.db_pool.clone();
+        let signer = self.signer.clone();
+        let job_chain_id = chain_id.clone();
+        let job_request_id = request_id.clone();
+
+        let job = self
+            .job_runner
+            .spawn(
+                JobKind::Connect,
+                &chain_id,
+                request_id.as_deref(),
+                move |notifier, cancellation| async move {
+                    CoreIbcService::new_with_notifier(db_pool.clone(), notifier)
+                        .with_cancellation(cancellation)
+                        .connect(signer, job_chain_id.clone(), job_request_id, memo, force)
+                        .await?;
+
+                    let connection_details = CoreChainService::new(db_pool)
+                        .get(&job_chain_id)
+                        .await?
+                        .and_then(|chain| chain.connection_details);
+
+                    Ok(Some(serde_json::to_value(connection_details)?))
+                },
+            )
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
 
-        Ok(Response::new(ConnectResponse {}))
+        Ok(Response::new(ConnectResponse {
+            operation_id: job.id,
+            dry_run_report: None,
+        }))
+    }
+
+    async fn close_channel(
+        &self,
+        request: Request<CloseChannelRequest>,
+    ) -> Result<Response<CloseChannelResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id: ChainId = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
+        let request_id = request.request_id;
+
+        let db_pool = self.db_pool.clone();
+        let signer = self.signer.clone();
+        let job_chain_id = chain_id.clone();
+        let job_request_id = request_id.clone();
+
+        let job = self
+            .job_runner
+            .spawn(
+                JobKind::CloseChannel,
+                &chain_id,
+                request_id.as_deref(),
+                move |notifier, cancellation| async move {
+                    CoreIbcService::new_with_notifier(db_pool.clone(), notifier)
+                        .with_cancellation(cancellation)
+                        .close_channel(signer, &job_chain_id, job_request_id, memo)
+                        .await?;
+
+                    let connection_details = CoreChainService::new(db_pool)
+                        .get(&job_chain_id)
+                        .await?
+                        .and_then(|chain| chain.connection_details);
+
+                    Ok(Some(serde_json::to_value(connection_details)?))
+                },
+            )
+            .await
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
+
+        Ok(Response::new(CloseChannelResponse {
+            operation_id: job.id,
+        }))
     }
 
     async fn mint(&self, request: Request<MintRequest>) -> Result<Response<MintResponse>, Status> {
@@ -81,6 +235,27 @@ where
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
         let receiver = request.receiver_address;
 
+        if request.dry_run {
+            let dry_run_report = self
+                .dry_run(
+                    chain_id,
+                    request_id,
+                    DryRunOperation::Mint {
+                        amount,
+                        denom,
+                        receiver,
+                        memo,
+                    },
+                    request.simulate,
+                )
+                .await?;
+
+            return Ok(Response::new(MintResponse {
+                transaction_hash: String::new(),
+                dry_run_report: Some(dry_run_report),
+            }));
+        }
+
         let transaction_hash = self
             .core_service
             .mint(
@@ -93,12 +268,12 @@ where
                 memo,
             )
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_mint_status)?;
 
-        Ok(Response::new(MintResponse { transaction_hash }))
+        Ok(Response::new(MintResponse {
+            transaction_hash,
+            dry_run_report: None,
+        }))
     }
 
     async fn burn(&self, request: Request<BurnRequest>) -> Result<Response<BurnResponse>, Status> {
@@ -117,16 +292,36 @@ where
             .parse()
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
 
+        if request.dry_run {
+            let dry_run_report = self
+                .dry_run(
+                    chain_id,
+                    request_id,
+                    DryRunOperation::Burn {
+                        amount,
+                        denom,
+                        memo,
+                    },
+                    request.simulate,
+                )
+                .await?;
+
+            return Ok(Response::new(BurnResponse {
+                transaction_hash: String::new(),
+                dry_run_report: Some(dry_run_report),
+            }));
+        }
+
         let transaction_hash = self
             .core_service
             .burn(&self.signer, chain_id, request_id, amount, denom, memo)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
-        Ok(Response::new(BurnResponse { transaction_hash }))
+        Ok(Response::new(BurnResponse {
+            transaction_hash,
+            dry_run_report: None,
+        }))
     }
 
     async fn update_signer(
@@ -163,53 +358,370 @@ where
             PublicKeyAlgo::EthSecp256k1 => PublicKey::EthSecp256k1(new_verifying_key),
         };
 
-        self.core_service
+        if request.dry_run {
+            let dry_run_report = self
+                .dry_run(
+                    chain_id,
+                    request_id,
+                    DryRunOperation::UpdateSigner {
+                        new_public_key,
+                        memo,
+                    },
+                    request.simulate,
+                )
+                .await?;
+
+            return Ok(Response::new(UpdateSignerResponse {
+                transaction_hash: String::new(),
+                dry_run_report: Some(dry_run_report),
+            }));
+        }
+
+        let transaction_hash = self
+            .core_service
             .update_signer(&self.signer, chain_id, request_id, new_public_key, memo)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
-        Ok(Response::new(UpdateSignerResponse {}))
+        Ok(Response::new(UpdateSignerResponse {
+            transaction_hash,
+            dry_run_report: None,
+        }))
     }
 
     async fn query_history(
         &self,
         request: Request<QueryHistoryRequest>,
     ) -> Result<Response<QueryHistoryResponse>, Status> {
-        let request = request.into_inner();
-
-        let limit = i32::try_from(request.limit.unwrap_or(10))
-            .or(Err(Status::invalid_argument("invalid `limit`")))?;
-        let offset = i32::try_from(request.offset.unwrap_or(0))
-            .or(Err(Status::invalid_argument("invalid `offset`")))?;
-
-        let history = self
-            .core_service
-            .history(&self.signer, limit, offset)
-            .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+        let history = self.history(&request.into_inner()).await?;
 
         let response = QueryHistoryResponse {
             operations: history
+                .operations
                 .into_iter()
                 .map(|op| Operation {
                     id: op.id,
                     request_id: op.request_id,
                     address: op.address,
-                    denom: op.denom.to_string(),
-                    amount: op.amount.to_string(),
+                    denom: op.denom.map(|denom| denom.to_string()),
+                    amount: op.amount.map(|amount| amount.to_string()),
                     operation_type: op.operation_type.to_string(),
                     transaction_hash: op.transaction_hash,
                     created_at: Some(SystemTime::from(op.created_at).into()),
+                    status: op.status.to_string(),
+                    error: op.error,
+                    block_height: op.block_height,
+                    gas_used: op.gas_used,
+                    fee_paid: op.fee_paid,
+                    updated_at: Some(SystemTime::from(op.updated_at).into()),
                 })
                 .collect(),
+            next_cursor: history.next_cursor,
         };
 
         Ok(Response::new(response))
     }
+
+    async fn query_client_state(
+        &self,
+        request: Request<QueryClientStateRequest>,
+    ) -> Result<Response<QueryClientStateResponse>, Status> {
+        let chain_id = request
+            .into_inner()
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        let client = self
+            .core_service
+            .get_tendermint_client(&chain_id)
+            .await
+            .map_err(into_status)?
+            .ok_or_else(|| Status::not_found("solo machine is not connected to chain"))?;
+
+        Ok(Response::new(QueryClientStateResponse {
+            client_id: client.client_id.to_string(),
+            client_state: Some(
+                Any::from_msg(&client.client_state)
+                    .map_err(|err| Status::internal(err.to_string()))?,
+            ),
+            consensus_state: client
+                .consensus_state
+                .as_ref()
+                .map(Any::from_msg)
+                .transpose()
+                .map_err(|err| Status::internal(err.to_string()))?,
+        }))
+    }
+
+    async fn query_connection(
+        &self,
+        request: Request<QueryConnectionRequest>,
+    ) -> Result<Response<QueryConnectionResponse>, Status> {
+        let chain_id = request
+            .into_inner()
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        let connection = self
+            .core_service
+            .get_connection(&chain_id)
+            .await
+            .map_err(into_status)?
+            .ok_or_else(|| Status::not_found("solo machine is not connected to chain"))?;
+
+        Ok(Response::new(QueryConnectionResponse {
+            connection_id: connection.connection_id.to_string(),
+            connection: Some(
+                Any::from_msg(&connection.connection)
+                    .map_err(|err| Status::internal(err.to_string()))?,
+            ),
+        }))
+    }
+
+    async fn query_channels(
+        &self,
+        request: Request<QueryChannelsRequest>,
+    ) -> Result<Response<QueryChannelsResponse>, Status> {
+        let chain_id = request
+            .into_inner()
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        let channels = self
+            .core_service
+            .get_channels(&chain_id)
+            .await
+            .map_err(into_status)?
+            .into_iter()
+            .map(|channel| {
+                Ok(IdentifiedChannel {
+                    port_id: channel.port_id.to_string(),
+                    channel_id: channel.channel_id.to_string(),
+                    channel: Some(Any::from_msg(&channel.channel)?),
+                })
+            })
+            .collect::<Result<_, EncodeError>>()
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(QueryChannelsResponse { channels }))
+    }
+}
+
+/// Converts an error returned while minting tokens into gRPC status (rejection by mint policies is not an internal
+/// error)
+fn into_mint_status(err: anyhow::Error) -> Status {
+    if err.is::<MintRejected>() {
+        log::warn!("{}", err);
+        Status::failed_precondition(err.to_string())
+    } else {
+        into_status(err)
+    }
+}
+
+impl TryFrom<&QueryHistoryRequest> for OperationFilter {
+    type Error = Status;
+
+    fn try_from(request: &QueryHistoryRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: request
+                .chain_id
+                .as_ref()
+                .map(|chain_id| chain_id.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            denom: request
+                .denom
+                .as_ref()
+                .map(|denom| denom.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            kind: request
+                .operation_type
+                .as_ref()
+                .map(|operation_type| operation_type.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            request_id: request.request_id.clone(),
+            status: request
+                .status
+                .as_ref()
+                .map(|status| status.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            created_after: request
+                .created_after
+                .map(|time| SystemTime::try_from(time).map(Into::into))
+                .transpose()
+                .map_err(|_| Status::invalid_argument("invalid `created_after`"))?,
+            created_before: request
+                .created_before
+                .map(|time| SystemTime::try_from(time).map(Into::into))
+                .transpose()
+                .map_err(|_| Status::invalid_argument("invalid `created_before`"))?,
+        })
+    }
+}
+
+impl TryFrom<CoreDryRunReport> for DryRunReport {
+    type Error = anyhow::Error;
+
+    fn try_from(report: CoreDryRunReport) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: report.chain_id.to_string(),
+            sequence: report.sequence,
+            next_sequence: report.next_sequence,
+            packet_sequence: report.packet_sequence,
+            next_packet_sequence: report.next_packet_sequence,
+            signed_messages: report
+                .signed_messages
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            transactions: report
+                .transactions
+                .into_iter()
+                .map(|transaction| DryRunTransaction {
+                    size: transaction.size as u64,
+                    messages: transaction
+                        .messages
+                        .into_iter()
+                        .map(|message| DryRunMessage {
+                            type_url: message.type_url,
+                            value: Some(json_to_value(message.value)),
+                        })
+                        .collect(),
+                    memo: transaction.memo,
+                    fee: transaction.fee,
+                    gas_limit: transaction.gas_limit,
+                    simulated_gas_used: transaction.simulated_gas_used,
+                    simulation_error: transaction.simulation_error,
+                })
+                .collect(),
+            remaining_steps: report.remaining_steps,
+        })
+    }
+}
+
+/// Converts a JSON value into `google.protobuf.Value`
+fn json_to_value(json: serde_json::Value) -> Value {
+    let kind = match json {
+        serde_json::Value::Null => Kind::NullValue(NullValue::NullValue.into()),
+        serde_json::Value::Bool(value) => Kind::BoolValue(value),
+        serde_json::Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        serde_json::Value::String(value) => Kind::StringValue(value),
+        serde_json::Value::Array(values) => Kind::ListValue(ListValue {
+            values: values.into_iter().map(json_to_value).collect(),
+        }),
+        serde_json::Value::Object(fields) => Kind::StructValue(Struct {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| (key, json_to_value(value)))
+                .collect(),
+        }),
+    };
+
+    Value { kind: Some(kind) }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use std::sync::Arc;
+
+    use k256::ecdsa::SigningKey;
+    use solo_machine_core::signer::OfflineSigner;
+    use tokio::sync::mpsc::unbounded_channel;
+    use tonic::Code;
+
+    use super::*;
+
+    async fn service() -> IbcService<Arc<OfflineSigner>> {
+        let db_pool = crate::server::test_db_pool().await;
+        let (sender, _) = unbounded_channel();
+        let job_runner = JobRunner::new(db_pool.clone(), sender.clone())
+            .await
+            .unwrap();
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let signer = OfflineSigner::new(
+            PublicKey::Secp256k1(*signing_key.verifying_key()),
+            "cosmos".to_owned(),
+            &[],
+        );
+
+        IbcService::new(db_pool, sender, Arc::new(signer), job_runner)
+    }
+
+    #[tokio::test]
+    async fn ibc_data_queries_map_errors_to_status() {
+        let service = service().await;
+
+        let err = service
+            .query_client_state(Request::new(QueryClientStateRequest {
+                chain_id: "invalid chain".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = service
+            .query_client_state(Request::new(QueryClientStateRequest {
+                chain_id: "test-1".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+
+        let err = service
+            .query_connection(Request::new(QueryConnectionRequest {
+                chain_id: "test-1".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+
+        let err = service
+            .query_channels(Request::new(QueryChannelsRequest {
+                chain_id: "test-1".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn close_channel_rejects_unknown_chain() {
+        let service = service().await;
+
+        let err = service
+            .close_channel(Request::new(CloseChannelRequest {
+                chain_id: "invalid chain".to_owned(),
+                request_id: None,
+                memo: None,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        // Operation is not started for an unknown chain
+        let err = service
+            .close_channel(Request::new(CloseChannelRequest {
+                chain_id: "test-1".to_owned(),
+                request_id: Some("request-1".to_owned()),
+                memo: None,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::FailedPrecondition);
+        assert!(err.message().contains("not found"));
+
+        assert!(service
+            .job_runner
+            .job_service()
+            .list(&Default::default(), 10, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, ensure, Result};
+use serde_json::Value;
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{Job, JobKind},
+    service::{CancellationToken, JobService},
+    DbPool, Event,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// Executes long-running operations (jobs) in background tasks and records their progress and results in database
+#[derive(Clone)]
+pub struct JobRunner {
+    job_service: JobService,
+    notifier: UnboundedSender<Event>,
+    cancellations: Arc<Mutex<HashMap<i64, CancellationToken>>>,
+}
+
+impl JobRunner {
+    /// Creates a new instance of job runner. Jobs which were running when solo machine was stopped are marked as
+    /// failed.
+    pub async fn new(db_pool: DbPool, notifier: UnboundedSender<Event>) -> Result<Self> {
+        let job_service = JobService::new(db_pool);
+
+        let interrupted = job_service.fail_interrupted().await?;
+
+        if interrupted > 0 {
+            log::warn!("marked {} interrupted jobs as failed", interrupted);
+        }
+
+        Ok(Self {
+            job_service,
+            notifier,
+            cancellations: Default::default(),
+        })
+    }
+
+    /// Returns job service used by runner
+    pub fn job_service(&self) -> &JobService {
+        &self.job_service
+    }
+
+    /// Starts a job in background and returns immediately. `run` receives the notifier to be used for emitting
+    /// events so that they're recorded as progress of job (and also forwarded to event handlers) and the token which is
+    /// cancelled when the job is cancelled.
+    pub async fn spawn<F, Fut>(
+        &self,
+        kind: JobKind,
+        chain_id: &ChainId,
+        request_id: Option<&str>,
+        run: F,
+    ) -> Result<Job>
+    where
+        F: FnOnce(UnboundedSender<Event>, CancellationToken) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Option<Value>>> + Send + 'static,
+    {
+        let job = self.job_service.start(kind, chain_id, request_id).await?;
+        let id = job.id;
+
+        let job_service = self.job_service.clone();
+        let notifier = self.notifier.clone();
+        let cancellations = self.cancellations.clone();
+
+        let (job_notifier, mut receiver) = unbounded_channel();
+        let cancellation = CancellationToken::new();
+
+        // Insert cancellation token before spawning so that the task cannot remove it before insertion
+        self.cancellations
+            .lock()
+            .map_err(|_| anyhow!("job cancellations lock poisoned"))?
+            .insert(id, cancellation.clone());
+
+        tokio::spawn(async move {
+            let forward = async {
+                // Receiver is closed once `run` drops its notifier, so all the events are recorded before finishing
+                // the job
+                while let Some(event) = receiver.recv().await {
+                    if let Err(err) = job_service.add_progress(id, &event).await {
+                        log::error!("unable to record progress of job {}: {:?}", id, err);
+                    }
+
+                    if let Err(err) = notifier.send(event) {
+                        log::error!("unable to send event to notifier: {}", err);
+                    }
+                }
+            };
+
+            let (result, ()) = tokio::join!(run(job_notifier, cancellation), forward);
+
+            if let Err(ref err) = result {
+                log::error!("job {} stopped: {:?}", id, err);
+            }
+
+            match job_service.finish(id, &result).await {
+                Ok(true) => {}
+                Ok(false) => log::warn!("job {} was already finished", id),
+                Err(err) => log::error!("unable to record result of job {}: {:?}", id, err),
+            }
+
+            cancellations
+                .lock()
+                .expect("job cancellations lock poisoned")
+                .remove(&id);
+        });
+
+        Ok(job)
+    }
+
+    /// Requests cancellation of a running job and returns it (still running). The job stops before its next step
+    /// (the step in progress is completed) and is marked as cancelled once it stops. If the job finishes its last step
+    /// anyway, it is marked as succeeded. Transactions already broadcasted by the job are not reverted.
+    pub async fn cancel(&self, id: i64) -> Result<Job> {
+        let job = self
+            .job_service
+            .get(id)
+            .await?
+            .ok_or_else(|| anyhow!("job with id {} not found", id))?;
+
+        ensure!(
+            !job.status.is_finished(),
+            "job with id {} is already {}",
+            id,
+            job.status
+        );
+
+        // Cancellation token is removed by the task of job once it finishes
+        let cancellation = self
+            .cancellations
+            .lock()
+            .map_err(|_| anyhow!("job cancellations lock poisoned"))?
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| anyhow!("job with id {} is already finishing", id))?;
+
+        cancellation.cancel();
+
+        Ok(job)
+    }
+}
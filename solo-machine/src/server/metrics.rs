@@ -0,0 +1,159 @@
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use rust_decimal::prelude::ToPrimitive;
+use solo_machine_core::{
+    service::{ChainService, IbcService},
+    signer::ToPublicKey,
+    DbPool,
+};
+use tokio::net::TcpListener;
+
+use crate::metrics::{Metrics, CONTENT_TYPE};
+
+/// Number of chains fetched from database in one go when refreshing gauges
+const CHAIN_BATCH_SIZE: i32 = 100;
+
+/// Serves metrics of solo machine on `/metrics` endpoint and periodically refreshes the gauges which can't be derived
+/// from events (sequences, tendermint client age and fee account balance of each chain).
+pub struct MetricsServer<S> {
+    db_pool: DbPool,
+    signer: S,
+    metrics: Arc<Metrics>,
+    interval: Duration,
+}
+
+impl<S> MetricsServer<S>
+where
+    S: ToPublicKey + Send + Sync + 'static,
+{
+    /// Creates a new instance of metrics server
+    pub fn new(db_pool: DbPool, signer: S, metrics: Arc<Metrics>, interval: Duration) -> Self {
+        Self {
+            db_pool,
+            signer,
+            metrics,
+            interval,
+        }
+    }
+
+    /// Spawns a task which refreshes gauges after every `interval` and starts serving metrics on given address
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let metrics = self.metrics.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(err) = self.refresh_gauges().await {
+                    log::warn!("unable to refresh metrics: {:#}", err);
+                }
+            }
+        });
+
+        let router = Router::new()
+            .route("/metrics", get(get_metrics))
+            .with_state(metrics);
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("unable to bind metrics server to: {}", addr))?;
+
+        log::info!("starting metrics server at {}", addr);
+
+        axum::serve(listener, router)
+            .await
+            .with_context(|| format!("unable to start metrics server at: {}", addr))
+    }
+
+    async fn refresh_gauges(&self) -> Result<()> {
+        let chain_service = ChainService::new(self.db_pool.clone());
+        let ibc_service = IbcService::new(self.db_pool.clone());
+
+        let mut sequences = Vec::new();
+        let mut packet_sequences = Vec::new();
+        let mut client_ages = Vec::new();
+        let mut balances = Vec::new();
+
+        let mut offset = 0;
+
+        loop {
+            let chains = chain_service.list(CHAIN_BATCH_SIZE, offset).await?;
+
+            for chain in chains.iter() {
+                sequences.push((chain.id.to_string(), chain.sequence.into()));
+                packet_sequences.push((chain.id.to_string(), chain.packet_sequence.into()));
+
+                let consensus_timestamp = ibc_service
+                    .get_tendermint_client(&chain.id)
+                    .await?
+                    .and_then(|client| client.consensus_state)
+                    .and_then(|consensus_state| consensus_state.timestamp);
+
+                if let Some(timestamp) = consensus_timestamp {
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs_f64();
+                    let timestamp = timestamp.seconds as f64 + f64::from(timestamp.nanos) / 1e9;
+
+                    client_ages.push((chain.id.to_string(), now - timestamp));
+                }
+
+                // Unreachable chains should not stop the rest of the gauges from being refreshed
+                match chain.get_fee_balance(&self.signer).await {
+                    Ok(balance) => balances.push((
+                        chain.id.to_string(),
+                        chain.config.fee.denom.to_string(),
+                        balance.to_f64().unwrap_or_default(),
+                    )),
+                    Err(err) => log::warn!(
+                        "unable to fetch fee account balance [Chain ID: {}]: {:#}",
+                        chain.id,
+                        err
+                    ),
+                }
+            }
+
+            if chains.len() < CHAIN_BATCH_SIZE as usize {
+                break;
+            }
+
+            offset += CHAIN_BATCH_SIZE;
+        }
+
+        self.metrics.chain_sequence.replace(
+            sequences
+                .iter()
+                .map(|(chain_id, value)| (vec![chain_id.as_str()], *value)),
+        );
+        self.metrics.packet_sequence.replace(
+            packet_sequences
+                .iter()
+                .map(|(chain_id, value)| (vec![chain_id.as_str()], *value)),
+        );
+        self.metrics.tendermint_client_age.replace(
+            client_ages
+                .iter()
+                .map(|(chain_id, value)| (vec![chain_id.as_str()], *value)),
+        );
+        self.metrics.fee_account_balance.replace(
+            balances
+                .iter()
+                .map(|(chain_id, denom, value)| (vec![chain_id.as_str(), denom.as_str()], *value)),
+        );
+
+        Ok(())
+    }
+}
+
+async fn get_metrics(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], metrics.encode())
+}
@@ -0,0 +1,140 @@
+tonic::include_proto!("operations");
+
+use std::time::SystemTime;
+
+use solo_machine_core::model::{Job, JobFilter, JobProgress};
+use tonic::{Request, Response, Status};
+
+use crate::event::broadcaster::EmittedEvent;
+
+use super::{events::Event, into_status, job_runner::JobRunner};
+
+use self::operations_server::Operations;
+
+const DEFAULT_LIMIT: u32 = 10;
+
+pub struct OperationsService {
+    job_runner: JobRunner,
+}
+
+impl OperationsService {
+    /// Creates a new instance of gRPC operations service
+    pub fn new(job_runner: JobRunner) -> Self {
+        Self { job_runner }
+    }
+}
+
+#[tonic::async_trait]
+impl Operations for OperationsService {
+    async fn get_operation(
+        &self,
+        request: Request<GetOperationRequest>,
+    ) -> Result<Response<Operation>, Status> {
+        let id = request.into_inner().operation_id;
+        let job_service = self.job_runner.job_service();
+
+        let job = job_service
+            .get(id)
+            .await
+            .map_err(into_status)?
+            .ok_or_else(|| Status::not_found(format!("operation with id {} not found", id)))?;
+
+        let progress = job_service.get_progress(id).await.map_err(into_status)?;
+
+        Ok(Response::new(to_operation(job, progress)))
+    }
+
+    async fn list_operations(
+        &self,
+        request: Request<ListOperationsRequest>,
+    ) -> Result<Response<ListOperationsResponse>, Status> {
+        let request = request.into_inner();
+
+        let filter = JobFilter {
+            chain_id: request
+                .chain_id
+                .map(|chain_id| chain_id.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            kind: request
+                .kind
+                .map(|kind| kind.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+            status: request
+                .status
+                .map(|status| status.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
+        };
+
+        let limit = request.limit.unwrap_or(DEFAULT_LIMIT) as i32;
+        let offset = request.offset.unwrap_or_default() as i32;
+
+        let jobs = self
+            .job_runner
+            .job_service()
+            .list(&filter, limit, offset)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(ListOperationsResponse {
+            operations: jobs
+                .into_iter()
+                .map(|job| to_operation(job, Vec::new()))
+                .collect(),
+        }))
+    }
+
+    async fn cancel_operation(
+        &self,
+        request: Request<CancelOperationRequest>,
+    ) -> Result<Response<Operation>, Status> {
+        let id = request.into_inner().operation_id;
+
+        self.job_runner
+            .job_service()
+            .get(id)
+            .await
+            .map_err(into_status)?
+            .ok_or_else(|| Status::not_found(format!("operation with id {} not found", id)))?;
+
+        let job = self
+            .job_runner
+            .cancel(id)
+            .await
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
+
+        let progress = self
+            .job_runner
+            .job_service()
+            .get_progress(id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(to_operation(job, progress)))
+    }
+}
+
+fn to_operation(job: Job, progress: Vec<JobProgress>) -> Operation {
+    Operation {
+        operation_id: job.id,
+        kind: job.kind.to_string(),
+        chain_id: job.chain_id.to_string(),
+        request_id: job.request_id,
+        status: job.status.to_string(),
+        result: job.result.map(|result| result.to_string()),
+        error: job.error,
+        progress: progress
+            .into_iter()
+            .map(|progress| {
+                Event::from(EmittedEvent {
+                    emitted_at: SystemTime::from(progress.created_at),
+                    event: progress.event,
+                })
+            })
+            .collect(),
+        created_at: Some(SystemTime::from(job.created_at).into()),
+        updated_at: Some(SystemTime::from(job.updated_at).into()),
+    }
+}
@@ -0,0 +1,152 @@
+tonic::include_proto!("policy");
+
+use std::{convert::TryFrom, time::SystemTime};
+
+use anyhow::{anyhow, Error};
+use primitive_types::U256;
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{MintLimits as CoreMintLimits, MintPolicy as CoreMintPolicy},
+    service::PolicyService as CorePolicyService,
+    DbPool,
+};
+use tonic::{Request, Response, Status};
+
+use super::into_status;
+
+use self::policy_server::Policy;
+
+pub struct PolicyService {
+    core_service: CorePolicyService,
+}
+
+impl PolicyService {
+    /// Creates a new instance of gRPC policy service
+    pub fn new(db_pool: DbPool) -> Self {
+        let core_service = CorePolicyService::new(db_pool);
+
+        Self { core_service }
+    }
+}
+
+#[tonic::async_trait]
+impl Policy for PolicyService {
+    async fn set_mint_policy(
+        &self,
+        request: Request<SetMintPolicyRequest>,
+    ) -> Result<Response<SetMintPolicyResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id: ChainId = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let denom: Identifier = request
+            .denom
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let limits = CoreMintLimits::try_from(request.limits.unwrap_or_default())
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let policy = self
+            .core_service
+            .set_mint_policy(&chain_id, &denom, &limits)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(SetMintPolicyResponse {
+            policy: Some(policy.into()),
+        }))
+    }
+
+    async fn remove_mint_policy(
+        &self,
+        request: Request<RemoveMintPolicyRequest>,
+    ) -> Result<Response<RemoveMintPolicyResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id: ChainId = request
+            .chain_id
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let denom: Identifier = request
+            .denom
+            .parse()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        self.core_service
+            .remove_mint_policy(&chain_id, &denom)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(RemoveMintPolicyResponse {}))
+    }
+
+    async fn list_mint_policies(
+        &self,
+        request: Request<ListMintPoliciesRequest>,
+    ) -> Result<Response<ListMintPoliciesResponse>, Status> {
+        let request = request.into_inner();
+
+        let chain_id: Option<ChainId> = request
+            .chain_id
+            .map(|chain_id| chain_id.parse())
+            .transpose()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+
+        let policies = self
+            .core_service
+            .get_mint_policies(chain_id.as_ref())
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(ListMintPoliciesResponse {
+            policies: policies.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+impl TryFrom<MintLimits> for CoreMintLimits {
+    type Error = Error;
+
+    fn try_from(limits: MintLimits) -> Result<Self, Self::Error> {
+        Ok(Self {
+            max_amount: parse_limit(limits.max_amount)?,
+            daily_limit: parse_limit(limits.daily_limit)?,
+            weekly_limit: parse_limit(limits.weekly_limit)?,
+            receiver_daily_limit: parse_limit(limits.receiver_daily_limit)?,
+            receiver_weekly_limit: parse_limit(limits.receiver_weekly_limit)?,
+        })
+    }
+}
+
+impl From<CoreMintPolicy> for MintPolicy {
+    fn from(policy: CoreMintPolicy) -> Self {
+        Self {
+            chain_id: policy.chain_id.to_string(),
+            denom: policy.denom.to_string(),
+            limits: Some(MintLimits {
+                max_amount: policy.limits.max_amount.map(|limit| limit.to_string()),
+                daily_limit: policy.limits.daily_limit.map(|limit| limit.to_string()),
+                weekly_limit: policy.limits.weekly_limit.map(|limit| limit.to_string()),
+                receiver_daily_limit: policy
+                    .limits
+                    .receiver_daily_limit
+                    .map(|limit| limit.to_string()),
+                receiver_weekly_limit: policy
+                    .limits
+                    .receiver_weekly_limit
+                    .map(|limit| limit.to_string()),
+            }),
+            created_at: Some(SystemTime::from(policy.created_at).into()),
+            updated_at: Some(SystemTime::from(policy.updated_at).into()),
+        }
+    }
+}
+
+fn parse_limit(limit: Option<String>) -> Result<Option<U256>, Error> {
+    limit
+        .map(|limit| U256::from_dec_str(&limit))
+        .transpose()
+        .map_err(|err| anyhow!("invalid mint limit: {}", err))
+}
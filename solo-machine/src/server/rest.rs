@@ -0,0 +1,1097 @@
+use std::{net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use axum::{
+    body::Bytes,
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use hyper::server::conn::http1;
+use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+use prost_types::{value::Kind, Any, Duration as ProtoDuration, Timestamp, Value as ProtoValue};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use solo_machine_core::{service::ChainNotFound, signer::MessageDescription, Signer};
+use tokio::net::TcpListener;
+use tokio_rustls::{rustls::ServerConfig, TlsAcceptor};
+use tonic::{metadata::MetadataMap, service::Interceptor, Code, Extensions, Request, Status};
+
+use super::{
+    auth::{AuthInterceptor, GrpcPath},
+    chain::{
+        chain_server::{self, Chain},
+        AddChainRequest, ArchiveChainRequest, ChainConfig, ChainService, FeeConfig,
+        GetIbcDenomRequest, GetPublicKeysRequest, ListChainsRequest, QueryBalanceRequest,
+        RemoveChainRequest, UpdateChainRequest,
+    },
+    ibc::{
+        ibc_server::{self, Ibc},
+        BurnRequest, CloseChannelRequest, ConnectRequest, DryRunReport, IbcService, MintRequest,
+        QueryChannelsRequest, QueryClientStateRequest, QueryConnectionRequest, QueryHistoryRequest,
+        UpdateSignerRequest,
+    },
+};
+use crate::output::{chains_json, history_json, success_json};
+
+/// Maximum time allowed for TLS handshake of a connection to REST gateway
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shared state of REST gateway
+pub struct RestGateway<S> {
+    chain_service: Arc<ChainService<S>>,
+    ibc_service: Arc<IbcService<S>>,
+    auth: AuthInterceptor,
+}
+
+impl<S> RestGateway<S>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    /// Creates a new instance of REST gateway which forwards requests to given gRPC services
+    pub fn new(
+        chain_service: Arc<ChainService<S>>,
+        ibc_service: Arc<IbcService<S>>,
+        auth: AuthInterceptor,
+    ) -> Self {
+        Self {
+            chain_service,
+            ibc_service,
+            auth,
+        }
+    }
+
+    /// Starts REST gateway on given address (served over TLS when `tls_config` is provided)
+    pub async fn serve(
+        self,
+        addr: SocketAddr,
+        tls_config: Option<Arc<ServerConfig>>,
+    ) -> Result<()> {
+        let router = Router::new()
+            .route("/v1/chains", get(list_chains::<S>).post(add_chain::<S>))
+            .route(
+                "/v1/chains/:chain_id",
+                get(get_chain::<S>)
+                    .patch(update_chain::<S>)
+                    .delete(remove_chain::<S>),
+            )
+            .route("/v1/chains/:chain_id/archive", post(archive_chain::<S>))
+            .route("/v1/chains/:chain_id/ibc-denom", get(get_ibc_denom::<S>))
+            .route("/v1/chains/:chain_id/balance", get(get_balance::<S>))
+            .route(
+                "/v1/chains/:chain_id/public-keys",
+                get(get_public_keys::<S>),
+            )
+            .route("/v1/chains/:chain_id/connect", post(connect::<S>))
+            .route(
+                "/v1/chains/:chain_id/close-channel",
+                post(close_channel::<S>),
+            )
+            .route("/v1/chains/:chain_id/mint", post(mint::<S>))
+            .route("/v1/chains/:chain_id/burn", post(burn::<S>))
+            .route(
+                "/v1/chains/:chain_id/update-signer",
+                post(update_signer::<S>),
+            )
+            .route(
+                "/v1/chains/:chain_id/client-state",
+                get(get_client_state::<S>),
+            )
+            .route("/v1/chains/:chain_id/connection", get(get_connection::<S>))
+            .route("/v1/chains/:chain_id/channels", get(get_channels::<S>))
+            .route("/v1/history", get(get_history::<S>))
+            .fallback(not_found)
+            .with_state(Arc::new(self));
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("unable to bind rest gateway to: {}", addr))?;
+
+        log::info!("starting rest gateway at {}", addr);
+
+        match tls_config {
+            Some(tls_config) => {
+                log::info!("enabling tls for rest gateway");
+                serve_tls(listener, router, TlsAcceptor::from(tls_config)).await
+            }
+            None => {
+                log::warn!("tls is not enabled for rest gateway");
+
+                axum::serve(listener, router)
+                    .await
+                    .with_context(|| format!("unable to start rest gateway at: {}", addr))
+            }
+        }
+    }
+
+    /// Checks if the API key in request headers is allowed to call given gRPC method (REST endpoints are authorized
+    /// as the gRPC methods they correspond to)
+    fn authorize(&self, headers: &HeaderMap, service: &str, method: &str) -> Result<(), RestError> {
+        let mut request = Request::from_parts(
+            MetadataMap::from_headers(headers.clone()),
+            Extensions::default(),
+            (),
+        );
+        request
+            .extensions_mut()
+            .insert(GrpcPath::new(service, method));
+
+        self.auth.clone().call(request)?;
+
+        Ok(())
+    }
+}
+
+/// Error returned by REST gateway (serialized in the same format as `--output json` errors of CLI)
+#[derive(Debug)]
+pub struct RestError {
+    status: StatusCode,
+    message: String,
+}
+
+impl RestError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Status> for RestError {
+    fn from(status: Status) -> Self {
+        let code = match status.code() {
+            Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+                StatusCode::BAD_REQUEST
+            }
+            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Code::PermissionDenied => StatusCode::FORBIDDEN,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status: code,
+            message: status.message().to_owned(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for RestError {
+    fn from(err: anyhow::Error) -> Self {
+        if err.is::<ChainNotFound>() {
+            return Self::not_found(err.to_string());
+        }
+
+        log::error!("{}", err);
+
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<QueryRejection> for RestError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        json_response(
+            self.status,
+            json!({
+                "result": "error",
+                "data": self.message,
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DenomQuery {
+    denom: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    limit: Option<u32>,
+    offset: Option<u32>,
+    status: Option<String>,
+    chain_id: Option<String>,
+    denom: Option<String>,
+    operation_type: Option<String>,
+    request_id: Option<String>,
+    created_after: Option<String>,
+    created_before: Option<String>,
+    cursor: Option<i64>,
+}
+
+/// Configuration of IBC enabled chain in request body (durations are in human readable format, e.g. `14days`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ChainConfigBody {
+    grpc_addr: Option<String>,
+    rpc_addr: Option<String>,
+    fee_amount: Option<String>,
+    fee_denom: Option<String>,
+    gas_limit: Option<u64>,
+    trust_level: Option<String>,
+    trusting_period: Option<String>,
+    max_clock_drift: Option<String>,
+    rpc_timeout: Option<String>,
+    diversifier: Option<String>,
+    port_id: Option<String>,
+    trusted_height: Option<u32>,
+    trusted_hash: Option<String>,
+    fallback_grpc_addrs: Vec<String>,
+    fallback_rpc_addrs: Vec<String>,
+}
+
+impl TryFrom<ChainConfigBody> for ChainConfig {
+    type Error = RestError;
+
+    fn try_from(body: ChainConfigBody) -> Result<Self, Self::Error> {
+        let fee_config =
+            if body.fee_amount.is_some() || body.fee_denom.is_some() || body.gas_limit.is_some() {
+                Some(FeeConfig {
+                    fee_amount: body.fee_amount,
+                    fee_denom: body.fee_denom,
+                    gas_limit: body.gas_limit,
+                })
+            } else {
+                None
+            };
+
+        Ok(Self {
+            grpc_addr: body.grpc_addr,
+            rpc_addr: body.rpc_addr,
+            fee_config,
+            trust_level: body.trust_level,
+            trusting_period: parse_duration("trusting_period", body.trusting_period)?,
+            max_clock_drift: parse_duration("max_clock_drift", body.max_clock_drift)?,
+            rpc_timeout: parse_duration("rpc_timeout", body.rpc_timeout)?,
+            diversifier: body.diversifier,
+            port_id: body.port_id,
+            trusted_height: body.trusted_height,
+            trusted_hash: body.trusted_hash,
+            fallback_grpc_addrs: body.fallback_grpc_addrs,
+            fallback_rpc_addrs: body.fallback_rpc_addrs,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct UpdateChainBody {
+    #[serde(flatten)]
+    config: ChainConfigBody,
+    allow_node_change: bool,
+    clear_fallback_addrs: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ConnectBody {
+    request_id: Option<String>,
+    memo: Option<String>,
+    force: bool,
+    dry_run: bool,
+    simulate: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CloseChannelBody {
+    request_id: Option<String>,
+    memo: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MintBody {
+    request_id: Option<String>,
+    memo: Option<String>,
+    amount: String,
+    denom: String,
+    receiver_address: Option<String>,
+    dry_run: bool,
+    simulate: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BurnBody {
+    request_id: Option<String>,
+    memo: Option<String>,
+    amount: String,
+    denom: String,
+    dry_run: bool,
+    simulate: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct UpdateSignerBody {
+    request_id: Option<String>,
+    memo: Option<String>,
+    new_public_key: String,
+    public_key_algo: Option<String>,
+    dry_run: bool,
+    simulate: bool,
+}
+
+type GatewayState<S> = State<Arc<RestGateway<S>>>;
+
+async fn list_chains<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    query: Result<Query<Pagination>, QueryRejection>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "ListChains")?;
+    let Query(pagination) = query?;
+
+    let request = ListChainsRequest {
+        limit: pagination.limit,
+        offset: pagination.offset,
+    };
+
+    let chains = gateway.chain_service.list(&request).await?;
+
+    Ok(json_response(StatusCode::OK, chains_json(chains)?))
+}
+
+async fn add_chain<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "Add")?;
+    let body: ChainConfigBody = parse_body(&body)?;
+
+    let request = AddChainRequest {
+        config: Some(body.try_into()?),
+    };
+
+    let chain_id = gateway
+        .chain_service
+        .add(Request::new(request))
+        .await?
+        .into_inner()
+        .chain_id;
+
+    gateway.chain_response(&chain_id).await
+}
+
+async fn get_chain<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "Query")?;
+
+    gateway.chain_response(&chain_id).await
+}
+
+async fn update_chain<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "UpdateChain")?;
+    let body: UpdateChainBody = parse_body(&body)?;
+
+    let request = UpdateChainRequest {
+        chain_id: chain_id.clone(),
+        config: Some(body.config.try_into()?),
+        allow_node_change: body.allow_node_change,
+        clear_fallback_addrs: body.clear_fallback_addrs,
+    };
+
+    gateway
+        .chain_service
+        .update_chain(Request::new(request))
+        .await?;
+
+    gateway.chain_response(&chain_id).await
+}
+
+async fn archive_chain<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "ArchiveChain")?;
+
+    gateway
+        .chain_service
+        .archive_chain(Request::new(ArchiveChainRequest { chain_id }))
+        .await?;
+
+    Ok(success("Chain archived!"))
+}
+
+async fn remove_chain<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "RemoveChain")?;
+
+    gateway
+        .chain_service
+        .remove_chain(Request::new(RemoveChainRequest { chain_id }))
+        .await?;
+
+    Ok(success("Chain removed!"))
+}
+
+async fn get_ibc_denom<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    query: Result<Query<DenomQuery>, QueryRejection>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "GetIbcDenom")?;
+    let Query(query) = query?;
+
+    let request = GetIbcDenomRequest {
+        chain_id,
+        denom: query.denom,
+    };
+
+    let ibc_denom = gateway
+        .chain_service
+        .get_ibc_denom(Request::new(request))
+        .await?
+        .into_inner()
+        .ibc_denom;
+
+    Ok(success(ibc_denom))
+}
+
+async fn get_balance<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    query: Result<Query<DenomQuery>, QueryRejection>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "QueryBalance")?;
+    let Query(query) = query?;
+
+    let request = QueryBalanceRequest {
+        chain_id,
+        denom: query.denom.clone(),
+    };
+
+    let balance = gateway
+        .chain_service
+        .query_balance(Request::new(request))
+        .await?
+        .into_inner()
+        .balance;
+
+    Ok(success(json!({
+        "balance": balance,
+        "denom": query.denom,
+    })))
+}
+
+async fn get_public_keys<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    query: Result<Query<Pagination>, QueryRejection>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, chain_server::SERVICE_NAME, "GetPublicKeys")?;
+    let Query(pagination) = query?;
+
+    let request = GetPublicKeysRequest {
+        chain_id,
+        limit: pagination.limit,
+        offset: pagination.offset,
+    };
+
+    let keys = gateway.chain_service.public_keys(&request).await?;
+
+    Ok(success(keys))
+}
+
+async fn connect<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "Connect")?;
+    let body: ConnectBody = parse_body(&body)?;
+
+    let request = ConnectRequest {
+        chain_id,
+        request_id: body.request_id,
+        memo: body.memo,
+        force: body.force,
+        dry_run: body.dry_run,
+        simulate: body.simulate,
+    };
+
+    let response = gateway
+        .ibc_service
+        .connect(Request::new(request))
+        .await?
+        .into_inner();
+
+    if let Some(report) = response.dry_run_report {
+        return dry_run_response(report);
+    }
+
+    Ok(success(json!({ "operation_id": response.operation_id })))
+}
+
+async fn close_channel<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "CloseChannel")?;
+    let body: CloseChannelBody = parse_body(&body)?;
+
+    let request = CloseChannelRequest {
+        chain_id,
+        request_id: body.request_id,
+        memo: body.memo,
+    };
+
+    let operation_id = gateway
+        .ibc_service
+        .close_channel(Request::new(request))
+        .await?
+        .into_inner()
+        .operation_id;
+
+    Ok(success(json!({ "operation_id": operation_id })))
+}
+
+async fn mint<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "Mint")?;
+    let body: MintBody = parse_body(&body)?;
+
+    let request = MintRequest {
+        chain_id,
+        request_id: body.request_id,
+        memo: body.memo,
+        amount: body.amount,
+        denom: body.denom,
+        receiver_address: body.receiver_address,
+        dry_run: body.dry_run,
+        simulate: body.simulate,
+    };
+
+    let response = gateway
+        .ibc_service
+        .mint(Request::new(request))
+        .await?
+        .into_inner();
+
+    if let Some(report) = response.dry_run_report {
+        return dry_run_response(report);
+    }
+
+    Ok(success(
+        json!({ "transaction_hash": response.transaction_hash }),
+    ))
+}
+
+async fn burn<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "Burn")?;
+    let body: BurnBody = parse_body(&body)?;
+
+    let request = BurnRequest {
+        chain_id,
+        request_id: body.request_id,
+        memo: body.memo,
+        amount: body.amount,
+        denom: body.denom,
+        dry_run: body.dry_run,
+        simulate: body.simulate,
+    };
+
+    let response = gateway
+        .ibc_service
+        .burn(Request::new(request))
+        .await?
+        .into_inner();
+
+    if let Some(report) = response.dry_run_report {
+        return dry_run_response(report);
+    }
+
+    Ok(success(
+        json!({ "transaction_hash": response.transaction_hash }),
+    ))
+}
+
+async fn update_signer<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+    body: Bytes,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "UpdateSigner")?;
+    let body: UpdateSignerBody = parse_body(&body)?;
+
+    let request = UpdateSignerRequest {
+        chain_id,
+        request_id: body.request_id,
+        memo: body.memo,
+        new_public_key: body.new_public_key,
+        public_key_algo: body.public_key_algo,
+        dry_run: body.dry_run,
+        simulate: body.simulate,
+    };
+
+    let response = gateway
+        .ibc_service
+        .update_signer(Request::new(request))
+        .await?
+        .into_inner();
+
+    if let Some(report) = response.dry_run_report {
+        return dry_run_response(report);
+    }
+
+    Ok(success(
+        json!({ "transaction_hash": response.transaction_hash }),
+    ))
+}
+
+async fn get_client_state<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "QueryClientState")?;
+
+    let response = gateway
+        .ibc_service
+        .query_client_state(Request::new(QueryClientStateRequest { chain_id }))
+        .await?
+        .into_inner();
+
+    Ok(success(json!({
+        "client_id": response.client_id,
+        "client_state": response.client_state.as_ref().map(any_to_json),
+        "consensus_state": response.consensus_state.as_ref().map(any_to_json),
+    })))
+}
+
+async fn get_connection<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "QueryConnection")?;
+
+    let response = gateway
+        .ibc_service
+        .query_connection(Request::new(QueryConnectionRequest { chain_id }))
+        .await?
+        .into_inner();
+
+    Ok(success(json!({
+        "connection_id": response.connection_id,
+        "connection": response.connection.as_ref().map(any_to_json),
+    })))
+}
+
+async fn get_channels<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    Path(chain_id): Path<String>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "QueryChannels")?;
+
+    let channels = gateway
+        .ibc_service
+        .query_channels(Request::new(QueryChannelsRequest { chain_id }))
+        .await?
+        .into_inner()
+        .channels
+        .into_iter()
+        .map(|channel| {
+            json!({
+                "port_id": channel.port_id,
+                "channel_id": channel.channel_id,
+                "channel": channel.channel.as_ref().map(any_to_json),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(success(channels))
+}
+
+async fn get_history<S>(
+    State(gateway): GatewayState<S>,
+    headers: HeaderMap,
+    query: Result<Query<HistoryQuery>, QueryRejection>,
+) -> Result<Response, RestError>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    gateway.authorize(&headers, ibc_server::SERVICE_NAME, "QueryHistory")?;
+    let Query(query) = query?;
+
+    let request = QueryHistoryRequest {
+        limit: query.limit,
+        offset: query.offset,
+        status: query.status,
+        chain_id: query.chain_id,
+        denom: query.denom,
+        operation_type: query.operation_type,
+        request_id: query.request_id,
+        created_after: parse_timestamp("created_after", query.created_after)?,
+        created_before: parse_timestamp("created_before", query.created_before)?,
+        cursor: query.cursor,
+    };
+
+    let history = gateway.ibc_service.history(&request).await?;
+
+    Ok(json_response(StatusCode::OK, history_json(&history)))
+}
+
+async fn not_found() -> RestError {
+    RestError::not_found("endpoint not found")
+}
+
+/// Accepts TLS connections on given listener and serves REST gateway on them
+async fn serve_tls(listener: TcpListener, router: Router, acceptor: TlsAcceptor) -> Result<()> {
+    loop {
+        let (stream, peer_addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                // Errors while accepting a connection (e.g., too many open files) are usually temporary
+                log::error!("unable to accept connection on rest gateway: {}", err);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let router = router.clone();
+
+        tokio::spawn(async move {
+            let stream =
+                match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(err)) => {
+                        log::warn!("tls handshake with {} failed: {}", peer_addr, err);
+                        return;
+                    }
+                    Err(_) => {
+                        log::warn!("tls handshake with {} timed out", peer_addr);
+                        return;
+                    }
+                };
+
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), TowerToHyperService::new(router))
+                .await
+            {
+                log::debug!(
+                    "error while serving rest connection from {}: {}",
+                    peer_addr,
+                    err
+                );
+            }
+        });
+    }
+}
+
+impl<S> RestGateway<S>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    async fn chain_response(&self, chain_id: &str) -> Result<Response, RestError> {
+        let chain = self.chain_service.get_chain(chain_id).await?;
+
+        Ok(success(chain))
+    }
+}
+
+fn parse_body<T>(body: &Bytes) -> Result<T, RestError>
+where
+    T: DeserializeOwned + Default,
+{
+    if body.is_empty() {
+        return Ok(T::default());
+    }
+
+    serde_json::from_slice(body)
+        .map_err(|err| RestError::bad_request(format!("invalid request body: {}", err)))
+}
+
+fn parse_duration(
+    name: &str,
+    duration: Option<String>,
+) -> Result<Option<ProtoDuration>, RestError> {
+    duration
+        .map(|duration| {
+            humantime::parse_duration(&duration)
+                .ok()
+                .and_then(|duration| ProtoDuration::try_from(duration).ok())
+                .ok_or_else(|| RestError::bad_request(format!("invalid `{}`", name)))
+        })
+        .transpose()
+}
+
+fn parse_timestamp(name: &str, timestamp: Option<String>) -> Result<Option<Timestamp>, RestError> {
+    timestamp
+        .map(|timestamp| {
+            Timestamp::from_str(&timestamp)
+                .map_err(|_| RestError::bad_request(format!("invalid `{}`", name)))
+        })
+        .transpose()
+}
+
+fn any_to_json(any: &Any) -> Value {
+    json!({
+        "type_url": any.type_url,
+        "value": hex::encode(&any.value),
+    })
+}
+
+/// Returns the report of a dry run as response data
+fn dry_run_response(report: DryRunReport) -> Result<Response, RestError> {
+    let signed_messages = report
+        .signed_messages
+        .into_iter()
+        .map(MessageDescription::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let transactions = report
+        .transactions
+        .into_iter()
+        .map(|transaction| {
+            json!({
+                "size": transaction.size,
+                "messages": transaction
+                    .messages
+                    .into_iter()
+                    .map(|message| json!({
+                        "type_url": message.type_url,
+                        "value": message.value.map(value_to_json).unwrap_or_default(),
+                    }))
+                    .collect::<Vec<_>>(),
+                "memo": transaction.memo,
+                "fee": transaction.fee,
+                "gas_limit": transaction.gas_limit,
+                "simulated_gas_used": transaction.simulated_gas_used,
+                "simulation_error": transaction.simulation_error,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(success(json!({
+        "dry_run_report": {
+            "chain_id": report.chain_id,
+            "sequence": report.sequence,
+            "next_sequence": report.next_sequence,
+            "packet_sequence": report.packet_sequence,
+            "next_packet_sequence": report.next_packet_sequence,
+            "signed_messages": signed_messages,
+            "transactions": transactions,
+            "remaining_steps": report.remaining_steps,
+        },
+    })))
+}
+
+/// Converts a `google.protobuf.Value` into JSON value
+fn value_to_json(value: ProtoValue) -> Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(value)) => Value::Bool(value),
+        Some(Kind::NumberValue(value)) => json!(value),
+        Some(Kind::StringValue(value)) => Value::String(value),
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.into_iter().map(value_to_json).collect())
+        }
+        Some(Kind::StructValue(object)) => Value::Object(
+            object
+                .fields
+                .into_iter()
+                .map(|(key, value)| (key, value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn success(data: impl serde::Serialize) -> Response {
+    json_response(StatusCode::OK, success_json(data))
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response {
+    (
+        status,
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use solo_machine_core::model::{Chain, ChainConfig, ConnectionDetails, Fee};
+
+    use super::*;
+
+    fn chain() -> Chain {
+        Chain {
+            id: "test-1".parse().unwrap(),
+            node_id: "0123456789abcdef0123456789abcdef01234567".parse().unwrap(),
+            config: ChainConfig {
+                grpc_addr: "http://127.0.0.1:9090".to_owned(),
+                rpc_addr: "http://127.0.0.1:26657".to_owned(),
+                fallback_grpc_addrs: vec!["http://127.0.0.1:9091".to_owned()],
+                fallback_rpc_addrs: vec!["http://127.0.0.1:26658".to_owned()],
+                fee: Fee {
+                    amount: "1000".parse().unwrap(),
+                    denom: "stake".parse().unwrap(),
+                    gas_limit: 300000,
+                },
+                trust_level: num_rational::Ratio::new(1, 3),
+                trusting_period: Duration::from_secs(14 * 24 * 60 * 60),
+                max_clock_drift: Duration::from_millis(3500),
+                rpc_timeout: Duration::from_secs(60),
+                diversifier: "solo-machine-diversifier".to_owned(),
+                port_id: "transfer".parse().unwrap(),
+                trusted_height: 1u32.into(),
+                trusted_hash: [1; 32],
+            },
+            consensus_timestamp: Default::default(),
+            sequence: 3,
+            packet_sequence: 2,
+            connection_details: Some(ConnectionDetails {
+                solo_machine_client_id: "06-solomachine-0".parse().unwrap(),
+                tendermint_client_id: "07-tendermint-0".parse().unwrap(),
+                solo_machine_connection_id: "connection-0".parse().unwrap(),
+                tendermint_connection_id: "connection-1".parse().unwrap(),
+                solo_machine_channel_id: Some("channel-0".parse().unwrap()),
+                tendermint_channel_id: None,
+            }),
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+
+    async fn response_json(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn chain_response_matches_cli_output() {
+        let rest = response_json(success(chain())).await;
+        let cli = crate::output::success_json(chain());
+
+        assert_eq!(rest, cli);
+
+        let config = &rest["data"]["config"];
+        assert_eq!(config["trust_level"], json!([1, 3]));
+        assert_eq!(
+            config["max_clock_drift"],
+            json!({ "secs": 3, "nanos": 500000000 })
+        );
+        assert!(rest["data"].get("connection_status").is_none());
+    }
+}
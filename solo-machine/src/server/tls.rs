@@ -0,0 +1,103 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use tokio_rustls::rustls::{
+    crypto::ring::default_provider, pki_types::CertificateDer, server::WebPkiClientVerifier,
+    RootCertStore, ServerConfig,
+};
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// TLS configuration of gRPC server (also used by REST gateway)
+#[derive(Debug)]
+pub struct TlsConfig {
+    /// Path to PEM encoded certificate (chain) of server
+    pub cert: PathBuf,
+    /// Path to PEM encoded private key of server
+    pub key: PathBuf,
+    /// Path to PEM encoded CA certificate(s) used to verify client certificates (enables mutual TLS)
+    pub client_ca: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Reads certificates and private key from files and builds tonic's server TLS config
+    pub async fn load(&self) -> Result<ServerTlsConfig> {
+        let cert = read_pem(&self.cert, "server certificate").await?;
+        let key = read_pem(&self.key, "server private key").await?;
+
+        let mut config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+
+        if let Some(ref client_ca) = self.client_ca {
+            let client_ca = read_pem(client_ca, "client ca certificate").await?;
+            config = config.client_ca_root(Certificate::from_pem(client_ca));
+        }
+
+        Ok(config)
+    }
+
+    /// Reads certificates and private key from files and builds rustls server config (used by REST gateway)
+    pub async fn load_rustls(&self) -> Result<Arc<ServerConfig>> {
+        let cert = read_pem(&self.cert, "server certificate").await?;
+        let key = read_pem(&self.key, "server private key").await?;
+
+        let certs = parse_certs(&cert, &self.cert)?;
+        let key = rustls_pemfile::private_key(&mut key.as_slice())
+            .with_context(|| format!("invalid server private key in {}", self.key.display()))?
+            .ok_or_else(|| anyhow!("server private key not found in {}", self.key.display()))?;
+
+        let provider = Arc::new(default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .context("unable to configure tls protocol versions")?;
+
+        let builder = match self.client_ca {
+            Some(ref client_ca) => {
+                let client_ca_pem = read_pem(client_ca, "client ca certificate").await?;
+
+                let mut roots = RootCertStore::empty();
+                for cert in parse_certs(&client_ca_pem, client_ca)? {
+                    roots.add(cert).with_context(|| {
+                        format!("invalid client ca certificate in {}", client_ca.display())
+                    })?;
+                }
+
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .context("unable to build client certificate verifier")?;
+
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder
+            .with_single_cert(certs, key)
+            .context("invalid server certificate or private key")?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        Ok(Arc::new(config))
+    }
+}
+
+async fn read_pem(path: &Path, name: &str) -> Result<Vec<u8>> {
+    tokio::fs::read(path)
+        .await
+        .with_context(|| format!("unable to read {} from {}", name, path.display()))
+}
+
+fn parse_certs(pem: &[u8], path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut &*pem)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("invalid certificate in {}", path.display()))?;
+
+    ensure!(
+        !certs.is_empty(),
+        "no certificate found in {}",
+        path.display()
+    );
+
+    Ok(certs)
+}
@@ -1,45 +1,114 @@
-use std::{convert::TryFrom, ffi::OsStr, path::PathBuf, sync::Arc};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{anyhow, Context, Error, Result};
-use libloading::{Library, Symbol};
-use solo_machine_core::{signer::SignerRegistrar as ISignerRegistrar, Signer};
+use solo_machine_core::{
+    signer::{RemoteSigner, RemoteSignerConfig, SignerRegistrar as ISignerRegistrar},
+    Signer,
+};
+use solo_machine_plugin::host::Plugin;
+use structopt::StructOpt;
 
-#[derive(Default)]
-pub struct SignerRegistrar {
-    signer: Option<Arc<dyn Signer>>,
+/// Options for configuring the signer used by solo machine (either a signer plugin or a remote signing service)
+#[derive(Debug, StructOpt)]
+pub struct SignerOptions {
+    /// Register a signer (path to signer's `*.so` file)
+    #[structopt(long, env = "SOLO_SIGNER", hide_env_values = true)]
+    signer: Option<PathBuf>,
+    /// URL of remote signing service implementing `solo_machine.signer.v1.Signer` gRPC protocol (used instead of
+    /// `signer`, e.g. `https://signer.example.com:9100`)
+    #[structopt(
+        long,
+        env = "SOLO_REMOTE_SIGNER",
+        hide_env_values = true,
+        conflicts_with = "signer"
+    )]
+    remote_signer: Option<String>,
+    /// Path to PEM encoded CA certificate used to verify certificate of remote signing service (system roots are used
+    /// if not provided)
+    #[structopt(long, env = "SOLO_REMOTE_SIGNER_CA_CERT")]
+    remote_signer_ca_cert: Option<PathBuf>,
+    /// Path to PEM encoded client certificate presented to remote signing service (enables mutual TLS)
+    #[structopt(long, env = "SOLO_REMOTE_SIGNER_CERT", requires = "remote-signer-key")]
+    remote_signer_cert: Option<PathBuf>,
+    /// Path to PEM encoded private key of client certificate presented to remote signing service
+    #[structopt(
+        long,
+        env = "SOLO_REMOTE_SIGNER_KEY",
+        hide_env_values = true,
+        requires = "remote-signer-cert"
+    )]
+    remote_signer_key: Option<PathBuf>,
+    /// Domain name used to verify certificate of remote signing service (defaults to host of remote signer URL)
+    #[structopt(long, env = "SOLO_REMOTE_SIGNER_DOMAIN")]
+    remote_signer_domain: Option<String>,
+    /// Timeout of each request to remote signing service
+    #[structopt(long, env = "SOLO_REMOTE_SIGNER_TIMEOUT", default_value = "30s")]
+    remote_signer_timeout: humantime::Duration,
+    /// Allows connecting to remote signing service without `https` and client certificate (only for local testing)
+    #[structopt(long, env = "SOLO_REMOTE_SIGNER_INSECURE")]
+    remote_signer_insecure: bool,
 }
 
-impl SignerRegistrar {
-    pub fn unwrap(self) -> Result<Arc<dyn Signer>> {
-        self.signer.ok_or_else(|| anyhow!("signer not registered"))
+impl SignerOptions {
+    /// Returns `true` if a signer plugin or a remote signer is configured
+    pub fn is_configured(&self) -> bool {
+        self.signer.is_some() || self.remote_signer.is_some()
     }
 
-    // TODO: remove conditional compilation when this issue is fixed:
-    // https://github.com/nagisa/rust_libloading/issues/41
-    fn register_signer(&mut self, file: impl AsRef<OsStr>) -> Result<()> {
-        unsafe {
-            #[cfg(target_os = "linux")]
-            let library: Library = {
-                // Load library with `RTLD_NOW | RTLD_NODELETE` to fix a SIGSEGV
-                libloading::os::unix::Library::open(
-                    Some(file),
-                    libloading::os::unix::RTLD_NOW | 0x1000,
-                )
-                .context("unable to load signer")?
-                .into()
+    /// Loads signer plugin or connects to remote signing service
+    pub async fn load(self) -> Result<Arc<dyn Signer>> {
+        if let Some(url) = self.remote_signer {
+            let ca_cert = match self.remote_signer_ca_cert {
+                Some(ref path) => Some(read_pem(path, "remote signer ca certificate").await?),
+                None => None,
             };
-            #[cfg(not(target_os = "linux"))]
-            let library = Library::new(file).context("unable to load signer")?;
 
-            let register_fn: Symbol<unsafe extern "C" fn(&mut dyn ISignerRegistrar) -> Result<()>> =
-                library
-                    .get("register_signer".as_bytes())
-                    .context("unable to load `register_signer` function from signer")?;
+            let identity = match (self.remote_signer_cert, self.remote_signer_key) {
+                (Some(cert), Some(key)) => Some((
+                    read_pem(&cert, "remote signer client certificate").await?,
+                    read_pem(&key, "remote signer client private key").await?,
+                )),
+                _ => None,
+            };
+
+            let config = RemoteSignerConfig {
+                url,
+                ca_cert,
+                identity,
+                domain: self.remote_signer_domain,
+                timeout: self.remote_signer_timeout.into(),
+                insecure: self.remote_signer_insecure,
+            };
 
-            register_fn(self).context("unable to register signer")?;
+            return Ok(Arc::new(RemoteSigner::connect(config).await?));
         }
 
-        Ok(())
+        let file = self
+            .signer
+            .ok_or_else(|| anyhow!("`signer` or `remote-signer` is required"))?;
+
+        SignerRegistrar::try_from(file)?.unwrap()
+    }
+}
+
+async fn read_pem(path: &Path, name: &str) -> Result<Vec<u8>> {
+    tokio::fs::read(path)
+        .await
+        .with_context(|| format!("unable to read {} from {}", name, path.display()))
+}
+
+#[derive(Default)]
+pub struct SignerRegistrar {
+    signer: Option<Arc<dyn Signer>>,
+}
+
+impl SignerRegistrar {
+    pub fn unwrap(self) -> Result<Arc<dyn Signer>> {
+        self.signer.ok_or_else(|| anyhow!("signer not registered"))
     }
 }
 
@@ -54,7 +123,12 @@ impl TryFrom<PathBuf> for SignerRegistrar {
 
     fn try_from(file: PathBuf) -> Result<Self, Self::Error> {
         let mut registrar = Self::default();
-        registrar.register_signer(file)?;
+
+        let plugin = Plugin::load(&file)
+            .with_context(|| format!("unable to load signer: {}", file.display()))?;
+        plugin
+            .register_signer(&mut registrar)
+            .context("unable to register signer")?;
 
         Ok(registrar)
     }